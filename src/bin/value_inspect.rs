@@ -0,0 +1,56 @@
+// runar_common/src/bin/value_inspect.rs
+//
+// Decode a captured value payload and print what it contains.
+//
+// Usage:
+//   value_inspect <file>       read the raw payload from a file
+//   value_inspect --hex <hex>  decode a hex string (whitespace is ignored)
+//   value_inspect [-]          read the raw payload from stdin
+
+use std::io::Read;
+use std::process::ExitCode;
+
+use anyhow::{anyhow, Context, Result};
+use runar_common::types::inspect;
+
+const USAGE: &str = "usage: value_inspect [<file> | --hex <hex> | -]";
+
+fn read_input(args: &[String]) -> Result<Vec<u8>> {
+    match args {
+        [] => read_stdin(),
+        [flag] if flag == "-" => read_stdin(),
+        [flag] if flag == "-h" || flag == "--help" => Err(anyhow!(USAGE)),
+        [flag, hex] if flag == "--hex" => {
+            let hex: String = hex.chars().filter(|c| !c.is_whitespace()).collect();
+            hex::decode(hex).context("Invalid hex input")
+        }
+        [path] => std::fs::read(path).with_context(|| format!("Cannot read {}", path)),
+        _ => Err(anyhow!(USAGE)),
+    }
+}
+
+fn read_stdin() -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    std::io::stdin()
+        .read_to_end(&mut bytes)
+        .context("Cannot read stdin")?;
+    Ok(bytes)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let bytes = match read_input(&args) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            return ExitCode::from(2);
+        }
+    };
+    let report = inspect(&bytes);
+    print!("{}", report);
+    if report.error.is_some() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
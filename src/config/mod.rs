@@ -0,0 +1,429 @@
+// runar_common/src/config/mod.rs
+//
+// Layered configuration loading for Runar binaries.
+//
+// Layers are merged in increasing order of precedence:
+// 1. Built-in defaults
+// 2. A TOML or JSON configuration file
+// 3. Environment variables with a prefix (e.g. RUNAR_NETWORK__PORT=9000 -> network.port)
+// 4. Runtime overrides
+//
+// Keys are dot-separated paths into the merged tree ("network.port").
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tokio::sync::broadcast;
+
+use crate::types::ArcValueType;
+use crate::utils::size::parse_size;
+use crate::utils::time::parse_duration;
+
+/// Separator used between nested keys in environment variable names
+pub const ENV_NESTING_SEPARATOR: &str = "__";
+
+/// Capacity of the change notification channel
+const CHANGE_CHANNEL_CAPACITY: usize = 64;
+
+/// Identifies which layer a configuration value came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConfigLayer {
+    Defaults,
+    File,
+    Environment,
+    Override,
+}
+
+/// Notification sent to subscribers when configuration values change
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigChange {
+    /// Dot-separated keys whose effective value changed
+    pub keys: Vec<String>,
+    /// Revision number of the configuration after the change
+    pub revision: u64,
+}
+
+/// Builder describing where configuration is loaded from
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLoader {
+    defaults: Value,
+    file: Option<PathBuf>,
+    env_prefix: Option<String>,
+}
+
+impl ConfigLoader {
+    /// Create a loader with no layers configured
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the built-in defaults from any serializable value (usually a struct)
+    pub fn with_defaults<T: Serialize>(mut self, defaults: &T) -> Result<Self> {
+        self.defaults = serde_json::to_value(defaults)
+            .map_err(|e| anyhow!("Failed to serialize configuration defaults: {}", e))?;
+        Ok(self)
+    }
+
+    /// Load a configuration file; the format is chosen by extension (.toml or .json)
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self
+    }
+
+    /// Read environment variables starting with `<prefix>_`
+    pub fn with_env_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.env_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Load all layers and build the configuration
+    pub fn load(self) -> Result<Config> {
+        let file = match &self.file {
+            Some(path) => read_file(path)?,
+            None => Value::Null,
+        };
+        let env = match &self.env_prefix {
+            Some(prefix) => read_env(prefix, std::env::vars()),
+            None => Value::Null,
+        };
+        let (sender, _) = broadcast::channel(CHANGE_CHANNEL_CAPACITY);
+        let state = ConfigState {
+            defaults: self.defaults.clone(),
+            file,
+            env,
+            overrides: Value::Null,
+            merged: Value::Null,
+            revision: 0,
+        };
+        let config = Config {
+            loader: self,
+            state: RwLock::new(state),
+            changes: sender,
+        };
+        config.update(|_| Ok(()))?;
+        Ok(config)
+    }
+}
+
+struct ConfigState {
+    defaults: Value,
+    file: Value,
+    env: Value,
+    overrides: Value,
+    merged: Value,
+    revision: u64,
+}
+
+impl ConfigState {
+    fn layer(&self, layer: ConfigLayer) -> &Value {
+        match layer {
+            ConfigLayer::Defaults => &self.defaults,
+            ConfigLayer::File => &self.file,
+            ConfigLayer::Environment => &self.env,
+            ConfigLayer::Override => &self.overrides,
+        }
+    }
+
+    fn remerge(&mut self) {
+        let mut merged = Value::Object(Map::new());
+        for layer in [&self.defaults, &self.file, &self.env, &self.overrides] {
+            merge(&mut merged, layer);
+        }
+        self.merged = merged;
+    }
+}
+
+/// Loaded, layered configuration
+pub struct Config {
+    loader: ConfigLoader,
+    state: RwLock<ConfigState>,
+    changes: broadcast::Sender<ConfigChange>,
+}
+
+impl Config {
+    /// Get a value by dot-separated key, deserialized into `T`.
+    /// Returns Ok(None) when the key is absent.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        let state = self.read_state();
+        match lookup(&state.merged, key) {
+            Some(value) => serde_json::from_value(value.clone())
+                .map(Some)
+                .map_err(|e| anyhow!("Invalid configuration value for '{}': {}", key, e)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get a value by key, falling back to `default` when absent
+    pub fn get_or<T: DeserializeOwned>(&self, key: &str, default: T) -> Result<T> {
+        Ok(self.get(key)?.unwrap_or(default))
+    }
+
+    /// Get a duration by key. Strings are parsed with `parse_duration` ("250ms", "5m");
+    /// integers are taken as seconds.
+    pub fn get_duration(&self, key: &str) -> Result<Option<Duration>> {
+        match self.get::<Value>(key)? {
+            None => Ok(None),
+            Some(Value::String(s)) => parse_duration(&s)
+                .map(Some)
+                .map_err(|e| anyhow!("Invalid configuration value for '{}': {}", key, e)),
+            Some(Value::Number(n)) if n.is_u64() => Ok(n.as_u64().map(Duration::from_secs)),
+            Some(other) => Err(anyhow!(
+                "Invalid configuration value for '{}': expected a duration, got {}",
+                key,
+                other
+            )),
+        }
+    }
+
+    /// Get a byte size by key. Strings are parsed with `parse_size` ("64MiB");
+    /// integers are taken as bytes.
+    pub fn get_size(&self, key: &str) -> Result<Option<u64>> {
+        match self.get::<Value>(key)? {
+            None => Ok(None),
+            Some(Value::String(s)) => parse_size(&s)
+                .map(Some)
+                .map_err(|e| anyhow!("Invalid configuration value for '{}': {}", key, e)),
+            Some(Value::Number(n)) if n.is_u64() => Ok(n.as_u64()),
+            Some(other) => Err(anyhow!(
+                "Invalid configuration value for '{}': expected a size, got {}",
+                key,
+                other
+            )),
+        }
+    }
+
+    /// Deserialize the whole merged configuration into a typed struct
+    pub fn extract<T: DeserializeOwned>(&self) -> Result<T> {
+        let state = self.read_state();
+        serde_json::from_value(state.merged.clone())
+            .map_err(|e| anyhow!("Failed to extract configuration: {}", e))
+    }
+
+    /// Get the layer that supplies the effective value of a key
+    pub fn source_of(&self, key: &str) -> Option<ConfigLayer> {
+        let state = self.read_state();
+        [
+            ConfigLayer::Override,
+            ConfigLayer::Environment,
+            ConfigLayer::File,
+            ConfigLayer::Defaults,
+        ]
+        .into_iter()
+        .find(|layer| lookup(state.layer(*layer), key).is_some())
+    }
+
+    /// Render the merged configuration as a Map-category `ArcValueType`
+    pub fn to_value(&self) -> ArcValueType {
+        ArcValueType::from_json(self.read_state().merged.clone())
+    }
+
+    /// Get the current revision (incremented on every effective change)
+    pub fn revision(&self) -> u64 {
+        self.read_state().revision
+    }
+
+    /// Set a runtime override for a key; values are merged with highest precedence
+    pub fn set_override<T: Serialize>(&self, key: &str, value: T) -> Result<()> {
+        let value = serde_json::to_value(value)
+            .map_err(|e| anyhow!("Failed to serialize override for '{}': {}", key, e))?;
+        self.update(|state| {
+            insert_at(&mut state.overrides, key, value);
+            Ok(())
+        })
+    }
+
+    /// Remove all runtime overrides
+    pub fn clear_overrides(&self) -> Result<()> {
+        self.update(|state| {
+            state.overrides = Value::Null;
+            Ok(())
+        })
+    }
+
+    /// Re-read the file and environment layers
+    pub fn reload(&self) -> Result<()> {
+        let file = match &self.loader.file {
+            Some(path) => read_file(path)?,
+            None => Value::Null,
+        };
+        let env = match &self.loader.env_prefix {
+            Some(prefix) => read_env(prefix, std::env::vars()),
+            None => Value::Null,
+        };
+        self.update(|state| {
+            state.file = file;
+            state.env = env;
+            Ok(())
+        })
+    }
+
+    /// Subscribe to change notifications
+    pub fn subscribe(&self) -> broadcast::Receiver<ConfigChange> {
+        self.changes.subscribe()
+    }
+
+    fn read_state(&self) -> std::sync::RwLockReadGuard<'_, ConfigState> {
+        self.state
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Apply a mutation, re-merge the layers and notify subscribers of changed keys
+    fn update(&self, mutate: impl FnOnce(&mut ConfigState) -> Result<()>) -> Result<()> {
+        let mut state = self
+            .state
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let before = flatten(&state.merged);
+        mutate(&mut state)?;
+        state.remerge();
+        let after = flatten(&state.merged);
+
+        let mut keys: Vec<String> = after
+            .iter()
+            .filter(|(k, v)| before.get(*k) != Some(*v))
+            .map(|(k, _)| k.clone())
+            .collect();
+        keys.extend(before.keys().filter(|k| !after.contains_key(*k)).cloned());
+        keys.sort();
+
+        if !keys.is_empty() {
+            state.revision += 1;
+            // Sending only fails when there are no subscribers
+            let _ = self.changes.send(ConfigChange {
+                keys,
+                revision: state.revision,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Read a TOML or JSON file into a JSON tree
+fn read_file(path: &Path) -> Result<Value> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        anyhow!(
+            "Failed to read configuration file {}: {}",
+            path.display(),
+            e
+        )
+    })?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("toml") => {
+            let parsed: toml::Value = toml::from_str(&contents)
+                .map_err(|e| anyhow!("Invalid TOML in {}: {}", path.display(), e))?;
+            serde_json::to_value(parsed)
+                .map_err(|e| anyhow!("Failed to convert {}: {}", path.display(), e))
+        }
+        Some("json") => serde_json::from_str(&contents)
+            .map_err(|e| anyhow!("Invalid JSON in {}: {}", path.display(), e)),
+        _ => Err(anyhow!(
+            "Unsupported configuration file format: {}",
+            path.display()
+        )),
+    }
+}
+
+/// Collect `<PREFIX>_A__B=value` variables into a tree ({"a": {"b": value}})
+fn read_env(prefix: &str, vars: impl Iterator<Item = (String, String)>) -> Value {
+    let full_prefix = format!("{}_", prefix);
+    let mut tree = Value::Null;
+    for (name, raw) in vars {
+        let Some(rest) = name.strip_prefix(&full_prefix) else {
+            continue;
+        };
+        if rest.is_empty() {
+            continue;
+        }
+        let key = rest
+            .split(ENV_NESTING_SEPARATOR)
+            .map(|s| s.to_lowercase())
+            .collect::<Vec<_>>()
+            .join(".");
+        insert_at(&mut tree, &key, parse_env_value(&raw));
+    }
+    tree
+}
+
+/// Interpret an environment value as JSON when possible (numbers, bools, arrays),
+/// otherwise as a plain string
+fn parse_env_value(raw: &str) -> Value {
+    match serde_json::from_str::<Value>(raw) {
+        Ok(Value::String(_)) | Err(_) => Value::String(raw.to_string()),
+        Ok(value) => value,
+    }
+}
+
+/// Deep-merge `overlay` into `base`; objects merge recursively, other values replace
+fn merge(base: &mut Value, overlay: &Value) {
+    match (base, overlay) {
+        (_, Value::Null) => {}
+        (Value::Object(base_map), Value::Object(overlay_map)) => {
+            for (key, value) in overlay_map {
+                match base_map.get_mut(key) {
+                    Some(existing) if existing.is_object() && value.is_object() => {
+                        merge(existing, value)
+                    }
+                    _ => {
+                        base_map.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay.clone(),
+    }
+}
+
+/// Find the value at a dot-separated key
+fn lookup<'a>(tree: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.')
+        .try_fold(tree, |node, segment| node.as_object()?.get(segment))
+        .filter(|v| !v.is_null())
+}
+
+/// Insert a value at a dot-separated key, creating intermediate objects
+fn insert_at(tree: &mut Value, key: &str, value: Value) {
+    let mut node = tree;
+    for segment in key.split('.') {
+        if !node.is_object() {
+            *node = Value::Object(Map::new());
+        }
+        node = node
+            .as_object_mut()
+            .expect("node was just made an object")
+            .entry(segment.to_string())
+            .or_insert(Value::Null);
+    }
+    *node = value;
+}
+
+/// Flatten a tree into dot-separated leaf keys for change detection
+fn flatten(tree: &Value) -> BTreeMap<String, Value> {
+    fn walk(prefix: &str, node: &Value, out: &mut BTreeMap<String, Value>) {
+        match node {
+            Value::Object(map) => {
+                for (k, v) in map {
+                    let key = if prefix.is_empty() {
+                        k.clone()
+                    } else {
+                        format!("{}.{}", prefix, k)
+                    };
+                    walk(&key, v, out);
+                }
+            }
+            _ if !prefix.is_empty() => {
+                out.insert(prefix.to_string(), node.clone());
+            }
+            _ => {}
+        }
+    }
+    let mut out = BTreeMap::new();
+    walk("", tree, &mut out);
+    out
+}
@@ -0,0 +1,112 @@
+// runar_common/src/errors/catalog.rs
+//
+// User-facing error messages from a message catalog.
+//
+// Services raise errors with a stable number (see `errors::codes`) and named
+// parameters (`RunarError::with_param`); the `message` they write is for
+// logs and developers. A gateway renders the text users see from a
+// `MessageCatalog` in the caller's locale, so messages can be translated
+// and reworded without touching service code.
+//
+// Templates name parameters in braces: "Order {order} was not found".
+// `{{` and `}}` stand for literal braces; placeholders without a parameter
+// are left as written.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use super::runar_error::RunarError;
+
+/// Source of message templates by error number and locale
+pub trait MessageCatalog: Send + Sync {
+    /// The template for error `number` in `locale` (e.g. "de-CH"), if any
+    fn template(&self, number: u32, locale: &str) -> Option<String>;
+}
+
+/// In-memory catalog, e.g. loaded from translation files at startup
+#[derive(Default)]
+pub struct StaticCatalog {
+    templates: RwLock<HashMap<(String, u32), String>>,
+}
+
+impl StaticCatalog {
+    /// Create an empty catalog
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the template for error `number` in `locale`
+    pub fn with_message(self, locale: &str, number: u32, template: impl Into<String>) -> Self {
+        self.add(locale, number, template);
+        self
+    }
+
+    /// Add or replace the template for error `number` in `locale`
+    pub fn add(&self, locale: &str, number: u32, template: impl Into<String>) {
+        let mut templates = self.templates.write().unwrap_or_else(|p| p.into_inner());
+        templates.insert((normalize_locale(locale), number), template.into());
+    }
+}
+
+impl MessageCatalog for StaticCatalog {
+    fn template(&self, number: u32, locale: &str) -> Option<String> {
+        let templates = self.templates.read().unwrap_or_else(|p| p.into_inner());
+        templates.get(&(normalize_locale(locale), number)).cloned()
+    }
+}
+
+// Locale tags are matched case-insensitively, with '_' read as '-'
+fn normalize_locale(locale: &str) -> String {
+    locale.replace('_', "-").to_ascii_lowercase()
+}
+
+/// Fill the `{name}` placeholders of `template` from `params`
+pub fn render_template<'a>(template: &str, params: impl Fn(&str) -> Option<&'a str>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            out.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+        let placeholder = rest
+            .strip_prefix('{')
+            .and_then(|inner| inner.find('}').map(|end| &inner[..end]));
+        match placeholder.and_then(|name| params(name).map(|value| (name, value))) {
+            Some((name, value)) => {
+                out.push_str(value);
+                rest = &rest[name.len() + 2..];
+            }
+            None => {
+                out.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+impl RunarError {
+    /// Render the user-facing message for `locale` from `catalog`.
+    ///
+    /// Looks up the error's number, then the number of its code, in the
+    /// locale and then its language alone ("de-CH", then "de"). Falls back
+    /// to the error's own message when the catalog has neither.
+    pub fn localized(&self, catalog: &dyn MessageCatalog, locale: &str) -> String {
+        let language = locale.split(['-', '_']).next().unwrap_or(locale);
+        let template = [self.number(), self.code.number()]
+            .into_iter()
+            .flat_map(|number| [(number, locale), (number, language)])
+            .find_map(|(number, locale)| catalog.template(number, locale));
+        match template {
+            Some(template) => render_template(&template, |name| {
+                self.params().get(name).map(String::as_str)
+            }),
+            None => self.message.clone(),
+        }
+    }
+}
@@ -0,0 +1,248 @@
+// runar_common/src/errors/codes.rs
+//
+// Stable numeric error codes.
+//
+// Every `RunarError` carries a number (serialized next to its code) so
+// non-Rust peers can branch on errors without parsing names or messages.
+// Numbers are allocated from ranges reserved per subsystem, so codes defined
+// by different crates and services never collide:
+//
+//   0..1000        `ErrorCode` categories (runar_common)
+//   1000..100000   other Runar crates, reserved with `ErrorCodeRegistry::reserve`
+//   100000..       services
+//
+// Numbers are part of the wire contract: once published, a number must keep
+// its meaning.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::RwLock;
+
+use anyhow::Result;
+use lazy_static::lazy_static;
+
+use super::runar_error::{ErrorCode, RunarError};
+
+/// Subsystem owning the `ErrorCode` category numbers
+pub const CORE_SUBSYSTEM: &str = "runar_common";
+
+/// Numbers of the `ErrorCode` categories
+pub const CORE_CODES: Range<u32> = 0..1000;
+
+/// Numbers available to other Runar crates
+pub const CRATE_CODES: Range<u32> = 1000..100_000;
+
+/// First number available to services
+pub const SERVICE_CODES_START: u32 = 100_000;
+
+const CATEGORIES: [ErrorCode; 10] = [
+    ErrorCode::Internal,
+    ErrorCode::InvalidInput,
+    ErrorCode::NotFound,
+    ErrorCode::Unauthorized,
+    ErrorCode::Forbidden,
+    ErrorCode::Timeout,
+    ErrorCode::Unavailable,
+    ErrorCode::Conflict,
+    ErrorCode::Serialization,
+    ErrorCode::Cancelled,
+];
+
+impl ErrorCode {
+    /// Get the stable number of this category
+    pub fn number(&self) -> u32 {
+        match self {
+            ErrorCode::Internal => 1,
+            ErrorCode::InvalidInput => 2,
+            ErrorCode::NotFound => 3,
+            ErrorCode::Unauthorized => 4,
+            ErrorCode::Forbidden => 5,
+            ErrorCode::Timeout => 6,
+            ErrorCode::Unavailable => 7,
+            ErrorCode::Conflict => 8,
+            ErrorCode::Serialization => 9,
+            ErrorCode::Cancelled => 10,
+        }
+    }
+
+    /// Get the category with the given number
+    pub fn from_number(number: u32) -> Option<ErrorCode> {
+        CATEGORIES.into_iter().find(|code| code.number() == number)
+    }
+}
+
+/// An error number registered by a subsystem
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisteredCode {
+    /// The stable number
+    pub number: u32,
+    /// Subsystem (crate or service) that owns the number
+    pub subsystem: String,
+    /// Name of the error within the subsystem, e.g. "quota_exceeded"
+    pub name: String,
+    /// Category the error belongs to
+    pub category: ErrorCode,
+}
+
+#[derive(Default)]
+struct Registry {
+    ranges: Vec<(String, Range<u32>)>,
+    codes: BTreeMap<u32, RegisteredCode>,
+}
+
+/// Maps error numbers to the subsystems and errors that own them
+pub struct ErrorCodeRegistry {
+    inner: RwLock<Registry>,
+}
+
+lazy_static! {
+    static ref GLOBAL_CODES: ErrorCodeRegistry = ErrorCodeRegistry::new();
+}
+
+impl Default for ErrorCodeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ErrorCodeRegistry {
+    /// Create a registry holding the `ErrorCode` categories
+    pub fn new() -> Self {
+        let registry = ErrorCodeRegistry {
+            inner: RwLock::new(Registry::default()),
+        };
+        registry
+            .reserve(CORE_SUBSYSTEM, CORE_CODES)
+            .expect("empty registry");
+        for code in CATEGORIES {
+            registry
+                .register(CORE_SUBSYSTEM, code.as_str(), code.number(), code)
+                .expect("core codes are in range");
+        }
+        registry
+    }
+
+    /// The process-wide registry
+    pub fn global() -> &'static ErrorCodeRegistry {
+        &GLOBAL_CODES
+    }
+
+    /// Reserve `range` for `subsystem`. Fails with a `Conflict` error if it
+    /// overlaps a range reserved by another subsystem.
+    pub fn reserve(&self, subsystem: &str, range: Range<u32>) -> Result<()> {
+        if range.is_empty() {
+            return Err(RunarError::invalid_input(format!(
+                "Cannot reserve empty error code range {:?} for '{}'",
+                range, subsystem
+            ))
+            .into());
+        }
+        let mut inner = self.inner.write().unwrap_or_else(|p| p.into_inner());
+        let overlapping = inner
+            .ranges
+            .iter()
+            .find(|(_, taken)| taken.start < range.end && range.start < taken.end);
+        match overlapping {
+            Some((owner, taken)) if owner == subsystem && *taken == range => Ok(()),
+            Some((owner, taken)) => Err(RunarError::new(
+                ErrorCode::Conflict,
+                format!(
+                    "Error code range {:?} for '{}' overlaps {:?} reserved by '{}'",
+                    range, subsystem, taken, owner
+                ),
+            )
+            .into()),
+            None => {
+                inner.ranges.push((subsystem.to_string(), range));
+                Ok(())
+            }
+        }
+    }
+
+    /// Register error `name` of `subsystem` as `number`, which must lie in
+    /// a range the subsystem reserved. Registering the same error again is
+    /// a no-op; reusing a number for anything else fails with `Conflict`.
+    pub fn register(
+        &self,
+        subsystem: &str,
+        name: &str,
+        number: u32,
+        category: ErrorCode,
+    ) -> Result<RegisteredCode> {
+        let mut inner = self.inner.write().unwrap_or_else(|p| p.into_inner());
+        let owns = inner
+            .ranges
+            .iter()
+            .any(|(owner, range)| owner == subsystem && range.contains(&number));
+        if !owns {
+            return Err(RunarError::invalid_input(format!(
+                "Error code {} is outside the ranges reserved by '{}'",
+                number, subsystem
+            ))
+            .into());
+        }
+        let code = RegisteredCode {
+            number,
+            subsystem: subsystem.to_string(),
+            name: name.to_string(),
+            category,
+        };
+        match inner.codes.get(&number) {
+            Some(existing) if *existing == code => Ok(code),
+            Some(existing) => Err(RunarError::new(
+                ErrorCode::Conflict,
+                format!(
+                    "Error code {} is already registered as '{}/{}'",
+                    number, existing.subsystem, existing.name
+                ),
+            )
+            .into()),
+            None => {
+                inner.codes.insert(number, code.clone());
+                Ok(code)
+            }
+        }
+    }
+
+    /// Look up a registered number
+    pub fn lookup(&self, number: u32) -> Option<RegisteredCode> {
+        let inner = self.inner.read().unwrap_or_else(|p| p.into_inner());
+        inner.codes.get(&number).cloned()
+    }
+
+    /// Find an error by subsystem and name
+    pub fn find(&self, subsystem: &str, name: &str) -> Option<RegisteredCode> {
+        let inner = self.inner.read().unwrap_or_else(|p| p.into_inner());
+        inner
+            .codes
+            .values()
+            .find(|code| code.subsystem == subsystem && code.name == name)
+            .cloned()
+    }
+
+    /// Subsystem that reserved the range containing `number`, if any
+    pub fn owner_of(&self, number: u32) -> Option<String> {
+        let inner = self.inner.read().unwrap_or_else(|p| p.into_inner());
+        inner
+            .ranges
+            .iter()
+            .find(|(_, range)| range.contains(&number))
+            .map(|(owner, _)| owner.clone())
+    }
+
+    /// Build an error for a registered `subsystem` error
+    pub fn error(
+        &self,
+        subsystem: &str,
+        name: &str,
+        message: impl Into<String>,
+    ) -> Result<RunarError> {
+        let code = self.find(subsystem, name).ok_or_else(|| {
+            RunarError::not_found(format!(
+                "No error '{}' registered for '{}'",
+                name, subsystem
+            ))
+        })?;
+        Ok(RunarError::new(code.category, message).with_number(code.number))
+    }
+}
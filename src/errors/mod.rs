@@ -4,6 +4,19 @@
 pub use anyhow::{anyhow, Result};
 pub use thiserror::Error;
 
+// Structured error type and result combinators
+pub mod catalog;
+pub mod codes;
+mod result_ext;
+mod runar_error;
+mod status;
+
+pub use catalog::{MessageCatalog, StaticCatalog};
+pub use codes::{ErrorCodeRegistry, RegisteredCode};
+pub use result_ext::ResultExt;
+pub use runar_error::{ErrorCode, RunarError};
+pub use status::StatusClass;
+
 // Export common error utilities
 pub mod utils {
     use crate::types::ArcValueType;
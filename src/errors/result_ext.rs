@@ -0,0 +1,39 @@
+// runar_common/src/errors/result_ext.rs
+//
+// Combinators for annotating and converting errors in handler code
+
+use std::fmt;
+
+use super::runar_error::{ErrorCode, RunarError};
+use super::utils::error_to_string_value;
+use crate::logging::Logger;
+use crate::types::ArcValueType;
+
+/// Extension methods for `Result` so errors can be logged and converted in one chained call
+pub trait ResultExt<T, E> {
+    /// Log the error (if any) at error level as `"<context>: <error>"` and pass the result through
+    fn log_err(self, logger: &Logger, context: &str) -> Result<T, E>;
+
+    /// Convert the error into a string `ArcValueType` suitable for returning to callers
+    fn to_value_err(self) -> Result<T, ArcValueType>;
+
+    /// Convert the error into a `RunarError` with the given code, keeping its message
+    fn with_code(self, code: ErrorCode) -> Result<T, RunarError>;
+}
+
+impl<T, E: fmt::Display> ResultExt<T, E> for Result<T, E> {
+    fn log_err(self, logger: &Logger, context: &str) -> Result<T, E> {
+        if let Err(e) = &self {
+            logger.error(format!("{}: {}", context, e));
+        }
+        self
+    }
+
+    fn to_value_err(self) -> Result<T, ArcValueType> {
+        self.map_err(error_to_string_value)
+    }
+
+    fn with_code(self, code: ErrorCode) -> Result<T, RunarError> {
+        self.map_err(|e| RunarError::new(code, e.to_string()))
+    }
+}
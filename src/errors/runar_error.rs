@@ -0,0 +1,245 @@
+// runar_common/src/errors/runar_error.rs
+//
+// Structured error type shared across the Runar stack
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::wire::WireError;
+
+/// Broad error classification used by handlers and transports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ErrorCode {
+    /// Unexpected failure inside the node or service
+    Internal,
+    /// The caller supplied malformed or invalid input
+    InvalidInput,
+    /// The requested service, action or resource does not exist
+    NotFound,
+    /// The caller is not authenticated
+    Unauthorized,
+    /// The caller is authenticated but not allowed to perform the operation
+    Forbidden,
+    /// The operation did not complete before its deadline
+    Timeout,
+    /// The target is temporarily unable to handle the request
+    Unavailable,
+    /// The operation conflicts with the current state
+    Conflict,
+    /// A value could not be serialized or deserialized
+    Serialization,
+    /// The operation was cancelled by the caller
+    Cancelled,
+}
+
+impl ErrorCode {
+    /// Get the string representation of the error code
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::Internal => "internal",
+            ErrorCode::InvalidInput => "invalid_input",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::Unauthorized => "unauthorized",
+            ErrorCode::Forbidden => "forbidden",
+            ErrorCode::Timeout => "timeout",
+            ErrorCode::Unavailable => "unavailable",
+            ErrorCode::Conflict => "conflict",
+            ErrorCode::Serialization => "serialization",
+            ErrorCode::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An error carrying an `ErrorCode` alongside a human-readable message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Error)]
+#[serde(into = "RunarErrorWire", from = "RunarErrorWire")]
+#[error("[{code}] {message}")]
+pub struct RunarError {
+    /// Classification of the error
+    pub code: ErrorCode,
+    /// Human-readable description of what went wrong
+    pub message: String,
+    // Registered number (see `errors::codes`), when more specific than the code's
+    number: Option<u32>,
+    // Values for the placeholders of catalog messages (see `errors::catalog`)
+    params: BTreeMap<String, String>,
+}
+
+/// Serialized form of a `RunarError`; `number` is always written so peers
+/// can branch on it, and defaults to the code's number when reading errors
+/// from peers that predate it. Likewise `params` may be absent.
+#[derive(Deserialize)]
+struct RunarErrorWire {
+    code: ErrorCode,
+    message: String,
+    #[serde(default)]
+    number: Option<u32>,
+    #[serde(default)]
+    params: BTreeMap<String, String>,
+}
+
+// Empty `params` are left out of human-readable formats such as JSON; binary
+// formats (bincode) read fields by position, so they always carry them
+impl Serialize for RunarErrorWire {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let skip_params = self.params.is_empty() && serializer.is_human_readable();
+        let len = if skip_params { 3 } else { 4 };
+        let mut state = serializer.serialize_struct("RunarErrorWire", len)?;
+        state.serialize_field("code", &self.code)?;
+        state.serialize_field("message", &self.message)?;
+        state.serialize_field("number", &self.number)?;
+        if skip_params {
+            state.skip_field("params")?;
+        } else {
+            state.serialize_field("params", &self.params)?;
+        }
+        state.end()
+    }
+}
+
+impl From<RunarError> for RunarErrorWire {
+    fn from(error: RunarError) -> Self {
+        RunarErrorWire {
+            number: Some(error.number()),
+            code: error.code,
+            message: error.message,
+            params: error.params,
+        }
+    }
+}
+
+impl From<RunarErrorWire> for RunarError {
+    fn from(wire: RunarErrorWire) -> Self {
+        let number = wire.number.unwrap_or_else(|| wire.code.number());
+        let mut error = RunarError::new(wire.code, wire.message).with_number(number);
+        error.params = wire.params;
+        error
+    }
+}
+
+impl RunarError {
+    /// Create a new error with the given code and message
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            number: None,
+            params: BTreeMap::new(),
+        }
+    }
+
+    /// Set a registered error number (see `ErrorCodeRegistry`)
+    pub fn with_number(mut self, number: u32) -> Self {
+        self.number = (number != self.code.number()).then_some(number);
+        self
+    }
+
+    /// Get the stable error number: the registered number if one was set,
+    /// otherwise the number of the code
+    pub fn number(&self) -> u32 {
+        self.number.unwrap_or_else(|| self.code.number())
+    }
+
+    /// Set a parameter for the error's catalog message, e.g. `("user", 42)`
+    /// for a template "User {user} not found"
+    pub fn with_param(mut self, name: impl Into<String>, value: impl fmt::Display) -> Self {
+        self.params.insert(name.into(), value.to_string());
+        self
+    }
+
+    /// Get the parameters for the error's catalog message
+    pub fn params(&self) -> &BTreeMap<String, String> {
+        &self.params
+    }
+
+    /// Create an internal error
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+
+    /// Create an invalid input error
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::InvalidInput, message)
+    }
+
+    /// Create a not found error
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+
+    /// Get the error code
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    /// Get the error message
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Check whether the failed operation may succeed if attempted again
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.code, ErrorCode::Timeout | ErrorCode::Unavailable)
+    }
+
+    /// Find the `RunarError` an `anyhow::Error` was built from, looking
+    /// through any context added on top of it
+    pub fn find_in(error: &anyhow::Error) -> Option<&RunarError> {
+        error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<RunarError>())
+    }
+
+    /// Get the code of an `anyhow::Error`: that of an embedded `RunarError`,
+    /// `Serialization` for a `WireError`, and `Internal` otherwise
+    pub fn code_of(error: &anyhow::Error) -> ErrorCode {
+        match Self::find_in(error) {
+            Some(runar) => runar.code,
+            None if error.chain().any(|cause| cause.is::<WireError>()) => ErrorCode::Serialization,
+            None => ErrorCode::Internal,
+        }
+    }
+}
+
+/// Wraps an `anyhow::Error`, keeping the code, number and parameters of an
+/// embedded `RunarError` (see `RunarError::code_of`). Context added on top
+/// of it is kept in the message.
+impl From<anyhow::Error> for RunarError {
+    fn from(error: anyhow::Error) -> Self {
+        if let Some(runar) = error.downcast_ref::<RunarError>() {
+            if error.chain().count() == 1 {
+                return runar.clone();
+            }
+        }
+        let message = error
+            .chain()
+            .map(|cause| match cause.downcast_ref::<RunarError>() {
+                Some(runar) => runar.message.clone(),
+                None => cause.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(": ");
+        let mut converted = RunarError::new(Self::code_of(&error), message);
+        if let Some(runar) = Self::find_in(&error) {
+            converted = converted.with_number(runar.number());
+            converted.params = runar.params.clone();
+        }
+        converted
+    }
+}
+
+impl From<WireError> for RunarError {
+    fn from(error: WireError) -> Self {
+        RunarError::new(ErrorCode::Serialization, error.to_string())
+    }
+}
@@ -0,0 +1,141 @@
+// runar_common/src/errors/status.rs
+//
+// Transport-agnostic status classes for errors
+//
+// The HTTP gateway and the P2P RPC layer both translate errors through this
+// table so a given ErrorCode always surfaces the same way on every transport.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::runar_error::{ErrorCode, RunarError};
+
+/// Transport-agnostic classification of an error response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StatusClass {
+    BadRequest,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    Conflict,
+    Timeout,
+    Cancelled,
+    Unavailable,
+    Internal,
+}
+
+impl StatusClass {
+    /// Get the string representation of the status class
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatusClass::BadRequest => "BadRequest",
+            StatusClass::Unauthorized => "Unauthorized",
+            StatusClass::Forbidden => "Forbidden",
+            StatusClass::NotFound => "NotFound",
+            StatusClass::Conflict => "Conflict",
+            StatusClass::Timeout => "Timeout",
+            StatusClass::Cancelled => "Cancelled",
+            StatusClass::Unavailable => "Unavailable",
+            StatusClass::Internal => "Internal",
+        }
+    }
+
+    /// Get the HTTP status code used by the gateway for this class
+    pub fn http_status(&self) -> u16 {
+        match self {
+            StatusClass::BadRequest => 400,
+            StatusClass::Unauthorized => 401,
+            StatusClass::Forbidden => 403,
+            StatusClass::NotFound => 404,
+            StatusClass::Conflict => 409,
+            // Non-standard but widely used "client closed request"
+            StatusClass::Cancelled => 499,
+            StatusClass::Internal => 500,
+            StatusClass::Unavailable => 503,
+            StatusClass::Timeout => 504,
+        }
+    }
+
+    /// Map an HTTP status code back to a class.
+    /// Returns None for non-error statuses (below 400).
+    pub fn from_http_status(status: u16) -> Option<Self> {
+        let class = match status {
+            0..=399 => return None,
+            400 | 422 => StatusClass::BadRequest,
+            401 => StatusClass::Unauthorized,
+            403 => StatusClass::Forbidden,
+            404 | 410 => StatusClass::NotFound,
+            409 | 412 => StatusClass::Conflict,
+            408 | 504 => StatusClass::Timeout,
+            499 => StatusClass::Cancelled,
+            429 | 502 | 503 => StatusClass::Unavailable,
+            400..=499 => StatusClass::BadRequest,
+            _ => StatusClass::Internal,
+        };
+        Some(class)
+    }
+
+    /// Get the canonical error code for this class
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            StatusClass::BadRequest => ErrorCode::InvalidInput,
+            StatusClass::Unauthorized => ErrorCode::Unauthorized,
+            StatusClass::Forbidden => ErrorCode::Forbidden,
+            StatusClass::NotFound => ErrorCode::NotFound,
+            StatusClass::Conflict => ErrorCode::Conflict,
+            StatusClass::Timeout => ErrorCode::Timeout,
+            StatusClass::Cancelled => ErrorCode::Cancelled,
+            StatusClass::Unavailable => ErrorCode::Unavailable,
+            StatusClass::Internal => ErrorCode::Internal,
+        }
+    }
+}
+
+impl fmt::Display for StatusClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl ErrorCode {
+    /// Get the transport-agnostic status class for this code
+    pub fn status_class(&self) -> StatusClass {
+        match self {
+            ErrorCode::InvalidInput | ErrorCode::Serialization => StatusClass::BadRequest,
+            ErrorCode::Unauthorized => StatusClass::Unauthorized,
+            ErrorCode::Forbidden => StatusClass::Forbidden,
+            ErrorCode::NotFound => StatusClass::NotFound,
+            ErrorCode::Conflict => StatusClass::Conflict,
+            ErrorCode::Timeout => StatusClass::Timeout,
+            ErrorCode::Cancelled => StatusClass::Cancelled,
+            ErrorCode::Unavailable => StatusClass::Unavailable,
+            ErrorCode::Internal => StatusClass::Internal,
+        }
+    }
+}
+
+impl RunarError {
+    /// Get the transport-agnostic status class for this error
+    pub fn status_class(&self) -> StatusClass {
+        self.code.status_class()
+    }
+
+    /// Get the HTTP status code the gateway should answer with
+    pub fn http_status(&self) -> u16 {
+        self.status_class().http_status()
+    }
+
+    /// Build an error from an HTTP error status received from a remote gateway.
+    /// Non-error statuses are treated as internal errors.
+    pub fn from_http_status(status: u16, message: impl Into<String>) -> Self {
+        let class = StatusClass::from_http_status(status).unwrap_or(StatusClass::Internal);
+        Self::new(class.error_code(), message)
+    }
+}
+
+impl From<StatusClass> for ErrorCode {
+    fn from(class: StatusClass) -> Self {
+        class.error_code()
+    }
+}
@@ -0,0 +1,299 @@
+// runar_common/src/flags/mod.rs
+//
+// Runtime feature flags.
+//
+// Flags are registered with a name, a type (bool or int) and a default.
+// The startup value can be overridden through environment variables
+// (prefix "RUNAR_FLAG", name "network.fast_path" -> RUNAR_FLAG_NETWORK_FAST_PATH)
+// and flags can be changed at runtime, with changes broadcast to subscribers.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::RwLock;
+
+use anyhow::{anyhow, Result};
+use tokio::sync::broadcast;
+
+use crate::types::ArcValueType;
+
+/// Default prefix for flag environment variables
+pub const DEFAULT_ENV_PREFIX: &str = "RUNAR_FLAG";
+
+/// Capacity of the change notification channel
+const CHANGE_CHANNEL_CAPACITY: usize = 64;
+
+/// Value of a flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagValue {
+    Bool(bool),
+    Int(i64),
+}
+
+impl FlagValue {
+    /// Get the type name of the value
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            FlagValue::Bool(_) => "bool",
+            FlagValue::Int(_) => "int",
+        }
+    }
+
+    /// Parse a string as a value of the same type as `self`
+    fn parse_like(&self, raw: &str) -> Result<FlagValue> {
+        let raw = raw.trim();
+        match self {
+            FlagValue::Bool(_) => match raw.to_ascii_lowercase().as_str() {
+                "1" | "true" | "on" | "yes" => Ok(FlagValue::Bool(true)),
+                "0" | "false" | "off" | "no" => Ok(FlagValue::Bool(false)),
+                _ => Err(anyhow!("Invalid bool flag value: {}", raw)),
+            },
+            FlagValue::Int(_) => raw
+                .parse()
+                .map(FlagValue::Int)
+                .map_err(|_| anyhow!("Invalid int flag value: {}", raw)),
+        }
+    }
+
+    fn to_value(self) -> ArcValueType {
+        match self {
+            FlagValue::Bool(b) => ArcValueType::new_primitive(b),
+            FlagValue::Int(i) => ArcValueType::new_primitive(i),
+        }
+    }
+}
+
+impl fmt::Display for FlagValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlagValue::Bool(b) => write!(f, "{}", b),
+            FlagValue::Int(i) => write!(f, "{}", i),
+        }
+    }
+}
+
+/// Where the current value of a flag came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagSource {
+    Default,
+    Environment,
+    Runtime,
+}
+
+/// Notification sent to subscribers when a flag changes
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlagChange {
+    pub name: String,
+    pub old: FlagValue,
+    pub new: FlagValue,
+}
+
+/// Current state of a registered flag
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlagState {
+    pub name: String,
+    pub description: String,
+    pub default: FlagValue,
+    pub value: FlagValue,
+    pub source: FlagSource,
+}
+
+/// Registry of named flags
+pub struct FlagRegistry {
+    env_prefix: Option<String>,
+    flags: RwLock<BTreeMap<String, FlagState>>,
+    changes: broadcast::Sender<FlagChange>,
+}
+
+impl Default for FlagRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FlagRegistry {
+    /// Create a registry that reads overrides from `RUNAR_FLAG_*` variables
+    pub fn new() -> Self {
+        Self::with_env_prefix(Some(DEFAULT_ENV_PREFIX))
+    }
+
+    /// Create a registry with a custom environment prefix (None disables env overrides)
+    pub fn with_env_prefix(prefix: Option<&str>) -> Self {
+        let (sender, _) = broadcast::channel(CHANGE_CHANNEL_CAPACITY);
+        Self {
+            env_prefix: prefix.map(str::to_string),
+            flags: RwLock::new(BTreeMap::new()),
+            changes: sender,
+        }
+    }
+
+    /// Register a boolean flag
+    pub fn register_bool(&self, name: &str, default: bool, description: &str) -> Result<()> {
+        self.register(name, FlagValue::Bool(default), description)
+    }
+
+    /// Register an integer flag
+    pub fn register_int(&self, name: &str, default: i64, description: &str) -> Result<()> {
+        self.register(name, FlagValue::Int(default), description)
+    }
+
+    /// Register a flag; the environment override (if any) is applied immediately
+    pub fn register(&self, name: &str, default: FlagValue, description: &str) -> Result<()> {
+        validate_name(name)?;
+        let mut state = FlagState {
+            name: name.to_string(),
+            description: description.to_string(),
+            default,
+            value: default,
+            source: FlagSource::Default,
+        };
+        if let Some(var) = self.env_var_name(name) {
+            if let Ok(raw) = std::env::var(&var) {
+                state.value = default
+                    .parse_like(&raw)
+                    .map_err(|e| anyhow!("Invalid value in {}: {}", var, e))?;
+                state.source = FlagSource::Environment;
+            }
+        }
+
+        let mut flags = self.write_flags();
+        if flags.contains_key(name) {
+            return Err(anyhow!("Flag '{}' is already registered", name));
+        }
+        flags.insert(name.to_string(), state);
+        Ok(())
+    }
+
+    /// Get the environment variable that overrides a flag
+    pub fn env_var_name(&self, name: &str) -> Option<String> {
+        self.env_prefix.as_ref().map(|prefix| {
+            let suffix: String = name
+                .chars()
+                .map(|c| match c {
+                    '.' | '-' => '_',
+                    c => c.to_ascii_uppercase(),
+                })
+                .collect();
+            format!("{}_{}", prefix, suffix)
+        })
+    }
+
+    /// Get the current value of a flag
+    pub fn get(&self, name: &str) -> Option<FlagValue> {
+        self.read_flags().get(name).map(|state| state.value)
+    }
+
+    /// Check whether a boolean flag is enabled (false for unknown or non-bool flags)
+    pub fn is_enabled(&self, name: &str) -> bool {
+        matches!(self.get(name), Some(FlagValue::Bool(true)))
+    }
+
+    /// Get the value of an integer flag
+    pub fn get_int(&self, name: &str) -> Option<i64> {
+        match self.get(name) {
+            Some(FlagValue::Int(i)) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// Get the full state of a flag
+    pub fn state(&self, name: &str) -> Option<FlagState> {
+        self.read_flags().get(name).cloned()
+    }
+
+    /// Change a flag at runtime; the value must have the flag's type
+    pub fn set(&self, name: &str, value: FlagValue) -> Result<()> {
+        self.update(name, value, FlagSource::Runtime)
+    }
+
+    /// Change a flag at runtime from its string form (e.g. from the admin API)
+    pub fn set_from_str(&self, name: &str, raw: &str) -> Result<()> {
+        let default = self
+            .read_flags()
+            .get(name)
+            .map(|state| state.default)
+            .ok_or_else(|| anyhow!("Unknown flag '{}'", name))?;
+        self.set(name, default.parse_like(raw)?)
+    }
+
+    /// Reset a flag to its default value
+    pub fn reset(&self, name: &str) -> Result<()> {
+        let default = self
+            .read_flags()
+            .get(name)
+            .map(|state| state.default)
+            .ok_or_else(|| anyhow!("Unknown flag '{}'", name))?;
+        self.update(name, default, FlagSource::Default)
+    }
+
+    /// Subscribe to change notifications
+    pub fn subscribe(&self) -> broadcast::Receiver<FlagChange> {
+        self.changes.subscribe()
+    }
+
+    /// Get the state of all flags, sorted by name
+    pub fn snapshot(&self) -> Vec<FlagState> {
+        self.read_flags().values().cloned().collect()
+    }
+
+    /// Render all flag values as a Map-category `ArcValueType` (name -> bool/i64)
+    pub fn to_value(&self) -> ArcValueType {
+        let map: HashMap<String, ArcValueType> = self
+            .read_flags()
+            .iter()
+            .map(|(name, state)| (name.clone(), state.value.to_value()))
+            .collect();
+        ArcValueType::from_map(map)
+    }
+
+    fn update(&self, name: &str, value: FlagValue, source: FlagSource) -> Result<()> {
+        let mut flags = self.write_flags();
+        let state = flags
+            .get_mut(name)
+            .ok_or_else(|| anyhow!("Unknown flag '{}'", name))?;
+        if state.default.type_name() != value.type_name() {
+            return Err(anyhow!(
+                "Flag '{}' expects a {} value, got {}",
+                name,
+                state.default.type_name(),
+                value.type_name()
+            ));
+        }
+        let old = state.value;
+        state.value = value;
+        state.source = source;
+        if old != value {
+            // Sending only fails when there are no subscribers
+            let _ = self.changes.send(FlagChange {
+                name: name.to_string(),
+                old,
+                new: value,
+            });
+        }
+        Ok(())
+    }
+
+    fn read_flags(&self) -> std::sync::RwLockReadGuard<'_, BTreeMap<String, FlagState>> {
+        self.flags
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write_flags(&self) -> std::sync::RwLockWriteGuard<'_, BTreeMap<String, FlagState>> {
+        self.flags
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Flag names are lowercase ASCII letters, digits, '.', '_' and '-'
+fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '_' | '-'));
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!("Invalid flag name '{}'", name))
+    }
+}
@@ -0,0 +1,184 @@
+// runar_common/src/health.rs
+//
+// Health check framework shared by node services.
+//
+// Each component implements `HealthCheck`; the `HealthAggregator` runs all
+// registered checks and combines them into a single `NodeHealth` report that
+// the admin API can return as an `ArcValueType`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::types::ArcValueType;
+use crate::utils::time::{SystemTime, UNIX_EPOCH};
+
+/// Health of a single component or of the whole node.
+/// Variants are ordered from best to worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum HealthStatus {
+    /// Fully operational
+    Healthy,
+    /// Operational with reduced capacity or functionality
+    Degraded,
+    /// Not operational
+    Unhealthy,
+}
+
+impl HealthStatus {
+    /// Get the string representation of the status
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthStatus::Healthy => "healthy",
+            HealthStatus::Degraded => "degraded",
+            HealthStatus::Unhealthy => "unhealthy",
+        }
+    }
+}
+
+impl fmt::Display for HealthStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Result of a single health check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentHealth {
+    /// Status reported by the check
+    pub status: HealthStatus,
+    /// Optional explanation, usually set when not healthy
+    pub message: Option<String>,
+}
+
+impl ComponentHealth {
+    /// A healthy result
+    pub fn healthy() -> Self {
+        Self {
+            status: HealthStatus::Healthy,
+            message: None,
+        }
+    }
+
+    /// A degraded result with an explanation
+    pub fn degraded(message: impl Into<String>) -> Self {
+        Self {
+            status: HealthStatus::Degraded,
+            message: Some(message.into()),
+        }
+    }
+
+    /// An unhealthy result with an explanation
+    pub fn unhealthy(message: impl Into<String>) -> Self {
+        Self {
+            status: HealthStatus::Unhealthy,
+            message: Some(message.into()),
+        }
+    }
+}
+
+/// A component that can report its health
+#[async_trait]
+pub trait HealthCheck: Send + Sync {
+    /// Name under which the result is reported
+    fn name(&self) -> &str;
+
+    /// Run the check
+    async fn check(&self) -> ComponentHealth;
+}
+
+/// Aggregated health report for a node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeHealth {
+    /// Overall status
+    pub status: HealthStatus,
+    /// Results per check name
+    pub checks: BTreeMap<String, ComponentHealth>,
+    /// When the checks were run (seconds since UNIX epoch)
+    pub checked_at: u64,
+}
+
+impl NodeHealth {
+    /// Check whether the node is fully healthy
+    pub fn is_healthy(&self) -> bool {
+        self.status == HealthStatus::Healthy
+    }
+
+    /// Convert the report into a Struct-category value for the admin API.
+    /// Register `NodeHealth` with the `SerializerRegistry` to send it over the wire.
+    pub fn to_value(&self) -> ArcValueType {
+        ArcValueType::from_struct(self.clone())
+    }
+}
+
+impl From<NodeHealth> for ArcValueType {
+    fn from(health: NodeHealth) -> Self {
+        ArcValueType::from_struct(health)
+    }
+}
+
+struct RegisteredCheck {
+    check: Arc<dyn HealthCheck>,
+    critical: bool,
+}
+
+/// Runs registered health checks and combines them into an overall status.
+///
+/// The overall status is the worst status among critical checks. A failing
+/// non-critical check only degrades the node, it never makes it unhealthy.
+#[derive(Default)]
+pub struct HealthAggregator {
+    checks: Vec<RegisteredCheck>,
+}
+
+impl HealthAggregator {
+    /// Create an aggregator with no checks
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a critical check
+    pub fn register(&mut self, check: Arc<dyn HealthCheck>) {
+        self.checks.push(RegisteredCheck {
+            check,
+            critical: true,
+        });
+    }
+
+    /// Register a check whose failure only degrades the node
+    pub fn register_non_critical(&mut self, check: Arc<dyn HealthCheck>) {
+        self.checks.push(RegisteredCheck {
+            check,
+            critical: false,
+        });
+    }
+
+    /// Run all checks and build the node report
+    pub async fn check_all(&self) -> NodeHealth {
+        let mut status = HealthStatus::Healthy;
+        let mut checks = BTreeMap::new();
+
+        for registered in &self.checks {
+            let result = registered.check.check().await;
+            let contribution = if registered.critical {
+                result.status
+            } else {
+                result.status.min(HealthStatus::Degraded)
+            };
+            status = status.max(contribution);
+            checks.insert(registered.check.name().to_string(), result);
+        }
+
+        NodeHealth {
+            status,
+            checks,
+            checked_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        }
+    }
+}
@@ -1,18 +1,47 @@
 // runar_common/src/lib.rs
 //
 // Common traits and utilities for the Runar P2P stack
+//
+// Everything except the `wire` module requires the (default) `std` feature.
+// Building with `default-features = false` leaves an alloc-only core with the
+// value wire format definitions for peers without std.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 // Export modules
+#[cfg(feature = "std")]
+pub mod config;
+#[cfg(feature = "std")]
 pub mod errors;
+#[cfg(feature = "std")]
+pub mod flags;
+#[cfg(feature = "std")]
+pub mod health;
+#[cfg(feature = "std")]
 pub mod logging;
+#[cfg(feature = "std")]
 pub mod macros;
+#[cfg(feature = "std")]
+pub mod metrics;
+#[cfg(feature = "std")]
 pub mod service_info;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "std")]
 pub mod types;
+#[cfg(feature = "std")]
 pub mod utils;
+pub mod wire;
 
 // Re-export traits and types at the root level
-pub use logging::{Component, Logger, LoggingContext};
-pub use service_info::ServiceInfo;
+#[cfg(feature = "std")]
+pub use errors::{ErrorCode, ResultExt, RunarError};
+#[cfg(feature = "std")]
+pub use logging::{Component, Logger, LoggerFactory, LoggingContext};
+#[cfg(feature = "std")]
+pub use service_info::{ServiceDescriptor, ServiceInfo};
 
 // Note: The logging macros have been removed in favor of direct logger usage.
 // See rust-common/src/logging/macros.rs for details on the recommended approach.
@@ -0,0 +1,206 @@
+// runar_common/src/logging/admin.rs
+//
+// Runtime logging configuration as `ArcValueType` maps, so the node's admin
+// action can inspect and change logging over the normal action mechanism.
+//
+// {
+//   "level":    "info",                  // default level
+//   "format":   "text",                  // "text", "json" or "pretty"
+//   "levels":   {"Registry": "debug"},   // per component / target prefix (globs allowed)
+//   "sinks":    {"stderr": true},        // enable or disable sinks by name
+//   "sampling": {"Network": 10}          // keep one record in N
+// }
+//
+// Every key is optional when applying a configuration. Entries in "levels"
+// and "sampling" are merged into the current rules; a null value removes a
+// rule. Nothing changes unless the whole value is valid.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use log::LevelFilter;
+use serde_json::{Map, Value};
+
+use super::backend::{installed, rule_name, LogFormat, RunarLogBackend, SamplingRule};
+use crate::types::ArcValueType;
+use crate::utils::glob::Glob;
+
+/// The installed backend's configuration as a Map value
+pub fn config_as_value() -> Result<ArcValueType> {
+    Ok(installed_backend()?.config_as_value())
+}
+
+/// Change the installed backend's configuration (see the module docs for the
+/// accepted keys)
+pub fn apply_config_value(value: &ArcValueType) -> Result<()> {
+    let backend = installed_backend()?;
+    backend.apply_config_value(value)?;
+    log::set_max_level(backend.max_level());
+    Ok(())
+}
+
+fn installed_backend() -> Result<std::sync::Arc<RunarLogBackend>> {
+    installed().ok_or_else(|| anyhow!("No log backend is installed"))
+}
+
+impl RunarLogBackend {
+    /// The current configuration as a Map value
+    pub fn config_as_value(&self) -> ArcValueType {
+        let state = self.state();
+        let levels: Map<String, Value> = state
+            .overrides
+            .iter()
+            .map(|(name, level)| (name.to_string(), level_name(*level).into()))
+            .collect();
+        let mut sinks = Map::new();
+        for named in &state.sinks {
+            // Sinks sharing a name are switched together
+            sinks.insert(named.name.clone(), named.enabled.into());
+        }
+        let sampling: Map<String, Value> = state
+            .sampling
+            .iter()
+            .map(|rule| (rule.name.to_string(), rule.every.into()))
+            .collect();
+        ArcValueType::from_json(serde_json::json!({
+            "level": level_name(state.level),
+            "format": format_name(state.format),
+            "levels": levels,
+            "sinks": sinks,
+            "sampling": sampling,
+        }))
+    }
+
+    /// Change the configuration. Call `log::set_max_level(backend.max_level())`
+    /// afterwards if the backend is installed (`logging::apply_config_value`
+    /// does this).
+    pub fn apply_config_value(&self, value: &ArcValueType) -> Result<()> {
+        let json = value
+            .to_json()
+            .map_err(|e| anyhow!("Invalid logging configuration: {}", e))?;
+        let config = json
+            .as_object()
+            .ok_or_else(|| anyhow!("Logging configuration must be a map"))?;
+        if let Some(key) = config
+            .keys()
+            .find(|key| !["level", "format", "levels", "sinks", "sampling"].contains(&key.as_str()))
+        {
+            return Err(anyhow!("Unknown logging configuration key '{}'", key));
+        }
+
+        // Parse everything before touching the state
+        let level = config.get("level").map(parse_level).transpose()?;
+        let format = config.get("format").map(parse_format).transpose()?;
+        let levels = entries(config, "levels", |value| parse_level(value).map(Some))?;
+        let sinks = entries(config, "sinks", |value| Ok(value.as_bool()))?
+            .into_iter()
+            .map(|(name, enabled)| {
+                enabled
+                    .map(|enabled| (name.clone(), enabled))
+                    .ok_or_else(|| anyhow!("Sink '{}' must be switched with true or false", name))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let sampling = entries(config, "sampling", |value| {
+            value
+                .as_u64()
+                .filter(|every| *every > 0)
+                .map(Some)
+                .ok_or_else(|| anyhow!("Sampling must be a positive integer, got {}", value))
+        })?;
+
+        for (name, _) in &levels {
+            Glob::new(name)?;
+        }
+        for (name, _) in &sampling {
+            Glob::new(name)?;
+        }
+
+        let mut state = self
+            .state
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((name, _)) = sinks
+            .iter()
+            .find(|(name, _)| !state.sinks.iter().any(|named| &named.name == name))
+        {
+            return Err(anyhow!("No log sink named '{}'", name));
+        }
+
+        if let Some(level) = level {
+            state.level = level;
+        }
+        if let Some(format) = format {
+            state.format = format;
+        }
+        for (name, level) in levels {
+            state
+                .overrides
+                .retain(|(existing, _)| existing.as_str() != name);
+            if let Some(level) = level {
+                state.overrides.push((rule_name(&name), level));
+            }
+        }
+        for (name, enabled) in sinks {
+            for named in state.sinks.iter_mut().filter(|named| named.name == name) {
+                named.enabled = enabled;
+            }
+        }
+        for (name, every) in sampling {
+            state.sampling.retain(|rule| rule.name.as_str() != name);
+            if let Some(every) = every.filter(|every| *every > 1) {
+                state
+                    .sampling
+                    .push(SamplingRule::new(rule_name(&name), every));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse the entries of a nested map; null values parse to None
+fn entries<T>(
+    config: &Map<String, Value>,
+    key: &str,
+    parse: impl Fn(&Value) -> Result<Option<T>>,
+) -> Result<Vec<(String, Option<T>)>> {
+    let Some(value) = config.get(key) else {
+        return Ok(Vec::new());
+    };
+    let map = value
+        .as_object()
+        .ok_or_else(|| anyhow!("'{}' must be a map", key))?;
+    map.iter()
+        .map(|(name, value)| {
+            let parsed = if value.is_null() { None } else { parse(value)? };
+            Ok((name.clone(), parsed))
+        })
+        .collect()
+}
+
+fn parse_level(value: &Value) -> Result<LevelFilter> {
+    value
+        .as_str()
+        .and_then(|name| LevelFilter::from_str(name).ok())
+        .ok_or_else(|| anyhow!("Invalid log level {}", value))
+}
+
+fn level_name(level: LevelFilter) -> String {
+    level.as_str().to_ascii_lowercase()
+}
+
+fn parse_format(value: &Value) -> Result<LogFormat> {
+    match value.as_str() {
+        Some("text") => Ok(LogFormat::Text),
+        Some("json") => Ok(LogFormat::Json),
+        Some("pretty") => Ok(LogFormat::Pretty),
+        _ => Err(anyhow!("Invalid log format {}", value)),
+    }
+}
+
+fn format_name(format: LogFormat) -> &'static str {
+    match format {
+        LogFormat::Text => "text",
+        LogFormat::Json => "json",
+        LogFormat::Pretty => "pretty",
+    }
+}
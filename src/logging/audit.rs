@@ -0,0 +1,221 @@
+// runar_common/src/logging/audit.rs
+//
+// Audit trail for security-relevant actions.
+//
+// Audit records are not log messages: `Logger::audit` ignores level filters
+// and the `log` backend, and hands every record to the audit sink configured
+// with `set_audit_sink` (stderr until one is set). Records cannot be changed
+// once built.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::encoding::to_hex;
+use crate::utils::integrity::blake3_hash;
+use crate::utils::paths::ActionPath;
+use crate::utils::time::{self, SystemTime};
+
+/// Outcome of the audited action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditDecision {
+    /// The action was permitted
+    Allowed,
+    /// The action was refused
+    Denied,
+}
+
+/// One entry of the audit trail
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    actor: String,
+    action: ActionPath,
+    decision: AuditDecision,
+    correlation_id: Option<String>,
+    node_id: Option<String>,
+    timestamp_ms: u64,
+    payload_digest: Option<String>,
+}
+
+impl AuditRecord {
+    /// Record that `actor` was allowed or denied `action`, timestamped now
+    pub fn new(actor: impl Into<String>, action: ActionPath, decision: AuditDecision) -> Self {
+        AuditRecord {
+            actor: actor.into(),
+            action,
+            decision,
+            correlation_id: None,
+            node_id: None,
+            timestamp_ms: time::now_millis(),
+            payload_digest: None,
+        }
+    }
+
+    /// Attach the correlation ID of the request
+    pub fn with_correlation_id(mut self, id: impl Into<String>) -> Self {
+        self.correlation_id = Some(id.into());
+        self
+    }
+
+    /// Attach the BLAKE3 digest of the request payload (the payload itself
+    /// is never stored)
+    pub fn with_payload(mut self, payload: &[u8]) -> Self {
+        self.payload_digest = Some(to_hex(&blake3_hash(payload)));
+        self
+    }
+
+    /// Who performed the action (user, peer or service ID)
+    pub fn actor(&self) -> &str {
+        &self.actor
+    }
+
+    /// The action performed
+    pub fn action(&self) -> &ActionPath {
+        &self.action
+    }
+
+    /// Whether the action was allowed
+    pub fn decision(&self) -> AuditDecision {
+        self.decision
+    }
+
+    /// Correlation ID of the request, if known
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.correlation_id.as_deref()
+    }
+
+    /// Node that recorded the action (set by `Logger::audit`)
+    pub fn node_id(&self) -> Option<&str> {
+        self.node_id.as_deref()
+    }
+
+    /// When the action was recorded
+    pub fn timestamp(&self) -> SystemTime {
+        time::from_epoch_millis(self.timestamp_ms)
+    }
+
+    /// Hex BLAKE3 digest of the request payload, if attached
+    pub fn payload_digest(&self) -> Option<&str> {
+        self.payload_digest.as_deref()
+    }
+
+    /// The record as a single JSON line
+    pub fn to_json_line(&self) -> String {
+        serde_json::to_string(self).expect("audit records always serialize")
+    }
+
+    // Fill in what the logger knows and the caller did not set
+    pub(crate) fn with_context(mut self, node_id: &str, correlation_id: Option<&str>) -> Self {
+        self.node_id.get_or_insert_with(|| node_id.to_string());
+        if self.correlation_id.is_none() {
+            self.correlation_id = correlation_id.map(str::to_string);
+        }
+        self
+    }
+}
+
+/// Destination of audit records; writes must be durable when they return
+pub trait AuditSink: Send + Sync {
+    /// Append a record to the trail
+    fn write(&self, record: &AuditRecord) -> Result<()>;
+}
+
+/// Writes audit records to standard error as JSON lines
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StderrAuditSink;
+
+impl AuditSink for StderrAuditSink {
+    fn write(&self, record: &AuditRecord) -> Result<()> {
+        eprintln!("{}", record.to_json_line());
+        Ok(())
+    }
+}
+
+/// Appends audit records to a file as JSON lines, flushing every record
+pub struct FileAuditSink {
+    file: Mutex<File>,
+}
+
+impl FileAuditSink {
+    /// Open (or create) an audit file for appending
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| anyhow!("Cannot open audit file {}: {}", path.display(), e))?;
+        Ok(FileAuditSink {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+impl AuditSink for FileAuditSink {
+    fn write(&self, record: &AuditRecord) -> Result<()> {
+        let mut file = self
+            .file
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        writeln!(file, "{}", record.to_json_line())
+            .and_then(|_| file.sync_data())
+            .map_err(|e| anyhow!("Cannot write audit record: {}", e))
+    }
+}
+
+/// Keeps audit records in memory, e.g. for tests. Clones share the records.
+#[derive(Clone, Default)]
+pub struct MemoryAuditSink {
+    records: Arc<Mutex<Vec<AuditRecord>>>,
+}
+
+impl MemoryAuditSink {
+    /// Create an empty sink
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records written so far
+    pub fn records(&self) -> Vec<AuditRecord> {
+        self.records
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+}
+
+impl AuditSink for MemoryAuditSink {
+    fn write(&self, record: &AuditRecord) -> Result<()> {
+        self.records
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(record.clone());
+        Ok(())
+    }
+}
+
+static AUDIT_SINK: RwLock<Option<Arc<dyn AuditSink>>> = RwLock::new(None);
+
+/// Send audit records of every logger to `sink`
+pub fn set_audit_sink(sink: Arc<dyn AuditSink>) {
+    *AUDIT_SINK
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(sink);
+}
+
+/// Write a record to the configured audit sink (stderr if none is set)
+pub fn write_audit(record: &AuditRecord) -> Result<()> {
+    let sink = AUDIT_SINK
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    match sink {
+        Some(sink) => sink.write(record),
+        None => StderrAuditSink.write(record),
+    }
+}
@@ -0,0 +1,614 @@
+// runar_common/src/logging/backend.rs
+//
+// A `log::Log` backend for nodes.
+//
+// Records from `Logger` and from third-party crates using the `log` macros
+// go through the same filters, formatter and sinks, so dependency output is
+// tagged with the node ID and can be written as JSON alongside node logs.
+// `Logger` attaches its node ID and component prefix as key-values; records
+// without them are tagged with the backend's own node ID.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, IsTerminal, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock, RwLockReadGuard};
+
+use anyhow::{anyhow, Result};
+use log::kv::Key;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::types::NodeId;
+use crate::utils::format;
+use crate::utils::glob::Glob;
+use crate::utils::time::{self, Clock, SystemClock, SystemTime};
+
+/// Output format of log lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// `2024-01-01T00:00:00.000Z INFO  [node][component] message`
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+    /// Column-aligned lines for local development, colored for sinks
+    /// writing to a terminal:
+    /// `00:00:00.000 INFO  Registry             node-1 message`
+    Pretty,
+}
+
+/// Width of the component column in `LogFormat::Pretty` lines
+pub const PRETTY_COMPONENT_WIDTH: usize = 20;
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_DIM: &str = "\x1b[2m";
+
+/// A log record with its node and component split out of the message
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRecord {
+    /// When the record was logged
+    pub timestamp: SystemTime,
+    /// Severity
+    pub level: Level,
+    /// `log` target (module path for records from the `log` macros)
+    pub target: String,
+    /// Node that logged the record
+    pub node_id: Option<String>,
+    /// Component prefix of the `Logger` (None for third-party records)
+    pub component: Option<String>,
+    /// The message, without the node and component tags
+    pub message: String,
+}
+
+impl LogRecord {
+    /// Build a record from a `log` record, tagging records that do not
+    /// carry a node ID with `default_node`
+    pub fn from_log(record: &Record, default_node: Option<&NodeId>) -> Self {
+        Self::from_log_at(record, default_node, SystemTime::now())
+    }
+
+    /// Like `from_log`, with an explicit timestamp
+    pub fn from_log_at(
+        record: &Record,
+        default_node: Option<&NodeId>,
+        timestamp: SystemTime,
+    ) -> Self {
+        let key_value = |key: &str| {
+            record
+                .key_values()
+                .get(Key::from(key))
+                .map(|value| value.to_string())
+        };
+        let node_id = key_value("node_id");
+        let component = key_value("component");
+        let mut message = record.args().to_string();
+
+        // Logger writes the tags into the message too, for plain `log` backends
+        if let Some(node) = &node_id {
+            let tags = match &component {
+                Some(component) => format!("[{}][{}] ", node, component),
+                None => format!("[{}] ", node),
+            };
+            if let Some(stripped) = message.strip_prefix(&tags) {
+                message = stripped.to_string();
+            }
+        }
+
+        LogRecord {
+            timestamp,
+            level: record.level(),
+            target: record.target().to_string(),
+            node_id: node_id.or_else(|| default_node.map(|node| node.as_str().to_string())),
+            component,
+            message,
+        }
+    }
+
+    /// The timestamp as RFC 3339 UTC with milliseconds
+    pub fn timestamp_rfc3339(&self) -> String {
+        format::format_timestamp(self.timestamp)
+    }
+
+    /// Format the record as a single line
+    pub fn format(&self, format: LogFormat) -> String {
+        match format {
+            LogFormat::Text => {
+                let mut line = format!("{} {:<5} ", self.timestamp_rfc3339(), self.level);
+                if let Some(node) = &self.node_id {
+                    line.push_str(&format!("[{}]", node));
+                }
+                let source = self.component.as_deref().unwrap_or(&self.target);
+                line.push_str(&format!("[{}] {}", source, self.message));
+                line
+            }
+            LogFormat::Json => serde_json::json!({
+                "timestamp": self.timestamp_rfc3339(),
+                "level": self.level.as_str(),
+                "node_id": self.node_id,
+                "component": self.component,
+                "target": self.target,
+                "message": self.message,
+            })
+            .to_string(),
+            LogFormat::Pretty => self.format_pretty(false),
+        }
+    }
+
+    /// Format the record as a single line for a terminal (`Pretty` lines
+    /// are colored with ANSI escapes, other formats are unchanged)
+    pub fn format_for_terminal(&self, format: LogFormat) -> String {
+        match format {
+            LogFormat::Pretty => self.format_pretty(true),
+            other => self.format(other),
+        }
+    }
+
+    fn format_pretty(&self, colored: bool) -> String {
+        let millis = time::to_epoch_millis(self.timestamp) as i64;
+        let clock = chrono::DateTime::from_timestamp_millis(millis)
+            .unwrap_or_default()
+            .format("%H:%M:%S%.3f");
+        let (dim, reset) = if colored {
+            (ANSI_DIM, ANSI_RESET)
+        } else {
+            ("", "")
+        };
+        let color = match self.level {
+            _ if !colored => "",
+            Level::Error => "\x1b[1;31m",
+            Level::Warn => "\x1b[33m",
+            Level::Info => "\x1b[32m",
+            Level::Debug => "\x1b[34m",
+            Level::Trace => "\x1b[35m",
+        };
+        let source = self.component.as_deref().unwrap_or(&self.target);
+        let mut line = format!(
+            "{dim}{clock}{reset} {color}{level:<5}{reset} {source:<width$} ",
+            level = self.level,
+            source = fit_column(source, PRETTY_COMPONENT_WIDTH),
+            width = PRETTY_COMPONENT_WIDTH,
+        );
+        if let Some(node) = &self.node_id {
+            line.push_str(&format!("{}{}{} ", dim, node, reset));
+        }
+        line.push_str(&self.message);
+        line
+    }
+}
+
+// Cut `text` to `width` characters, marking the cut with '…'
+fn fit_column(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width - 1).collect();
+    cut.push('…');
+    cut
+}
+
+/// Destination for formatted log lines
+pub trait LogSink: Send + Sync {
+    /// Write one record; `line` is the record in the backend's format
+    fn write(&self, record: &LogRecord, line: &str);
+
+    /// Flush buffered output
+    fn flush(&self) {}
+
+    /// Name used to enable or disable the sink at runtime
+    fn name(&self) -> &str {
+        "custom"
+    }
+
+    /// Whether the sink writes to a terminal, so `Pretty` lines it gets
+    /// are colored
+    fn is_terminal(&self) -> bool {
+        false
+    }
+}
+
+/// Writes lines to standard error
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StderrSink;
+
+impl LogSink for StderrSink {
+    fn write(&self, _record: &LogRecord, line: &str) {
+        eprintln!("{}", line);
+    }
+
+    fn name(&self) -> &str {
+        "stderr"
+    }
+
+    fn is_terminal(&self) -> bool {
+        static STDERR_IS_TERMINAL: OnceLock<bool> = OnceLock::new();
+        *STDERR_IS_TERMINAL.get_or_init(|| std::io::stderr().is_terminal())
+    }
+}
+
+/// Appends lines to a file
+pub struct FileSink {
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl FileSink {
+    /// Open (or create) a log file for appending
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| anyhow!("Cannot open log file {}: {}", path.display(), e))?;
+        Ok(FileSink {
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+}
+
+impl LogSink for FileSink {
+    fn write(&self, _record: &LogRecord, line: &str) {
+        let mut writer = self
+            .writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let _ = writeln!(writer, "{}", line);
+    }
+
+    fn flush(&self) {
+        let mut writer = self
+            .writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let _ = writer.flush();
+    }
+
+    fn name(&self) -> &str {
+        "file"
+    }
+}
+
+/// Keeps records in memory, e.g. to assert on log output in tests.
+/// Clones share the same buffer.
+#[derive(Clone, Default)]
+pub struct MemorySink {
+    entries: Arc<Mutex<Vec<(LogRecord, String)>>>,
+}
+
+impl MemorySink {
+    /// Create an empty sink
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records written so far
+    pub fn records(&self) -> Vec<LogRecord> {
+        self.lock()
+            .iter()
+            .map(|(record, _)| record.clone())
+            .collect()
+    }
+
+    /// Formatted lines written so far
+    pub fn lines(&self) -> Vec<String> {
+        self.lock().iter().map(|(_, line)| line.clone()).collect()
+    }
+
+    /// Discard everything written so far
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<(LogRecord, String)>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl LogSink for MemorySink {
+    fn write(&self, record: &LogRecord, line: &str) {
+        self.lock().push((record.clone(), line.to_string()));
+    }
+
+    fn name(&self) -> &str {
+        "memory"
+    }
+}
+
+/// Sink with the name it is addressed by in the runtime configuration
+pub(super) struct NamedSink {
+    pub(super) name: String,
+    pub(super) sink: Arc<dyn LogSink>,
+    pub(super) enabled: bool,
+}
+
+/// Keep one record in `every` for records matching `name`
+pub(super) struct SamplingRule {
+    pub(super) name: Glob,
+    pub(super) every: u64,
+    seen: AtomicU64,
+}
+
+impl SamplingRule {
+    pub(super) fn new(name: Glob, every: u64) -> Self {
+        SamplingRule {
+            name,
+            every,
+            seen: AtomicU64::new(0),
+        }
+    }
+
+    fn keep(&self) -> bool {
+        self.seen
+            .fetch_add(1, Ordering::Relaxed)
+            .is_multiple_of(self.every)
+    }
+}
+
+/// Settings of a backend that can change while it is installed
+pub(super) struct BackendState {
+    pub(super) level: LevelFilter,
+    /// Level overrides by component or target prefix
+    pub(super) overrides: Vec<(Glob, LevelFilter)>,
+    pub(super) format: LogFormat,
+    pub(super) sinks: Vec<NamedSink>,
+    pub(super) sampling: Vec<SamplingRule>,
+}
+
+impl BackendState {
+    fn enabled_sinks(&self) -> Vec<Arc<dyn LogSink>> {
+        self.sinks
+            .iter()
+            .filter(|named| named.enabled)
+            .map(|named| named.sink.clone())
+            .collect()
+    }
+
+    pub(super) fn max_level(&self) -> LevelFilter {
+        self.overrides
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.level, Ord::max)
+    }
+
+    pub(super) fn level_for(&self, component: Option<&str>, target: &str) -> LevelFilter {
+        self.overrides
+            .iter()
+            .filter(|(name, _)| rule_matches(name, component, target))
+            .max_by_key(|(name, _)| name.as_str().len())
+            .map_or(self.level, |(_, level)| *level)
+    }
+}
+
+/// Whether a level or sampling rule named `name` applies to a record: the
+/// name matches one of the components of the Logger prefix, or a target
+/// prefix ending at a `::` boundary
+fn rule_matches(name: &Glob, component: Option<&str>, target: &str) -> bool {
+    let matches_component = component
+        .and_then(|prefix| prefix.split('|').next())
+        .is_some_and(|prefix| prefix.split('.').any(|part| name.matches(part)));
+    let matches_target = target
+        .match_indices("::")
+        .map(|(end, _)| &target[..end])
+        .chain(std::iter::once(target))
+        .any(|prefix| name.matches(prefix));
+    matches_component || matches_target
+}
+
+/// Compile a rule name; names that are not valid globs match literally
+pub(super) fn rule_name(name: &str) -> Glob {
+    Glob::new(name).unwrap_or_else(|_| Glob::literal(name))
+}
+
+/// `log::Log` implementation routing all `log` output through level
+/// filters, sampling, a formatter and a set of sinks.
+///
+/// Levels, format, sinks and sampling can be changed after installation
+/// (see `logging::apply_config_value`).
+pub struct RunarLogBackend {
+    node_id: Option<NodeId>,
+    clock: Arc<dyn Clock>,
+    pub(super) state: RwLock<BackendState>,
+}
+
+impl RunarLogBackend {
+    /// Create a backend logging at `level` and above, with no sinks
+    pub fn new(level: LevelFilter) -> Self {
+        RunarLogBackend {
+            node_id: None,
+            clock: Arc::new(SystemClock),
+            state: RwLock::new(BackendState {
+                level,
+                overrides: Vec::new(),
+                format: LogFormat::default(),
+                sinks: Vec::new(),
+                sampling: Vec::new(),
+            }),
+        }
+    }
+
+    /// Set the output format
+    pub fn with_format(mut self, format: LogFormat) -> Self {
+        self.state_mut().format = format;
+        self
+    }
+
+    /// Tag records from third-party crates with this node ID
+    pub fn with_node_id(mut self, node_id: NodeId) -> Self {
+        self.node_id = Some(node_id);
+        self
+    }
+
+    /// Take record timestamps from `clock` instead of the system clock,
+    /// e.g. a `MockClock` for stable output in tests
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Override the level for a component (e.g. `Registry`, matched against
+    /// the components of the Logger prefix) or a target prefix (e.g. `hyper`).
+    /// Names may be globs (`Net*`, `runar_*`); the longest matching name wins.
+    pub fn with_level_for(mut self, name: impl Into<String>, level: LevelFilter) -> Self {
+        self.state_mut()
+            .overrides
+            .push((rule_name(&name.into()), level));
+        self
+    }
+
+    /// Keep only one in `every` records of a component or target prefix
+    /// (matched like `with_level_for`)
+    pub fn with_sampling(mut self, name: impl Into<String>, every: u64) -> Self {
+        self.state_mut()
+            .sampling
+            .push(SamplingRule::new(rule_name(&name.into()), every.max(1)));
+        self
+    }
+
+    /// Add a sink, addressed by its `LogSink::name` in the runtime configuration
+    pub fn with_sink(self, sink: impl LogSink + 'static) -> Self {
+        self.with_shared_sink(Arc::new(sink))
+    }
+
+    /// Add a sink that is also held elsewhere
+    pub fn with_shared_sink(mut self, sink: Arc<dyn LogSink>) -> Self {
+        let name = sink.name().to_string();
+        self.state_mut().sinks.push(NamedSink {
+            name,
+            sink,
+            enabled: true,
+        });
+        self
+    }
+
+    /// The output format
+    pub fn format(&self) -> LogFormat {
+        self.state().format
+    }
+
+    /// The most verbose level any filter lets through
+    pub fn max_level(&self) -> LevelFilter {
+        self.state().max_level()
+    }
+
+    /// The level that applies to records of a component and target
+    pub fn level_for(&self, component: Option<&str>, target: &str) -> LevelFilter {
+        self.state().level_for(component, target)
+    }
+
+    pub(super) fn state(&self) -> RwLockReadGuard<'_, BackendState> {
+        self.state
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn state_mut(&mut self) -> &mut BackendState {
+        self.state
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Log for RunarLogBackend {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let record = LogRecord::from_log_at(record, self.node_id.as_ref(), self.clock.now());
+        let component = record.component.as_deref();
+        let state = self.state();
+        if record.level > state.level_for(component, &record.target) {
+            return;
+        }
+        let sampled_out = state
+            .sampling
+            .iter()
+            .filter(|rule| rule_matches(&rule.name, component, &record.target))
+            .max_by_key(|rule| rule.name.as_str().len())
+            .is_some_and(|rule| !rule.keep());
+        if sampled_out {
+            return;
+        }
+        let format = state.format;
+        let sinks = state.enabled_sinks();
+        // Sinks run without the lock: one that logs, or a reconfiguration
+        // waiting for the write lock, must not deadlock the backend
+        drop(state);
+        let line = record.format(format);
+        let mut terminal_line = None;
+        for sink in sinks {
+            if format == LogFormat::Pretty && sink.is_terminal() {
+                let line = terminal_line.get_or_insert_with(|| record.format_for_terminal(format));
+                sink.write(&record, line);
+            } else {
+                sink.write(&record, &line);
+            }
+        }
+    }
+
+    fn flush(&self) {
+        let sinks: Vec<_> = self
+            .state()
+            .sinks
+            .iter()
+            .map(|named| named.sink.clone())
+            .collect();
+        for sink in sinks {
+            sink.flush();
+        }
+    }
+}
+
+// Forwards to the installed backend, which stays reachable through `installed()`
+struct InstalledBackend(Arc<RunarLogBackend>);
+
+impl Log for InstalledBackend {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.0.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        self.0.log(record)
+    }
+
+    fn flush(&self) {
+        self.0.flush()
+    }
+}
+
+static INSTALLED: OnceLock<Arc<RunarLogBackend>> = OnceLock::new();
+
+/// Install `backend` as the global `log` backend.
+///
+/// A backend without sinks writes to stderr. Fails if a `log` backend
+/// (this or another, e.g. env_logger) is already installed.
+pub fn install(mut backend: RunarLogBackend) -> Result<Arc<RunarLogBackend>> {
+    if backend.state_mut().sinks.is_empty() {
+        backend = backend.with_sink(StderrSink);
+    }
+    let backend = Arc::new(backend);
+    log::set_boxed_logger(Box::new(InstalledBackend(backend.clone())))
+        .map_err(|e| anyhow!("Cannot install log backend: {}", e))?;
+    log::set_max_level(backend.max_level());
+    let _ = INSTALLED.set(backend.clone());
+    Ok(backend)
+}
+
+/// Install a backend writing `LogFormat::Pretty` lines to stderr, for local
+/// runs. The level is taken from `RUNAR_LOG` (e.g. `debug`), default `info`.
+pub fn init_pretty() -> Result<Arc<RunarLogBackend>> {
+    let level = std::env::var("RUNAR_LOG")
+        .ok()
+        .and_then(|level| level.parse().ok())
+        .unwrap_or(LevelFilter::Info);
+    install(RunarLogBackend::new(level).with_format(LogFormat::Pretty))
+}
+
+/// The backend installed with `install` (if any)
+pub fn installed() -> Option<Arc<RunarLogBackend>> {
+    INSTALLED.get().cloned()
+}
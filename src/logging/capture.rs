@@ -0,0 +1,152 @@
+// runar_common/src/logging/capture.rs
+//
+// Log capture for integration tests that run several nodes in one process.
+//
+// Every node's `Logger` tags its records with its node ID, so a single
+// `NodeLogCapture` sink on the installed backend collects all nodes into one
+// timeline. Tests can then look at one node's records, or assert that
+// something on one node was logged before something on another. Failed
+// assertions print the whole timeline.
+
+use std::fmt::Write;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use super::backend::{LogRecord, LogSink};
+
+/// A `LogSink` keeping the records of all nodes in the order they were logged
+#[derive(Debug, Clone, Default)]
+pub struct NodeLogCapture {
+    records: Arc<Mutex<Vec<LogRecord>>>,
+}
+
+impl NodeLogCapture {
+    /// Create an empty capture
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All records, in the order they were logged
+    pub fn records(&self) -> Vec<LogRecord> {
+        self.lock().clone()
+    }
+
+    /// Records of one node, in the order they were logged
+    pub fn for_node(&self, node_id: &str) -> Vec<LogRecord> {
+        self.lock()
+            .iter()
+            .filter(|record| record.node_id.as_deref() == Some(node_id))
+            .cloned()
+            .collect()
+    }
+
+    /// Messages of one node, in the order they were logged
+    pub fn messages_for(&self, node_id: &str) -> Vec<String> {
+        self.for_node(node_id)
+            .into_iter()
+            .map(|record| record.message)
+            .collect()
+    }
+
+    /// IDs of the nodes that logged something, in order of their first record
+    pub fn nodes(&self) -> Vec<String> {
+        let mut nodes: Vec<String> = Vec::new();
+        for node in self
+            .lock()
+            .iter()
+            .filter_map(|record| record.node_id.as_ref())
+        {
+            if !nodes.contains(node) {
+                nodes.push(node.clone());
+            }
+        }
+        nodes
+    }
+
+    /// Position in the timeline of the first record of `node_id` whose
+    /// message contains `needle`
+    pub fn position(&self, node_id: &str, needle: &str) -> Option<usize> {
+        self.lock().iter().position(|record| {
+            record.node_id.as_deref() == Some(node_id) && record.message.contains(needle)
+        })
+    }
+
+    /// Panic unless `first` (node ID, message substring) was logged before `second`
+    pub fn assert_before(&self, first: (&str, &str), second: (&str, &str)) {
+        let position = |(node, needle): (&str, &str)| {
+            self.position(node, needle).unwrap_or_else(|| {
+                panic!(
+                    "no record from {} containing '{}'\n{}",
+                    node,
+                    needle,
+                    self.timeline()
+                )
+            })
+        };
+        let (a, b) = (position(first), position(second));
+        assert!(
+            a < b,
+            "expected [{}] '{}' before [{}] '{}'\n{}",
+            first.0,
+            first.1,
+            second.0,
+            second.1,
+            self.timeline()
+        );
+    }
+
+    /// Panic unless `node_id` logged messages containing `needles`, in that
+    /// order (other records may come in between)
+    pub fn assert_node_order(&self, node_id: &str, needles: &[&str]) {
+        let messages = self.messages_for(node_id);
+        let mut remaining = messages.iter();
+        for needle in needles {
+            assert!(
+                remaining.any(|message| message.contains(needle)),
+                "expected [{}] to log {:?} in order, missing '{}'\n{}",
+                node_id,
+                needles,
+                needle,
+                self.timeline()
+            );
+        }
+    }
+
+    /// The timeline as text, one `#index LEVEL [node][component] message` line
+    /// per record
+    pub fn timeline(&self) -> String {
+        let mut out = String::new();
+        for (index, record) in self.lock().iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "#{:<4} {:<5} [{}][{}] {}",
+                index,
+                record.level,
+                record.node_id.as_deref().unwrap_or("-"),
+                record.component.as_deref().unwrap_or(&record.target),
+                record.message
+            );
+        }
+        out
+    }
+
+    /// Discard everything captured so far
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<LogRecord>> {
+        self.records
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl LogSink for NodeLogCapture {
+    fn write(&self, record: &LogRecord, _line: &str) {
+        self.lock().push(record.clone());
+    }
+
+    fn name(&self) -> &str {
+        "capture"
+    }
+}
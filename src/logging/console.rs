@@ -0,0 +1,53 @@
+// Browser console log sink (wasm32 only)
+//
+// Routes `log` records to the browser console so the same `Logger` prefixes
+// and formatting are used in the browser SDK as on native nodes.
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use wasm_bindgen::JsValue;
+use web_sys::console;
+
+/// A `log::Log` implementation that writes to the browser console
+pub struct ConsoleLogger {
+    level: LevelFilter,
+}
+
+impl ConsoleLogger {
+    /// Create a console logger that emits records at `level` and above
+    pub fn new(level: LevelFilter) -> Self {
+        Self { level }
+    }
+}
+
+impl Log for ConsoleLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = JsValue::from_str(&format_record(record));
+        match record.level() {
+            Level::Error => console::error_1(&line),
+            Level::Warn => console::warn_1(&line),
+            Level::Info => console::info_1(&line),
+            Level::Debug | Level::Trace => console::debug_1(&line),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Format a record the way the native log output does: "LEVEL [target] message"
+pub fn format_record(record: &Record) -> String {
+    format!("{} [{}] {}", record.level(), record.target(), record.args())
+}
+
+/// Install the console logger as the global `log` backend
+pub fn init(level: LevelFilter) -> Result<(), log::SetLoggerError> {
+    log::set_boxed_logger(Box::new(ConsoleLogger::new(level)))?;
+    log::set_max_level(level);
+    Ok(())
+}
@@ -0,0 +1,121 @@
+// runar_common/src/logging/events.rs
+//
+// Publishes WARN and ERROR records as events, so remote operators can
+// subscribe to a node's error stream like to any other topic.
+//
+// Each record becomes an `EventEnvelope` on `system/logs/warn` or
+// `system/logs/error`, sent without waiting on the channel the node's event
+// dispatcher reads from. A token bucket limits the publishing rate; the
+// number of records dropped by the limit is carried in the next event.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use log::Level;
+
+use super::backend::{LogRecord, LogSink};
+use crate::types::{ArcValueType, EventEnvelope, NodeId};
+use crate::utils::channel::BoundedSender;
+use crate::utils::paths::TopicPath;
+use crate::utils::rate_limit::{RateLimit, TokenBucket};
+use crate::utils::time;
+
+/// Topic prefix of log events; the lowercase level is appended
+pub const LOG_EVENT_TOPIC_PREFIX: &str = "system/logs";
+
+/// Default publishing limit: 10 events per second, bursts of 50
+pub const DEFAULT_LOG_EVENT_RATE: RateLimit = RateLimit {
+    per_second: 10.0,
+    burst: 50,
+};
+
+thread_local! {
+    // Set while publishing, so records logged by the channel itself are skipped
+    static PUBLISHING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A `LogSink` publishing WARN and ERROR records as `EventEnvelope`s.
+///
+/// The payload is a Map with `level`, `target`, `node_id`, `component`,
+/// `message`, `timestamp_millis` and `suppressed` (records dropped by the
+/// rate limit since the previous event).
+pub struct EventLogSink {
+    publisher: NodeId,
+    sender: BoundedSender<EventEnvelope>,
+    limiter: TokenBucket,
+    suppressed: AtomicU64,
+}
+
+impl EventLogSink {
+    /// Publish events from `publisher` on `sender`, limited to `DEFAULT_LOG_EVENT_RATE`
+    pub fn new(publisher: NodeId, sender: BoundedSender<EventEnvelope>) -> Self {
+        EventLogSink {
+            publisher,
+            sender,
+            limiter: TokenBucket::new(DEFAULT_LOG_EVENT_RATE),
+            suppressed: AtomicU64::new(0),
+        }
+    }
+
+    /// Set the publishing limit
+    pub fn with_rate_limit(mut self, limit: RateLimit) -> Self {
+        self.limiter = TokenBucket::new(limit);
+        self
+    }
+
+    /// Records dropped by the rate limit and not yet reported in an event
+    pub fn suppressed(&self) -> u64 {
+        self.suppressed.load(Ordering::Relaxed)
+    }
+
+    /// The topic log events of `level` are published on
+    pub fn topic_for(level: Level) -> TopicPath {
+        let path = format!(
+            "{}/{}",
+            LOG_EVENT_TOPIC_PREFIX,
+            level.as_str().to_ascii_lowercase()
+        );
+        TopicPath::new(&path).expect("log event topics are valid")
+    }
+
+    fn to_event(&self, record: &LogRecord, suppressed: u64) -> EventEnvelope {
+        let payload = ArcValueType::from_json(serde_json::json!({
+            "level": record.level.as_str(),
+            "target": record.target,
+            "node_id": record.node_id,
+            "component": record.component,
+            "message": record.message,
+            "timestamp_millis": time::to_epoch_millis(record.timestamp),
+            "suppressed": suppressed,
+        }));
+        let mut event = EventEnvelope::new(
+            Self::topic_for(record.level),
+            self.publisher.clone(),
+            payload,
+        );
+        event.timestamp_millis = time::to_epoch_millis(record.timestamp);
+        event
+    }
+}
+
+impl LogSink for EventLogSink {
+    fn write(&self, record: &LogRecord, _line: &str) {
+        if record.level > Level::Warn || PUBLISHING.with(Cell::get) {
+            return;
+        }
+        if !self.limiter.try_acquire() {
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        let suppressed = self.suppressed.swap(0, Ordering::Relaxed);
+        let event = self.to_event(record, suppressed);
+        PUBLISHING.with(|publishing| publishing.set(true));
+        // A full or closed channel drops the event; the channel reports it
+        let _ = self.sender.try_send(event);
+        PUBLISHING.with(|publishing| publishing.set(false));
+    }
+
+    fn name(&self) -> &str {
+        "events"
+    }
+}
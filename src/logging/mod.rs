@@ -9,9 +9,43 @@
 
 use log::{debug, error, info, warn};
 
+use crate::types::{NodeId, TraceContext};
+
 // Include macros submodule
 pub mod macros;
 
+// Runtime configuration for the admin action
+pub mod admin;
+
+// Audit trail records and sinks
+pub mod audit;
+
+// `log::Log` backend with sinks and formatters
+pub mod backend;
+
+// Capturing the logs of several in-process nodes in tests
+pub mod capture;
+
+// WARN and ERROR records published as events
+pub mod events;
+
+pub use admin::{apply_config_value, config_as_value};
+pub use audit::{
+    set_audit_sink, AuditDecision, AuditRecord, AuditSink, FileAuditSink, MemoryAuditSink,
+    StderrAuditSink,
+};
+
+pub use backend::{
+    init_pretty, install, installed, FileSink, LogFormat, LogRecord, LogSink, MemorySink,
+    RunarLogBackend, StderrSink, PRETTY_COMPONENT_WIDTH,
+};
+pub use capture::NodeLogCapture;
+pub use events::{EventLogSink, DEFAULT_LOG_EVENT_RATE, LOG_EVENT_TOPIC_PREFIX};
+
+// Browser console sink for the wasm32 build
+#[cfg(target_arch = "wasm32")]
+pub mod console;
+
 /// Predefined components for logging categorization
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Component {
@@ -47,25 +81,31 @@ pub struct Logger {
     /// Component this logger is for
     component: Component,
     /// Node ID for distributed tracing
-    node_id: String,
+    node_id: NodeId,
     /// Parent component for hierarchical logging (if any)
     parent_component: Option<Component>,
     /// Action path for request/action tracing
     action_path: Option<String>,
     /// Event path for event subscription tracing
     event_path: Option<String>,
+    /// Correlation ID for tracing an action chain across nodes
+    correlation_id: Option<String>,
+    /// W3C trace context of the current operation
+    trace: Option<TraceContext>,
 }
 
 impl Logger {
     /// Create a new root logger for a specific component and node ID
     /// This should only be called by the Node root component
-    pub fn new_root(component: Component, node_id: &str) -> Self {
+    pub fn new_root(component: Component, node_id: NodeId) -> Self {
         Self {
             component,
-            node_id: node_id.to_string(),
+            node_id,
             parent_component: None,
             action_path: None,
             event_path: None,
+            correlation_id: None,
+            trace: None,
         }
     }
 
@@ -78,6 +118,8 @@ impl Logger {
             parent_component: Some(self.component),
             action_path: self.action_path.clone(),
             event_path: self.event_path.clone(),
+            correlation_id: self.correlation_id.clone(),
+            trace: self.trace.clone(),
         }
     }
 
@@ -90,11 +132,14 @@ impl Logger {
             parent_component: self.parent_component,
             action_path: Some(path.into()),
             event_path: self.event_path.clone(),
+            correlation_id: self.correlation_id.clone(),
+            trace: self.trace.clone(),
         }
     }
 
     /// Create a logger with an event path
-    /// This is used to track event publications and subscriptions
+    /// This is used to track event publications and subscriptions.
+    /// Prefer passing an `EventPath` over a hand-built string.
     pub fn with_event_path(&self, path: impl Into<String>) -> Self {
         Self {
             component: self.component,
@@ -102,6 +147,31 @@ impl Logger {
             parent_component: self.parent_component,
             action_path: self.action_path.clone(),
             event_path: Some(path.into()),
+            correlation_id: self.correlation_id.clone(),
+            trace: self.trace.clone(),
+        }
+    }
+
+    /// Create a logger with a correlation ID
+    /// This is used to trace an action chain across nodes
+    pub fn with_correlation_id(&self, id: impl Into<String>) -> Self {
+        Self {
+            component: self.component,
+            node_id: self.node_id.clone(),
+            parent_component: self.parent_component,
+            action_path: self.action_path.clone(),
+            event_path: self.event_path.clone(),
+            correlation_id: Some(id.into()),
+            trace: self.trace.clone(),
+        }
+    }
+
+    /// Create a logger for an operation that is part of a trace.
+    /// Messages are tagged with the trace and span IDs.
+    pub fn with_trace(&self, trace: &TraceContext) -> Self {
+        Self {
+            trace: Some(trace.clone()),
+            ..self.clone()
         }
     }
 
@@ -111,8 +181,13 @@ impl Logger {
         self.clone()
     }
 
+    /// Get the component this logger is for
+    pub fn component(&self) -> Component {
+        self.component
+    }
+
     /// Get a reference to the node ID
-    pub fn node_id(&self) -> &str {
+    pub fn node_id(&self) -> &NodeId {
         &self.node_id
     }
 
@@ -126,6 +201,16 @@ impl Logger {
         self.event_path.as_deref()
     }
 
+    /// Get a reference to the correlation ID if available
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.correlation_id.as_deref()
+    }
+
+    /// Get the trace context if available
+    pub fn trace(&self) -> Option<&TraceContext> {
+        self.trace.as_ref()
+    }
+
     /// Get the component prefix for logging, including parent if available
     fn component_prefix(&self) -> String {
         match self.parent_component {
@@ -153,78 +238,98 @@ impl Logger {
             parts.push(format!("event={}", path));
         }
 
+        // Add correlation ID if available
+        if let Some(id) = &self.correlation_id {
+            parts.push(format!("cid={}", id));
+        }
+
+        // Add trace and span IDs if available
+        if let Some(trace) = &self.trace {
+            parts.push(format!(
+                "trace={}/{}",
+                trace.trace_id_hex(),
+                trace.span_id_hex()
+            ));
+        }
+
         parts.join("|")
     }
 
     /// Log a debug message
     pub fn debug(&self, message: impl Into<String>) {
-        if log::log_enabled!(log::Level::Debug) {
-            // Skip displaying the component if it's Node to avoid redundancy
-            if self.component == Component::Node && self.parent_component.is_none() {
-                debug!("[{}] {}", self.node_id, message.into());
-            } else {
-                debug!(
-                    "[{}][{}] {}",
-                    self.node_id,
-                    self.full_prefix(),
-                    message.into()
-                );
-            }
-        }
+        self.emit(log::Level::Debug, message.into());
     }
 
     /// Log an info message
     pub fn info(&self, message: impl Into<String>) {
-        if log::log_enabled!(log::Level::Info) {
-            // Skip displaying the component if it's Node to avoid redundancy
-            if self.component == Component::Node && self.parent_component.is_none() {
-                info!("[{}] {}", self.node_id, message.into());
-            } else {
-                info!(
-                    "[{}][{}] {}",
-                    self.node_id,
-                    self.full_prefix(),
-                    message.into()
-                );
-            }
-        }
+        self.emit(log::Level::Info, message.into());
     }
 
     /// Log a warning message
     pub fn warn(&self, message: impl Into<String>) {
-        if log::log_enabled!(log::Level::Warn) {
-            // Skip displaying the component if it's Node to avoid redundancy
-            if self.component == Component::Node && self.parent_component.is_none() {
-                warn!("[{}] {}", self.node_id, message.into());
-            } else {
-                warn!(
-                    "[{}][{}] {}",
-                    self.node_id,
-                    self.full_prefix(),
-                    message.into()
-                );
-            }
-        }
+        self.emit(log::Level::Warn, message.into());
     }
 
     /// Log an error message
     pub fn error(&self, message: impl Into<String>) {
-        if log::log_enabled!(log::Level::Error) {
-            // Skip displaying the component if it's Node to avoid redundancy
-            if self.component == Component::Node && self.parent_component.is_none() {
-                error!("[{}] {}", self.node_id, message.into());
-            } else {
-                error!(
-                    "[{}][{}] {}",
-                    self.node_id,
-                    self.full_prefix(),
-                    message.into()
-                );
-            }
+        self.emit(log::Level::Error, message.into());
+    }
+
+    /// Write an audit record, tagged with this logger's node ID and
+    /// correlation ID. Audit records bypass level filtering and go to the
+    /// audit sink (see `set_audit_sink`).
+    pub fn audit(&self, record: AuditRecord) -> anyhow::Result<()> {
+        let record = record.with_context(self.node_id.as_str(), self.correlation_id.as_deref());
+        audit::write_audit(&record)
+    }
+
+    // The node ID and prefix are also attached as `node_id` and `component`
+    // key-values, so `RunarLogBackend` can report them as structured fields
+    fn emit(&self, level: log::Level, message: String) {
+        if !log::log_enabled!(level) {
+            return;
+        }
+        let node_id = self.node_id.as_str();
+        // Skip displaying the component if it's Node to avoid redundancy
+        if self.component == Component::Node && self.parent_component.is_none() {
+            log::log!(level, node_id = node_id; "[{}] {}", node_id, message);
+        } else {
+            let prefix = self.full_prefix();
+            log::log!(
+                level,
+                node_id = node_id,
+                component = prefix.as_str();
+                "[{}][{}] {}",
+                node_id,
+                prefix,
+                message
+            );
         }
     }
 }
 
+/// Source of component loggers.
+///
+/// Libraries can take a `&dyn LoggerFactory` instead of a `Logger` and create
+/// their own child loggers, parented to the node's root logger, without
+/// knowing the node ID or how the node sets up logging.
+pub trait LoggerFactory: Send + Sync {
+    /// Create a logger for `component`
+    fn logger_for(&self, component: Component) -> Logger;
+}
+
+impl LoggerFactory for Logger {
+    fn logger_for(&self, component: Component) -> Logger {
+        self.with_component(component)
+    }
+}
+
+impl<T: LoggerFactory + ?Sized> LoggerFactory for std::sync::Arc<T> {
+    fn logger_for(&self, component: Component) -> Logger {
+        (**self).logger_for(component)
+    }
+}
+
 /// Logging context for structured logging with additional context
 pub trait LoggingContext {
     /// Get the component
@@ -39,7 +39,7 @@ mod vmap_macros;
 /// let empty = vmap!{};
 /// ```
 // vmap! is defined in vmap_macros.rs
-
+///
 /// Create a HashMap with key-value pairs
 ///
 /// This macro allows you to create a HashMap with string keys and arbitrary values.
@@ -60,7 +60,7 @@ mod vmap_macros;
 /// use runar_common::hmap;
 /// use std::collections::HashMap;
 /// // Create an empty map
-/// let empty = hmap!{};
+/// let empty: HashMap<String, f64> = hmap!{};
 /// ```
 #[macro_export]
 macro_rules! hmap {
@@ -160,3 +160,65 @@ macro_rules! vmap {
         }
     };
 }
+
+/// Create an ArcValueType map keyed by a non-string type such as `i64`, `u64` or `Uuid`
+///
+/// The key type is given first so integer literals need no suffix.
+///
+/// # Examples
+///
+/// ```
+/// use runar_common::vmap_keyed;
+///
+/// let routes = vmap_keyed! { u64; 1 => "node-a", 2 => "node-b" };
+///
+/// // Create an empty map
+/// let empty = vmap_keyed! { i64; };
+/// ```
+#[macro_export]
+macro_rules! vmap_keyed {
+    { $key_type:ty; $($key:expr => $value:expr),* $(,)? } => {
+        {
+            use std::collections::HashMap;
+            use $crate::types::ArcValueType;
+            #[allow(unused_mut)]
+            let mut map: HashMap<$key_type, ArcValueType> = HashMap::new();
+            $(
+                let key: $key_type = $key;
+                map.insert(key, ArcValueType::new_primitive($value));
+            )*
+            ArcValueType::new_map(map)
+        }
+    };
+}
+
+/// Look up a key of an ArcValueType map, telling "set to null" apart from
+/// "left out"
+///
+/// Evaluates to `anyhow::Result<EntryState<ArcValueType>>`, or to
+/// `anyhow::Result<EntryState<T>>` when a target type is given.
+///
+/// # Examples
+///
+/// ```
+/// use runar_common::types::{ArcValueType, EntryState};
+/// use runar_common::{vmap, vmap_entry};
+///
+/// let mut params = vmap! { "name" => "probe".to_string() };
+/// params.update_map(|map| map.insert("site".to_string(), ArcValueType::null())).unwrap();
+///
+/// assert_eq!(vmap_entry!(params, "name" => String).unwrap(), EntryState::Present("probe".to_string()));
+/// assert!(vmap_entry!(params, "site").unwrap().is_null());
+/// assert!(vmap_entry!(params, "owner").unwrap().is_missing());
+/// ```
+#[macro_export]
+macro_rules! vmap_entry {
+    ($map:expr, $key:expr => $t:ty) => {
+        $crate::vmap_entry!($map, $key).and_then(|state| {
+            state.try_map(|value| <$t as $crate::types::FromArcValue>::from_arc_value(value))
+        })
+    };
+    ($map:expr, $key:expr) => {
+        $crate::types::ArcValueType::entry_state(&mut ::std::clone::Clone::clone(&$map), $key)
+    };
+}
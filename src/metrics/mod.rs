@@ -0,0 +1,408 @@
+// runar_common/src/metrics/mod.rs
+//
+// Lightweight metrics primitives shared by the node and network crates.
+//
+// Metrics are identified by a name plus `MetricLabels` (component, and
+// optionally service and action). Counters, gauges and histograms are
+// lock-free on the hot path; the registry only takes a lock when a metric
+// is first created or when a snapshot is taken.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use lazy_static::lazy_static;
+
+use crate::logging::Component;
+
+// Prometheus text exposition format
+mod prometheus;
+// Per-action latency timers
+mod timing;
+// Snapshots as ArcValueType maps
+mod value;
+
+pub use prometheus::{prometheus_text, PROMETHEUS_CONTENT_TYPE};
+pub use timing::{
+    set_slow_action_threshold, slow_action_threshold, time_action, ActionTimer,
+    ACTION_DURATION_METRIC, DEFAULT_SLOW_ACTION_THRESHOLD,
+};
+pub use value::{snapshot_as_value, snapshot_schema};
+
+/// Default histogram buckets (in seconds), suitable for request latencies
+pub const DEFAULT_BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Labels attached to every metric
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MetricLabels {
+    /// The component recording the metric
+    pub component: String,
+    /// The service the metric relates to (if any)
+    pub service: Option<String>,
+    /// The action the metric relates to (if any)
+    pub action: Option<String>,
+}
+
+impl MetricLabels {
+    /// Create labels for a component
+    pub fn component(component: Component) -> Self {
+        Self {
+            component: component.as_str().to_string(),
+            service: None,
+            action: None,
+        }
+    }
+
+    /// Add a service label
+    pub fn with_service(mut self, service: impl Into<String>) -> Self {
+        self.service = Some(service.into());
+        self
+    }
+
+    /// Add an action label
+    pub fn with_action(mut self, action: impl Into<String>) -> Self {
+        self.action = Some(action.into());
+        self
+    }
+
+    /// Get the labels as (name, value) pairs, omitting unset labels
+    pub fn pairs(&self) -> Vec<(&'static str, &str)> {
+        let mut pairs = vec![("component", self.component.as_str())];
+        if let Some(service) = &self.service {
+            pairs.push(("service", service));
+        }
+        if let Some(action) = &self.action {
+            pairs.push(("action", action));
+        }
+        pairs
+    }
+}
+
+impl fmt::Display for MetricLabels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rendered: Vec<String> = self
+            .pairs()
+            .into_iter()
+            .map(|(k, v)| format!("{}=\"{}\"", k, v))
+            .collect();
+        write!(f, "{{{}}}", rendered.join(","))
+    }
+}
+
+/// A monotonically increasing counter
+#[derive(Debug, Default)]
+pub struct Counter {
+    value: AtomicU64,
+}
+
+impl Counter {
+    /// Increment by one
+    pub fn inc(&self) {
+        self.add(1);
+    }
+
+    /// Increment by `n`
+    pub fn add(&self, n: u64) {
+        self.value.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Get the current value
+    pub fn get(&self) -> u64 {
+        self.value.load(Ordering::Relaxed)
+    }
+}
+
+/// A value that can go up and down
+#[derive(Debug, Default)]
+pub struct Gauge {
+    bits: AtomicU64,
+}
+
+impl Gauge {
+    /// Set the gauge to a value
+    pub fn set(&self, value: f64) {
+        self.bits.store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Add to the gauge (use a negative value to subtract)
+    pub fn add(&self, delta: f64) {
+        update_f64(&self.bits, |v| v + delta);
+    }
+
+    /// Increment by one
+    pub fn inc(&self) {
+        self.add(1.0);
+    }
+
+    /// Decrement by one
+    pub fn dec(&self) {
+        self.add(-1.0);
+    }
+
+    /// Get the current value
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.bits.load(Ordering::Relaxed))
+    }
+}
+
+/// A histogram with fixed, cumulative upper-bound buckets
+#[derive(Debug)]
+pub struct Histogram {
+    bounds: Vec<f64>,
+    // One count per bound, plus a final +Inf bucket
+    buckets: Vec<AtomicU64>,
+    count: AtomicU64,
+    sum_bits: AtomicU64,
+}
+
+impl Histogram {
+    /// Create a histogram with the given bucket upper bounds
+    pub fn with_buckets(bounds: &[f64]) -> Self {
+        let mut bounds: Vec<f64> = bounds.iter().copied().filter(|b| b.is_finite()).collect();
+        bounds.sort_by(|a, b| a.total_cmp(b));
+        bounds.dedup();
+        let buckets = (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect();
+        Self {
+            bounds,
+            buckets,
+            count: AtomicU64::new(0),
+            sum_bits: AtomicU64::new(0f64.to_bits()),
+        }
+    }
+
+    /// Record an observation
+    pub fn observe(&self, value: f64) {
+        let index = self
+            .bounds
+            .iter()
+            .position(|bound| value <= *bound)
+            .unwrap_or(self.bounds.len());
+        self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        update_f64(&self.sum_bits, |sum| sum + value);
+    }
+
+    /// Get the number of observations
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Get the sum of all observations
+    pub fn sum(&self) -> f64 {
+        f64::from_bits(self.sum_bits.load(Ordering::Relaxed))
+    }
+
+    /// Get cumulative (upper bound, count) pairs, ending with the +Inf bucket
+    pub fn cumulative_buckets(&self) -> Vec<(f64, u64)> {
+        let mut total = 0;
+        self.bounds
+            .iter()
+            .copied()
+            .chain(std::iter::once(f64::INFINITY))
+            .zip(&self.buckets)
+            .map(|(bound, count)| {
+                total += count.load(Ordering::Relaxed);
+                (bound, total)
+            })
+            .collect()
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::with_buckets(DEFAULT_BUCKETS)
+    }
+}
+
+/// Atomically apply `f` to an f64 stored as bits
+fn update_f64(bits: &AtomicU64, f: impl Fn(f64) -> f64) {
+    let mut current = bits.load(Ordering::Relaxed);
+    loop {
+        let next = f(f64::from_bits(current)).to_bits();
+        match bits.compare_exchange_weak(current, next, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => return,
+            Err(actual) => current = actual,
+        }
+    }
+}
+
+type MetricKey = (String, MetricLabels);
+
+/// Registry holding all metrics of a process (or of a node, when several share a process)
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    counters: RwLock<HashMap<MetricKey, Arc<Counter>>>,
+    gauges: RwLock<HashMap<MetricKey, Arc<Gauge>>>,
+    histograms: RwLock<HashMap<MetricKey, Arc<Histogram>>>,
+    descriptions: RwLock<HashMap<String, String>>,
+}
+
+/// Get an existing metric or create it with `create`
+fn get_or_create<T>(
+    map: &RwLock<HashMap<MetricKey, Arc<T>>>,
+    name: &str,
+    labels: &MetricLabels,
+    create: impl FnOnce() -> T,
+) -> Arc<T> {
+    let key = (name.to_string(), labels.clone());
+    if let Some(metric) = map
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&key)
+    {
+        return metric.clone();
+    }
+    map.write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .entry(key)
+        .or_insert_with(|| Arc::new(create()))
+        .clone()
+}
+
+/// Collect a sorted snapshot of one metric family map
+fn collect<T, S>(
+    map: &RwLock<HashMap<MetricKey, Arc<T>>>,
+    read: impl Fn(&T) -> S,
+) -> Vec<(String, MetricLabels, S)> {
+    let mut entries: Vec<_> = map
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .iter()
+        .map(|((name, labels), metric)| (name.clone(), labels.clone(), read(metric)))
+        .collect();
+    entries.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+    entries
+}
+
+impl MetricsRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get or create a counter
+    pub fn counter(&self, name: &str, labels: &MetricLabels) -> Arc<Counter> {
+        get_or_create(&self.counters, name, labels, Counter::default)
+    }
+
+    /// Get or create a gauge
+    pub fn gauge(&self, name: &str, labels: &MetricLabels) -> Arc<Gauge> {
+        get_or_create(&self.gauges, name, labels, Gauge::default)
+    }
+
+    /// Get or create a histogram with the default buckets
+    pub fn histogram(&self, name: &str, labels: &MetricLabels) -> Arc<Histogram> {
+        get_or_create(&self.histograms, name, labels, Histogram::default)
+    }
+
+    /// Get or create a histogram with custom buckets.
+    /// Buckets are only applied when the histogram is first created.
+    pub fn histogram_with_buckets(
+        &self,
+        name: &str,
+        labels: &MetricLabels,
+        bounds: &[f64],
+    ) -> Arc<Histogram> {
+        get_or_create(&self.histograms, name, labels, || {
+            Histogram::with_buckets(bounds)
+        })
+    }
+
+    /// Set the help text of a metric (shown as `# HELP` in Prometheus output)
+    pub fn describe(&self, name: &str, help: impl Into<String>) {
+        self.descriptions
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(name.to_string(), help.into());
+    }
+
+    /// Take a point-in-time snapshot of all metrics, sorted by name and labels
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            descriptions: self
+                .descriptions
+                .read()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .iter()
+                .map(|(name, help)| (name.clone(), help.clone()))
+                .collect(),
+            counters: collect(&self.counters, Counter::get)
+                .into_iter()
+                .map(|(name, labels, value)| CounterSample {
+                    name,
+                    labels,
+                    value,
+                })
+                .collect(),
+            gauges: collect(&self.gauges, Gauge::get)
+                .into_iter()
+                .map(|(name, labels, value)| GaugeSample {
+                    name,
+                    labels,
+                    value,
+                })
+                .collect(),
+            histograms: collect(&self.histograms, |h| {
+                (h.cumulative_buckets(), h.count(), h.sum())
+            })
+            .into_iter()
+            .map(|(name, labels, (buckets, count, sum))| HistogramSample {
+                name,
+                labels,
+                buckets,
+                count,
+                sum,
+            })
+            .collect(),
+        }
+    }
+}
+
+lazy_static! {
+    static ref GLOBAL_REGISTRY: MetricsRegistry = MetricsRegistry::new();
+}
+
+/// Get the process-wide metrics registry
+pub fn global() -> &'static MetricsRegistry {
+    &GLOBAL_REGISTRY
+}
+
+/// Point-in-time value of a counter
+#[derive(Debug, Clone, PartialEq)]
+pub struct CounterSample {
+    pub name: String,
+    pub labels: MetricLabels,
+    pub value: u64,
+}
+
+/// Point-in-time value of a gauge
+#[derive(Debug, Clone, PartialEq)]
+pub struct GaugeSample {
+    pub name: String,
+    pub labels: MetricLabels,
+    pub value: f64,
+}
+
+/// Point-in-time state of a histogram
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistogramSample {
+    pub name: String,
+    pub labels: MetricLabels,
+    /// Cumulative (upper bound, count) pairs, ending with +Inf
+    pub buckets: Vec<(f64, u64)>,
+    pub count: u64,
+    pub sum: f64,
+}
+
+/// Snapshot of every metric in a registry
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    /// Help text by metric name
+    pub descriptions: BTreeMap<String, String>,
+    pub counters: Vec<CounterSample>,
+    pub gauges: Vec<GaugeSample>,
+    pub histograms: Vec<HistogramSample>,
+}
@@ -0,0 +1,172 @@
+// runar_common/src/metrics/prometheus.rs
+//
+// Prometheus text exposition format (version 0.0.4), so gateway nodes can
+// serve /metrics without depending on the prometheus crate.
+//
+// Each metric family gets an optional `# HELP` line and a `# TYPE` line.
+// Labels are written in name order (histogram `le` last), label values and
+// help text are escaped, and characters not allowed in metric names are
+// replaced with '_'.
+
+use std::fmt::Write;
+
+use super::{global, MetricLabels, MetricsRegistry, MetricsSnapshot};
+
+/// Content type of the text produced by `to_prometheus_text`
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Render the process-wide registry in the Prometheus text format
+pub fn prometheus_text() -> String {
+    global().to_prometheus_text()
+}
+
+impl MetricsRegistry {
+    /// Render all metrics in the Prometheus text format
+    pub fn to_prometheus_text(&self) -> String {
+        self.snapshot().to_prometheus_text()
+    }
+}
+
+impl MetricsSnapshot {
+    /// Render the snapshot in the Prometheus text exposition format
+    pub fn to_prometheus_text(&self) -> String {
+        let mut out = String::new();
+        let mut last_name: Option<&str> = None;
+
+        for sample in &self.counters {
+            self.push_header(&mut out, &mut last_name, &sample.name, "counter");
+            push_sample(
+                &mut out,
+                &sample.name,
+                "",
+                &sample.labels,
+                None,
+                sample.value as f64,
+            );
+        }
+        for sample in &self.gauges {
+            self.push_header(&mut out, &mut last_name, &sample.name, "gauge");
+            push_sample(
+                &mut out,
+                &sample.name,
+                "",
+                &sample.labels,
+                None,
+                sample.value,
+            );
+        }
+        for sample in &self.histograms {
+            self.push_header(&mut out, &mut last_name, &sample.name, "histogram");
+            for (bound, count) in &sample.buckets {
+                let le = format_value(*bound);
+                push_sample(
+                    &mut out,
+                    &sample.name,
+                    "_bucket",
+                    &sample.labels,
+                    Some(&le),
+                    *count as f64,
+                );
+            }
+            push_sample(
+                &mut out,
+                &sample.name,
+                "_sum",
+                &sample.labels,
+                None,
+                sample.sum,
+            );
+            push_sample(
+                &mut out,
+                &sample.name,
+                "_count",
+                &sample.labels,
+                None,
+                sample.count as f64,
+            );
+        }
+        out
+    }
+
+    /// Emit the `# HELP` and `# TYPE` lines the first time a metric name is seen
+    fn push_header<'a>(
+        &self,
+        out: &mut String,
+        last_name: &mut Option<&'a str>,
+        name: &'a str,
+        kind: &str,
+    ) {
+        if *last_name == Some(name) {
+            return;
+        }
+        let name_out = sanitize_name(name);
+        if let Some(help) = self.descriptions.get(name) {
+            let _ = writeln!(out, "# HELP {} {}", name_out, escape_help(help));
+        }
+        let _ = writeln!(out, "# TYPE {} {}", name_out, kind);
+        *last_name = Some(name);
+    }
+}
+
+fn push_sample(
+    out: &mut String,
+    name: &str,
+    suffix: &str,
+    labels: &MetricLabels,
+    le: Option<&str>,
+    value: f64,
+) {
+    let mut pairs = labels.pairs();
+    pairs.sort_by_key(|(label, _)| *label);
+    pairs.extend(le.map(|le| ("le", le)));
+    let rendered: Vec<String> = pairs
+        .into_iter()
+        .map(|(label, value)| format!("{}=\"{}\"", label, escape_label_value(value)))
+        .collect();
+    let _ = writeln!(
+        out,
+        "{}{}{{{}}} {}",
+        sanitize_name(name),
+        suffix,
+        rendered.join(","),
+        format_value(value)
+    );
+}
+
+/// Replace characters not allowed in metric names (`[a-zA-Z_:][a-zA-Z0-9_:]*`)
+fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let allowed = c.is_ascii_alphabetic() || c == '_' || c == ':';
+            if allowed || (i > 0 && c.is_ascii_digit()) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,120 @@
+// runar_common/src/metrics/timing.rs
+//
+// Per-action latency instrumentation.
+//
+// `time_action` returns a guard that, when dropped, records the elapsed time
+// in the `action_duration_seconds` histogram labeled with the service and
+// action, and logs a warning if the call took longer than the slow-call
+// threshold.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{global, Histogram, MetricLabels, MetricsRegistry};
+use crate::logging::{Component, Logger};
+use crate::utils::paths::ActionPath;
+use crate::utils::time::Instant;
+
+/// Name of the histogram recording action latencies (in seconds)
+pub const ACTION_DURATION_METRIC: &str = "action_duration_seconds";
+
+/// Slow-call threshold used until `set_slow_action_threshold` is called
+pub const DEFAULT_SLOW_ACTION_THRESHOLD: Duration = Duration::from_secs(1);
+
+static SLOW_ACTION_THRESHOLD_MICROS: AtomicU64 =
+    AtomicU64::new(DEFAULT_SLOW_ACTION_THRESHOLD.as_micros() as u64);
+
+/// Set the process-wide threshold above which timed actions are logged as slow
+pub fn set_slow_action_threshold(threshold: Duration) {
+    SLOW_ACTION_THRESHOLD_MICROS.store(threshold.as_micros() as u64, Ordering::Relaxed);
+}
+
+/// The process-wide slow-call threshold
+pub fn slow_action_threshold() -> Duration {
+    Duration::from_micros(SLOW_ACTION_THRESHOLD_MICROS.load(Ordering::Relaxed))
+}
+
+/// Time an action using the process-wide registry
+pub fn time_action(action: &ActionPath) -> ActionTimer {
+    global().time_action(action)
+}
+
+impl MetricsRegistry {
+    /// Start timing an action; the latency is recorded when the guard drops
+    pub fn time_action(&self, action: &ActionPath) -> ActionTimer {
+        let labels = MetricLabels::component(Component::Service)
+            .with_service(action.service().as_str())
+            .with_action(action.action_name());
+        ActionTimer {
+            histogram: self.histogram(ACTION_DURATION_METRIC, &labels),
+            action: action.clone(),
+            start: Instant::now(),
+            threshold: slow_action_threshold(),
+            logger: None,
+            done: false,
+        }
+    }
+}
+
+/// Guard recording the latency of an action when dropped (or `finish`ed)
+pub struct ActionTimer {
+    histogram: Arc<Histogram>,
+    action: ActionPath,
+    start: Instant,
+    threshold: Duration,
+    logger: Option<Logger>,
+    done: bool,
+}
+
+impl ActionTimer {
+    /// Log slow calls through this logger instead of the `log` crate directly
+    pub fn with_logger(mut self, logger: &Logger) -> Self {
+        self.logger = Some(logger.clone());
+        self
+    }
+
+    /// Override the slow-call threshold for this call
+    pub fn with_slow_threshold(mut self, threshold: Duration) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Time elapsed since the timer started
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Stop the timer now, returning the recorded latency
+    pub fn finish(mut self) -> Duration {
+        self.record()
+    }
+
+    fn record(&mut self) -> Duration {
+        let elapsed = self.elapsed();
+        if self.done {
+            return elapsed;
+        }
+        self.done = true;
+        self.histogram.observe(elapsed.as_secs_f64());
+        if elapsed >= self.threshold {
+            let message = format!(
+                "Slow action {}: took {:?} (threshold {:?})",
+                self.action, elapsed, self.threshold
+            );
+            match &self.logger {
+                Some(logger) => logger
+                    .with_action_path(self.action.to_string())
+                    .warn(message),
+                None => log::warn!("{}", message),
+            }
+        }
+        elapsed
+    }
+}
+
+impl Drop for ActionTimer {
+    fn drop(&mut self) {
+        self.record();
+    }
+}
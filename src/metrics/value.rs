@@ -0,0 +1,176 @@
+// runar_common/src/metrics/value.rs
+//
+// Metrics snapshots as `ArcValueType` maps, so a node's "metrics" action can
+// return them like any other action result. The layout is published as
+// `snapshot_schema()`:
+//
+// {
+//   "counters":   [{"name", "labels": {"component", "service"?, "action"?}, "value"}],
+//   "gauges":     [{"name", "labels", "value"}],
+//   "histograms": [{"name", "labels", "buckets": [{"le", "count"}], "count", "sum"}]
+// }
+//
+// Histogram buckets are cumulative and only list the finite bounds; the +Inf
+// bucket always equals `count`.
+
+use std::collections::HashMap;
+
+use super::{global, MetricLabels, MetricsRegistry, MetricsSnapshot};
+use crate::types::{ArcValueType, FieldSchema};
+
+/// Snapshot the process-wide registry as a Map value (see `snapshot_schema`)
+pub fn snapshot_as_value() -> ArcValueType {
+    global().snapshot_as_value()
+}
+
+/// Schema of the values returned by `snapshot_as_value`
+pub fn snapshot_schema() -> FieldSchema {
+    let labels = object(
+        "labels",
+        vec![
+            FieldSchema::string("component"),
+            FieldSchema::string("service"),
+            FieldSchema::string("action"),
+        ],
+        &["component"],
+    );
+    let samples = |name: &str, value: FieldSchema| {
+        array(
+            name,
+            object(
+                "sample",
+                vec![FieldSchema::string("name"), labels.clone(), value],
+                &["name", "labels", "value"],
+            ),
+        )
+    };
+    let counters = samples("counters", FieldSchema::long("value"));
+    let gauges = samples("gauges", FieldSchema::double("value"));
+    let bucket = object(
+        "bucket",
+        vec![FieldSchema::double("le"), FieldSchema::long("count")],
+        &["le", "count"],
+    );
+    let histograms = array(
+        "histograms",
+        object(
+            "histogram",
+            vec![
+                FieldSchema::string("name"),
+                labels.clone(),
+                array("buckets", bucket),
+                FieldSchema::long("count"),
+                FieldSchema::double("sum"),
+            ],
+            &["name", "labels", "buckets", "count", "sum"],
+        ),
+    );
+    object(
+        "metrics",
+        vec![counters, gauges, histograms],
+        &["counters", "gauges", "histograms"],
+    )
+}
+
+fn object(name: &str, fields: Vec<FieldSchema>, required: &[&str]) -> FieldSchema {
+    let properties: HashMap<String, Box<FieldSchema>> = fields
+        .into_iter()
+        .map(|field| (field.name.clone(), Box::new(field)))
+        .collect();
+    FieldSchema::object(
+        name,
+        properties,
+        Some(required.iter().map(|field| field.to_string()).collect()),
+    )
+}
+
+fn array(name: &str, items: FieldSchema) -> FieldSchema {
+    FieldSchema::array(name, Box::new(items))
+}
+
+impl MetricsRegistry {
+    /// Snapshot all metrics as a Map value (see `snapshot_schema`)
+    pub fn snapshot_as_value(&self) -> ArcValueType {
+        self.snapshot().to_value()
+    }
+}
+
+impl MetricsSnapshot {
+    /// Render the snapshot as a Map value (see `snapshot_schema`)
+    pub fn to_value(&self) -> ArcValueType {
+        let counters: Vec<ArcValueType> = self
+            .counters
+            .iter()
+            .map(|sample| {
+                sample_value(
+                    &sample.name,
+                    &sample.labels,
+                    ArcValueType::new_primitive(saturating_i64(sample.value)),
+                )
+            })
+            .collect();
+        let gauges: Vec<ArcValueType> = self
+            .gauges
+            .iter()
+            .map(|sample| {
+                sample_value(
+                    &sample.name,
+                    &sample.labels,
+                    ArcValueType::new_primitive(sample.value),
+                )
+            })
+            .collect();
+        let histograms: Vec<ArcValueType> = self
+            .histograms
+            .iter()
+            .map(|sample| {
+                let buckets: Vec<ArcValueType> = sample
+                    .buckets
+                    .iter()
+                    .filter(|(bound, _)| bound.is_finite())
+                    .map(|(bound, count)| {
+                        ArcValueType::from_pairs([
+                            ("le", ArcValueType::new_primitive(*bound)),
+                            ("count", ArcValueType::new_primitive(saturating_i64(*count))),
+                        ])
+                    })
+                    .collect();
+                ArcValueType::from_pairs([
+                    ("name", ArcValueType::new_primitive(sample.name.clone())),
+                    ("labels", labels_value(&sample.labels)),
+                    ("buckets", ArcValueType::new_list(buckets)),
+                    (
+                        "count",
+                        ArcValueType::new_primitive(saturating_i64(sample.count)),
+                    ),
+                    ("sum", ArcValueType::new_primitive(sample.sum)),
+                ])
+            })
+            .collect();
+        ArcValueType::from_pairs([
+            ("counters", ArcValueType::new_list(counters)),
+            ("gauges", ArcValueType::new_list(gauges)),
+            ("histograms", ArcValueType::new_list(histograms)),
+        ])
+    }
+}
+
+fn sample_value(name: &str, labels: &MetricLabels, value: ArcValueType) -> ArcValueType {
+    ArcValueType::from_pairs([
+        ("name", ArcValueType::new_primitive(name.to_string())),
+        ("labels", labels_value(labels)),
+        ("value", value),
+    ])
+}
+
+fn labels_value(labels: &MetricLabels) -> ArcValueType {
+    labels
+        .pairs()
+        .into_iter()
+        .map(|(key, value)| (key, ArcValueType::new_primitive(value.to_string())))
+        .collect()
+}
+
+fn saturating_i64(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
@@ -1,12 +1,21 @@
-// WARNING: THIS FILE IS DEPRECATED AND SHOULD BE DELETED
+// NOTE: The ServiceInfo trait is deprecated in favor of `ServiceDescriptor`.
 // The ServiceInfo trait functionality has been merged into AbstractService trait
 // to reduce duplication and simplify the codebase.
-// All service implementations should now directly implement AbstractService
-// with the required metadata methods (name, path, description, version) instead.
-// This file is kept temporarily for backward compatibility but will be removed.
+// New code should build a `ServiceDescriptor` and convert it into
+// `ServiceMetadata`; the trait is implemented for `ServiceDescriptor` so that
+// existing callers keep working during the migration.
+
+use anyhow::{anyhow, Result};
+
+use crate::types::{NetworkId, ServiceMetadata, Version};
+use crate::utils::paths::ServicePath;
 
 /// The ServiceInfo trait defines the interface for accessing
 /// basic information about a service.
+///
+/// Implemented by `ServiceDescriptor`, by `Arc`s of implementors and, with
+/// the `abstract_service` feature, by every `utils::MetadataProvider`.
+/// Legacy services implement it directly.
 pub trait ServiceInfo {
     /// Returns the service name
     fn service_name(&self) -> &str;
@@ -21,6 +30,180 @@ pub trait ServiceInfo {
     fn service_version(&self) -> &str;
 }
 
+// Shared services describe themselves through the service they wrap. `&T`
+// and `Box<T>` are left out: they would overlap the `MetadataProvider`
+// blanket impl.
+impl<T: ServiceInfo + ?Sized> ServiceInfo for std::sync::Arc<T> {
+    fn service_name(&self) -> &str {
+        (**self).service_name()
+    }
+
+    fn service_path(&self) -> &str {
+        (**self).service_path()
+    }
+
+    fn service_description(&self) -> &str {
+        (**self).service_description()
+    }
+
+    fn service_version(&self) -> &str {
+        (**self).service_version()
+    }
+}
+
+/// Network used when a descriptor does not specify one
+pub const DEFAULT_NETWORK_ID: &str = "default";
+
+/// Identity and description of a service
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceDescriptor {
+    name: String,
+    path: ServicePath,
+    version: Version,
+    // Rendered version, kept so the descriptor can hand out &str through ServiceInfo
+    version_text: String,
+    description: String,
+    network_id: NetworkId,
+}
+
+impl ServiceDescriptor {
+    /// Start building a descriptor for a service
+    pub fn builder(name: impl Into<String>, path: impl Into<String>) -> ServiceDescriptorBuilder {
+        ServiceDescriptorBuilder {
+            name: name.into(),
+            path: path.into(),
+            version: "0.1.0".to_string(),
+            description: String::new(),
+            network_id: DEFAULT_NETWORK_ID.to_string(),
+        }
+    }
+
+    /// Build a descriptor from any legacy `ServiceInfo` implementation
+    pub fn from_service_info(info: &dyn ServiceInfo, network_id: &str) -> Result<Self> {
+        Self::builder(info.service_name(), info.service_path())
+            .with_version(info.service_version())
+            .with_description(info.service_description())
+            .with_network_id(network_id)
+            .build()
+    }
+
+    /// Get the service name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the service path
+    pub fn path(&self) -> &ServicePath {
+        &self.path
+    }
+
+    /// Get the service version
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// Get the service description
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Get the network the service belongs to
+    pub fn network_id(&self) -> &NetworkId {
+        &self.network_id
+    }
+
+    /// Build the metadata advertised for this service (without actions or events)
+    pub fn to_metadata(&self, registration_time: u64) -> ServiceMetadata {
+        ServiceMetadata {
+            network_id: self.network_id.to_string(),
+            service_path: self.path.to_string(),
+            name: self.name.clone(),
+            version: self.version.clone(),
+            description: self.description.clone(),
+            actions: Vec::new(),
+            events: Vec::new(),
+            registration_time,
+            last_start_time: None,
+        }
+    }
+}
+
+impl From<&ServiceDescriptor> for ServiceMetadata {
+    fn from(descriptor: &ServiceDescriptor) -> Self {
+        descriptor.to_metadata(crate::utils::time::now_secs())
+    }
+}
+
+impl From<ServiceDescriptor> for ServiceMetadata {
+    fn from(descriptor: ServiceDescriptor) -> Self {
+        ServiceMetadata::from(&descriptor)
+    }
+}
+
+impl ServiceInfo for ServiceDescriptor {
+    fn service_name(&self) -> &str {
+        &self.name
+    }
+
+    fn service_path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    fn service_description(&self) -> &str {
+        &self.description
+    }
+
+    fn service_version(&self) -> &str {
+        &self.version_text
+    }
+}
+
+/// Builder for `ServiceDescriptor`; values are validated in `build`
+#[derive(Debug, Clone)]
+pub struct ServiceDescriptorBuilder {
+    name: String,
+    path: String,
+    version: String,
+    description: String,
+    network_id: String,
+}
+
+impl ServiceDescriptorBuilder {
+    /// Set the version (semantic version string, defaults to 0.1.0)
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    /// Set the description
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Set the network id (defaults to "default")
+    pub fn with_network_id(mut self, network_id: impl Into<String>) -> Self {
+        self.network_id = network_id.into();
+        self
+    }
+
+    /// Validate the fields and build the descriptor
+    pub fn build(self) -> Result<ServiceDescriptor> {
+        if self.name.trim().is_empty() {
+            return Err(anyhow!("Service name cannot be empty"));
+        }
+        let version = Version::parse(&self.version)?;
+        Ok(ServiceDescriptor {
+            name: self.name,
+            path: ServicePath::new(&self.path)?,
+            version_text: version.to_string(),
+            version,
+            description: self.description,
+            network_id: NetworkId::new(self.network_id)?,
+        })
+    }
+}
+
 /// Utility module to help implement ServiceInfo for AbstractService implementors
 #[cfg(feature = "abstract_service")]
 pub mod utils {
@@ -0,0 +1,115 @@
+// runar_common/src/testing.rs
+//
+// Proptest strategies for generating ArcValueType values.
+// Enabled by the `testing` feature so downstream crates can reuse them in
+// their own property tests.
+
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::{hash_map, vec};
+use proptest::prelude::*;
+
+use crate::types::ArcValueType;
+
+/// Largest number of elements generated for a list or map
+pub const MAX_COLLECTION_LEN: usize = 8;
+
+/// Short strings (including empty and non-ASCII) used for keys and values
+pub fn arb_string() -> impl Strategy<Value = String> + Clone {
+    "\\PC{0,12}"
+}
+
+/// Finite floats; NaN never compares equal, so it is left out
+pub fn arb_f64() -> impl Strategy<Value = f64> + Clone {
+    prop_oneof![Just(0.0), -1.0e12..1.0e12, proptest::num::f64::NORMAL]
+}
+
+/// Primitive values of the types registered by `SerializerRegistry::with_defaults`
+pub fn arb_primitive() -> BoxedStrategy<ArcValueType> {
+    prop_oneof![
+        any::<bool>().prop_map(ArcValueType::new_primitive),
+        any::<i32>().prop_map(ArcValueType::new_primitive),
+        any::<i64>().prop_map(ArcValueType::new_primitive),
+        arb_f64().prop_map(ArcValueType::new_primitive),
+        arb_string().prop_map(ArcValueType::new_primitive),
+    ]
+    .boxed()
+}
+
+/// Lists of the element types registered by default
+pub fn arb_list() -> BoxedStrategy<ArcValueType> {
+    let len = 0..=MAX_COLLECTION_LEN;
+    prop_oneof![
+        vec(any::<bool>(), len.clone()).prop_map(ArcValueType::new_list),
+        vec(any::<i32>(), len.clone()).prop_map(ArcValueType::new_list),
+        vec(any::<i64>(), len.clone()).prop_map(ArcValueType::new_list),
+        vec(arb_f64(), len.clone()).prop_map(ArcValueType::new_list),
+        vec(arb_string(), len).prop_map(ArcValueType::new_list),
+    ]
+    .boxed()
+}
+
+/// String-keyed maps of the value types registered by default
+pub fn arb_map() -> BoxedStrategy<ArcValueType> {
+    let len = 0..=MAX_COLLECTION_LEN;
+    prop_oneof![
+        hash_map(arb_string(), any::<bool>(), len.clone()).prop_map(ArcValueType::new_map),
+        hash_map(arb_string(), any::<i32>(), len.clone()).prop_map(ArcValueType::new_map),
+        hash_map(arb_string(), any::<i64>(), len.clone()).prop_map(ArcValueType::new_map),
+        hash_map(arb_string(), arb_f64(), len.clone()).prop_map(ArcValueType::new_map),
+        hash_map(arb_string(), arb_string(), len).prop_map(ArcValueType::new_map),
+    ]
+    .boxed()
+}
+
+/// Raw byte values
+pub fn arb_bytes() -> BoxedStrategy<ArcValueType> {
+    vec(any::<u8>(), 0..64)
+        .prop_map(ArcValueType::new_bytes)
+        .boxed()
+}
+
+/// Any value that round-trips through a `SerializerRegistry::with_defaults`
+/// registry: null, primitives, lists, maps and bytes
+pub fn arb_registered_value() -> BoxedStrategy<ArcValueType> {
+    prop_oneof![
+        1 => Just(ArcValueType::null()),
+        4 => arb_primitive(),
+        2 => arb_list(),
+        2 => arb_map(),
+        1 => arb_bytes(),
+    ]
+    .boxed()
+}
+
+/// Nested value trees of at most `depth` levels, built from
+/// `Vec<ArcValueType>` lists and `HashMap<String, ArcValueType>` maps.
+///
+/// Leaves are null, bool, i64, f64 and String, so every tree can be converted
+/// to JSON. Nested values are not registered for the wire format; use
+/// `arb_registered_value` for serialization round trips.
+pub fn arb_value_tree(depth: u32) -> BoxedStrategy<ArcValueType> {
+    let leaf = prop_oneof![
+        Just(ArcValueType::null()),
+        any::<bool>().prop_map(ArcValueType::new_primitive),
+        any::<i64>().prop_map(ArcValueType::new_primitive),
+        arb_f64().prop_map(ArcValueType::new_primitive),
+        arb_string().prop_map(ArcValueType::new_primitive),
+    ];
+    leaf.prop_recursive(depth, 64, MAX_COLLECTION_LEN as u32, |inner| {
+        prop_oneof![
+            vec(inner.clone(), 0..=MAX_COLLECTION_LEN).prop_map(ArcValueType::new_list),
+            hash_map(arb_string(), inner, 0..=MAX_COLLECTION_LEN).prop_map(ArcValueType::new_map),
+        ]
+    })
+    .boxed()
+}
+
+/// `any::<ArcValueType>()` generates values registered by default
+impl Arbitrary for ArcValueType {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        arb_registered_value()
+    }
+}
@@ -0,0 +1,172 @@
+// runar_common/src/types/address.rs
+//
+// Addresses at which a peer can be reached, written in a multiaddr-like
+// grammar shared by discovery records and configuration files:
+//
+//   /ip4/192.168.1.10/udp/4433/quic      QUIC to an IPv4 socket address
+//   /ip6/::1/udp/4433/quic               QUIC to an IPv6 socket address
+//   /dns/node.example.com/udp/4433/quic  QUIC to a host name, resolved on connect
+//   /relay/<peer id>                     through a relay, to the given peer
+//   /memory/7                            in-process channel (tests and embedding)
+
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{ArcValueType, PeerId};
+
+/// Longest DNS name accepted, in bytes
+const MAX_DNS_NAME_LEN: usize = 253;
+
+/// Where and how a peer can be reached
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum PeerAddress {
+    /// QUIC to a socket address
+    Quic(SocketAddr),
+    /// QUIC to a host name, resolved when connecting
+    Dns {
+        /// Host name
+        name: String,
+        /// UDP port
+        port: u16,
+    },
+    /// Through a relay, to the given peer
+    Relay(PeerId),
+    /// In-process channel with the given ID
+    Memory(u64),
+}
+
+impl PeerAddress {
+    /// Parse an address such as `/ip4/10.0.0.1/udp/4433/quic`
+    pub fn parse(address: &str) -> Result<Self> {
+        let invalid = |reason: &str| anyhow!("Invalid peer address '{}': {}", address, reason);
+        let rest = address
+            .strip_prefix('/')
+            .ok_or_else(|| invalid("must start with '/'"))?;
+        let parts: Vec<&str> = rest.split('/').collect();
+        match parts.as_slice() {
+            [proto @ ("ip4" | "ip6"), host, "udp", port, "quic"] => {
+                let ip: IpAddr = host.parse().map_err(|_| invalid("bad IP address"))?;
+                if (*proto == "ip4") != ip.is_ipv4() {
+                    return Err(invalid("IP address does not match the protocol"));
+                }
+                Ok(PeerAddress::Quic(SocketAddr::new(
+                    ip,
+                    parse_port(port, invalid)?,
+                )))
+            }
+            ["dns", name, "udp", port, "quic"] => {
+                validate_dns_name(name).map_err(|reason| invalid(&reason))?;
+                Ok(PeerAddress::Dns {
+                    name: name.to_ascii_lowercase(),
+                    port: parse_port(port, invalid)?,
+                })
+            }
+            ["relay", peer] => Ok(PeerAddress::Relay(
+                PeerId::new(*peer).map_err(|e| invalid(&e.to_string()))?,
+            )),
+            ["memory", id] => Ok(PeerAddress::Memory(
+                id.parse().map_err(|_| invalid("bad channel ID"))?,
+            )),
+            _ => Err(invalid("unknown address format")),
+        }
+    }
+
+    /// The port, for QUIC and DNS addresses
+    pub fn port(&self) -> Option<u16> {
+        match self {
+            PeerAddress::Quic(socket) => Some(socket.port()),
+            PeerAddress::Dns { port, .. } => Some(*port),
+            PeerAddress::Relay(_) | PeerAddress::Memory(_) => None,
+        }
+    }
+
+    /// Check whether the address needs a name lookup before connecting
+    pub fn needs_resolution(&self) -> bool {
+        matches!(self, PeerAddress::Dns { .. })
+    }
+}
+
+fn parse_port(port: &str, invalid: impl Fn(&str) -> anyhow::Error) -> Result<u16> {
+    port.parse().map_err(|_| invalid("bad port"))
+}
+
+fn validate_dns_name(name: &str) -> std::result::Result<(), String> {
+    if name.is_empty() || name.len() > MAX_DNS_NAME_LEN {
+        return Err(format!("DNS name must be 1 to {} bytes", MAX_DNS_NAME_LEN));
+    }
+    for label in name.split('.') {
+        let valid = !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if !valid {
+            return Err(format!("bad DNS label '{}'", label));
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for PeerAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PeerAddress::Quic(SocketAddr::V4(socket)) => {
+                write!(f, "/ip4/{}/udp/{}/quic", socket.ip(), socket.port())
+            }
+            PeerAddress::Quic(SocketAddr::V6(socket)) => {
+                write!(f, "/ip6/{}/udp/{}/quic", socket.ip(), socket.port())
+            }
+            PeerAddress::Dns { name, port } => write!(f, "/dns/{}/udp/{}/quic", name, port),
+            PeerAddress::Relay(peer) => write!(f, "/relay/{}", peer),
+            PeerAddress::Memory(id) => write!(f, "/memory/{}", id),
+        }
+    }
+}
+
+impl FromStr for PeerAddress {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for PeerAddress {
+    type Error = anyhow::Error;
+
+    fn try_from(address: String) -> Result<Self> {
+        Self::parse(&address)
+    }
+}
+
+impl From<PeerAddress> for String {
+    fn from(address: PeerAddress) -> Self {
+        address.to_string()
+    }
+}
+
+impl From<SocketAddr> for PeerAddress {
+    fn from(socket: SocketAddr) -> Self {
+        PeerAddress::Quic(socket)
+    }
+}
+
+impl From<PeerAddress> for ArcValueType {
+    fn from(address: PeerAddress) -> Self {
+        ArcValueType::new_primitive(address.to_string())
+    }
+}
+
+impl TryFrom<ArcValueType> for PeerAddress {
+    type Error = anyhow::Error;
+
+    fn try_from(mut value: ArcValueType) -> Result<Self> {
+        let address: String = value.as_type()?;
+        Self::parse(&address)
+    }
+}
@@ -0,0 +1,82 @@
+// runar_common/src/types/arrow.rs
+//
+// Columnar export of struct lists (requires the `arrow` feature).
+//
+// Rows are written straight from their serde representation into Arrow
+// arrays, so analytics sinks get one RecordBatch instead of decoding every
+// event themselves.
+
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use arrow_array::RecordBatch;
+use arrow_json::reader::{infer_json_schema_from_iterator, ReaderBuilder};
+use arrow_schema::SchemaRef;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::value_type::{ArcValueType, ValueCategory};
+
+impl ArcValueType {
+    /// Convert a `Vec<T>` list of structs into an Arrow record batch, with the
+    /// schema inferred from the rows (integers become Int64, floats Float64,
+    /// nested structs and lists become Struct and List columns).
+    ///
+    /// Lazily deserialized lists are decoded as `Vec<T>` first. Inference
+    /// walks every row; use [`ArcValueType::to_arrow_batch_with_schema`] when
+    /// the schema is known up front.
+    pub fn to_arrow_batch<T>(&self) -> Result<RecordBatch>
+    where
+        T: 'static + Serialize + DeserializeOwned + Clone + fmt::Debug + Send + Sync,
+    {
+        let rows = self.struct_rows::<T>()?;
+        let values = rows
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| {
+                anyhow!(
+                    "Cannot convert rows of {}: {}",
+                    std::any::type_name::<T>(),
+                    e
+                )
+            })?;
+        let schema = infer_json_schema_from_iterator(values.iter().map(Ok))
+            .map_err(|e| anyhow!("Cannot infer Arrow schema: {}", e))?;
+        write_batch(&rows, Arc::new(schema))
+    }
+
+    /// Convert a `Vec<T>` list of structs into an Arrow record batch with the
+    /// given schema. Struct fields missing from the schema are ignored.
+    pub fn to_arrow_batch_with_schema<T>(&self, schema: SchemaRef) -> Result<RecordBatch>
+    where
+        T: 'static + Serialize + DeserializeOwned + Clone + fmt::Debug + Send + Sync,
+    {
+        let rows = self.struct_rows::<T>()?;
+        write_batch(&rows, schema)
+    }
+
+    // The rows of a `Vec<T>` list value
+    fn struct_rows<T>(&self) -> Result<Arc<Vec<T>>>
+    where
+        T: 'static + DeserializeOwned + Clone + fmt::Debug + Send + Sync,
+    {
+        self.expect_type::<Vec<T>>(ValueCategory::List)?;
+        let mut value = self.clone();
+        value.as_list_ref::<T>()
+    }
+}
+
+fn write_batch<T: Serialize>(rows: &[T], schema: SchemaRef) -> Result<RecordBatch> {
+    let mut decoder = ReaderBuilder::new(schema.clone())
+        .build_decoder()
+        .map_err(|e| anyhow!("Invalid Arrow schema: {}", e))?;
+    decoder
+        .serialize(rows)
+        .map_err(|e| anyhow!("Cannot write rows to Arrow: {}", e))?;
+    let batch = decoder
+        .flush()
+        .map_err(|e| anyhow!("Cannot write rows to Arrow: {}", e))?;
+    Ok(batch.unwrap_or_else(|| RecordBatch::new_empty(schema)))
+}
@@ -0,0 +1,177 @@
+// runar_common/src/types/capability.rs
+//
+// Differences between two versions of a service's metadata.
+//
+// When a service restarts or is updated, discovery gossips a
+// `CapabilityDelta` instead of the full `ServiceMetadata`. Actions are
+// matched by name and events by path; peers apply the delta to the metadata
+// they already hold.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::schemas::{ActionMetadata, EventMetadata, ServiceMetadata};
+use super::Version;
+
+/// Changes turning one `ServiceMetadata` into another
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct CapabilityDelta {
+    /// Path of the service the delta applies to
+    pub service_path: String,
+    /// Actions that are new
+    pub actions_added: Vec<ActionMetadata>,
+    /// Names of actions that were removed
+    pub actions_removed: Vec<String>,
+    /// New metadata of actions whose description or schemas changed
+    pub actions_changed: Vec<ActionMetadata>,
+    /// Events that are new
+    pub events_added: Vec<EventMetadata>,
+    /// Paths of events that were removed
+    pub events_removed: Vec<String>,
+    /// New metadata of events whose description or schema changed
+    pub events_changed: Vec<EventMetadata>,
+    /// New version, if it changed
+    pub version: Option<Version>,
+    /// New description, if it changed
+    pub description: Option<String>,
+    /// New last start time, if it changed
+    pub last_start_time: Option<u64>,
+}
+
+impl CapabilityDelta {
+    /// Check whether the delta changes nothing
+    pub fn is_empty(&self) -> bool {
+        self.actions_added.is_empty()
+            && self.actions_removed.is_empty()
+            && self.actions_changed.is_empty()
+            && self.events_added.is_empty()
+            && self.events_removed.is_empty()
+            && self.events_changed.is_empty()
+            && self.version.is_none()
+            && self.description.is_none()
+            && self.last_start_time.is_none()
+    }
+}
+
+impl ServiceMetadata {
+    /// Compute the changes from `old` to `new`. Both must describe the same
+    /// service path; the delta is computed either way and `apply` checks it.
+    pub fn diff(old: &ServiceMetadata, new: &ServiceMetadata) -> CapabilityDelta {
+        let (actions_added, actions_removed, actions_changed) =
+            diff_by_key(&old.actions, &new.actions, |action| &action.name);
+        let (events_added, events_removed, events_changed) =
+            diff_by_key(&old.events, &new.events, |event| &event.path);
+        CapabilityDelta {
+            service_path: new.service_path.clone(),
+            actions_added,
+            actions_removed,
+            actions_changed,
+            events_added,
+            events_removed,
+            events_changed,
+            version: (old.version != new.version).then(|| new.version.clone()),
+            description: (old.description != new.description).then(|| new.description.clone()),
+            last_start_time: new
+                .last_start_time
+                .filter(|_| old.last_start_time != new.last_start_time),
+        }
+    }
+
+    /// Apply a delta computed by `diff`. Nothing changes if the delta does not
+    /// fit this metadata (other service, or unknown or duplicate entries).
+    pub fn apply(&mut self, delta: &CapabilityDelta) -> Result<()> {
+        if delta.service_path != self.service_path {
+            return Err(anyhow!(
+                "Capability delta for '{}' cannot be applied to '{}'",
+                delta.service_path,
+                self.service_path
+            ));
+        }
+        let actions = apply_by_key(
+            &self.actions,
+            &delta.actions_added,
+            &delta.actions_removed,
+            &delta.actions_changed,
+            |action| &action.name,
+            "action",
+        )?;
+        let events = apply_by_key(
+            &self.events,
+            &delta.events_added,
+            &delta.events_removed,
+            &delta.events_changed,
+            |event| &event.path,
+            "event",
+        )?;
+
+        self.actions = actions;
+        self.events = events;
+        if let Some(version) = &delta.version {
+            self.version = version.clone();
+        }
+        if let Some(description) = &delta.description {
+            self.description = description.clone();
+        }
+        if delta.last_start_time.is_some() {
+            self.last_start_time = delta.last_start_time;
+        }
+        Ok(())
+    }
+}
+
+// (added, removed keys, changed) between two lists keyed by `key`
+fn diff_by_key<T: Clone + PartialEq>(
+    old: &[T],
+    new: &[T],
+    key: impl Fn(&T) -> &String,
+) -> (Vec<T>, Vec<String>, Vec<T>) {
+    let find =
+        |items: &[T], wanted: &String| items.iter().find(|item| key(item) == wanted).cloned();
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for item in new {
+        match find(old, key(item)) {
+            None => added.push(item.clone()),
+            Some(previous) if previous != *item => changed.push(item.clone()),
+            Some(_) => {}
+        }
+    }
+    let removed = old
+        .iter()
+        .filter(|item| find(new, key(item)).is_none())
+        .map(|item| key(item).clone())
+        .collect();
+    (added, removed, changed)
+}
+
+fn apply_by_key<T: Clone>(
+    current: &[T],
+    added: &[T],
+    removed: &[String],
+    changed: &[T],
+    key: impl Fn(&T) -> &String,
+    kind: &str,
+) -> Result<Vec<T>> {
+    let mut items = current.to_vec();
+    for name in removed {
+        let index = items
+            .iter()
+            .position(|item| key(item) == name)
+            .ok_or_else(|| anyhow!("Cannot remove unknown {} '{}'", kind, name))?;
+        items.remove(index);
+    }
+    for item in changed {
+        let slot = items
+            .iter_mut()
+            .find(|existing| key(existing) == key(item))
+            .ok_or_else(|| anyhow!("Cannot change unknown {} '{}'", kind, key(item)))?;
+        *slot = item.clone();
+    }
+    for item in added {
+        if items.iter().any(|existing| key(existing) == key(item)) {
+            return Err(anyhow!("Cannot add existing {} '{}'", kind, key(item)));
+        }
+        items.push(item.clone());
+    }
+    Ok(items)
+}
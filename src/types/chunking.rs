@@ -0,0 +1,300 @@
+// runar_common/src/types/chunking.rs
+//
+// Content-defined chunking of serialized values.
+//
+// Large Struct and Bytes payloads are split at positions chosen by a rolling
+// gear hash of the content rather than at fixed offsets, so an edit in the
+// middle of a value only changes the chunks around it. A manifest lists the
+// chunks by BLAKE3 hash; the sync layer compares manifests and transfers
+// only the chunks the other peer does not already hold.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::value_type::{ArcValueType, SerializerRegistry};
+use crate::utils::encoding::to_hex;
+use crate::utils::integrity::blake3_hash;
+
+/// BLAKE3 hash identifying a chunk
+pub type ChunkHash = [u8; 32];
+
+/// Random values mixed into the rolling hash, one per byte value
+const GEAR: [u64; 256] = gear_table();
+
+// Deterministic table (splitmix64), so every peer cuts at the same positions
+const fn gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+    let mut i = 0;
+    while i < 256 {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+/// Largest value `ChunkManifest::assemble` rebuilds by default (256 MiB)
+pub const DEFAULT_MAX_ASSEMBLED_LEN: u64 = 256 * 1024 * 1024;
+
+/// Chunk size limits for content-defined chunking
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkingConfig {
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+    max_assembled_len: u64,
+}
+
+impl Default for ChunkingConfig {
+    /// 2 KiB minimum, 8 KiB average and 64 KiB maximum chunks
+    fn default() -> Self {
+        ChunkingConfig {
+            min_size: 2 * 1024,
+            avg_size: 8 * 1024,
+            max_size: 64 * 1024,
+            max_assembled_len: DEFAULT_MAX_ASSEMBLED_LEN,
+        }
+    }
+}
+
+impl ChunkingConfig {
+    /// Create a config; `avg_size` must be a power of two between the other
+    /// two, and `max_size` must fit the `u32` chunk length of a manifest
+    pub fn new(min_size: usize, avg_size: usize, max_size: usize) -> Result<Self> {
+        if !avg_size.is_power_of_two() {
+            return Err(anyhow!(
+                "Average chunk size must be a power of two, got {}",
+                avg_size
+            ));
+        }
+        if min_size == 0 || min_size > avg_size || avg_size > max_size {
+            return Err(anyhow!(
+                "Chunk sizes must satisfy 0 < min <= avg <= max, got {} / {} / {}",
+                min_size,
+                avg_size,
+                max_size
+            ));
+        }
+        if max_size > u32::MAX as usize {
+            return Err(anyhow!(
+                "Maximum chunk size must be at most {} bytes, got {}",
+                u32::MAX,
+                max_size
+            ));
+        }
+        Ok(ChunkingConfig {
+            min_size,
+            avg_size,
+            max_size,
+            max_assembled_len: DEFAULT_MAX_ASSEMBLED_LEN,
+        })
+    }
+
+    /// Set the largest value `ChunkManifest::assemble` rebuilds
+    pub fn with_max_assembled_len(mut self, max_assembled_len: u64) -> Self {
+        self.max_assembled_len = max_assembled_len;
+        self
+    }
+
+    /// Smallest chunk produced (except for the last one)
+    pub fn min_size(&self) -> usize {
+        self.min_size
+    }
+
+    /// Expected chunk size
+    pub fn avg_size(&self) -> usize {
+        self.avg_size
+    }
+
+    /// Largest chunk produced
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Largest value `ChunkManifest::assemble` rebuilds
+    pub fn max_assembled_len(&self) -> u64 {
+        self.max_assembled_len
+    }
+
+    /// Split `data` into content-defined chunks, returning their byte ranges
+    pub fn boundaries(&self, data: &[u8]) -> Vec<Range<usize>> {
+        let mask = (self.avg_size - 1) as u64;
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        while start < data.len() {
+            let end = (start + self.max_size).min(data.len());
+            let mut cut = end;
+            let mut hash = 0u64;
+            for (offset, byte) in data[start..end].iter().enumerate() {
+                hash = (hash << 1).wrapping_add(GEAR[*byte as usize]);
+                if offset + 1 >= self.min_size && hash & mask == 0 {
+                    cut = start + offset + 1;
+                    break;
+                }
+            }
+            ranges.push(start..cut);
+            start = cut;
+        }
+        ranges
+    }
+}
+
+/// A chunk listed in a manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkInfo {
+    /// BLAKE3 hash of the chunk
+    pub hash: ChunkHash,
+    /// Size of the chunk in bytes
+    pub len: u32,
+}
+
+/// The chunks of a serialized value, in order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkManifest {
+    /// BLAKE3 hash of the whole serialized value
+    pub digest: ChunkHash,
+    /// Size of the whole serialized value
+    pub total_len: u64,
+    /// Chunks in payload order
+    pub chunks: Vec<ChunkInfo>,
+}
+
+impl ChunkManifest {
+    /// Chunks of this manifest that `previous` does not contain, i.e. the
+    /// chunks to send to a peer that holds the previous version (each hash
+    /// is listed once)
+    pub fn changed_since(&self, previous: &ChunkManifest) -> Vec<ChunkInfo> {
+        let known: HashSet<&ChunkHash> = previous.chunks.iter().map(|chunk| &chunk.hash).collect();
+        self.missing(|hash| known.contains(hash))
+    }
+
+    /// Chunks for which `has` returns false (each hash is listed once)
+    pub fn missing(&self, has: impl Fn(&ChunkHash) -> bool) -> Vec<ChunkInfo> {
+        let mut seen = HashSet::new();
+        self.chunks
+            .iter()
+            .filter(|chunk| !has(&chunk.hash) && seen.insert(chunk.hash))
+            .copied()
+            .collect()
+    }
+
+    /// Rebuild the serialized value from chunks returned by `lookup`,
+    /// verifying every chunk and the final digest.
+    ///
+    /// Manifests come from peers, so the lengths they claim are checked
+    /// against each other and `config.max_assembled_len()` before anything
+    /// is allocated.
+    pub fn assemble(
+        &self,
+        config: &ChunkingConfig,
+        lookup: impl Fn(&ChunkHash) -> Option<Arc<[u8]>>,
+    ) -> Result<Arc<[u8]>> {
+        let chunks_len: u64 = self.chunks.iter().map(|chunk| chunk.len as u64).sum();
+        if chunks_len != self.total_len {
+            return Err(anyhow!(
+                "Manifest claims {} bytes but its chunks add up to {}",
+                self.total_len,
+                chunks_len
+            ));
+        }
+        if self.total_len > config.max_assembled_len {
+            return Err(anyhow!(
+                "Chunked value of {} bytes exceeds the limit of {} bytes",
+                self.total_len,
+                config.max_assembled_len
+            ));
+        }
+        let mut data = Vec::with_capacity(self.total_len as usize);
+        for (index, info) in self.chunks.iter().enumerate() {
+            let chunk = lookup(&info.hash)
+                .ok_or_else(|| anyhow!("Missing chunk {} ({})", index, to_hex(&info.hash)))?;
+            if chunk.len() != info.len as usize || blake3_hash(&chunk) != info.hash {
+                return Err(anyhow!(
+                    "Chunk {} does not match its hash {}",
+                    index,
+                    to_hex(&info.hash)
+                ));
+            }
+            data.extend_from_slice(&chunk);
+        }
+        if data.len() as u64 != self.total_len || blake3_hash(&data) != self.digest {
+            return Err(anyhow!(
+                "Reassembled value does not match digest {}",
+                to_hex(&self.digest)
+            ));
+        }
+        Ok(Arc::from(data))
+    }
+}
+
+/// A serialized value split into chunks
+#[derive(Debug, Clone)]
+pub struct ChunkedPayload {
+    /// The chunk list to send ahead of the chunks themselves
+    pub manifest: ChunkManifest,
+    /// Chunk contents by hash
+    pub chunks: HashMap<ChunkHash, Arc<[u8]>>,
+}
+
+impl ChunkedPayload {
+    /// Split serialized bytes into chunks
+    pub fn from_bytes(data: &[u8], config: &ChunkingConfig) -> Self {
+        let mut infos = Vec::new();
+        let mut chunks = HashMap::new();
+        for range in config.boundaries(data) {
+            let chunk = &data[range];
+            let hash = blake3_hash(chunk);
+            infos.push(ChunkInfo {
+                hash,
+                len: chunk.len() as u32,
+            });
+            chunks.entry(hash).or_insert_with(|| Arc::from(chunk));
+        }
+        ChunkedPayload {
+            manifest: ChunkManifest {
+                digest: blake3_hash(data),
+                total_len: data.len() as u64,
+                chunks: infos,
+            },
+            chunks,
+        }
+    }
+
+    /// Look up a chunk by hash
+    pub fn chunk(&self, hash: &ChunkHash) -> Option<Arc<[u8]>> {
+        self.chunks.get(hash).cloned()
+    }
+}
+
+impl SerializerRegistry {
+    /// Serialize a value and split it into content-defined chunks.
+    ///
+    /// Meant for large Struct and Bytes values that change a little at a
+    /// time; small values are cheaper to send whole.
+    pub fn serialize_chunked(
+        &self,
+        value: &ArcValueType,
+        config: &ChunkingConfig,
+    ) -> Result<ChunkedPayload> {
+        let bytes = self.serialize_value(value)?;
+        Ok(ChunkedPayload::from_bytes(&bytes, config))
+    }
+
+    /// Reassemble and deserialize a chunked value within the limits of `config`
+    pub fn deserialize_chunked(
+        &self,
+        manifest: &ChunkManifest,
+        config: &ChunkingConfig,
+        lookup: impl Fn(&ChunkHash) -> Option<Arc<[u8]>>,
+    ) -> Result<ArcValueType> {
+        self.deserialize_value(manifest.assemble(config, lookup)?)
+    }
+}
@@ -0,0 +1,189 @@
+// runar_common/src/types/codec.rs
+//
+// Payload codecs used by the SerializerRegistry.
+// The codec of every serialized value is recorded in its wire header (see
+// `wire::CodecId`), so a receiver decodes with whatever codec the sender chose.
+
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+pub use crate::wire::CodecId;
+
+/// An encoding for value payloads.
+///
+/// Codecs are stateless; a registry selects one with
+/// `SerializerRegistry::set_codec` and values are decoded through
+/// [`decode_with`] using the codec named in their header.
+pub trait Codec {
+    /// Identifier written to the wire header
+    const ID: CodecId;
+
+    /// Encode a value
+    fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>>;
+
+    /// Encode a value, appending it to `out`
+    fn encode_into<T: Serialize + ?Sized>(value: &T, out: &mut Vec<u8>) -> Result<()> {
+        out.extend_from_slice(&Self::encode(value)?);
+        Ok(())
+    }
+
+    /// Decode a value
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T>;
+}
+
+/// bincode 1.x, the compact default used between Rust peers
+pub struct BincodeCodec;
+
+impl Codec for BincodeCodec {
+    const ID: CodecId = CodecId::Bincode;
+
+    fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+        bincode::serialize(value).map_err(|e| anyhow!("bincode encoding error: {}", e))
+    }
+
+    fn encode_into<T: Serialize + ?Sized>(value: &T, out: &mut Vec<u8>) -> Result<()> {
+        bincode::serialize_into(out, value).map_err(|e| anyhow!("bincode encoding error: {}", e))
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+        bincode::deserialize(bytes).map_err(|e| anyhow!("bincode decoding error: {}", e))
+    }
+}
+
+/// JSON text, readable by any language
+pub struct JsonCodec;
+
+impl Codec for JsonCodec {
+    const ID: CodecId = CodecId::Json;
+
+    fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+        serde_json::to_vec(value).map_err(|e| anyhow!("JSON encoding error: {}", e))
+    }
+
+    fn encode_into<T: Serialize + ?Sized>(value: &T, out: &mut Vec<u8>) -> Result<()> {
+        serde_json::to_writer(out, value).map_err(|e| anyhow!("JSON encoding error: {}", e))
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+        serde_json::from_slice(bytes).map_err(|e| anyhow!("JSON decoding error: {}", e))
+    }
+}
+
+/// CBOR (requires the `cbor` feature)
+#[cfg(feature = "cbor")]
+pub struct CborCodec;
+
+#[cfg(feature = "cbor")]
+impl Codec for CborCodec {
+    const ID: CodecId = CodecId::Cbor;
+
+    fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        Self::encode_into(value, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn encode_into<T: Serialize + ?Sized>(value: &T, out: &mut Vec<u8>) -> Result<()> {
+        ciborium::into_writer(value, out).map_err(|e| anyhow!("CBOR encoding error: {}", e))
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+        ciborium::from_reader(bytes).map_err(|e| anyhow!("CBOR decoding error: {}", e))
+    }
+}
+
+/// MessagePack (requires the `msgpack` feature)
+#[cfg(feature = "msgpack")]
+pub struct MessagePackCodec;
+
+#[cfg(feature = "msgpack")]
+impl Codec for MessagePackCodec {
+    const ID: CodecId = CodecId::MessagePack;
+
+    fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+        rmp_serde::to_vec(value).map_err(|e| anyhow!("MessagePack encoding error: {}", e))
+    }
+
+    fn encode_into<T: Serialize + ?Sized>(value: &T, out: &mut Vec<u8>) -> Result<()> {
+        rmp_serde::encode::write(out, value)
+            .map_err(|e| anyhow!("MessagePack encoding error: {}", e))
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+        rmp_serde::from_slice(bytes).map_err(|e| anyhow!("MessagePack decoding error: {}", e))
+    }
+}
+
+/// The bincode encoded size of `value` if it is at most `limit` bytes.
+/// Measuring stops as soon as the limit is passed, so it costs at most as
+/// much as encoding `limit` bytes, and allocates nothing.
+pub fn bincode_size_within<T: Serialize + ?Sized>(value: &T, limit: u64) -> Option<u64> {
+    use bincode::Options;
+
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(limit)
+        .serialized_size(value)
+        .ok()
+}
+
+/// Whether this build can encode and decode `codec`
+pub fn is_supported(codec: CodecId) -> bool {
+    match codec {
+        CodecId::Bincode | CodecId::Json => true,
+        CodecId::Cbor => cfg!(feature = "cbor"),
+        CodecId::MessagePack => cfg!(feature = "msgpack"),
+    }
+}
+
+fn unsupported(codec: CodecId) -> anyhow::Error {
+    anyhow!("Codec {:?} is not enabled in this build", codec)
+}
+
+/// Encode a value with the codec identified by `codec`
+pub fn encode_with<T: Serialize + ?Sized>(codec: CodecId, value: &T) -> Result<Vec<u8>> {
+    match codec {
+        CodecId::Bincode => BincodeCodec::encode(value),
+        CodecId::Json => JsonCodec::encode(value),
+        #[cfg(feature = "cbor")]
+        CodecId::Cbor => CborCodec::encode(value),
+        #[cfg(feature = "msgpack")]
+        CodecId::MessagePack => MessagePackCodec::encode(value),
+        #[allow(unreachable_patterns)]
+        other => Err(unsupported(other)),
+    }
+}
+
+/// Encode a value with the codec identified by `codec`, appending it to `out`
+pub fn encode_into_with<T: Serialize + ?Sized>(
+    codec: CodecId,
+    value: &T,
+    out: &mut Vec<u8>,
+) -> Result<()> {
+    match codec {
+        CodecId::Bincode => BincodeCodec::encode_into(value, out),
+        CodecId::Json => JsonCodec::encode_into(value, out),
+        #[cfg(feature = "cbor")]
+        CodecId::Cbor => CborCodec::encode_into(value, out),
+        #[cfg(feature = "msgpack")]
+        CodecId::MessagePack => MessagePackCodec::encode_into(value, out),
+        #[allow(unreachable_patterns)]
+        other => Err(unsupported(other)),
+    }
+}
+
+/// Decode a value with the codec identified by `codec`
+pub fn decode_with<T: DeserializeOwned>(codec: CodecId, bytes: &[u8]) -> Result<T> {
+    match codec {
+        CodecId::Bincode => BincodeCodec::decode(bytes),
+        CodecId::Json => JsonCodec::decode(bytes),
+        #[cfg(feature = "cbor")]
+        CodecId::Cbor => CborCodec::decode(bytes),
+        #[cfg(feature = "msgpack")]
+        CodecId::MessagePack => MessagePackCodec::decode(bytes),
+        #[allow(unreachable_patterns)]
+        other => Err(unsupported(other)),
+    }
+}
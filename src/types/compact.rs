@@ -0,0 +1,593 @@
+// runar_common/src/types/compact.rs
+//
+// Compact binary encoding of `ServiceMetadata` for discovery packets.
+//
+// Layout: [format version][flags][service count][string table][services]
+//
+// Every string (names, paths, descriptions, versions, schema field names) is
+// written once in the string table and referenced by index, so the network
+// ID, repeated field names and common descriptions cost one byte after their
+// first use. Integers are LEB128 varints. Schemas can be left out, in which
+// case decoded actions and events have no schemas; peers fetch them later
+// when they need them.
+//
+// For a typical service with schemas the encoding is less than half the size
+// of bincode, and under a fifth without schemas (see `tests/compact_test.rs`).
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+
+use super::schemas::{ActionMetadata, EventMetadata, FieldSchema, SchemaDataType, ServiceMetadata};
+use super::Version;
+
+/// Version byte at the start of every encoding
+pub const COMPACT_FORMAT_VERSION: u8 = 1;
+
+/// Flag bit set when schemas are included
+const FLAG_SCHEMAS: u8 = 0x01;
+
+/// Deepest schema nesting the decoder accepts
+pub const MAX_SCHEMA_DEPTH: usize = 32;
+
+/// Encode services, sharing one string table
+pub fn encode_services(services: &[ServiceMetadata], include_schemas: bool) -> Vec<u8> {
+    let mut encoder = Encoder {
+        body: Vec::new(),
+        strings: Vec::new(),
+        indexes: HashMap::new(),
+        include_schemas,
+    };
+    for service in services {
+        encoder.service(service);
+    }
+
+    let mut out = vec![
+        COMPACT_FORMAT_VERSION,
+        if include_schemas { FLAG_SCHEMAS } else { 0 },
+    ];
+    write_varint(&mut out, services.len() as u64);
+    write_varint(&mut out, encoder.strings.len() as u64);
+    for string in &encoder.strings {
+        write_varint(&mut out, string.len() as u64);
+        out.extend_from_slice(string.as_bytes());
+    }
+    out.extend_from_slice(&encoder.body);
+    out
+}
+
+/// Decode services written by `encode_services`
+pub fn decode_services(bytes: &[u8]) -> Result<Vec<ServiceMetadata>> {
+    let mut reader = Reader { bytes, pos: 0 };
+    let version = reader.byte()?;
+    if version != COMPACT_FORMAT_VERSION {
+        return Err(anyhow!(
+            "Unsupported compact metadata version {} (expected {})",
+            version,
+            COMPACT_FORMAT_VERSION
+        ));
+    }
+    let include_schemas = reader.byte()? & FLAG_SCHEMAS != 0;
+    let service_count = reader.count()?;
+    let string_count = reader.count()?;
+    let mut strings = Vec::with_capacity(string_count);
+    for _ in 0..string_count {
+        let len = reader.count()?;
+        let raw = reader.take(len)?;
+        let string = std::str::from_utf8(raw)
+            .map_err(|e| anyhow!("Invalid string in compact metadata: {}", e))?;
+        strings.push(string.to_string());
+    }
+
+    let mut decoder = Decoder {
+        reader,
+        strings,
+        include_schemas,
+    };
+    let services = (0..service_count)
+        .map(|_| decoder.service())
+        .collect::<Result<Vec<_>>>()?;
+    if decoder.reader.pos != bytes.len() {
+        return Err(anyhow!(
+            "{} trailing bytes after compact metadata",
+            bytes.len() - decoder.reader.pos
+        ));
+    }
+    Ok(services)
+}
+
+impl ServiceMetadata {
+    /// Encode this service in the compact format, with schemas
+    pub fn to_compact_bytes(&self) -> Vec<u8> {
+        encode_services(std::slice::from_ref(self), true)
+    }
+
+    /// Encode this service in the compact format, leaving out schemas
+    pub fn to_compact_bytes_without_schemas(&self) -> Vec<u8> {
+        encode_services(std::slice::from_ref(self), false)
+    }
+
+    /// Decode a single service written by `to_compact_bytes`
+    pub fn from_compact_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut services = decode_services(bytes)?;
+        if services.len() != 1 {
+            return Err(anyhow!(
+                "Expected one service in compact metadata, found {}",
+                services.len()
+            ));
+        }
+        Ok(services.remove(0))
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+// Field presence bits of `FieldSchema` options
+const HAS_DESCRIPTION: u32 = 1 << 0;
+const HAS_NULLABLE: u32 = 1 << 1;
+const HAS_DEFAULT: u32 = 1 << 2;
+const HAS_PROPERTIES: u32 = 1 << 3;
+const HAS_REQUIRED: u32 = 1 << 4;
+const HAS_ITEMS: u32 = 1 << 5;
+const HAS_PATTERN: u32 = 1 << 6;
+const HAS_ENUM: u32 = 1 << 7;
+const HAS_MINIMUM: u32 = 1 << 8;
+const HAS_MAXIMUM: u32 = 1 << 9;
+const HAS_EXCLUSIVE_MINIMUM: u32 = 1 << 10;
+const HAS_EXCLUSIVE_MAXIMUM: u32 = 1 << 11;
+const HAS_MIN_LENGTH: u32 = 1 << 12;
+const HAS_MAX_LENGTH: u32 = 1 << 13;
+const HAS_MIN_ITEMS: u32 = 1 << 14;
+const HAS_MAX_ITEMS: u32 = 1 << 15;
+const HAS_EXAMPLE: u32 = 1 << 16;
+const HAS_SENSITIVE: u32 = 1 << 17;
+const HAS_ENCRYPTED: u32 = 1 << 18;
+
+struct Encoder {
+    body: Vec<u8>,
+    strings: Vec<String>,
+    indexes: HashMap<String, usize>,
+    include_schemas: bool,
+}
+
+impl Encoder {
+    fn varint(&mut self, value: u64) {
+        write_varint(&mut self.body, value);
+    }
+
+    fn string(&mut self, string: &str) {
+        let index = match self.indexes.get(string) {
+            Some(index) => *index,
+            None => {
+                let index = self.strings.len();
+                self.strings.push(string.to_string());
+                self.indexes.insert(string.to_string(), index);
+                index
+            }
+        };
+        self.varint(index as u64);
+    }
+
+    fn service(&mut self, service: &ServiceMetadata) {
+        self.string(&service.network_id);
+        self.string(&service.service_path);
+        self.string(&service.name);
+        self.string(&service.version.to_string());
+        self.string(&service.description);
+        self.varint(service.registration_time);
+        // 0 means never started
+        self.varint(service.last_start_time.map_or(0, |t| t.saturating_add(1)));
+
+        self.varint(service.actions.len() as u64);
+        for action in &service.actions {
+            self.string(&action.name);
+            self.string(&action.description);
+            if self.include_schemas {
+                self.optional_schema(action.input_schema.as_ref());
+                self.optional_schema(action.output_schema.as_ref());
+            }
+        }
+        self.varint(service.events.len() as u64);
+        for event in &service.events {
+            self.string(&event.path);
+            self.string(&event.description);
+            if self.include_schemas {
+                self.optional_schema(event.data_schema.as_ref());
+            }
+        }
+    }
+
+    fn optional_schema(&mut self, schema: Option<&FieldSchema>) {
+        match schema {
+            Some(schema) => {
+                self.body.push(1);
+                self.schema(schema);
+            }
+            None => self.body.push(0),
+        }
+    }
+
+    fn schema(&mut self, schema: &FieldSchema) {
+        let mut present = 0;
+        for (bit, set) in [
+            (HAS_DESCRIPTION, schema.description.is_some()),
+            (HAS_NULLABLE, schema.nullable.is_some()),
+            (HAS_DEFAULT, schema.default_value.is_some()),
+            (HAS_PROPERTIES, schema.properties.is_some()),
+            (HAS_REQUIRED, schema.required.is_some()),
+            (HAS_ITEMS, schema.items.is_some()),
+            (HAS_PATTERN, schema.pattern.is_some()),
+            (HAS_ENUM, schema.enum_values.is_some()),
+            (HAS_MINIMUM, schema.minimum.is_some()),
+            (HAS_MAXIMUM, schema.maximum.is_some()),
+            (HAS_EXCLUSIVE_MINIMUM, schema.exclusive_minimum.is_some()),
+            (HAS_EXCLUSIVE_MAXIMUM, schema.exclusive_maximum.is_some()),
+            (HAS_MIN_LENGTH, schema.min_length.is_some()),
+            (HAS_MAX_LENGTH, schema.max_length.is_some()),
+            (HAS_MIN_ITEMS, schema.min_items.is_some()),
+            (HAS_MAX_ITEMS, schema.max_items.is_some()),
+            (HAS_EXAMPLE, schema.example.is_some()),
+            (HAS_SENSITIVE, schema.sensitive.is_some()),
+            (HAS_ENCRYPTED, schema.encrypted.is_some()),
+        ] {
+            if set {
+                present |= bit;
+            }
+        }
+
+        self.string(&schema.name);
+        self.data_type(&schema.data_type);
+        self.varint(present as u64);
+        if let Some(description) = &schema.description {
+            self.string(description);
+        }
+        if let Some(nullable) = schema.nullable {
+            self.body.push(nullable as u8);
+        }
+        if let Some(default_value) = &schema.default_value {
+            self.string(default_value);
+        }
+        if let Some(properties) = &schema.properties {
+            // Sorted so equal schemas encode to equal bytes
+            let mut entries: Vec<_> = properties.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            self.varint(entries.len() as u64);
+            for (key, property) in entries {
+                self.string(key);
+                self.schema(property);
+            }
+        }
+        if let Some(required) = &schema.required {
+            self.strings_list(required);
+        }
+        if let Some(items) = &schema.items {
+            self.schema(items);
+        }
+        if let Some(pattern) = &schema.pattern {
+            self.string(pattern);
+        }
+        if let Some(values) = &schema.enum_values {
+            self.strings_list(values);
+        }
+        for value in [schema.minimum, schema.maximum].into_iter().flatten() {
+            self.body.extend_from_slice(&value.to_le_bytes());
+        }
+        for flag in [schema.exclusive_minimum, schema.exclusive_maximum]
+            .into_iter()
+            .flatten()
+        {
+            self.body.push(flag as u8);
+        }
+        for limit in [
+            schema.min_length,
+            schema.max_length,
+            schema.min_items,
+            schema.max_items,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            self.varint(limit as u64);
+        }
+        if let Some(example) = &schema.example {
+            self.string(example);
+        }
+        if let Some(sensitive) = schema.sensitive {
+            self.body.push(sensitive as u8);
+        }
+        if let Some(encrypted) = schema.encrypted {
+            self.body.push(encrypted as u8);
+        }
+    }
+
+    fn strings_list(&mut self, strings: &[String]) {
+        self.varint(strings.len() as u64);
+        for string in strings {
+            self.string(string);
+        }
+    }
+
+    fn data_type(&mut self, data_type: &SchemaDataType) {
+        match data_type {
+            SchemaDataType::String => self.body.push(0),
+            SchemaDataType::Int32 => self.body.push(1),
+            SchemaDataType::Int64 => self.body.push(2),
+            SchemaDataType::Float => self.body.push(3),
+            SchemaDataType::Double => self.body.push(4),
+            SchemaDataType::Boolean => self.body.push(5),
+            SchemaDataType::Timestamp => self.body.push(6),
+            SchemaDataType::Binary => self.body.push(7),
+            SchemaDataType::Object => self.body.push(8),
+            SchemaDataType::Array => self.body.push(9),
+            SchemaDataType::Reference(name) => {
+                self.body.push(10);
+                self.string(name);
+            }
+            SchemaDataType::Union(types) => {
+                self.body.push(11);
+                self.varint(types.len() as u64);
+                for member in types {
+                    self.data_type(member);
+                }
+            }
+            SchemaDataType::Any => self.body.push(12),
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| anyhow!("Compact metadata is truncated at byte {}", self.pos))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(anyhow!("Varint too long at byte {}", self.pos))
+    }
+
+    // A length or count; each counted item takes at least one byte, so
+    // counts beyond the remaining input are rejected before allocating
+    fn count(&mut self) -> Result<usize> {
+        let count = self.varint()?;
+        if count > (self.bytes.len() - self.pos) as u64 {
+            return Err(anyhow!(
+                "Count {} exceeds the remaining compact metadata",
+                count
+            ));
+        }
+        Ok(count as usize)
+    }
+}
+
+struct Decoder<'a> {
+    reader: Reader<'a>,
+    strings: Vec<String>,
+    include_schemas: bool,
+}
+
+impl Decoder<'_> {
+    fn string(&mut self) -> Result<String> {
+        let index = self.reader.varint()?;
+        self.strings
+            .get(index as usize)
+            .cloned()
+            .ok_or_else(|| anyhow!("String index {} out of range", index))
+    }
+
+    fn flag(&mut self) -> Result<bool> {
+        match self.reader.byte()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(anyhow!("Invalid flag byte {}", other)),
+        }
+    }
+
+    fn service(&mut self) -> Result<ServiceMetadata> {
+        let network_id = self.string()?;
+        let service_path = self.string()?;
+        let name = self.string()?;
+        let version = Version::parse(&self.string()?)?;
+        let description = self.string()?;
+        let registration_time = self.reader.varint()?;
+        let last_start_time = self.reader.varint()?.checked_sub(1);
+
+        let mut actions = Vec::new();
+        for _ in 0..self.reader.count()? {
+            let name = self.string()?;
+            let description = self.string()?;
+            let (input_schema, output_schema) = if self.include_schemas {
+                (self.optional_schema()?, self.optional_schema()?)
+            } else {
+                (None, None)
+            };
+            actions.push(ActionMetadata {
+                name,
+                description,
+                input_schema,
+                output_schema,
+            });
+        }
+        let mut events = Vec::new();
+        for _ in 0..self.reader.count()? {
+            let path = self.string()?;
+            let description = self.string()?;
+            let data_schema = if self.include_schemas {
+                self.optional_schema()?
+            } else {
+                None
+            };
+            events.push(EventMetadata {
+                path,
+                description,
+                data_schema,
+            });
+        }
+
+        Ok(ServiceMetadata {
+            network_id,
+            service_path,
+            name,
+            version,
+            description,
+            actions,
+            events,
+            registration_time,
+            last_start_time,
+        })
+    }
+
+    fn optional_schema(&mut self) -> Result<Option<FieldSchema>> {
+        if self.flag()? {
+            Ok(Some(self.schema(0)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn schema(&mut self, depth: usize) -> Result<FieldSchema> {
+        if depth > MAX_SCHEMA_DEPTH {
+            return Err(anyhow!(
+                "Schema nesting exceeds {} levels",
+                MAX_SCHEMA_DEPTH
+            ));
+        }
+        let name = self.string()?;
+        let data_type = self.data_type(depth)?;
+        let present = self.reader.varint()? as u32;
+        let has = |bit: u32| present & bit != 0;
+        let mut schema = FieldSchema::new(&name, data_type);
+
+        if has(HAS_DESCRIPTION) {
+            schema.description = Some(self.string()?);
+        }
+        if has(HAS_NULLABLE) {
+            schema.nullable = Some(self.flag()?);
+        }
+        if has(HAS_DEFAULT) {
+            schema.default_value = Some(self.string()?);
+        }
+        if has(HAS_PROPERTIES) {
+            let mut properties = HashMap::new();
+            for _ in 0..self.reader.count()? {
+                let key = self.string()?;
+                properties.insert(key, Box::new(self.schema(depth + 1)?));
+            }
+            schema.properties = Some(properties);
+        }
+        if has(HAS_REQUIRED) {
+            schema.required = Some(self.strings_list()?);
+        }
+        if has(HAS_ITEMS) {
+            schema.items = Some(Box::new(self.schema(depth + 1)?));
+        }
+        if has(HAS_PATTERN) {
+            schema.pattern = Some(self.string()?);
+        }
+        if has(HAS_ENUM) {
+            schema.enum_values = Some(self.strings_list()?);
+        }
+        if has(HAS_MINIMUM) {
+            schema.minimum = Some(self.float()?);
+        }
+        if has(HAS_MAXIMUM) {
+            schema.maximum = Some(self.float()?);
+        }
+        if has(HAS_EXCLUSIVE_MINIMUM) {
+            schema.exclusive_minimum = Some(self.flag()?);
+        }
+        if has(HAS_EXCLUSIVE_MAXIMUM) {
+            schema.exclusive_maximum = Some(self.flag()?);
+        }
+        if has(HAS_MIN_LENGTH) {
+            schema.min_length = Some(self.reader.varint()? as usize);
+        }
+        if has(HAS_MAX_LENGTH) {
+            schema.max_length = Some(self.reader.varint()? as usize);
+        }
+        if has(HAS_MIN_ITEMS) {
+            schema.min_items = Some(self.reader.varint()? as usize);
+        }
+        if has(HAS_MAX_ITEMS) {
+            schema.max_items = Some(self.reader.varint()? as usize);
+        }
+        if has(HAS_EXAMPLE) {
+            schema.example = Some(self.string()?);
+        }
+        if has(HAS_SENSITIVE) {
+            schema.sensitive = Some(self.flag()?);
+        }
+        if has(HAS_ENCRYPTED) {
+            schema.encrypted = Some(self.flag()?);
+        }
+        Ok(schema)
+    }
+
+    fn strings_list(&mut self) -> Result<Vec<String>> {
+        (0..self.reader.count()?).map(|_| self.string()).collect()
+    }
+
+    fn float(&mut self) -> Result<f64> {
+        let raw = self.reader.take(8)?;
+        Ok(f64::from_le_bytes(raw.try_into().expect("eight bytes")))
+    }
+
+    fn data_type(&mut self, depth: usize) -> Result<SchemaDataType> {
+        Ok(match self.reader.byte()? {
+            0 => SchemaDataType::String,
+            1 => SchemaDataType::Int32,
+            2 => SchemaDataType::Int64,
+            3 => SchemaDataType::Float,
+            4 => SchemaDataType::Double,
+            5 => SchemaDataType::Boolean,
+            6 => SchemaDataType::Timestamp,
+            7 => SchemaDataType::Binary,
+            8 => SchemaDataType::Object,
+            9 => SchemaDataType::Array,
+            10 => SchemaDataType::Reference(self.string()?),
+            11 => {
+                if depth > MAX_SCHEMA_DEPTH {
+                    return Err(anyhow!(
+                        "Schema nesting exceeds {} levels",
+                        MAX_SCHEMA_DEPTH
+                    ));
+                }
+                let count = self.reader.count()?;
+                let members = (0..count)
+                    .map(|_| self.data_type(depth + 1))
+                    .collect::<Result<Vec<_>>>()?;
+                SchemaDataType::Union(members)
+            }
+            12 => SchemaDataType::Any,
+            other => return Err(anyhow!("Unknown schema data type tag {}", other)),
+        })
+    }
+}
@@ -0,0 +1,340 @@
+// runar_common/src/types/convert.rs
+//
+// Conversion traits between Rust values and ArcValueType
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::value_type::{ArcValueType, ValueCategory};
+use crate::utils::num::Number;
+
+/// Types that can be turned into an `ArcValueType`.
+///
+/// Lets helpers take `impl ToArcValue` instead of requiring callers to pick
+/// the right `ArcValueType` constructor.
+pub trait ToArcValue {
+    /// Convert into an `ArcValueType`
+    fn to_arc_value(self) -> ArcValueType;
+}
+
+/// Types that can be extracted from an `ArcValueType`.
+///
+/// Lazily deserialized values are decoded on extraction, so struct types must
+/// be registered with the `SerializerRegistry` that produced the value.
+pub trait FromArcValue: Sized {
+    /// Extract from an `ArcValueType`, failing if the stored type does not match
+    fn from_arc_value(value: ArcValueType) -> Result<Self>;
+}
+
+impl ToArcValue for ArcValueType {
+    fn to_arc_value(self) -> ArcValueType {
+        self
+    }
+}
+
+impl FromArcValue for ArcValueType {
+    fn from_arc_value(value: ArcValueType) -> Result<Self> {
+        Ok(value)
+    }
+}
+
+macro_rules! impl_primitive_conversions {
+    ($($t:ty),*) => {
+        $(
+            impl ToArcValue for $t {
+                fn to_arc_value(self) -> ArcValueType {
+                    ArcValueType::new_primitive(self)
+                }
+            }
+
+            impl FromArcValue for $t {
+                fn from_arc_value(value: ArcValueType) -> Result<Self> {
+                    value.expect_type::<$t>(ValueCategory::Primitive)?;
+                    value.into_type::<$t>()
+                }
+            }
+
+            crate::implement_from_for_valuetype!($t, Primitive);
+            impl_try_from_arc_value!($t);
+        )*
+    };
+}
+
+// `TryFrom<ArcValueType>` mirrors `FromArcValue` so `.try_into()` works too
+macro_rules! impl_try_from_arc_value {
+    ($t:ty $(, $param:ident)*) => {
+        impl<$($param),*> TryFrom<ArcValueType> for $t
+        where
+            $t: FromArcValue,
+        {
+            type Error = anyhow::Error;
+
+            fn try_from(value: ArcValueType) -> Result<Self> {
+                <$t as FromArcValue>::from_arc_value(value)
+            }
+        }
+    };
+}
+
+impl_primitive_conversions!(bool, char);
+
+// Numbers are also read from any other numeric primitive whose value fits,
+// so an `i32` value reads as `i64` (see `utils::num`)
+macro_rules! impl_numeric_conversions {
+    ($($t:ty),*) => {
+        $(
+            impl ToArcValue for $t {
+                fn to_arc_value(self) -> ArcValueType {
+                    ArcValueType::new_primitive(self)
+                }
+            }
+
+            impl FromArcValue for $t {
+                fn from_arc_value(value: ArcValueType) -> Result<Self> {
+                    if let Err(mismatch) = value.expect_type::<$t>(ValueCategory::Primitive) {
+                        return Number::from_value(&value).ok_or(mismatch)?.to_checked();
+                    }
+                    value.into_type::<$t>()
+                }
+            }
+
+            crate::implement_from_for_valuetype!($t, Primitive);
+            impl_try_from_arc_value!($t);
+        )*
+    };
+}
+
+impl_numeric_conversions!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64);
+
+impl ToArcValue for String {
+    fn to_arc_value(self) -> ArcValueType {
+        ArcValueType::new_primitive(self)
+    }
+}
+
+impl ToArcValue for &str {
+    fn to_arc_value(self) -> ArcValueType {
+        ArcValueType::new_primitive(self.to_string())
+    }
+}
+
+crate::implement_from_for_valuetype!(String, String);
+
+impl From<&str> for ArcValueType {
+    fn from(value: &str) -> Self {
+        value.to_arc_value()
+    }
+}
+
+impl_try_from_arc_value!(String);
+
+impl FromArcValue for String {
+    fn from_arc_value(value: ArcValueType) -> Result<Self> {
+        // Values built by `vmap!` hold string literals rather than Strings
+        if value
+            .expect_type::<&'static str>(ValueCategory::Primitive)
+            .is_ok()
+        {
+            return Ok(value.value.as_arc::<&'static str>()?.to_string());
+        }
+        value.expect_type::<String>(ValueCategory::Primitive)?;
+        value.into_type::<String>()
+    }
+}
+
+// Addresses and paths are stored as themselves, and are also read from
+// strings since configuration often carries them as text
+macro_rules! impl_parsed_conversions {
+    ($($t:ty),*) => {
+        $(
+            impl ToArcValue for $t {
+                fn to_arc_value(self) -> ArcValueType {
+                    ArcValueType::new_primitive(self)
+                }
+            }
+
+            impl FromArcValue for $t {
+                fn from_arc_value(value: ArcValueType) -> Result<Self> {
+                    if let Err(e) = value.expect_type::<$t>(ValueCategory::Primitive) {
+                        let Ok(text) = String::from_arc_value(value) else {
+                            return Err(e);
+                        };
+                        return text.parse::<$t>().map_err(|parse| {
+                            anyhow!("Cannot read '{}' as {}: {}", text, stringify!($t), parse)
+                        });
+                    }
+                    value.into_type::<$t>()
+                }
+            }
+
+            crate::implement_from_for_valuetype!($t, Primitive);
+            impl_try_from_arc_value!($t);
+        )*
+    };
+}
+
+impl_parsed_conversions!(SocketAddr, IpAddr, PathBuf);
+
+impl<T> ToArcValue for Vec<T>
+where
+    T: 'static + fmt::Debug + Send + Sync,
+{
+    fn to_arc_value(self) -> ArcValueType {
+        ArcValueType::new_list(self)
+    }
+}
+
+impl<T> FromArcValue for Vec<T>
+where
+    T: 'static + Clone + for<'de> Deserialize<'de> + fmt::Debug + Send + Sync,
+{
+    fn from_arc_value(value: ArcValueType) -> Result<Self> {
+        value.expect_type::<Vec<T>>(ValueCategory::List)?;
+        value.into_list::<T>()
+    }
+}
+
+impl<T> From<Vec<T>> for ArcValueType
+where
+    T: 'static + fmt::Debug + Send + Sync,
+{
+    fn from(values: Vec<T>) -> Self {
+        ArcValueType::new_list(values)
+    }
+}
+
+impl_try_from_arc_value!(Vec<T>, T);
+
+impl<K, V> ToArcValue for HashMap<K, V>
+where
+    K: 'static + fmt::Debug + Send + Sync,
+    V: 'static + fmt::Debug + Send + Sync,
+{
+    fn to_arc_value(self) -> ArcValueType {
+        ArcValueType::new_map(self)
+    }
+}
+
+impl<K, V> FromArcValue for HashMap<K, V>
+where
+    K: 'static
+        + Clone
+        + Serialize
+        + for<'de> Deserialize<'de>
+        + fmt::Debug
+        + Eq
+        + Hash
+        + Send
+        + Sync,
+    V: 'static + Clone + Serialize + for<'de> Deserialize<'de> + fmt::Debug + Send + Sync,
+{
+    fn from_arc_value(value: ArcValueType) -> Result<Self> {
+        value.expect_type::<HashMap<K, V>>(ValueCategory::Map)?;
+        value.into_map::<K, V>()
+    }
+}
+
+impl<T> From<HashMap<String, T>> for ArcValueType
+where
+    T: 'static + fmt::Debug + Send + Sync,
+{
+    fn from(map: HashMap<String, T>) -> Self {
+        ArcValueType::new_map(map)
+    }
+}
+
+impl_try_from_arc_value!(HashMap<K, V>, K, V);
+
+impl ArcValueType {
+    /// Build a map value from key/value pairs.
+    ///
+    /// Later pairs overwrite earlier ones with the same key, matching
+    /// `HashMap::from_iter`.
+    pub fn from_pairs<K, V, I>(pairs: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<ArcValueType>,
+    {
+        let map: HashMap<String, ArcValueType> = pairs
+            .into_iter()
+            .map(|(key, value)| (key.into(), value.into()))
+            .collect();
+        ArcValueType::new_map(map)
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for ArcValueType
+where
+    K: Into<String>,
+    V: Into<ArcValueType>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        ArcValueType::from_pairs(iter)
+    }
+}
+
+impl<T: ToArcValue> ToArcValue for Option<T> {
+    fn to_arc_value(self) -> ArcValueType {
+        match self {
+            Some(inner) => inner.to_arc_value(),
+            None => ArcValueType::null(),
+        }
+    }
+}
+
+impl<T: FromArcValue> FromArcValue for Option<T> {
+    fn from_arc_value(value: ArcValueType) -> Result<Self> {
+        if value.category == ValueCategory::Null {
+            return Ok(None);
+        }
+        T::from_arc_value(value).map(Some)
+    }
+}
+
+/// Implement `ToArcValue` and `FromArcValue` for serde structs.
+///
+/// Structs are stored with the Struct category; extracting a lazily
+/// deserialized struct requires the type to be registered with the registry.
+///
+/// ```
+/// use runar_common::impl_arc_value_struct;
+/// use runar_common::types::{FromArcValue, ToArcValue};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+/// impl_arc_value_struct!(Point);
+///
+/// let value = Point { x: 1, y: 2 }.to_arc_value();
+/// assert_eq!(Point::from_arc_value(value).unwrap(), Point { x: 1, y: 2 });
+/// ```
+#[macro_export]
+macro_rules! impl_arc_value_struct {
+    ($($t:ty),+ $(,)?) => {
+        $(
+            impl $crate::types::ToArcValue for $t {
+                fn to_arc_value(self) -> $crate::types::ArcValueType {
+                    $crate::types::ArcValueType::from_struct(self)
+                }
+            }
+
+            impl $crate::types::FromArcValue for $t {
+                fn from_arc_value(mut value: $crate::types::ArcValueType) -> anyhow::Result<Self> {
+                    let arc = value.as_struct_ref::<$t>()?;
+                    drop(value);
+                    Ok(std::sync::Arc::try_unwrap(arc).unwrap_or_else(|shared| (*shared).clone()))
+                }
+            }
+        )+
+    };
+}
@@ -0,0 +1,110 @@
+// runar_common/src/types/deadline.rs
+//
+// Deadline propagation for requests.
+//
+// A Deadline is an absolute point on the local monotonic clock. On the wire it
+// travels as the number of milliseconds remaining, so nodes with skewed wall
+// clocks still agree on how much time a request has left.
+
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::errors::{ErrorCode, RunarError};
+use crate::utils::time::Instant;
+
+/// An absolute deadline for completing an operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Deadline {
+    instant: Instant,
+}
+
+impl Deadline {
+    /// Create a deadline the given duration from now
+    pub fn after(timeout: Duration) -> Self {
+        Self {
+            instant: Instant::now() + timeout,
+        }
+    }
+
+    /// Create a deadline at the given instant
+    pub fn at(instant: Instant) -> Self {
+        Self { instant }
+    }
+
+    /// Recreate a deadline from a remaining-millis value received on the wire
+    pub fn from_remaining_millis(millis: u64) -> Self {
+        Self::after(Duration::from_millis(millis))
+    }
+
+    /// Get the underlying instant
+    pub fn instant(&self) -> Instant {
+        self.instant
+    }
+
+    /// Get the time remaining until the deadline (zero once expired)
+    pub fn remaining(&self) -> Duration {
+        self.instant.saturating_duration_since(Instant::now())
+    }
+
+    /// Get the time remaining in whole milliseconds, as sent on the wire
+    pub fn remaining_millis(&self) -> u64 {
+        self.remaining().as_millis().min(u64::MAX as u128) as u64
+    }
+
+    /// Check whether the deadline has passed
+    pub fn is_expired(&self) -> bool {
+        Instant::now() >= self.instant
+    }
+
+    /// Return a Timeout error if the deadline has passed
+    pub fn check(&self) -> Result<(), RunarError> {
+        if self.is_expired() {
+            Err(RunarError::new(ErrorCode::Timeout, "Deadline exceeded"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Derive a deadline for a downstream call that is at most `timeout` from now,
+    /// but never later than this deadline
+    pub fn child(&self, timeout: Duration) -> Self {
+        self.earliest(&Self::after(timeout))
+    }
+
+    /// Derive a deadline that leaves `margin` for this handler to process the
+    /// downstream result before its own deadline expires
+    pub fn child_with_margin(&self, margin: Duration) -> Self {
+        let instant = self.instant.checked_sub(margin).unwrap_or(self.instant);
+        Self { instant }
+    }
+
+    /// Return whichever of the two deadlines comes first
+    pub fn earliest(&self, other: &Deadline) -> Self {
+        if self.instant <= other.instant {
+            *self
+        } else {
+            *other
+        }
+    }
+}
+
+impl fmt::Display for Deadline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Deadline(remaining: {}ms)", self.remaining_millis())
+    }
+}
+
+impl Serialize for Deadline {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.remaining_millis())
+    }
+}
+
+impl<'de> Deserialize<'de> for Deadline {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let millis = u64::deserialize(deserializer)?;
+        Ok(Deadline::from_remaining_millis(millis))
+    }
+}
@@ -0,0 +1,356 @@
+// runar_common/src/types/decode_context.rs
+//
+// Quotas and cancellation for decoding untrusted payloads.
+//
+// A node decodes values for many peers on a small pool of threads. A
+// `DecodeContext` passed to `SerializerRegistry::deserialize_value_in`
+// carries the sending peer's `DecodeQuota` (bytes and CPU time it may still
+// use) and a `CancellationToken`. The decoder charges the quota as it goes:
+// raw bytes are copied `chunk_size` bytes at a time, other payloads are
+// charged before they are decoded, and the values nested in lists and maps
+// are materialized one at a time with a check after each. One peer sending
+// huge or expensive values therefore fails fast instead of holding a decode
+// thread.
+//
+// Quotas are shared (`Arc`) by every decode for the same peer and count
+// what has been used until `reset` is called, e.g. once per rate window.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use super::codec;
+use super::ids::NodeId;
+use super::provenance::Provenance;
+use super::value_type::{ArcValueType, SerializerRegistry, ValueCategory};
+use super::visit::{is_value_list, is_value_map};
+use crate::errors::{ErrorCode, RunarError};
+use crate::utils::time::Instant;
+
+/// Bytes decoded between quota and cancellation checks by default
+pub const DEFAULT_DECODE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Shared flag telling in-progress work to stop
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a token that is not cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel all work holding a clone of this token
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether `cancel` has been called
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Bytes and CPU time one peer may spend on decoding
+#[derive(Debug, Default)]
+pub struct DecodeQuota {
+    max_bytes: Option<u64>,
+    max_cpu: Option<Duration>,
+    bytes_used: AtomicU64,
+    cpu_nanos_used: AtomicU64,
+}
+
+impl DecodeQuota {
+    /// Create a quota with no limits
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Limit the bytes decoded until the next `reset`
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Limit the time spent decoding until the next `reset`
+    pub fn with_max_cpu(mut self, max_cpu: Duration) -> Self {
+        self.max_cpu = Some(max_cpu);
+        self
+    }
+
+    /// Bytes decoded since the last reset
+    pub fn bytes_used(&self) -> u64 {
+        self.bytes_used.load(Ordering::Relaxed)
+    }
+
+    /// Time spent decoding since the last reset
+    pub fn cpu_used(&self) -> Duration {
+        Duration::from_nanos(self.cpu_nanos_used.load(Ordering::Relaxed))
+    }
+
+    /// Start counting from zero again
+    pub fn reset(&self) {
+        self.bytes_used.store(0, Ordering::Relaxed);
+        self.cpu_nanos_used.store(0, Ordering::Relaxed);
+    }
+
+    // Record usage, failing without recording it if it would take the quota
+    // over a limit
+    fn charge(&self, peer: &str, bytes: u64, cpu: Duration) -> Result<()> {
+        if let Err(used) = try_add(&self.bytes_used, bytes, self.max_bytes) {
+            return Err(RunarError::new(
+                ErrorCode::Unavailable,
+                format!(
+                    "Decode quota exceeded for {}: {} bytes used of {}",
+                    peer,
+                    used.saturating_add(bytes),
+                    self.max_bytes.unwrap_or_default()
+                ),
+            )
+            .into());
+        }
+        let cpu_nanos = cpu.as_nanos().min(u64::MAX as u128) as u64;
+        let max_cpu_nanos = self
+            .max_cpu
+            .map(|max| max.as_nanos().min(u64::MAX as u128) as u64);
+        if let Err(used) = try_add(&self.cpu_nanos_used, cpu_nanos, max_cpu_nanos) {
+            // Leave the bytes uncharged too, as the whole charge was refused
+            self.bytes_used.fetch_sub(bytes, Ordering::Relaxed);
+            return Err(RunarError::new(
+                ErrorCode::Unavailable,
+                format!(
+                    "Decode quota exceeded for {}: {:?} of CPU used of {:?}",
+                    peer,
+                    Duration::from_nanos(used.saturating_add(cpu_nanos)),
+                    self.max_cpu.unwrap_or_default()
+                ),
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+// Add `amount` to `used` unless the sum would exceed `max`; on failure
+// returns the usage the sum was checked against
+fn try_add(used: &AtomicU64, amount: u64, max: Option<u64>) -> std::result::Result<u64, u64> {
+    used.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+        let next = current.saturating_add(amount);
+        max.is_none_or(|max| next <= max).then_some(next)
+    })
+}
+
+/// Quota, cancellation and origin of one decode
+#[derive(Debug, Clone)]
+pub struct DecodeContext {
+    peer: Option<NodeId>,
+    quota: Arc<DecodeQuota>,
+    cancellation: CancellationToken,
+    chunk_size: usize,
+    transport: Option<String>,
+}
+
+impl Default for DecodeContext {
+    fn default() -> Self {
+        Self::new(Arc::new(DecodeQuota::unlimited()))
+    }
+}
+
+impl DecodeContext {
+    /// Create a context charging `quota`
+    pub fn new(quota: Arc<DecodeQuota>) -> Self {
+        DecodeContext {
+            peer: None,
+            quota,
+            cancellation: CancellationToken::new(),
+            chunk_size: DEFAULT_DECODE_CHUNK_SIZE,
+            transport: None,
+        }
+    }
+
+    /// Set the peer the payload came from; it is named in errors and logs
+    /// and recorded as the value's provenance origin
+    pub fn with_peer(mut self, peer: NodeId) -> Self {
+        self.peer = Some(peer);
+        self
+    }
+
+    /// Set the transport recorded in the value's provenance
+    pub fn with_transport(mut self, transport: impl Into<String>) -> Self {
+        self.transport = Some(transport.into());
+        self
+    }
+
+    /// Stop decoding when `cancellation` is cancelled
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Set how many bytes are decoded between checks (at least 1)
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// The peer the payload came from, if known
+    pub fn peer(&self) -> Option<&NodeId> {
+        self.peer.as_ref()
+    }
+
+    /// The quota charged by this context
+    pub fn quota(&self) -> &Arc<DecodeQuota> {
+        &self.quota
+    }
+
+    /// The context's cancellation token
+    pub fn cancellation(&self) -> &CancellationToken {
+        &self.cancellation
+    }
+
+    /// Bytes decoded between checks
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Fail with a `Cancelled` `RunarError` if the decode was cancelled
+    pub fn check_cancelled(&self) -> Result<()> {
+        if self.cancellation.is_cancelled() {
+            return Err(RunarError::new(
+                ErrorCode::Cancelled,
+                format!("Decode cancelled for {}", self.peer_name()),
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    fn peer_name(&self) -> String {
+        self.peer.as_ref().map_or_else(
+            || "unknown peer".to_string(),
+            |peer| format!("peer '{}'", peer),
+        )
+    }
+
+    fn provenance(&self) -> Provenance {
+        let mut provenance = Provenance::received();
+        provenance.origin = self.peer.clone();
+        provenance.transport = self.transport.clone();
+        provenance
+    }
+}
+
+// Charges a decode to its context as it goes
+struct DecodeMeter<'a> {
+    context: &'a DecodeContext,
+    last_check: Instant,
+}
+
+impl<'a> DecodeMeter<'a> {
+    fn start(context: &'a DecodeContext) -> Result<Self> {
+        context.check_cancelled()?;
+        Ok(DecodeMeter {
+            context,
+            last_check: Instant::now(),
+        })
+    }
+
+    // Charge `bytes` and the time since the last check, then check again
+    fn charge(&mut self, bytes: usize) -> Result<()> {
+        let now = Instant::now();
+        let cpu = now.duration_since(self.last_check);
+        self.last_check = now;
+        self.context
+            .quota
+            .charge(&self.context.peer_name(), bytes as u64, cpu)?;
+        self.context.check_cancelled()
+    }
+}
+
+impl SerializerRegistry {
+    /// Deserialize and materialize a value within `context`'s quota and
+    /// cancellation.
+    ///
+    /// Raw bytes are copied in chunks and other payloads are charged before
+    /// they are decoded; values nested in lists and maps are materialized
+    /// one at a time, which (as for `ArcValueType::materialize`) needs a
+    /// registry scope. Quota and cancellation are checked after every step
+    /// and between the chunks of a checksum, if configured. Exceeding the
+    /// quota fails with an `Unavailable` `RunarError` and cancellation with
+    /// a `Cancelled` one. The value's provenance names the context's peer
+    /// and transport.
+    pub fn deserialize_value_in(
+        &self,
+        bytes_arc: Arc<[u8]>,
+        context: &DecodeContext,
+    ) -> Result<ArcValueType> {
+        let mut meter = DecodeMeter::start(context)?;
+        let result = self
+            .check_checksum_flag(&bytes_arc)
+            .and_then(|_| match self.checksum() {
+                // Bytes are counted once, as they are decoded
+                Some(algorithm) => algorithm
+                    .verify_trailing_chunked(&bytes_arc, context.chunk_size, |_| meter.charge(0))
+                    .and_then(|payload| self.decode_metered(Arc::from(payload), &mut meter)),
+                None => self.decode_metered(bytes_arc.clone(), &mut meter),
+            });
+        // Quota and cancellation failures are the peer's doing, not bad payloads
+        let value = result.inspect_err(|error| {
+            let code = RunarError::code_of(error);
+            if !matches!(code, ErrorCode::Cancelled | ErrorCode::Unavailable) {
+                let source = context.peer.as_ref().map(|peer| peer.to_string());
+                self.log_failure(&bytes_arc, source.as_deref(), error);
+            }
+        })?;
+        Ok(value.with_provenance(context.provenance()))
+    }
+
+    // Decode and materialize a value, charging `meter` before each step
+    fn decode_metered(
+        &self,
+        bytes_arc: Arc<[u8]>,
+        meter: &mut DecodeMeter,
+    ) -> Result<ArcValueType> {
+        let (header, payload) = self.extract_header_from_slice(&bytes_arc)?;
+        meter.charge(bytes_arc.len() - payload.len())?;
+        if header.category == ValueCategory::Bytes && codec::is_supported(header.codec) {
+            let mut data = Vec::with_capacity(payload.len());
+            for chunk in payload.chunks(meter.context.chunk_size) {
+                meter.charge(chunk.len())?;
+                data.extend_from_slice(chunk);
+            }
+            return Ok(ArcValueType::new_bytes(data));
+        }
+        // Other payloads can only be decoded whole
+        meter.charge(payload.len())?;
+        let mut value = self.decode_value(bytes_arc.clone())?;
+        self.materialize_metered(&mut value, meter)?;
+        Ok(value)
+    }
+
+    // Materialize `value` and the values nested in its lists and maps,
+    // charging the time of each to `meter`. Nested payloads were counted
+    // with their parent's bytes.
+    fn materialize_metered(&self, value: &mut ArcValueType, meter: &mut DecodeMeter) -> Result<()> {
+        if let Ok(lazy) = value.value.try_get_lazy_data() {
+            value.value = self.materialize_lazy(&lazy)?;
+            meter.charge(0)?;
+        }
+        if is_value_list(value) {
+            value.update_list(|items: &mut Vec<ArcValueType>| {
+                items
+                    .iter_mut()
+                    .try_for_each(|item| self.materialize_metered(item, meter))
+            })??;
+        } else if is_value_map(value) {
+            value.update_map(|entries| {
+                entries
+                    .values_mut()
+                    .try_for_each(|entry| self.materialize_metered(entry, meter))
+            })??;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,265 @@
+// runar_common/src/types/encryption.rs
+//
+// Encryption of schema-marked fields.
+//
+// `encrypt_fields` walks a value alongside its schema and replaces every
+// field marked `encrypted` with a `Bytes` value holding the field's
+// serialized subtree, encrypted under a key the node chooses. The rest of
+// the payload stays readable, so it can still be routed, validated and
+// logged; only holders of the key can read the encrypted fields back with
+// `decrypt_fields` or, one field at a time, `EncryptedField::decrypt`.
+// Decryption goes by the same schema, so ordinary `Bytes` fields that happen
+// to start with the magic prefix are never mistaken for encrypted ones.
+//
+// Encrypted field layout:
+//
+//   ENCRYPTED_FIELD_MAGIC | key ID length (u16 LE) | key ID | ciphertext
+//
+// The key ID travels with each field so data encrypted under rotated keys
+// stays readable. Ciphers and key storage are left to the node through the
+// `FieldEncryptor` and `FieldDecryptor` traits.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use super::schemas::FieldSchema;
+use super::value_type::{ArcValueType, SerializerRegistry, ValueCategory};
+use super::visit::{format_path, is_value_list, is_value_map, PathSegment, ValueVisitor, Visit};
+use crate::errors::{ErrorCode, RunarError};
+
+/// Prefix identifying an encrypted field's bytes
+pub const ENCRYPTED_FIELD_MAGIC: &[u8] = b"RNENC1";
+
+/// Encrypts field values with a node key
+pub trait FieldEncryptor: Send + Sync {
+    /// ID of the key, stored with each field so it can be decrypted later
+    fn key_id(&self) -> &str;
+
+    /// Encrypt `plaintext`
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Decrypts field values
+pub trait FieldDecryptor: Send + Sync {
+    /// Decrypt `ciphertext` made with the key `key_id`
+    fn decrypt(&self, key_id: &str, ciphertext: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// An encrypted field value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedField {
+    key_id: String,
+    ciphertext: Vec<u8>,
+}
+
+impl EncryptedField {
+    /// Read an encrypted field, or None if `value` is not one
+    pub fn from_value(value: &ArcValueType) -> Option<Self> {
+        if value.category != ValueCategory::Bytes {
+            return None;
+        }
+        Self::from_bytes(&value.as_bytes_ref().ok()?)
+    }
+
+    /// Parse the encrypted field layout, or None if `bytes` do not hold one
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let rest = bytes.strip_prefix(ENCRYPTED_FIELD_MAGIC)?;
+        let (len, rest) = rest.split_first_chunk::<2>()?;
+        let len = u16::from_le_bytes(*len) as usize;
+        if rest.len() < len {
+            return None;
+        }
+        let (key_id, ciphertext) = rest.split_at(len);
+        Some(EncryptedField {
+            key_id: String::from_utf8(key_id.to_vec()).ok()?,
+            ciphertext: ciphertext.to_vec(),
+        })
+    }
+
+    /// Encrypt `value`, serialized with `registry`
+    pub fn encrypt(
+        value: &ArcValueType,
+        registry: &SerializerRegistry,
+        encryptor: &dyn FieldEncryptor,
+    ) -> Result<Self> {
+        let plaintext = registry.serialize_value(value)?;
+        Ok(EncryptedField {
+            key_id: encryptor.key_id().to_string(),
+            ciphertext: encryptor.encrypt(&plaintext)?,
+        })
+    }
+
+    /// Decrypt the field and deserialize it with `registry`. Fails with an
+    /// `Unauthorized` `RunarError` if the decryptor cannot decrypt it.
+    pub fn decrypt(
+        &self,
+        registry: &SerializerRegistry,
+        decryptor: &dyn FieldDecryptor,
+    ) -> Result<ArcValueType> {
+        let plaintext = decryptor
+            .decrypt(&self.key_id, &self.ciphertext)
+            .map_err(|e| {
+                RunarError::new(
+                    ErrorCode::Unauthorized,
+                    format!("Cannot decrypt field with key '{}': {}", self.key_id, e),
+                )
+            })?;
+        registry.deserialize_value(Arc::from(plaintext))
+    }
+
+    /// ID of the key the field was encrypted with
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// The encrypted field layout
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(
+            ENCRYPTED_FIELD_MAGIC.len() + 2 + self.key_id.len() + self.ciphertext.len(),
+        );
+        bytes.extend_from_slice(ENCRYPTED_FIELD_MAGIC);
+        bytes.extend_from_slice(&(self.key_id.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(self.key_id.as_bytes());
+        bytes.extend_from_slice(&self.ciphertext);
+        bytes
+    }
+
+    /// The field as a `Bytes` value
+    pub fn to_value(&self) -> ArcValueType {
+        ArcValueType::new_bytes(self.to_bytes())
+    }
+}
+
+/// Return a copy of `value` with every field marked `encrypted` in `schema`
+/// replaced by an `EncryptedField` `Bytes` value.
+///
+/// Object properties and array items are matched against `properties` and
+/// `items`, like `redact`; `Reference` types are not followed. Null fields
+/// are left as they are. Structs, JSON values and typed lists and maps that
+/// hold encrypted fields are walked through their JSON form
+/// (`SerializerRegistry::value_to_json`), so they come back as maps and
+/// lists; if they have no JSON form (e.g. an unregistered struct type)
+/// encryption fails rather than leaving the fields readable.
+pub fn encrypt_fields(
+    value: ArcValueType,
+    schema: &FieldSchema,
+    registry: &SerializerRegistry,
+    encryptor: &dyn FieldEncryptor,
+) -> Result<ArcValueType> {
+    if encryptor.key_id().len() > u16::MAX as usize {
+        return Err(
+            RunarError::new(ErrorCode::InvalidInput, "Encryption key ID is too long").into(),
+        );
+    }
+    value.transform(&mut FieldEncryption {
+        schema,
+        registry,
+        encryptor,
+    })
+}
+
+/// Return a copy of `value` with every field marked `encrypted` in `schema`
+/// decrypted. Fields are found the way `encrypt_fields` finds them; marked
+/// fields that do not hold an `EncryptedField` (e.g. nulls) and any `Bytes`
+/// outside marked fields are left as they are.
+pub fn decrypt_fields(
+    value: ArcValueType,
+    schema: &FieldSchema,
+    registry: &SerializerRegistry,
+    decryptor: &dyn FieldDecryptor,
+) -> Result<ArcValueType> {
+    value.transform(&mut FieldDecryption {
+        schema,
+        registry,
+        decryptor,
+    })
+}
+
+struct FieldEncryption<'a> {
+    schema: &'a FieldSchema,
+    registry: &'a SerializerRegistry,
+    encryptor: &'a dyn FieldEncryptor,
+}
+
+impl ValueVisitor for FieldEncryption<'_> {
+    fn enter(&mut self, path: &[PathSegment], value: &ArcValueType) -> Result<Visit> {
+        let Some(schema) = schema_at(self.schema, path) else {
+            return Ok(Visit::Skip);
+        };
+        if schema.encrypted == Some(true) && !value.is_null() {
+            let field = EncryptedField::encrypt(value, self.registry, self.encryptor)?;
+            return Ok(Visit::Replace(field.to_value()));
+        }
+        // `transform` only walks lists and maps of values
+        let opaque = matches!(
+            value.category,
+            ValueCategory::Struct | ValueCategory::Json | ValueCategory::List | ValueCategory::Map
+        ) && !is_value_list(value)
+            && !is_value_map(value);
+        if opaque && has_encrypted_fields(schema) {
+            let json = self.registry.value_to_json(value).map_err(|e| {
+                RunarError::new(
+                    ErrorCode::InvalidInput,
+                    format!("Cannot encrypt fields inside {}: {}", format_path(path), e),
+                )
+            })?;
+            let walked = encrypt_fields(
+                ArcValueType::from_json(json),
+                schema,
+                self.registry,
+                self.encryptor,
+            )?;
+            return Ok(Visit::Replace(walked));
+        }
+        Ok(Visit::Continue)
+    }
+}
+
+struct FieldDecryption<'a> {
+    schema: &'a FieldSchema,
+    registry: &'a SerializerRegistry,
+    decryptor: &'a dyn FieldDecryptor,
+}
+
+impl ValueVisitor for FieldDecryption<'_> {
+    fn enter(&mut self, path: &[PathSegment], value: &ArcValueType) -> Result<Visit> {
+        let Some(schema) = schema_at(self.schema, path) else {
+            return Ok(Visit::Skip);
+        };
+        if schema.encrypted == Some(true) {
+            return match EncryptedField::from_value(value) {
+                Some(field) => field
+                    .decrypt(self.registry, self.decryptor)
+                    .map(Visit::Replace)
+                    .map_err(|e| e.context(format!("At {}", format_path(path)))),
+                None => Ok(Visit::Skip),
+            };
+        }
+        if has_encrypted_fields(schema) {
+            Ok(Visit::Continue)
+        } else {
+            Ok(Visit::Skip)
+        }
+    }
+}
+
+// Whether `schema` marks a field below it as encrypted
+fn has_encrypted_fields(schema: &FieldSchema) -> bool {
+    let properties = schema
+        .properties
+        .iter()
+        .flat_map(|properties| properties.values());
+    properties
+        .map(|property| &**property)
+        .chain(schema.items.as_deref())
+        .any(|child| child.encrypted == Some(true) || has_encrypted_fields(child))
+}
+
+// The schema describing the node at `path`, if the schema goes that deep
+fn schema_at<'a>(root: &'a FieldSchema, path: &[PathSegment]) -> Option<&'a FieldSchema> {
+    path.iter().try_fold(root, |schema, segment| match segment {
+        PathSegment::Key(key) => schema.properties.as_ref()?.get(key).map(|b| &**b),
+        PathSegment::Index(_) => schema.items.as_deref(),
+    })
+}
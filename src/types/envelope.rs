@@ -0,0 +1,311 @@
+// runar_common/src/types/envelope.rs
+//
+// Request, response and event envelopes shared by the transport and node crates.
+// The envelope metadata is encoded with bincode and the payload is encoded
+// through the SerializerRegistry, so payloads keep their lazy semantics.
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    ArcValueType, CorrelationId, Deadline, NodeId, QosHints, SchemaRef, SchemaRegistry,
+    SerializerRegistry, TraceContext,
+};
+use crate::errors::RunarError;
+use crate::utils::paths::{ActionPath, TopicPath};
+use crate::utils::time::now_millis;
+
+/// A request to invoke an action on a service
+#[derive(Debug, Clone)]
+pub struct RequestEnvelope {
+    /// Identifier used to match the response to this request
+    pub correlation_id: CorrelationId,
+    /// The action being invoked
+    pub action_path: ActionPath,
+    /// Deadline the handler should honor (if any)
+    pub deadline: Option<Deadline>,
+    /// Trace the request is part of (if any)
+    pub trace: Option<TraceContext>,
+    /// Scheduling hints for transports (if any)
+    pub qos: Option<QosHints>,
+    /// The request parameters
+    pub payload: ArcValueType,
+}
+
+/// The response to a `RequestEnvelope`
+#[derive(Debug, Clone)]
+pub struct ResponseEnvelope {
+    /// Correlation id copied from the request
+    pub correlation_id: CorrelationId,
+    /// The response data (null when the request failed)
+    pub payload: ArcValueType,
+    /// The error, if the request failed
+    pub error: Option<RunarError>,
+}
+
+/// An event published on a topic
+#[derive(Debug, Clone)]
+pub struct EventEnvelope {
+    /// The topic the event was published on
+    pub topic: TopicPath,
+    /// The node that published the event
+    pub publisher: NodeId,
+    /// When the event was published (milliseconds since UNIX epoch)
+    pub timestamp_millis: u64,
+    /// Correlation id of the request that caused the event (if any)
+    pub correlation_id: Option<CorrelationId>,
+    /// Schema describing the payload (if any)
+    pub schema: Option<SchemaRef>,
+    /// Scheduling hints for transports (if any)
+    pub qos: Option<QosHints>,
+    /// The event data
+    pub payload: ArcValueType,
+}
+
+/// Wire representation of a request envelope
+#[derive(Serialize, Deserialize)]
+struct RequestWire {
+    correlation_id: CorrelationId,
+    action_path: ActionPath,
+    deadline: Option<Deadline>,
+    payload: Vec<u8>,
+    trace: Option<TraceContext>,
+    qos: Option<QosHints>,
+}
+
+/// Wire representation of a response envelope
+#[derive(Serialize, Deserialize)]
+struct ResponseWire {
+    correlation_id: CorrelationId,
+    payload: Vec<u8>,
+    error: Option<RunarError>,
+}
+
+/// Wire representation of an event envelope
+#[derive(Serialize, Deserialize)]
+struct EventWire {
+    topic: TopicPath,
+    publisher: NodeId,
+    timestamp_millis: u64,
+    correlation_id: Option<CorrelationId>,
+    schema: Option<SchemaRef>,
+    payload: Vec<u8>,
+    qos: Option<QosHints>,
+}
+
+impl RequestEnvelope {
+    /// Create a new request envelope without a deadline
+    pub fn new(
+        correlation_id: CorrelationId,
+        action_path: ActionPath,
+        payload: ArcValueType,
+    ) -> Self {
+        Self {
+            correlation_id,
+            action_path,
+            deadline: None,
+            trace: None,
+            qos: None,
+            payload,
+        }
+    }
+
+    /// Set the deadline the handler should honor
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Set the trace context propagated to the handler
+    pub fn with_trace(mut self, trace: TraceContext) -> Self {
+        self.trace = Some(trace);
+        self
+    }
+
+    /// Set the scheduling hints
+    pub fn with_qos(mut self, qos: QosHints) -> Self {
+        self.qos = Some(qos);
+        self
+    }
+
+    /// Check whether the request deadline (if any) has passed
+    pub fn is_expired(&self) -> bool {
+        self.deadline.is_some_and(|d| d.is_expired())
+    }
+
+    /// Build a successful response to this request
+    pub fn respond(&self, payload: ArcValueType) -> ResponseEnvelope {
+        ResponseEnvelope::ok(self.correlation_id, payload)
+    }
+
+    /// Build a failed response to this request
+    pub fn respond_err(&self, error: RunarError) -> ResponseEnvelope {
+        ResponseEnvelope::err(self.correlation_id, error)
+    }
+
+    /// Serialize the envelope, encoding the payload through the registry
+    pub fn to_bytes(&self, registry: &SerializerRegistry) -> Result<Arc<[u8]>> {
+        let wire = RequestWire {
+            correlation_id: self.correlation_id,
+            action_path: self.action_path.clone(),
+            deadline: self.deadline,
+            payload: registry.serialize_value(&self.payload)?.to_vec(),
+            trace: self.trace.clone(),
+            qos: self.qos,
+        };
+        let bytes = bincode::serialize(&wire)
+            .map_err(|e| anyhow!("Request envelope serialization error: {}", e))?;
+        Ok(Arc::from(bytes))
+    }
+
+    /// Deserialize an envelope, decoding the payload through the registry
+    pub fn from_bytes(registry: &SerializerRegistry, bytes: &[u8]) -> Result<Self> {
+        let wire: RequestWire = bincode::deserialize(bytes)
+            .map_err(|e| anyhow!("Request envelope deserialization error: {}", e))?;
+        Ok(Self {
+            correlation_id: wire.correlation_id,
+            action_path: wire.action_path,
+            deadline: wire.deadline,
+            trace: wire.trace,
+            qos: wire.qos,
+            payload: registry.deserialize_value(Arc::from(wire.payload))?,
+        })
+    }
+}
+
+impl ResponseEnvelope {
+    /// Create a successful response
+    pub fn ok(correlation_id: CorrelationId, payload: ArcValueType) -> Self {
+        Self {
+            correlation_id,
+            payload,
+            error: None,
+        }
+    }
+
+    /// Create a failed response
+    pub fn err(correlation_id: CorrelationId, error: RunarError) -> Self {
+        Self {
+            correlation_id,
+            payload: ArcValueType::null(),
+            error: Some(error),
+        }
+    }
+
+    /// Check whether the response carries an error
+    pub fn is_err(&self) -> bool {
+        self.error.is_some()
+    }
+
+    /// Convert the response into a `Result` over its payload
+    pub fn into_result(self) -> Result<ArcValueType, RunarError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.payload),
+        }
+    }
+
+    /// Serialize the envelope, encoding the payload through the registry
+    pub fn to_bytes(&self, registry: &SerializerRegistry) -> Result<Arc<[u8]>> {
+        let wire = ResponseWire {
+            correlation_id: self.correlation_id,
+            payload: registry.serialize_value(&self.payload)?.to_vec(),
+            error: self.error.clone(),
+        };
+        let bytes = bincode::serialize(&wire)
+            .map_err(|e| anyhow!("Response envelope serialization error: {}", e))?;
+        Ok(Arc::from(bytes))
+    }
+
+    /// Deserialize an envelope, decoding the payload through the registry
+    pub fn from_bytes(registry: &SerializerRegistry, bytes: &[u8]) -> Result<Self> {
+        let wire: ResponseWire = bincode::deserialize(bytes)
+            .map_err(|e| anyhow!("Response envelope deserialization error: {}", e))?;
+        Ok(Self {
+            correlation_id: wire.correlation_id,
+            payload: registry.deserialize_value(Arc::from(wire.payload))?,
+            error: wire.error,
+        })
+    }
+}
+
+impl EventEnvelope {
+    /// Create an event stamped with the current time
+    pub fn new(topic: TopicPath, publisher: NodeId, payload: ArcValueType) -> Self {
+        Self {
+            topic,
+            publisher,
+            timestamp_millis: now_millis(),
+            correlation_id: None,
+            schema: None,
+            qos: None,
+            payload,
+        }
+    }
+
+    /// Set the correlation id of the request that caused the event
+    pub fn with_correlation_id(mut self, correlation_id: CorrelationId) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
+
+    /// Set the schema describing the payload
+    pub fn with_schema(mut self, schema: SchemaRef) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Set the scheduling hints
+    pub fn with_qos(mut self, qos: QosHints) -> Self {
+        self.qos = Some(qos);
+        self
+    }
+
+    /// Check whether the event has outlived the TTL of its hints (if any)
+    pub fn is_expired(&self) -> bool {
+        self.qos
+            .is_some_and(|qos| qos.is_expired_at(self.timestamp_millis, now_millis()))
+    }
+
+    /// Validate the payload against its schema.
+    /// Events without a schema reference are accepted as-is.
+    pub fn validate(&self, schemas: &SchemaRegistry) -> Result<()> {
+        match &self.schema {
+            Some(schema) => schemas.validate(schema, &self.payload),
+            None => Ok(()),
+        }
+    }
+
+    /// Serialize the envelope, encoding the payload through the registry
+    pub fn to_bytes(&self, registry: &SerializerRegistry) -> Result<Arc<[u8]>> {
+        let wire = EventWire {
+            topic: self.topic.clone(),
+            publisher: self.publisher.clone(),
+            timestamp_millis: self.timestamp_millis,
+            correlation_id: self.correlation_id,
+            schema: self.schema.clone(),
+            payload: registry.serialize_value(&self.payload)?.to_vec(),
+            qos: self.qos,
+        };
+        let bytes = bincode::serialize(&wire)
+            .map_err(|e| anyhow!("Event envelope serialization error: {}", e))?;
+        Ok(Arc::from(bytes))
+    }
+
+    /// Deserialize an envelope, decoding the payload through the registry
+    pub fn from_bytes(registry: &SerializerRegistry, bytes: &[u8]) -> Result<Self> {
+        let wire: EventWire = bincode::deserialize(bytes)
+            .map_err(|e| anyhow!("Event envelope deserialization error: {}", e))?;
+        Ok(Self {
+            topic: wire.topic,
+            publisher: wire.publisher,
+            timestamp_millis: wire.timestamp_millis,
+            correlation_id: wire.correlation_id,
+            schema: wire.schema,
+            qos: wire.qos,
+            payload: registry.deserialize_value(Arc::from(wire.payload))?,
+        })
+    }
+}
@@ -24,12 +24,13 @@ pub trait ArcRead: fmt::Debug + Send + Sync {
 
     /// Get this value as a dynamic Any
     fn as_any(&self) -> &dyn Any;
+
+    /// Get a new reference to the Arc itself, for downcasts checked by TypeId
+    fn arc_any(&self) -> Arc<dyn Any + Send + Sync>;
 }
 
 // Custom serde implementation for ErasedArc
 // Only registered types can be (de)serialized.
-use serde::de::Error as DeError;
-use serde::ser::Error as SerError;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 impl Serialize for ErasedArc {
@@ -60,11 +61,11 @@ impl Clone for Box<dyn ArcRead> {
     }
 }
 
-/// The actual type-erased Arc implementation
 // NOTE: ErasedArc cannot be serialized or deserialized because it is type-erased and dynamic.
 // Any attempt to serialize/deserialize should panic at compile time.
 // This is documented in ArcValueType, and the field is marked with #[serde(skip_serializing, skip_deserializing)].
 
+/// The actual type-erased Arc implementation
 pub struct ErasedArc {
     /// The type-erased Arc reader
     pub reader: Box<dyn ArcRead>,
@@ -153,7 +154,7 @@ impl<T: 'static + fmt::Debug + Send + Sync> ArcRead for ArcReader<T> {
         if std::any::type_name::<T>().contains("Box<dyn") {
             // For a type that is Box<dyn Any>, we need to first get the reference to T
             // and then get the reference to the boxed value
-            let arc_ref: &T = &*self.arc;
+            let arc_ref: &T = &self.arc;
 
             // Check if the boxed value is a Box<dyn Any + Send + Sync>
             if let Some(boxed_any) =
@@ -177,6 +178,54 @@ impl<T: 'static + fmt::Debug + Send + Sync> ArcRead for ArcReader<T> {
         // For other types, return the Arc contents
         &*self.arc
     }
+
+    fn arc_any(&self) -> Arc<dyn Any + Send + Sync> {
+        self.arc.clone()
+    }
+}
+
+/// ArcRead for types without a Debug impl, such as structs decoded by
+/// `ArcValueType::materialize` (whose registrations do not require Debug)
+struct OpaqueReader<T: 'static + Send + Sync> {
+    arc: Arc<T>,
+}
+
+impl<T: 'static + Send + Sync> fmt::Debug for OpaqueReader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OpaqueReader<{}>", std::any::type_name::<T>())
+    }
+}
+
+impl<T: 'static + Send + Sync> ArcRead for OpaqueReader<T> {
+    fn ptr(&self) -> *const () {
+        Arc::as_ptr(&self.arc) as *const ()
+    }
+
+    fn strong_count(&self) -> usize {
+        Arc::strong_count(&self.arc)
+    }
+
+    fn weak_count(&self) -> usize {
+        Arc::weak_count(&self.arc)
+    }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+
+    fn clone_box(&self) -> Box<dyn ArcRead> {
+        Box::new(OpaqueReader {
+            arc: self.arc.clone(),
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        &*self.arc
+    }
+
+    fn arc_any(&self) -> Arc<dyn Any + Send + Sync> {
+        self.arc.clone()
+    }
 }
 
 impl fmt::Debug for ErasedArc {
@@ -207,6 +256,15 @@ impl ErasedArc {
         }
     }
 
+    /// Create a new ErasedArc from an Arc of a type that may not implement
+    /// Debug; the value is shown by type name only when debug printed
+    pub fn new_opaque<T: 'static + Send + Sync>(arc: Arc<T>) -> Self {
+        ErasedArc {
+            reader: Box::new(OpaqueReader { arc }),
+            is_lazy: false,
+        }
+    }
+
     /// Create a new ErasedArc from a value by wrapping it in an Arc
     pub fn from_value<T: 'static + fmt::Debug + Send + Sync>(value: T) -> Self {
         // Use TypeId for a more reliable check for the lazy data struct
@@ -310,8 +368,8 @@ impl ErasedArc {
         // Handle some common cases where type names might differ but are compatible
         match (expected_type_name, actual_type_name) {
             // String variations
-            ("alloc::string::String", "String") => return true,
-            ("String", "alloc::string::String") => return true,
+            ("alloc::string::String", "String") => true,
+            ("String", "alloc::string::String") => true,
 
             // Vec variations
             (e, a) if e.contains("Vec<") && a.contains("Vec<") => {
@@ -330,11 +388,11 @@ impl ErasedArc {
                     .split('>')
                     .next()
                     .unwrap_or("");
-                return e_elem == a_elem
+                e_elem == a_elem
                     || (e_elem.contains("String") && a_elem.contains("String"))
                     || (e_elem.contains("i32") && a_elem.contains("i32"))
                     || (e_elem.contains("i64") && a_elem.contains("i64"))
-                    || (e_elem.contains("f64") && a_elem.contains("f64"));
+                    || (e_elem.contains("f64") && a_elem.contains("f64"))
             }
 
             // HashMap variations - more robust check for both simple and complex value types
@@ -388,63 +446,101 @@ impl ErasedArc {
                     // Handle when one side has a fully qualified path and the other has a simple type name
                     || compare_type_names(&e_value, &a_value);
 
-                return keys_compatible && values_compatible;
+                keys_compatible && values_compatible
             }
 
             // Generic structs and other types
-            (e, a) => {
-                return compare_type_names(e, a);
-            }
+            (e, a) => compare_type_names(e, a),
         }
     }
 
-    /// Try to extract an Arc<T> from this ErasedArc
+    /// Try to extract an Arc<T> from this ErasedArc.
+    ///
+    /// The stored type is checked by TypeId, so types that merely share a
+    /// name (e.g. two `Status` structs in different modules) are rejected.
     pub fn as_arc<T: 'static>(&self) -> Result<Arc<T>> {
-        // Check if the type matches based on name (potentially overridden)
-        if !self.is_type::<T>() {
-            let expected_type_name = std::any::type_name::<T>();
-            let actual_type_name = self.type_name();
-            return Err(anyhow!(
+        downcast_arc(self.reader.arc_any()).ok_or_else(|| {
+            anyhow!(
                 "Type mismatch: expected {}, but has {}",
-                expected_type_name,
-                actual_type_name
-            ));
-        }
+                std::any::type_name::<T>(),
+                self.type_name()
+            )
+        })
+    }
 
-        // Attempt to downcast
-        let ptr = self.as_ptr() as *const T;
-        let arc = unsafe {
-            // Safety: Cloning an Arc with a known type as we've verified the type above
-            let arc = Arc::from_raw(ptr);
-            let clone = arc.clone();
-            // Prevent dropping the original Arc
-            std::mem::forget(arc);
-            clone
-        };
+    /// Whether this is the only reference to the contained value, so it can
+    /// be moved out without cloning
+    pub fn is_unique(&self) -> bool {
+        self.strong_count() == 1 && self.weak_count() == 0
+    }
 
-        Ok(arc)
+    /// Consume this ErasedArc and return the contained value.
+    ///
+    /// The value is moved out when this was its last holder and cloned
+    /// otherwise. Lazy values must be materialized first (e.g. through
+    /// `ArcValueType::into_type`), as the type cannot be decoded here.
+    pub fn try_unwrap<T: 'static + Clone>(self) -> Result<T> {
+        if self.is_lazy {
+            return Err(anyhow!(
+                "Cannot unwrap lazy value of type {} before it is deserialized",
+                self.type_name()
+            ));
+        }
+        let arc = self.as_arc::<T>()?;
+        drop(self);
+        Ok(Arc::try_unwrap(arc).unwrap_or_else(|shared| (*shared).clone()))
     }
 
-    /// Directly get the LazyDataWithOffset when we know this contains one
+    /// Get the LazyDataWithOffset held by a lazy value.
+    /// Equivalent to [`ErasedArc::try_get_lazy_data`].
     pub fn get_lazy_data(&self) -> Result<Arc<crate::types::value_type::LazyDataWithOffset>> {
+        self.try_get_lazy_data()
+    }
+
+    /// Get the LazyDataWithOffset held by a lazy value, verifying the stored
+    /// type by TypeId rather than trusting the `is_lazy` flag alone.
+    pub fn try_get_lazy_data(&self) -> Result<Arc<crate::types::value_type::LazyDataWithOffset>> {
         if !self.is_lazy {
             return Err(anyhow!("Value is not lazy (is_lazy flag is false)"));
         }
+        downcast_arc(self.reader.arc_any())
+            .ok_or_else(|| anyhow!("Value is flagged as lazy but holds {}", self.type_name()))
+    }
 
-        // Since we know it's lazy based on the flag, directly extract it
-        let ptr = self.reader.ptr() as *const crate::types::value_type::LazyDataWithOffset;
+    /// The type name recorded in the serialized data, if this value is still lazy
+    pub fn lazy_type_name(&self) -> Option<&str> {
+        self.lazy_data_ref().map(|lazy| lazy.type_name.as_str())
+    }
 
-        let arc = unsafe {
-            // Safety: We trust that when is_lazy is true, the pointed value is LazyDataWithOffset
-            let arc = Arc::from_raw(ptr);
-            let clone = arc.clone();
-            // Prevent dropping the original Arc
-            std::mem::forget(arc);
-            clone
-        };
+    /// The number of serialized bytes backing this value, if it is still lazy
+    pub fn lazy_len(&self) -> Option<usize> {
+        self.lazy_data_ref()
+            .map(|lazy| lazy.end_offset.saturating_sub(lazy.start_offset))
+    }
+
+    /// Whether this value holds a concrete (already deserialized) value
+    pub fn is_materialized(&self) -> bool {
+        self.lazy_data_ref().is_none()
+    }
+
+    fn lazy_data_ref(&self) -> Option<&crate::types::value_type::LazyDataWithOffset> {
+        if !self.is_lazy {
+            return None;
+        }
+        self.reader
+            .as_any()
+            .downcast_ref::<crate::types::value_type::LazyDataWithOffset>()
+    }
+}
 
-        Ok(arc)
+// `Arc::downcast` for any `T: 'static` (the std version also requires Send
+// and Sync, which `as_arc` callers do not promise)
+fn downcast_arc<T: 'static>(arc: Arc<dyn Any + Send + Sync>) -> Option<Arc<T>> {
+    if !(*arc).is::<T>() {
+        return None;
     }
+    // Safety: the TypeId check above guarantees the pointee is a T
+    Some(unsafe { Arc::from_raw(Arc::into_raw(arc) as *const T) })
 }
 
 /// Helper to compare type names accounting for namespaces
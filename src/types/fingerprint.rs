@@ -0,0 +1,415 @@
+// runar_common/src/types/fingerprint.rs
+//
+// Stable fingerprints of a type's serialized layout.
+//
+// Registries can embed the fingerprint in the wire header so a receiver whose
+// copy of a struct has different fields rejects the payload with a schema
+// drift error, rather than decoding garbage from a bincode stream that no
+// longer lines up with the struct.
+//
+// The layout is discovered through serde: a tracing deserializer feeds the
+// type's `Deserialize` impl placeholder values and records every struct,
+// field and primitive it is asked for. Types whose `Deserialize` impl needs a
+// self-describing format (untagged enums, `flatten`) or rejects placeholder
+// values cannot be traced; fingerprint those from a schema instead.
+
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+use serde::de::{
+    self, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess,
+    Visitor,
+};
+use serde::Deserialize;
+
+use super::schemas::FieldSchema;
+
+/// How deep options, sequences and maps are followed. Bounds the trace of
+/// recursive types such as `Option<Box<Self>>`.
+const MAX_TRACE_DEPTH: usize = 8;
+
+/// A textual description of the serialized layout of `T`, e.g.
+/// `struct Point{x:i32,y:i32}`.
+///
+/// Field names and types are included; for enums the variant names are
+/// listed but only the first variant's payload is traced.
+pub fn type_layout<T: for<'de> Deserialize<'de>>() -> Result<String> {
+    let mut layout = String::new();
+    T::deserialize(Tracer {
+        out: &mut layout,
+        depth: 0,
+    })
+    .map_err(|e| {
+        anyhow!(
+            "Cannot trace the layout of {}: {}",
+            std::any::type_name::<T>(),
+            e
+        )
+    })?;
+    Ok(layout)
+}
+
+/// Fingerprint of the serialized layout of `T` (see [`type_layout`])
+pub fn type_fingerprint<T: for<'de> Deserialize<'de>>() -> Result<u64> {
+    type_layout::<T>().map(|layout| fingerprint_of(&layout))
+}
+
+/// Fingerprint of the shape described by a schema.
+///
+/// Field names, data types, nullability and enumeration values are
+/// included; descriptions, examples and constraints are not.
+pub fn schema_fingerprint(schema: &FieldSchema) -> u64 {
+    let mut layout = String::new();
+    write_schema_layout(schema, &mut layout);
+    fingerprint_of(&layout)
+}
+
+fn fingerprint_of(layout: &str) -> u64 {
+    let hash = blake3::hash(layout.as_bytes());
+    let mut raw = [0u8; 8];
+    raw.copy_from_slice(&hash.as_bytes()[..8]);
+    u64::from_le_bytes(raw)
+}
+
+fn write_schema_layout(schema: &FieldSchema, out: &mut String) {
+    out.push_str(&format!("{:?}", schema.data_type));
+    if schema.nullable == Some(true) {
+        out.push('?');
+    }
+    if let Some(values) = &schema.enum_values {
+        out.push_str(&format!("[{}]", values.join("|")));
+    }
+    if let Some(properties) = &schema.properties {
+        let mut names: Vec<&String> = properties.keys().collect();
+        names.sort();
+        out.push('{');
+        for name in names {
+            out.push_str(name);
+            out.push(':');
+            write_schema_layout(&properties[name], out);
+            out.push(',');
+        }
+        out.push('}');
+    }
+    if let Some(items) = &schema.items {
+        out.push('<');
+        write_schema_layout(items, out);
+        out.push('>');
+    }
+}
+
+#[derive(Debug)]
+struct TraceError(String);
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for TraceError {}
+
+impl de::Error for TraceError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        TraceError(msg.to_string())
+    }
+}
+
+/// Deserializer that records what it is asked for and answers with placeholders
+struct Tracer<'a> {
+    out: &'a mut String,
+    depth: usize,
+}
+
+impl<'a> Tracer<'a> {
+    fn nested(&mut self) -> Tracer<'_> {
+        Tracer {
+            out: self.out,
+            depth: self.depth + 1,
+        }
+    }
+
+    fn reborrow(&mut self) -> Tracer<'_> {
+        Tracer {
+            out: self.out,
+            depth: self.depth,
+        }
+    }
+
+    fn can_nest(&self) -> bool {
+        self.depth < MAX_TRACE_DEPTH
+    }
+}
+
+macro_rules! trace_primitive {
+    ($($method:ident => $visit:ident($($placeholder:expr)?), $name:literal;)+) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TraceError> {
+                self.out.push_str($name);
+                visitor.$visit($($placeholder)?)
+            }
+        )+
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Tracer<'_> {
+    type Error = TraceError;
+
+    trace_primitive! {
+        deserialize_bool => visit_bool(false), "bool";
+        deserialize_i8 => visit_i8(0), "i8";
+        deserialize_i16 => visit_i16(0), "i16";
+        deserialize_i32 => visit_i32(0), "i32";
+        deserialize_i64 => visit_i64(0), "i64";
+        deserialize_i128 => visit_i128(0), "i128";
+        deserialize_u8 => visit_u8(0), "u8";
+        deserialize_u16 => visit_u16(0), "u16";
+        deserialize_u32 => visit_u32(0), "u32";
+        deserialize_u64 => visit_u64(0), "u64";
+        deserialize_u128 => visit_u128(0), "u128";
+        deserialize_f32 => visit_f32(0.0), "f32";
+        deserialize_f64 => visit_f64(0.0), "f64";
+        deserialize_char => visit_char('\0'), "char";
+        deserialize_str => visit_str(""), "string";
+        deserialize_string => visit_str(""), "string";
+        deserialize_identifier => visit_str(""), "string";
+        deserialize_bytes => visit_bytes(&[]), "bytes";
+        deserialize_byte_buf => visit_bytes(&[]), "bytes";
+        deserialize_unit => visit_unit(), "unit";
+        deserialize_any => visit_unit(), "any";
+        deserialize_ignored_any => visit_unit(), "ignored";
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, TraceError> {
+        if !self.can_nest() {
+            self.out.push_str("option");
+            return visitor.visit_none();
+        }
+        self.out.push_str("option<");
+        let value = visitor.visit_some(self.nested())?;
+        self.out.push('>');
+        Ok(value)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        self.out.push_str(name);
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        mut self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        self.out.push_str(name);
+        self.out.push('(');
+        let value = visitor.visit_newtype_struct(self.nested())?;
+        self.out.push(')');
+        Ok(value)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, TraceError> {
+        let len = usize::from(self.can_nest());
+        self.out.push_str("seq<");
+        let value = visitor.visit_seq(TraceSeq {
+            tracer: self.nested(),
+            fields: None,
+            index: 0,
+            len,
+        })?;
+        self.out.push('>');
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        mut self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        self.out.push('(');
+        let value = visitor.visit_seq(TraceSeq {
+            tracer: self.nested(),
+            fields: None,
+            index: 0,
+            len,
+        })?;
+        self.out.push(')');
+        Ok(value)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        self.out.push_str(name);
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, TraceError> {
+        let entries = usize::from(self.can_nest());
+        self.out.push_str("map<");
+        let value = visitor.visit_map(TraceMap {
+            tracer: self.nested(),
+            remaining: entries,
+        })?;
+        self.out.push('>');
+        Ok(value)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        mut self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        self.out.push_str("struct ");
+        self.out.push_str(name);
+        self.out.push('{');
+        let value = visitor.visit_seq(TraceSeq {
+            tracer: self.nested(),
+            fields: Some(fields),
+            index: 0,
+            len: fields.len(),
+        })?;
+        self.out.push('}');
+        Ok(value)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        mut self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        self.out.push_str("enum ");
+        self.out.push_str(name);
+        self.out.push('[');
+        self.out.push_str(&variants.join("|"));
+        self.out.push(']');
+        visitor.visit_enum(TraceEnum {
+            tracer: self.nested(),
+        })
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Sequence of traced elements; struct fields are labelled with their names
+struct TraceSeq<'a> {
+    tracer: Tracer<'a>,
+    fields: Option<&'static [&'static str]>,
+    index: usize,
+    len: usize,
+}
+
+impl<'de> SeqAccess<'de> for TraceSeq<'_> {
+    type Error = TraceError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, TraceError> {
+        if self.index >= self.len {
+            return Ok(None);
+        }
+        if self.index > 0 {
+            self.tracer.out.push(',');
+        }
+        if let Some(fields) = self.fields {
+            self.tracer.out.push_str(fields[self.index]);
+            self.tracer.out.push(':');
+        }
+        self.index += 1;
+        seed.deserialize(self.tracer.reborrow()).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.index)
+    }
+}
+
+/// Map with at most one traced entry
+struct TraceMap<'a> {
+    tracer: Tracer<'a>,
+    remaining: usize,
+}
+
+impl<'de> MapAccess<'de> for TraceMap<'_> {
+    type Error = TraceError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, TraceError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(self.tracer.reborrow()).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, TraceError> {
+        self.tracer.out.push(',');
+        seed.deserialize(self.tracer.reborrow())
+    }
+}
+
+/// Enum access that always selects the first variant
+struct TraceEnum<'a> {
+    tracer: Tracer<'a>,
+}
+
+impl<'de, 'a> EnumAccess<'de> for TraceEnum<'a> {
+    type Error = TraceError;
+    type Variant = Tracer<'a>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), TraceError> {
+        let variant = seed.deserialize(IntoDeserializer::<TraceError>::into_deserializer(0u32))?;
+        Ok((variant, self.tracer))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Tracer<'_> {
+    type Error = TraceError;
+
+    fn unit_variant(self) -> Result<(), TraceError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, TraceError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, TraceError> {
+        de::Deserializer::deserialize_struct(self, "", fields, visitor)
+    }
+}
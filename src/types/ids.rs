@@ -0,0 +1,284 @@
+// runar_common/src/types/ids.rs
+//
+// Typed identifiers for nodes, networks, services and peers.
+// Each identifier is a validated string newtype so the compiler catches
+// call sites that mix up, for example, a network id and a node id.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::ArcValueType;
+
+/// Maximum length (in bytes) accepted for any identifier
+pub const MAX_ID_LEN: usize = 256;
+
+/// Validate the common identifier rules shared by all id types
+fn validate_id(kind: &str, id: &str) -> Result<()> {
+    if id.is_empty() {
+        return Err(anyhow!("{} cannot be empty", kind));
+    }
+    if id.len() > MAX_ID_LEN {
+        return Err(anyhow!(
+            "{} is too long: {} bytes (max {})",
+            kind,
+            id.len(),
+            MAX_ID_LEN
+        ));
+    }
+    if let Some(c) = id
+        .chars()
+        .find(|c| c.is_whitespace() || c.is_control() || *c == '/')
+    {
+        return Err(anyhow!(
+            "{} contains invalid character {:?}: {}",
+            kind,
+            c,
+            id
+        ));
+    }
+    Ok(())
+}
+
+macro_rules! define_id {
+    ($(#[$meta:meta])* $name:ident, $kind:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(try_from = "String", into = "String")]
+        pub struct $name(String);
+
+        impl $name {
+            /// Create a new identifier, validating its contents
+            pub fn new(id: impl Into<String>) -> Result<Self> {
+                let id = id.into();
+                validate_id($kind, &id)?;
+                Ok(Self(id))
+            }
+
+            /// Get the identifier as a string slice
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Consume the identifier and return the inner string
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = anyhow::Error;
+
+            fn from_str(s: &str) -> Result<Self> {
+                Self::new(s)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = anyhow::Error;
+
+            fn try_from(id: String) -> Result<Self> {
+                Self::new(id)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = anyhow::Error;
+
+            fn try_from(id: &str) -> Result<Self> {
+                Self::new(id)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl From<$name> for ArcValueType {
+            fn from(id: $name) -> Self {
+                ArcValueType::new_primitive(id.0)
+            }
+        }
+
+        impl TryFrom<ArcValueType> for $name {
+            type Error = anyhow::Error;
+
+            fn try_from(mut value: ArcValueType) -> Result<Self> {
+                let id: String = value.as_type()?;
+                Self::new(id)
+            }
+        }
+    };
+}
+
+define_id!(
+    /// Identifier of a Runar node
+    NodeId,
+    "NodeId"
+);
+
+define_id!(
+    /// Identifier of a Runar network
+    NetworkId,
+    "NetworkId"
+);
+
+define_id!(
+    /// Identifier of a service within a network
+    ServiceId,
+    "ServiceId"
+);
+
+define_id!(
+    /// Identifier of a remote peer as seen by the transport layer
+    PeerId,
+    "PeerId"
+);
+
+/// Crockford base32 alphabet used for correlation ids
+const CROCKFORD_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Length of the textual form of a correlation id
+const CORRELATION_ID_LEN: usize = 26;
+
+lazy_static::lazy_static! {
+    /// Last generated id, used to keep ids strictly increasing within a millisecond
+    static ref LAST_CORRELATION_ID: std::sync::Mutex<u128> = std::sync::Mutex::new(0);
+}
+
+/// Compact, time-sortable identifier for tracing action chains across nodes.
+///
+/// Follows the ULID layout: a 48-bit millisecond timestamp followed by 80 random
+/// bits, rendered as 26 Crockford base32 characters. Ids generated by the same
+/// process are strictly increasing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CorrelationId(u128);
+
+impl CorrelationId {
+    /// Generate a new correlation id for the current time
+    pub fn generate() -> Self {
+        let millis = crate::utils::time::SystemTime::now()
+            .duration_since(crate::utils::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0)
+            & 0xFFFF_FFFF_FFFF;
+        let random = u128::from_be_bytes(*uuid::Uuid::new_v4().as_bytes()) & ((1u128 << 80) - 1);
+        let candidate = ((millis as u128) << 80) | random;
+
+        let mut last = LAST_CORRELATION_ID
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let id = if candidate > *last {
+            candidate
+        } else {
+            *last + 1
+        };
+        *last = id;
+        Self(id)
+    }
+
+    /// Create a correlation id from its raw 128-bit value
+    pub fn from_u128(value: u128) -> Self {
+        Self(value)
+    }
+
+    /// Get the raw 128-bit value
+    pub fn as_u128(&self) -> u128 {
+        self.0
+    }
+
+    /// Get the millisecond timestamp embedded in the id
+    pub fn timestamp_millis(&self) -> u64 {
+        (self.0 >> 80) as u64
+    }
+
+    /// Parse a correlation id from its Crockford base32 form (case-insensitive)
+    pub fn parse(s: &str) -> Result<Self> {
+        if s.len() != CORRELATION_ID_LEN {
+            return Err(anyhow!(
+                "CorrelationId must be {} characters, got {}",
+                CORRELATION_ID_LEN,
+                s.len()
+            ));
+        }
+        let mut value: u128 = 0;
+        for (i, c) in s.bytes().enumerate() {
+            let upper = c.to_ascii_uppercase();
+            let digit = CROCKFORD_ALPHABET
+                .iter()
+                .position(|&a| a == upper)
+                .ok_or_else(|| {
+                    anyhow!("CorrelationId contains invalid character {:?}", c as char)
+                })?;
+            // The first character only carries the top 3 bits
+            if i == 0 && digit > 7 {
+                return Err(anyhow!("CorrelationId is out of range: {}", s));
+            }
+            value = (value << 5) | digit as u128;
+        }
+        Ok(Self(value))
+    }
+}
+
+impl fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = [0u8; CORRELATION_ID_LEN];
+        for (i, slot) in buf.iter_mut().enumerate() {
+            let shift = 5 * (CORRELATION_ID_LEN - 1 - i);
+            *slot = CROCKFORD_ALPHABET[((self.0 >> shift) & 0x1F) as usize];
+        }
+        // The alphabet is ASCII so this cannot fail
+        f.write_str(std::str::from_utf8(&buf).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl FromStr for CorrelationId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for CorrelationId {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        Self::parse(&s)
+    }
+}
+
+impl From<CorrelationId> for String {
+    fn from(id: CorrelationId) -> Self {
+        id.to_string()
+    }
+}
+
+impl From<&CorrelationId> for String {
+    fn from(id: &CorrelationId) -> Self {
+        id.to_string()
+    }
+}
+
+impl From<CorrelationId> for ArcValueType {
+    fn from(id: CorrelationId) -> Self {
+        ArcValueType::new_primitive(id.to_string())
+    }
+}
@@ -0,0 +1,183 @@
+// runar_common/src/types/inspect.rs
+//
+// Offline decoding of serialized values for debugging.
+//
+// `inspect` never fails: whatever can be read from the payload (header,
+// sizes, content) is reported, and the first problem encountered is recorded
+// in the report instead of aborting.
+
+use std::fmt;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use super::codec::{self, CodecId};
+use super::ids::NodeId;
+use super::value_type::{SerializerRegistry, ValueCategory};
+use crate::logging::{Component, Logger};
+use crate::wire;
+
+/// Bytes per row of the hexdump shown for raw byte payloads
+const HEXDUMP_ROW: usize = 16;
+
+/// What could be read from a serialized value
+#[derive(Debug, Clone, PartialEq)]
+pub struct InspectReport {
+    /// Category from the header (None if the header is unreadable)
+    pub category: Option<ValueCategory>,
+    /// Codec of the payload
+    pub codec: Option<CodecId>,
+    /// Type name from the header (empty for Null)
+    pub type_name: Option<String>,
+    /// Layout fingerprint, if the writer embedded one
+    pub fingerprint: Option<u64>,
+    /// Whether the inspecting registry knows the type
+    pub registered: bool,
+    /// Total size of the serialized value
+    pub total_len: usize,
+    /// Size of the header (marker, type name and fingerprint)
+    pub header_len: usize,
+    /// Size of the payload following the header
+    pub payload_len: usize,
+    /// Pretty-printed content, when the payload could be decoded
+    pub content: Option<String>,
+    /// Why the header or content could not be decoded
+    pub error: Option<String>,
+}
+
+/// Inspect a serialized value using a registry with the default types.
+///
+/// Struct payloads can only be shown if they were written with a
+/// self-describing codec; use [`inspect_with`] and a registry that knows the
+/// type for bincode structs.
+pub fn inspect(bytes: &[u8]) -> InspectReport {
+    let logger = Logger::new_root(
+        Component::Custom("Inspect"),
+        NodeId::new("inspect").expect("static node id is valid"),
+    );
+    inspect_with(bytes, &SerializerRegistry::with_defaults(Arc::new(logger)))
+}
+
+/// Inspect a serialized value, decoding the content with `registry`
+pub fn inspect_with(bytes: &[u8], registry: &SerializerRegistry) -> InspectReport {
+    let mut report = InspectReport {
+        category: None,
+        codec: None,
+        type_name: None,
+        fingerprint: None,
+        registered: false,
+        total_len: bytes.len(),
+        header_len: 0,
+        payload_len: 0,
+        content: None,
+        error: None,
+    };
+    let header = match wire::decode_header(bytes) {
+        Ok(header) => header,
+        Err(e) => {
+            report.error = Some(e.to_string());
+            return report;
+        }
+    };
+    report.category = Some(header.category);
+    report.codec = Some(header.codec);
+    report.type_name = Some(header.type_name.to_string());
+    report.fingerprint = header.fingerprint;
+    report.header_len = header.data_offset;
+    report.payload_len = bytes.len() - header.data_offset;
+    report.registered = registry.get_deserializer_arc(header.type_name).is_some();
+
+    let payload = &bytes[header.data_offset..];
+    let content = match header.category {
+        ValueCategory::Null => Ok("null".to_string()),
+        ValueCategory::Bytes => Ok(hexdump(payload)),
+        ValueCategory::Json => Ok(match serde_json::from_slice::<Value>(payload) {
+            Ok(json) => pretty(&json),
+            Err(_) => String::from_utf8_lossy(payload).into_owned(),
+        }),
+        _ if report.registered => decode_registered(bytes, &header, registry),
+        _ if header.codec == CodecId::Bincode => Err(format!(
+            "type '{}' is not registered and bincode payloads are not self-describing",
+            header.type_name
+        )),
+        _ => codec::decode_with::<Value>(header.codec, payload)
+            .map(|json| pretty(&json))
+            .map_err(|e| e.to_string()),
+    };
+    match content {
+        Ok(content) => report.content = Some(content),
+        Err(e) => report.error = Some(e),
+    }
+    report
+}
+
+// Values with nested ArcValueTypes only render through the regular decode
+// path; structs only through their registered serializer
+fn decode_registered(
+    bytes: &[u8],
+    header: &wire::WireHeader<'_>,
+    registry: &SerializerRegistry,
+) -> Result<String, String> {
+    let value = registry
+        .deserialize_value(Arc::from(bytes))
+        .map_err(|e| e.to_string())?;
+    if let Ok(json) = value.to_json() {
+        return Ok(pretty(&json));
+    }
+    registry
+        .payload_to_json(header.type_name, &bytes[header.data_offset..], header.codec)
+        .map(|json| pretty(&json))
+        .map_err(|e| e.to_string())
+}
+
+fn pretty(json: &Value) -> String {
+    serde_json::to_string_pretty(json).unwrap_or_else(|_| json.to_string())
+}
+
+fn hexdump(bytes: &[u8]) -> String {
+    bytes
+        .chunks(HEXDUMP_ROW)
+        .enumerate()
+        .map(|(row, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|byte| format!("{:02x}", byte)).collect();
+            format!("{:08x}  {}", row * HEXDUMP_ROW, hex.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl fmt::Display for InspectReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.category, &self.codec) {
+            (Some(category), Some(codec)) => {
+                writeln!(f, "category:    {:?}", category)?;
+                writeln!(f, "codec:       {:?}", codec)?;
+            }
+            _ => writeln!(f, "category:    <unreadable>")?,
+        }
+        if let Some(type_name) = self.type_name.as_deref().filter(|name| !name.is_empty()) {
+            let known = if self.registered {
+                "registered"
+            } else {
+                "not registered"
+            };
+            writeln!(f, "type:        {} ({})", type_name, known)?;
+        }
+        if let Some(fingerprint) = self.fingerprint {
+            writeln!(f, "fingerprint: {:016x}", fingerprint)?;
+        }
+        writeln!(
+            f,
+            "size:        {} bytes (header {}, payload {})",
+            self.total_len, self.header_len, self.payload_len
+        )?;
+        if let Some(error) = &self.error {
+            writeln!(f, "error:       {}", error)?;
+        }
+        if let Some(content) = &self.content {
+            writeln!(f, "content:")?;
+            writeln!(f, "{}", content)?;
+        }
+        Ok(())
+    }
+}
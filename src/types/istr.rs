@@ -0,0 +1,218 @@
+// runar_common/src/types/istr.rs
+//
+// Interned strings for values that repeat constantly (topic segments,
+// map keys, component names).
+//
+// An `IStr` is an Arc to a string with a precomputed hash, so cloning is a
+// reference-count increment, hashing writes a single u64, and two strings
+// from the same pool compare equal by pointer. Strings from different pools
+// still compare correctly, falling back to comparing contents.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+use lazy_static::lazy_static;
+use rustc_hash::FxHasher;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+struct Inner {
+    hash: u64,
+    value: Box<str>,
+}
+
+/// An interned, immutable string
+#[derive(Clone)]
+pub struct IStr(Arc<Inner>);
+
+fn hash_str(s: &str) -> u64 {
+    let mut hasher = FxHasher::default();
+    s.hash(&mut hasher);
+    hasher.finish()
+}
+
+lazy_static! {
+    static ref GLOBAL_POOL: Interner = Interner::new();
+}
+
+impl IStr {
+    /// Intern a string in the global pool
+    pub fn new(s: &str) -> Self {
+        GLOBAL_POOL.intern(s)
+    }
+
+    /// Get the string
+    pub fn as_str(&self) -> &str {
+        &self.0.value
+    }
+
+    /// Check whether two interned strings share the same allocation
+    pub fn ptr_eq(a: &IStr, b: &IStr) -> bool {
+        Arc::ptr_eq(&a.0, &b.0)
+    }
+}
+
+/// A pool of interned strings. `IStr::new` uses a process-wide pool; nodes that
+/// want to release their strings independently can keep their own `Interner`.
+#[derive(Default)]
+pub struct Interner {
+    // Buckets keyed by the precomputed hash
+    strings: Mutex<HashMap<u64, Vec<IStr>>>,
+}
+
+impl Interner {
+    /// Create an empty pool
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the pooled copy of a string, adding it if needed
+    pub fn intern(&self, s: &str) -> IStr {
+        let hash = hash_str(s);
+        let mut strings = self.lock();
+        let bucket = strings.entry(hash).or_default();
+        if let Some(existing) = bucket.iter().find(|i| i.as_str() == s) {
+            return existing.clone();
+        }
+        let interned = IStr(Arc::new(Inner {
+            hash,
+            value: s.into(),
+        }));
+        bucket.push(interned.clone());
+        interned
+    }
+
+    /// Get the number of pooled strings
+    pub fn len(&self) -> usize {
+        self.lock().values().map(Vec::len).sum()
+    }
+
+    /// Check whether the pool is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop pooled strings that are no longer referenced outside the pool
+    pub fn purge_unused(&self) {
+        let mut strings = self.lock();
+        strings.retain(|_, bucket| {
+            bucket.retain(|i| Arc::strong_count(&i.0) > 1);
+            !bucket.is_empty()
+        });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<u64, Vec<IStr>>> {
+        self.strings
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Get the process-wide pool used by `IStr::new`
+pub fn global_interner() -> &'static Interner {
+    &GLOBAL_POOL
+}
+
+impl PartialEq for IStr {
+    fn eq(&self, other: &Self) -> bool {
+        IStr::ptr_eq(self, other) || (self.0.hash == other.0.hash && self.0.value == other.0.value)
+    }
+}
+
+impl Eq for IStr {}
+
+impl Hash for IStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.0.hash);
+    }
+}
+
+impl PartialOrd for IStr {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for IStr {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl PartialEq<str> for IStr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for IStr {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for IStr {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl Deref for IStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for IStr {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for IStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for IStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for IStr {
+    fn from(s: &str) -> Self {
+        IStr::new(s)
+    }
+}
+
+impl From<String> for IStr {
+    fn from(s: String) -> Self {
+        IStr::new(&s)
+    }
+}
+
+impl From<IStr> for String {
+    fn from(s: IStr) -> Self {
+        s.as_str().to_string()
+    }
+}
+
+impl Serialize for IStr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for IStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        Ok(IStr::new(&s))
+    }
+}
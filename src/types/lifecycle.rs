@@ -0,0 +1,238 @@
+// runar_common/src/types/lifecycle.rs
+//
+// Service lifecycle states and the transitions allowed between them.
+//
+//   Created -> Initializing -> Running <-> Paused
+//                                 |          |
+//                                 v          v
+//                              Stopping -> Stopped -> Initializing (restart)
+//
+// Any state except Stopped can fail; a failed service can be restarted or
+// marked stopped. `ServiceLifecycle` tracks the current state with the time
+// it was entered and the last start time kept in `ServiceMetadata`.
+
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{ArcValueType, ServiceMetadata};
+use crate::utils::time::{self, SystemTime};
+
+/// State of a service
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ServiceState {
+    /// Registered but not initialized yet
+    Created,
+    /// Running its init logic
+    Initializing,
+    /// Handling requests
+    Running,
+    /// Temporarily not handling requests
+    Paused,
+    /// Shutting down
+    Stopping,
+    /// Shut down cleanly
+    Stopped,
+    /// Stopped because of an error
+    Failed {
+        /// What went wrong
+        error: String,
+    },
+}
+
+impl ServiceState {
+    /// A failed state with `error`
+    pub fn failed(error: impl Into<String>) -> Self {
+        ServiceState::Failed {
+            error: error.into(),
+        }
+    }
+
+    /// Get the string representation of the state
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServiceState::Created => "created",
+            ServiceState::Initializing => "initializing",
+            ServiceState::Running => "running",
+            ServiceState::Paused => "paused",
+            ServiceState::Stopping => "stopping",
+            ServiceState::Stopped => "stopped",
+            ServiceState::Failed { .. } => "failed",
+        }
+    }
+
+    /// Check whether a service in this state may move to `next`
+    pub fn can_transition_to(&self, next: &ServiceState) -> bool {
+        use ServiceState::*;
+        match (self, next) {
+            (Stopped, Failed { .. }) => false,
+            (_, Failed { .. }) => true,
+            (Created, Initializing)
+            | (Initializing, Running)
+            | (Initializing, Stopping)
+            | (Running, Paused)
+            | (Running, Stopping)
+            | (Paused, Running)
+            | (Paused, Stopping)
+            | (Stopping, Stopped)
+            | (Stopped, Initializing)
+            | (Failed { .. }, Initializing)
+            | (Failed { .. }, Stopped) => true,
+            _ => false,
+        }
+    }
+
+    /// Check whether the service handles requests in this state
+    pub fn is_running(&self) -> bool {
+        *self == ServiceState::Running
+    }
+
+    /// The state as a Map value: `{"state": "failed", "error": "..."}`
+    /// (`error` only for failed states)
+    pub fn to_value(&self) -> ArcValueType {
+        ArcValueType::from_json(self.to_json())
+    }
+
+    /// Read a state from a value produced by `to_value`
+    pub fn from_value(value: &ArcValueType) -> Result<Self> {
+        let json = value.to_json()?;
+        Self::from_json(&json)
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            ServiceState::Failed { error } => json!({"state": self.as_str(), "error": error}),
+            _ => json!({"state": self.as_str()}),
+        }
+    }
+
+    fn from_json(json: &serde_json::Value) -> Result<Self> {
+        let name = json["state"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Service state value has no 'state' string"))?;
+        Ok(match name {
+            "created" => ServiceState::Created,
+            "initializing" => ServiceState::Initializing,
+            "running" => ServiceState::Running,
+            "paused" => ServiceState::Paused,
+            "stopping" => ServiceState::Stopping,
+            "stopped" => ServiceState::Stopped,
+            "failed" => ServiceState::failed(json["error"].as_str().unwrap_or_default()),
+            other => return Err(anyhow!("Unknown service state '{}'", other)),
+        })
+    }
+}
+
+impl fmt::Display for ServiceState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceState::Failed { error } => write!(f, "failed: {}", error),
+            _ => f.write_str(self.as_str()),
+        }
+    }
+}
+
+impl From<ServiceState> for ArcValueType {
+    fn from(state: ServiceState) -> Self {
+        state.to_value()
+    }
+}
+
+/// Current state of a service with the times of its transitions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceLifecycle {
+    state: ServiceState,
+    /// When the current state was entered (milliseconds since UNIX epoch)
+    since_millis: u64,
+    /// When the service last went from Initializing to Running
+    /// (seconds since UNIX epoch, like `ServiceMetadata::last_start_time`)
+    last_start_time: Option<u64>,
+}
+
+impl Default for ServiceLifecycle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServiceLifecycle {
+    /// A lifecycle in the Created state, entered now
+    pub fn new() -> Self {
+        Self::created_at(SystemTime::now())
+    }
+
+    /// A lifecycle in the Created state, entered at `at`
+    pub fn created_at(at: SystemTime) -> Self {
+        ServiceLifecycle {
+            state: ServiceState::Created,
+            since_millis: time::to_epoch_millis(at),
+            last_start_time: None,
+        }
+    }
+
+    /// The current state
+    pub fn state(&self) -> &ServiceState {
+        &self.state
+    }
+
+    /// When the current state was entered
+    pub fn since(&self) -> SystemTime {
+        time::from_epoch_millis(self.since_millis)
+    }
+
+    /// When the service last started (None if it never ran)
+    pub fn last_started_at(&self) -> Option<SystemTime> {
+        self.last_start_time.map(time::from_epoch_secs)
+    }
+
+    /// Move to `next` now (see `transition_at`)
+    pub fn transition(&mut self, next: ServiceState) -> Result<()> {
+        self.transition_at(next, SystemTime::now())
+    }
+
+    /// Move to `next` at `at`, failing if the transition is not allowed.
+    /// Entering Running from Initializing records the start time.
+    pub fn transition_at(&mut self, next: ServiceState, at: SystemTime) -> Result<()> {
+        if !self.state.can_transition_to(&next) {
+            return Err(anyhow!(
+                "Invalid service state transition from {} to {}",
+                self.state.as_str(),
+                next.as_str()
+            ));
+        }
+        if self.state == ServiceState::Initializing && next.is_running() {
+            self.last_start_time = Some(time::to_epoch_secs(at));
+        }
+        self.state = next;
+        self.since_millis = time::to_epoch_millis(at);
+        Ok(())
+    }
+
+    /// Copy the last start time into `metadata`
+    pub fn apply_to(&self, metadata: &mut ServiceMetadata) {
+        metadata.last_start_time = self.last_start_time;
+    }
+
+    /// The lifecycle as a Map value:
+    /// `{"state": "running", "since_millis": ..., "last_start_time": ...}`
+    pub fn to_value(&self) -> ArcValueType {
+        let mut json = self.state.to_json();
+        json["since_millis"] = self.since_millis.into();
+        json["last_start_time"] = self.last_start_time.into();
+        ArcValueType::from_json(json)
+    }
+
+    /// Read a lifecycle from a value produced by `to_value`
+    pub fn from_value(value: &ArcValueType) -> Result<Self> {
+        let json = value.to_json()?;
+        Ok(ServiceLifecycle {
+            state: ServiceState::from_json(&json)?,
+            since_millis: json["since_millis"]
+                .as_u64()
+                .ok_or_else(|| anyhow!("Service lifecycle value has no 'since_millis'"))?,
+            last_start_time: json["last_start_time"].as_u64(),
+        })
+    }
+}
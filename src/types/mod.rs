@@ -3,18 +3,89 @@
 // Type definitions for runar common
 
 // Type modules
+mod address;
+#[cfg(feature = "arrow")]
+mod arrow;
+mod capability;
+mod chunking;
+pub mod codec;
+pub mod compact;
+mod convert;
+mod deadline;
+mod decode_context;
+mod encryption;
+mod envelope;
 mod erased_arc;
+mod fingerprint;
+pub mod ids;
+mod inspect;
+mod istr;
+mod lifecycle;
+#[cfg(feature = "protobuf")]
+mod protobuf;
+mod provenance;
+mod qos;
+mod raw_json;
+mod redact;
+mod registry_set;
+mod schema_json;
+mod schema_registry;
 pub mod schemas;
+mod signed;
+mod trace;
 mod value_type;
+mod version;
+mod visit;
 mod vmap;
 
 // Export our types
+pub use self::address::PeerAddress;
+pub use self::capability::CapabilityDelta;
+pub use self::chunking::{
+    ChunkHash, ChunkInfo, ChunkManifest, ChunkedPayload, ChunkingConfig, DEFAULT_MAX_ASSEMBLED_LEN,
+};
+pub use self::codec::{Codec, CodecId};
+pub use self::convert::{FromArcValue, ToArcValue};
+pub use self::deadline::Deadline;
+pub use self::decode_context::{
+    CancellationToken, DecodeContext, DecodeQuota, DEFAULT_DECODE_CHUNK_SIZE,
+};
+pub use self::encryption::{
+    decrypt_fields, encrypt_fields, EncryptedField, FieldDecryptor, FieldEncryptor,
+    ENCRYPTED_FIELD_MAGIC,
+};
+pub use self::envelope::{EventEnvelope, RequestEnvelope, ResponseEnvelope};
 pub use self::erased_arc::ErasedArc;
+pub use self::fingerprint::{schema_fingerprint, type_fingerprint, type_layout};
+pub use self::ids::{CorrelationId, NetworkId, NodeId, PeerId, ServiceId};
+pub use self::inspect::{inspect, inspect_with, InspectReport};
+pub use self::istr::{global_interner, IStr, Interner};
+pub use self::lifecycle::{ServiceLifecycle, ServiceState};
+#[cfg(feature = "protobuf")]
+pub use self::protobuf::ProtobufBridge;
+pub use self::provenance::Provenance;
+pub use self::qos::{Priority, QosHints};
+pub use self::raw_json::RawJson;
+pub use self::redact::{redact, redact_json, REDACTED};
+pub use self::registry_set::RegistrySet;
+pub use self::schema_json::{render_json_with_schema, to_json_with_schema};
+pub use self::schema_registry::{SchemaRef, SchemaRegistry};
 pub use self::schemas::{
     ActionMetadata, EventMetadata, FieldSchema, SchemaDataType, ServiceMetadata,
 };
-pub use self::value_type::{ArcValueType, SerializerRegistry, ValueCategory};
-pub use vmap::VMap;
+pub use self::signed::{
+    MetadataSigner, MetadataVerifier, SignedServiceMetadata, METADATA_SIGNATURE_DOMAIN,
+};
+pub use self::trace::TraceContext;
+pub use self::value_type::{
+    ArcValueType, FailureLogging, MaterializationMetrics, MaterializationStats, RegistrySnapshot,
+    SerializerRegistry, SimpleNameCollision, SimpleNamePolicy, ValueCategory,
+    DEFAULT_BLOCKING_THRESHOLD,
+};
+pub use self::version::Version;
+pub use self::visit::{format_path, PathSegment, ValueVisitor, Visit};
+pub use crate::wire::{hex_snippet, WireError};
+pub use vmap::{EntryState, VMap};
 // Export the implement_from_for_valuetype macro
 #[macro_export]
 macro_rules! implement_from_for_valuetype {
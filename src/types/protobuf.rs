@@ -0,0 +1,161 @@
+// runar_common/src/types/protobuf.rs
+//
+// Protobuf bridge for struct values (requires the `protobuf` feature).
+//
+// Registered Rust structs are mapped to message descriptors from a
+// `DescriptorPool` and converted field by field through their serde
+// representation, so Rust field names must match the proto field names.
+
+use std::any::type_name;
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use prost_reflect::prost::Message;
+use prost_reflect::{
+    DescriptorPool, DeserializeOptions, DynamicMessage, MessageDescriptor, SerializeOptions,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::value_type::ArcValueType;
+
+type ToJsonFn = dyn Fn(&ArcValueType) -> Result<serde_json::Value> + Send + Sync;
+type FromJsonFn = dyn Fn(serde_json::Value) -> Result<ArcValueType> + Send + Sync;
+
+/// Conversions for one registered struct type
+struct Binding {
+    descriptor: MessageDescriptor,
+    type_name: &'static str,
+    to_json: Box<ToJsonFn>,
+    from_json: Box<FromJsonFn>,
+}
+
+/// Encodes and decodes `ArcValueType` structs as protobuf messages, for peers
+/// that only speak protobuf.
+///
+/// Messages are converted through the proto3 JSON mapping: proto field names
+/// are matched against the serde field names of the Rust struct, and encoding
+/// fails if the struct has fields the message does not.
+pub struct ProtobufBridge {
+    pool: DescriptorPool,
+    by_type: HashMap<&'static str, Binding>,
+    // Message full name -> Rust type name
+    by_message: HashMap<String, &'static str>,
+}
+
+impl ProtobufBridge {
+    /// Create a bridge over the messages in `pool`
+    pub fn new(pool: DescriptorPool) -> Self {
+        Self {
+            pool,
+            by_type: HashMap::new(),
+            by_message: HashMap::new(),
+        }
+    }
+
+    /// Create a bridge from an encoded `FileDescriptorSet` (as written by
+    /// `protoc --descriptor_set_out` or prost-build)
+    pub fn from_file_descriptor_set(bytes: &[u8]) -> Result<Self> {
+        let pool = DescriptorPool::decode(bytes)
+            .map_err(|e| anyhow!("Invalid protobuf descriptor set: {}", e))?;
+        Ok(Self::new(pool))
+    }
+
+    /// The descriptor pool used by the bridge
+    pub fn pool(&self) -> &DescriptorPool {
+        &self.pool
+    }
+
+    /// Map struct type `T` to the message `message_name` (e.g. "sensors.Reading")
+    pub fn register<T>(&mut self, message_name: &str) -> Result<()>
+    where
+        T: 'static + Serialize + DeserializeOwned + Clone + std::fmt::Debug + Send + Sync,
+    {
+        let descriptor = self
+            .pool
+            .get_message_by_name(message_name)
+            .ok_or_else(|| anyhow!("Unknown protobuf message: {}", message_name))?;
+        if let Some(existing) = self.by_message.get(message_name) {
+            if *existing != type_name::<T>() {
+                return Err(anyhow!(
+                    "Protobuf message {} is already mapped to {}",
+                    message_name,
+                    existing
+                ));
+            }
+        }
+
+        let binding = Binding {
+            descriptor,
+            type_name: type_name::<T>(),
+            to_json: Box::new(|value: &ArcValueType| {
+                let mut value = value.clone();
+                let typed = value.as_struct_ref::<T>()?;
+                serde_json::to_value(&*typed)
+                    .map_err(|e| anyhow!("Cannot convert {} to JSON: {}", type_name::<T>(), e))
+            }),
+            from_json: Box::new(|json: serde_json::Value| {
+                let typed: T = serde_json::from_value(json).map_err(|e| {
+                    anyhow!("Cannot convert message to {}: {}", type_name::<T>(), e)
+                })?;
+                Ok(ArcValueType::from_struct(typed))
+            }),
+        };
+        if let Some(previous) = self.by_type.insert(type_name::<T>(), binding) {
+            self.by_message.remove(previous.descriptor.full_name());
+        }
+        self.by_message
+            .insert(message_name.to_string(), type_name::<T>());
+        Ok(())
+    }
+
+    /// The message descriptor mapped to `T` (if registered)
+    pub fn descriptor_for<T: 'static>(&self) -> Option<MessageDescriptor> {
+        self.by_type
+            .get(type_name::<T>())
+            .map(|binding| binding.descriptor.clone())
+    }
+
+    /// Encode a struct value to protobuf wire format.
+    /// Lazily deserialized values are decoded first.
+    pub fn encode(&self, value: &ArcValueType) -> Result<Vec<u8>> {
+        let stored_type = value.stored_type_name()?;
+        let binding = self
+            .by_type
+            .get(stored_type.as_str())
+            .ok_or_else(|| anyhow!("No protobuf message registered for {}", stored_type))?;
+        let json = (binding.to_json)(value)?;
+        let options = DeserializeOptions::new().deny_unknown_fields(true);
+        let message =
+            DynamicMessage::deserialize_with_options(binding.descriptor.clone(), json, &options)
+                .map_err(|e| {
+                    anyhow!(
+                        "{} does not match protobuf message {}: {}",
+                        binding.type_name,
+                        binding.descriptor.full_name(),
+                        e
+                    )
+                })?;
+        Ok(message.encode_to_vec())
+    }
+
+    /// Decode a protobuf message named `message_name` into a struct value
+    pub fn decode(&self, message_name: &str, bytes: &[u8]) -> Result<ArcValueType> {
+        let type_name = self
+            .by_message
+            .get(message_name)
+            .ok_or_else(|| anyhow!("No type registered for protobuf message {}", message_name))?;
+        let binding = &self.by_type[type_name];
+        let message = DynamicMessage::decode(binding.descriptor.clone(), bytes)
+            .map_err(|e| anyhow!("Invalid protobuf message {}: {}", message_name, e))?;
+        // Proto field names and unquoted 64-bit integers match serde's defaults
+        let options = SerializeOptions::new()
+            .use_proto_field_name(true)
+            .stringify_64_bit_integers(false)
+            .skip_default_fields(false);
+        let json = message
+            .serialize_with_options(serde_json::value::Serializer, &options)
+            .map_err(|e| anyhow!("Cannot convert protobuf message {}: {}", message_name, e))?;
+        (binding.from_json)(json)
+    }
+}
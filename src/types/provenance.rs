@@ -0,0 +1,80 @@
+// runar_common/src/types/provenance.rs
+//
+// Where a value came from.
+//
+// `SerializerRegistry::deserialize_value` stamps every decoded value with a
+// `Provenance` (when it was received and, if known, the node and transport
+// it came from), so handlers can log or make trust decisions from the value
+// itself instead of threading the sender through as extra parameters.
+//
+// Provenance is local metadata: it is not serialized, it does not take part
+// in equality, and it is attached to the decoded root value only. Clones and
+// in-place updates keep it; building a new value starts without one.
+
+use std::sync::Arc;
+
+use super::ids::NodeId;
+use super::value_type::ArcValueType;
+use crate::utils::time::SystemTime;
+
+/// Origin of a received value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    /// Node the value was received from, if known
+    pub origin: Option<NodeId>,
+    /// When the value was received
+    pub received_at: SystemTime,
+    /// Transport the value arrived over (e.g. "quic"), if known
+    pub transport: Option<String>,
+}
+
+impl Provenance {
+    /// Provenance of a value received now, from an unknown origin
+    pub fn received() -> Self {
+        Self::received_at(SystemTime::now())
+    }
+
+    /// Provenance of a value received at `received_at`
+    pub fn received_at(received_at: SystemTime) -> Self {
+        Provenance {
+            origin: None,
+            received_at,
+            transport: None,
+        }
+    }
+
+    /// Set the node the value came from
+    pub fn with_origin(mut self, origin: NodeId) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Set the transport the value arrived over
+    pub fn with_transport(mut self, transport: impl Into<String>) -> Self {
+        self.transport = Some(transport.into());
+        self
+    }
+}
+
+impl ArcValueType {
+    /// Where the value came from, if it was received rather than built locally
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_deref()
+    }
+
+    /// Attach `provenance`, replacing any previous one
+    pub fn set_provenance(&mut self, provenance: Provenance) {
+        self.provenance = Some(Arc::new(provenance));
+    }
+
+    /// Return the value with `provenance` attached
+    pub fn with_provenance(mut self, provenance: Provenance) -> Self {
+        self.set_provenance(provenance);
+        self
+    }
+
+    /// Remove and return the value's provenance
+    pub fn take_provenance(&mut self) -> Option<Provenance> {
+        self.provenance.take().map(Arc::unwrap_or_clone)
+    }
+}
@@ -0,0 +1,106 @@
+// runar_common/src/types/qos.rs
+//
+// Quality-of-service hints attached to requests and events.
+//
+// Hints travel in the envelope header so every transport schedules and drops
+// traffic the same way: higher priorities are sent first, messages older
+// than their TTL are dropped instead of delivered late, and idempotent
+// messages may be retried or delivered more than once.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// Scheduling priority, ordered from lowest to highest
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum Priority {
+    /// Background traffic, sent when nothing else is waiting
+    Low,
+    /// Regular traffic
+    #[default]
+    Normal,
+    /// Latency-sensitive traffic
+    High,
+    /// Control traffic that must not wait behind anything else
+    Critical,
+}
+
+impl Priority {
+    /// Get the string representation of the priority
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Priority::Low => "low",
+            Priority::Normal => "normal",
+            Priority::High => "high",
+            Priority::Critical => "critical",
+        }
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Priority {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "low" => Ok(Priority::Low),
+            "normal" => Ok(Priority::Normal),
+            "high" => Ok(Priority::High),
+            "critical" => Ok(Priority::Critical),
+            _ => Err(anyhow!("Unknown priority '{}'", s)),
+        }
+    }
+}
+
+/// Scheduling hints for a request or event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct QosHints {
+    /// Scheduling priority
+    pub priority: Priority,
+    /// How long after sending the message is still worth delivering
+    pub ttl: Option<Duration>,
+    /// Whether handling the message twice has the same effect as once
+    pub idempotent: bool,
+}
+
+impl QosHints {
+    /// Normal priority, no TTL, not idempotent
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the priority
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Set the time to live
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Mark the message as safe to handle more than once
+    pub fn idempotent(mut self) -> Self {
+        self.idempotent = true;
+        self
+    }
+
+    /// Check whether a message sent at `sent_at_millis` has outlived its TTL
+    /// at `now_millis` (both milliseconds since UNIX epoch)
+    pub fn is_expired_at(&self, sent_at_millis: u64, now_millis: u64) -> bool {
+        self.ttl
+            .is_some_and(|ttl| now_millis.saturating_sub(sent_at_millis) as u128 > ttl.as_millis())
+    }
+}
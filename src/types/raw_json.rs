@@ -0,0 +1,87 @@
+// runar_common/src/types/raw_json.rs
+//
+// Unparsed JSON payloads carried by Json-category values
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+
+/// Raw JSON text that is only parsed when something asks for its contents.
+///
+/// Gateways that merely forward JSON can move the bytes through the value
+/// system untouched; the first call to [`RawJson::parse`] caches the parsed tree.
+#[derive(Clone)]
+pub struct RawJson {
+    bytes: Vec<u8>,
+    parsed: OnceLock<serde_json::Value>,
+}
+
+impl RawJson {
+    /// Wrap JSON bytes without validating them
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        Self {
+            bytes: bytes.into(),
+            parsed: OnceLock::new(),
+        }
+    }
+
+    /// Encode an already parsed JSON value (the parsed form is kept)
+    pub fn from_value(value: serde_json::Value) -> Self {
+        let bytes = serde_json::to_vec(&value).expect("serde_json::Value always serializes");
+        Self {
+            bytes,
+            parsed: OnceLock::from(value),
+        }
+    }
+
+    /// The raw JSON bytes, exactly as received
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The raw JSON text
+    pub fn as_str(&self) -> Result<&str> {
+        std::str::from_utf8(&self.bytes).map_err(|e| anyhow!("JSON is not valid UTF-8: {}", e))
+    }
+
+    /// Whether the JSON has been parsed yet
+    pub fn is_parsed(&self) -> bool {
+        self.parsed.get().is_some()
+    }
+
+    /// Parse the JSON (once) and return the cached tree
+    pub fn parse(&self) -> Result<&serde_json::Value> {
+        if let Some(value) = self.parsed.get() {
+            return Ok(value);
+        }
+        let value: serde_json::Value = serde_json::from_slice(&self.bytes)
+            .map_err(|e| anyhow!("Invalid JSON payload: {}", e))?;
+        Ok(self.parsed.get_or_init(|| value))
+    }
+
+    /// Deserialize the JSON into a concrete type
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T> {
+        match self.parsed.get() {
+            Some(value) => T::deserialize(value),
+            None => serde_json::from_slice(&self.bytes),
+        }
+        .map_err(|e| anyhow!("JSON deserialization error: {}", e))
+    }
+}
+
+impl PartialEq for RawJson {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl fmt::Debug for RawJson {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RawJson")
+            .field("len", &self.bytes.len())
+            .field("parsed", &self.is_parsed())
+            .finish()
+    }
+}
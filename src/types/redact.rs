@@ -0,0 +1,64 @@
+// runar_common/src/types/redact.rs
+//
+// Redaction of sensitive fields before payloads are logged or exported.
+//
+// Values are converted to JSON and walked alongside their schema; any field
+// whose schema is marked `sensitive` is replaced by a placeholder string.
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+use super::schemas::FieldSchema;
+use super::ArcValueType;
+
+/// Placeholder written in place of sensitive fields
+pub const REDACTED: &str = "***";
+
+/// Return a copy of `value` with every field marked `sensitive` in `schema`
+/// replaced by [`REDACTED`].
+///
+/// Object properties and array items are matched against `properties` and
+/// `items`; fields not described by the schema are kept as they are, and
+/// `Reference` types are not followed. The copy is built from the JSON form
+/// of the value, so structs come back as maps.
+pub fn redact(value: &ArcValueType, schema: &FieldSchema) -> Result<ArcValueType> {
+    let json = value
+        .to_json()
+        .map_err(|e| anyhow!("Cannot redact value of field '{}': {}", schema.name, e))?;
+    Ok(ArcValueType::from_json_value(redact_json(&json, schema)))
+}
+
+/// Return a copy of a JSON value with its sensitive fields replaced by [`REDACTED`]
+pub fn redact_json(value: &Value, schema: &FieldSchema) -> Value {
+    if schema.sensitive == Some(true) && !value.is_null() {
+        return Value::String(REDACTED.to_string());
+    }
+    match value {
+        Value::Object(object) => {
+            let Some(properties) = &schema.properties else {
+                return value.clone();
+            };
+            let redacted = object
+                .iter()
+                .map(|(key, child)| {
+                    let child = match properties.get(key) {
+                        Some(field) => redact_json(child, field),
+                        None => child.clone(),
+                    };
+                    (key.clone(), child)
+                })
+                .collect();
+            Value::Object(redacted)
+        }
+        Value::Array(items) => match &schema.items {
+            Some(item_schema) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| redact_json(item, item_schema))
+                    .collect(),
+            ),
+            None => value.clone(),
+        },
+        _ => value.clone(),
+    }
+}
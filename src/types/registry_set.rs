@@ -0,0 +1,123 @@
+// runar_common/src/types/registry_set.rs
+//
+// Serializer registries keyed by network, for tenant isolation.
+//
+// Every network gets its own registry forked from a shared base that holds
+// the default primitive and container types. Payload types registered for one
+// network are unknown to the others, so their values fail to deserialize
+// there with `WireError::UnknownType`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::ids::NetworkId;
+use super::value_type::{ArcValueType, SerializerRegistry};
+use crate::logging::Logger;
+
+/// A set of `SerializerRegistry`s, one per network, sharing a base registry
+pub struct RegistrySet {
+    /// Types and settings every network starts from
+    shared: SerializerRegistry,
+    networks: HashMap<NetworkId, SerializerRegistry>,
+}
+
+impl RegistrySet {
+    /// Create a set whose networks start with the default types
+    pub fn new(logger: Arc<Logger>) -> Self {
+        Self::with_shared(SerializerRegistry::with_defaults(logger))
+    }
+
+    /// Create a set whose networks start from `shared`
+    pub fn with_shared(shared: SerializerRegistry) -> Self {
+        Self {
+            shared,
+            networks: HashMap::new(),
+        }
+    }
+
+    /// The registry shared by networks without registrations of their own
+    pub fn shared(&self) -> &SerializerRegistry {
+        &self.shared
+    }
+
+    /// Register a type for every network, current and future
+    pub fn register_shared<T>(&mut self) -> Result<()>
+    where
+        T: 'static + Serialize + for<'de> Deserialize<'de> + Clone + Send + Sync,
+    {
+        self.shared.register::<T>()?;
+        for registry in self.networks.values_mut() {
+            registry.register::<T>()?;
+        }
+        Ok(())
+    }
+
+    /// The registry of `network`, created from the shared registry on first use
+    pub fn network_mut(&mut self, network: &NetworkId) -> &mut SerializerRegistry {
+        self.networks.entry(network.clone()).or_insert_with(|| {
+            let mut registry = self.shared.fork();
+            if self.shared.is_sealed() {
+                registry.seal();
+            }
+            registry
+        })
+    }
+
+    /// The registry used for `network`; networks without registrations of
+    /// their own use the shared registry
+    pub fn get(&self, network: &NetworkId) -> &SerializerRegistry {
+        self.networks.get(network).unwrap_or(&self.shared)
+    }
+
+    /// Networks with registrations of their own
+    pub fn networks(&self) -> impl Iterator<Item = &NetworkId> {
+        self.networks.keys()
+    }
+
+    /// Remove the registry of a network (e.g. when leaving it)
+    pub fn remove(&mut self, network: &NetworkId) -> Option<SerializerRegistry> {
+        self.networks.remove(network)
+    }
+
+    /// Seal the shared registry and every network registry, including ones
+    /// created afterwards
+    pub fn seal(&mut self) {
+        self.shared.seal();
+        for registry in self.networks.values_mut() {
+            registry.seal();
+        }
+    }
+
+    /// Serialize a value with the registry of `network`
+    pub fn serialize_value(&self, network: &NetworkId, value: &ArcValueType) -> Result<Arc<[u8]>> {
+        self.get(network)
+            .serialize_value(value)
+            .map_err(|e| annotate(e, network))
+    }
+
+    /// Deserialize a value with the registry of `network`
+    pub fn deserialize_value(&self, network: &NetworkId, bytes: Arc<[u8]>) -> Result<ArcValueType> {
+        self.get(network)
+            .deserialize_value(bytes)
+            .map_err(|e| annotate(e, network))
+    }
+}
+
+// Keep the original error (e.g. a WireError) downcastable
+fn annotate(error: anyhow::Error, network: &NetworkId) -> anyhow::Error {
+    error.context(format!("in network {}", network))
+}
+
+impl fmt::Debug for RegistrySet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut networks: Vec<_> = self.networks.keys().map(|id| id.as_str()).collect();
+        networks.sort_unstable();
+        f.debug_struct("RegistrySet")
+            .field("networks", &networks)
+            .finish()
+    }
+}
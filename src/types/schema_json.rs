@@ -0,0 +1,98 @@
+// runar_common/src/types/schema_json.rs
+//
+// Schema-aware JSON rendering.
+//
+// `ArcValueType::to_json` renders whatever the handler stored: a timestamp
+// may be epoch milliseconds or a string, an enum may be its index. Rendering
+// through the schema normalizes these so gateway responses look the same no
+// matter how the value was built.
+
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::Value;
+
+use super::schemas::{FieldSchema, SchemaDataType};
+use super::ArcValueType;
+
+/// Convert a value to JSON, using `schema` to pick representations:
+///
+/// - `Binary` fields are base64 strings (byte arrays are encoded)
+/// - `Timestamp` fields are RFC 3339 strings in UTC with millisecond
+///   precision (integers are read as milliseconds since the UNIX epoch)
+/// - fields with `enum_values` are rendered by name (integers are read as
+///   indexes into `enum_values`)
+///
+/// Object properties and array items follow `properties` and `items`; values
+/// the schema does not describe are rendered as by `ArcValueType::to_json`.
+pub fn to_json_with_schema(value: &ArcValueType, schema: &FieldSchema) -> Result<Value> {
+    let json = value
+        .to_json()
+        .map_err(|e| anyhow!("Cannot render field '{}' as JSON: {}", schema.name, e))?;
+    Ok(render_json_with_schema(&json, schema))
+}
+
+/// Normalize an existing JSON value with `schema` (see [`to_json_with_schema`])
+pub fn render_json_with_schema(value: &Value, schema: &FieldSchema) -> Value {
+    if let Some(name) = enum_name(value, schema) {
+        return Value::String(name);
+    }
+    match (&schema.data_type, value) {
+        (SchemaDataType::Binary, Value::Array(items)) => {
+            let bytes: Option<Vec<u8>> = items
+                .iter()
+                .map(|item| item.as_u64().and_then(|b| u8::try_from(b).ok()))
+                .collect();
+            match bytes {
+                Some(bytes) => {
+                    Value::String(base64::engine::general_purpose::STANDARD.encode(bytes))
+                }
+                None => value.clone(),
+            }
+        }
+        (SchemaDataType::Timestamp, Value::Number(number)) => number
+            .as_i64()
+            .and_then(DateTime::<Utc>::from_timestamp_millis)
+            .map(|time| Value::String(format_timestamp(time)))
+            .unwrap_or_else(|| value.clone()),
+        (SchemaDataType::Timestamp, Value::String(text)) => DateTime::parse_from_rfc3339(text)
+            .map(|time| Value::String(format_timestamp(time.with_timezone(&Utc))))
+            .unwrap_or_else(|_| value.clone()),
+        (_, Value::Object(object)) => match &schema.properties {
+            Some(properties) => Value::Object(
+                object
+                    .iter()
+                    .map(|(key, child)| {
+                        let child = match properties.get(key) {
+                            Some(field) => render_json_with_schema(child, field),
+                            None => child.clone(),
+                        };
+                        (key.clone(), child)
+                    })
+                    .collect(),
+            ),
+            None => value.clone(),
+        },
+        (_, Value::Array(items)) => match &schema.items {
+            Some(item_schema) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| render_json_with_schema(item, item_schema))
+                    .collect(),
+            ),
+            None => value.clone(),
+        },
+        _ => value.clone(),
+    }
+}
+
+// The enum name for an index into `enum_values`
+fn enum_name(value: &Value, schema: &FieldSchema) -> Option<String> {
+    let names = schema.enum_values.as_ref()?;
+    let index = usize::try_from(value.as_u64()?).ok()?;
+    names.get(index).cloned()
+}
+
+fn format_timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
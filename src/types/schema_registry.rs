@@ -0,0 +1,303 @@
+// runar_common/src/types/schema_registry.rs
+//
+// Registry of named, versioned payload schemas.
+//
+// Schemas are `FieldSchema` trees. Values are validated by converting them
+// to JSON (see `ArcValueType::to_json`) and walking the schema; validation
+// failures are reported as `RunarError` with the `InvalidInput` code and the
+// path of the offending field.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::RwLock;
+
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::schemas::{FieldSchema, SchemaDataType};
+use super::ArcValueType;
+use crate::errors::RunarError;
+use crate::utils::num::Number;
+
+/// Reference to a specific version of a named schema
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SchemaRef {
+    pub name: String,
+    pub version: u32,
+}
+
+impl SchemaRef {
+    /// Create a schema reference
+    pub fn new(name: impl Into<String>, version: u32) -> Self {
+        Self {
+            name: name.into(),
+            version,
+        }
+    }
+}
+
+impl fmt::Display for SchemaRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@v{}", self.name, self.version)
+    }
+}
+
+/// Registry of payload schemas, keyed by name and version
+#[derive(Debug, Clone, Default)]
+pub struct SchemaRegistry {
+    schemas: HashMap<String, BTreeMap<u32, FieldSchema>>,
+}
+
+impl SchemaRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a schema version. Re-registering an identical schema is a no-op;
+    /// registering a different schema under an existing version is an error.
+    pub fn register(&mut self, name: &str, version: u32, schema: FieldSchema) -> Result<SchemaRef> {
+        let versions = self.schemas.entry(name.to_string()).or_default();
+        match versions.get(&version) {
+            Some(existing) if *existing != schema => Err(anyhow!(
+                "Schema {}@v{} is already registered with a different definition",
+                name,
+                version
+            )),
+            _ => {
+                versions.insert(version, schema);
+                Ok(SchemaRef::new(name, version))
+            }
+        }
+    }
+
+    /// Get a specific schema version
+    pub fn get(&self, schema: &SchemaRef) -> Option<&FieldSchema> {
+        self.schemas.get(&schema.name)?.get(&schema.version)
+    }
+
+    /// Get the latest version of a schema
+    pub fn latest(&self, name: &str) -> Option<(SchemaRef, &FieldSchema)> {
+        let (version, schema) = self.schemas.get(name)?.iter().next_back()?;
+        Some((SchemaRef::new(name, *version), schema))
+    }
+
+    /// Validate a value against a registered schema
+    pub fn validate(&self, schema: &SchemaRef, value: &ArcValueType) -> Result<()> {
+        let json = value
+            .to_json()
+            .map_err(|e| anyhow!("Cannot validate payload against {}: {}", schema, e))?;
+        self.validate_json(schema, &json)
+    }
+
+    /// Validate a JSON value against a registered schema
+    pub fn validate_json(&self, schema: &SchemaRef, value: &Value) -> Result<()> {
+        let field = self
+            .get(schema)
+            .ok_or_else(|| anyhow!("Schema {} is not registered", schema))?;
+        self.validate_field(field, value, "$")
+    }
+
+    /// Validate a JSON value against a schema tree; `Reference` types resolve
+    /// to the latest registered version of the referenced schema
+    pub fn validate_field(&self, schema: &FieldSchema, value: &Value, path: &str) -> Result<()> {
+        if value.is_null() {
+            return if schema.nullable == Some(true) || schema.data_type == SchemaDataType::Any {
+                Ok(())
+            } else {
+                Err(invalid(path, "must not be null"))
+            };
+        }
+
+        self.check_type(schema, &schema.data_type, value, path)?;
+        check_constraints(schema, value, path)
+    }
+
+    fn check_type(
+        &self,
+        schema: &FieldSchema,
+        data_type: &SchemaDataType,
+        value: &Value,
+        path: &str,
+    ) -> Result<()> {
+        match data_type {
+            SchemaDataType::Any => Ok(()),
+            SchemaDataType::String => expect(value.is_string(), path, "must be a string"),
+            SchemaDataType::Timestamp => {
+                let valid = value
+                    .as_str()
+                    .is_some_and(|s| chrono::DateTime::parse_from_rfc3339(s).is_ok());
+                expect(valid, path, "must be an RFC 3339 timestamp")
+            }
+            SchemaDataType::Binary => {
+                let valid = value
+                    .as_str()
+                    .is_some_and(|s| base64::engine::general_purpose::STANDARD.decode(s).is_ok());
+                expect(valid, path, "must be a base64 string")
+            }
+            SchemaDataType::Int32 => {
+                let valid = value.as_i64().is_some_and(|i| i32::try_from(i).is_ok());
+                expect(valid, path, "must be a 32-bit integer")
+            }
+            SchemaDataType::Int64 => expect(value.is_i64(), path, "must be a 64-bit integer"),
+            SchemaDataType::Float | SchemaDataType::Double => {
+                expect(value.is_number(), path, "must be a number")
+            }
+            SchemaDataType::Boolean => expect(value.is_boolean(), path, "must be a boolean"),
+            SchemaDataType::Object => {
+                let object = value
+                    .as_object()
+                    .ok_or_else(|| invalid(path, "must be an object"))?;
+                for required in schema.required.iter().flatten() {
+                    if !object.contains_key(required) {
+                        return Err(invalid(
+                            path,
+                            &format!("is missing required field '{}'", required),
+                        ));
+                    }
+                }
+                if let Some(properties) = &schema.properties {
+                    for (key, field) in properties {
+                        if let Some(child) = object.get(key) {
+                            self.validate_field(field, child, &format!("{}.{}", path, key))?;
+                        }
+                    }
+                }
+                Ok(())
+            }
+            SchemaDataType::Array => {
+                let items = value
+                    .as_array()
+                    .ok_or_else(|| invalid(path, "must be an array"))?;
+                if let Some(item_schema) = &schema.items {
+                    for (i, item) in items.iter().enumerate() {
+                        self.validate_field(item_schema, item, &format!("{}[{}]", path, i))?;
+                    }
+                }
+                Ok(())
+            }
+            SchemaDataType::Reference(name) => {
+                let (_, referenced) = self
+                    .latest(name)
+                    .ok_or_else(|| anyhow!("Referenced schema '{}' is not registered", name))?;
+                self.validate_field(referenced, value, path)
+            }
+            SchemaDataType::Union(variants) => {
+                let matches = variants
+                    .iter()
+                    .any(|variant| self.check_type(schema, variant, value, path).is_ok());
+                expect(matches, path, "does not match any of the union types")
+            }
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    // Compiled `pattern` constraints, shared by every schema using them
+    static ref PATTERNS: RwLock<HashMap<String, Regex>> = RwLock::new(HashMap::new());
+}
+
+// Compile `pattern` once and reuse it for later validations
+fn compiled_pattern(pattern: &str) -> Result<Regex, regex::Error> {
+    if let Some(regex) = PATTERNS
+        .read()
+        .unwrap_or_else(|p| p.into_inner())
+        .get(pattern)
+    {
+        return Ok(regex.clone());
+    }
+    let regex = Regex::new(pattern)?;
+    PATTERNS
+        .write()
+        .unwrap_or_else(|p| p.into_inner())
+        .insert(pattern.to_string(), regex.clone());
+    Ok(regex)
+}
+
+/// Check enum, numeric, length and pattern constraints
+fn check_constraints(schema: &FieldSchema, value: &Value, path: &str) -> Result<()> {
+    if let Some(allowed) = &schema.enum_values {
+        let rendered = match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        if !allowed.contains(&rendered) {
+            return Err(invalid(path, &format!("must be one of {:?}", allowed)));
+        }
+    }
+
+    // Integers are compared exactly, not through a lossy cast to f64
+    if let Some(n) = value.as_number().map(Number::from_json) {
+        let exclusive_minimum = schema.exclusive_minimum == Some(true);
+        let exclusive_maximum = schema.exclusive_maximum == Some(true);
+        if let Some(min) = schema.minimum {
+            let ok = n.in_range(Some(min), exclusive_minimum, None, false);
+            expect(ok, path, &format!("must be above {}", min))?;
+        }
+        if let Some(max) = schema.maximum {
+            let ok = n.in_range(None, false, Some(max), exclusive_maximum);
+            expect(ok, path, &format!("must be below {}", max))?;
+        }
+    }
+
+    if let Some(s) = value.as_str() {
+        let len = s.chars().count();
+        if let Some(min) = schema.min_length {
+            expect(
+                len >= min,
+                path,
+                &format!("must be at least {} characters", min),
+            )?;
+        }
+        if let Some(max) = schema.max_length {
+            expect(
+                len <= max,
+                path,
+                &format!("must be at most {} characters", max),
+            )?;
+        }
+        if let Some(pattern) = &schema.pattern {
+            let regex = compiled_pattern(pattern).map_err(|e| {
+                anyhow!("Invalid pattern '{}' in schema at {}: {}", pattern, path, e)
+            })?;
+            expect(
+                regex.is_match(s),
+                path,
+                &format!("must match pattern '{}'", pattern),
+            )?;
+        }
+    }
+
+    if let Some(items) = value.as_array() {
+        if let Some(min) = schema.min_items {
+            expect(
+                items.len() >= min,
+                path,
+                &format!("must have at least {} items", min),
+            )?;
+        }
+        if let Some(max) = schema.max_items {
+            expect(
+                items.len() <= max,
+                path,
+                &format!("must have at most {} items", max),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn expect(condition: bool, path: &str, message: &str) -> Result<()> {
+    if condition {
+        Ok(())
+    } else {
+        Err(invalid(path, message))
+    }
+}
+
+fn invalid(path: &str, message: &str) -> anyhow::Error {
+    RunarError::invalid_input(format!("{} {}", path, message)).into()
+}
@@ -2,8 +2,69 @@
 //
 // Schema definitions for the Runar system
 
+use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::time::Duration;
+
+use super::{ArcValueType, ToArcValue, Version};
+use crate::utils::glob::Glob;
+use crate::utils::num::{Number, Numeric};
+use crate::utils::paths::{ActionPath, EventPath, ServicePath};
+use crate::utils::time::SystemTime;
+use crate::utils::{format, size, time};
+
+// Dotted-quad IPv4
+const IPV4: &str =
+    r"(?:(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\.){3}(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)";
+
+lazy_static::lazy_static! {
+    static ref IPV6: String = ipv6_pattern();
+    /// Pattern of `FieldSchema::ip_addr` fields (`IpAddr` values)
+    pub static ref IP_ADDR_PATTERN: String = format!("^(?:{}|{})$", IPV4, *IPV6);
+    /// Pattern of `FieldSchema::socket_addr` fields (`SocketAddr` values):
+    /// `1.2.3.4:80` or `[::1]:80`
+    pub static ref SOCKET_ADDR_PATTERN: String =
+        format!(r"^(?:{}|\[{}(?:%[0-9A-Za-z]+)?\]):\d{{1,5}}$", IPV4, *IPV6);
+}
+
+// Colon-separated IPv6: eight groups, or six followed by an IPv4 address,
+// either written in full or with one run of zero groups shortened to "::"
+fn ipv6_pattern() -> String {
+    const GROUP: &str = "[0-9A-Fa-f]{1,4}";
+    // `count` groups separated by colons
+    let groups = |count: usize| match count {
+        0 => String::new(),
+        _ => format!("{}(?::{}){{{}}}", GROUP, GROUP, count - 1),
+    };
+    let mut forms = vec![
+        format!("(?:{}:){{7}}{}", GROUP, GROUP),
+        format!("(?:{}:){{6}}{}", GROUP, IPV4),
+    ];
+    // "::" stands for at least one group, so at most 7 (or 5 before an
+    // IPv4 address) are written around it
+    for left in 0..=7 {
+        let right = match left {
+            7 => String::new(),
+            _ => format!("(?:{}(?::{}){{0,{}}})?", GROUP, GROUP, 6 - left),
+        };
+        forms.push(format!("{}::{}", groups(left), right));
+    }
+    for left in 0..=5 {
+        forms.push(format!(
+            "{}::(?:{}:){{0,{}}}{}",
+            groups(left),
+            GROUP,
+            5 - left,
+            IPV4
+        ));
+    }
+    format!("(?:{})", forms.join("|"))
+}
+
+/// Pattern of `FieldSchema::path` fields (`PathBuf` values): non-empty, no NUL
+pub const PATH_PATTERN: &str = r"^[^\x00]+$";
 
 /// Represents metadata for a service action
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -18,6 +79,14 @@ pub struct ActionMetadata {
     pub output_schema: Option<FieldSchema>,
 }
 
+impl ActionMetadata {
+    /// Path of this action on `service`; the action name may be a template
+    /// such as "files/{file_id}/read"
+    pub fn action_path(&self, service: &ServicePath) -> anyhow::Result<ActionPath> {
+        service.action(&self.name)
+    }
+}
+
 /// Represents metadata for a service event
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EventMetadata {
@@ -29,6 +98,28 @@ pub struct EventMetadata {
     pub data_schema: Option<FieldSchema>,
 }
 
+impl EventMetadata {
+    /// Create metadata for an event, storing its validated path
+    pub fn new(path: &EventPath, description: impl Into<String>) -> Self {
+        EventMetadata {
+            path: path.to_string(),
+            description: description.into(),
+            data_schema: None,
+        }
+    }
+
+    /// Set the schema of the event data
+    pub fn with_data_schema(mut self, schema: FieldSchema) -> Self {
+        self.data_schema = Some(schema);
+        self
+    }
+
+    /// Parse the stored path
+    pub fn event_path(&self) -> anyhow::Result<EventPath> {
+        EventPath::new(&self.path)
+    }
+}
+
 /// Represents metadata for a service.
 /// This is a unified struct that replaces ServiceCapability.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -39,8 +130,10 @@ pub struct ServiceMetadata {
     pub service_path: String,
     /// The name of the service
     pub name: String,
-    /// The version of the service
-    pub version: String,
+    /// The version of the service; metadata from older nodes may carry
+    /// non-semver versions, which are read leniently
+    #[serde(deserialize_with = "Version::deserialize_lenient")]
+    pub version: Version,
     /// The description of the service
     pub description: String,
     /// The actions provided by this service
@@ -54,6 +147,50 @@ pub struct ServiceMetadata {
     pub last_start_time: Option<u64>,
 }
 
+impl ServiceMetadata {
+    /// Get the registration time as a `SystemTime`
+    pub fn registered_at(&self) -> SystemTime {
+        time::from_epoch_secs(self.registration_time)
+    }
+
+    /// Set the registration time from a `SystemTime`
+    pub fn set_registered_at(&mut self, at: SystemTime) {
+        self.registration_time = time::to_epoch_secs(at);
+    }
+
+    /// Get the last start time as a `SystemTime` (None if never started)
+    pub fn last_started_at(&self) -> Option<SystemTime> {
+        self.last_start_time.map(time::from_epoch_secs)
+    }
+
+    /// Record that the service was started now
+    pub fn mark_started(&mut self) {
+        self.last_start_time = Some(time::now_secs());
+    }
+
+    /// Get how long the service has been running since its last start
+    pub fn uptime(&self) -> Option<Duration> {
+        self.last_started_at()
+            .map(|at| SystemTime::now().duration_since(at).unwrap_or_default())
+    }
+
+    /// Actions whose name matches `pattern`, e.g. `get_*`
+    pub fn actions_matching(&self, pattern: &Glob) -> Vec<&ActionMetadata> {
+        self.actions
+            .iter()
+            .filter(|action| pattern.matches(&action.name))
+            .collect()
+    }
+
+    /// Events whose path matches `pattern`, e.g. `math/*_changed`
+    pub fn events_matching(&self, pattern: &Glob) -> Vec<&EventMetadata> {
+        self.events
+            .iter()
+            .filter(|event| pattern.matches(&event.path))
+            .collect()
+    }
+}
+
 /// Represents a field in a schema
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FieldSchema {
@@ -90,6 +227,12 @@ pub struct FieldSchema {
     pub max_items: Option<usize>,
     /// Example value as a string
     pub example: Option<String>,
+    /// Whether the field holds secrets or personal data that must be
+    /// redacted before logging (see `types::redact`)
+    pub sensitive: Option<bool>,
+    /// Whether the field's value is encrypted in payloads (see
+    /// `types::encryption`)
+    pub encrypted: Option<bool>,
 }
 
 /// Represents the data type of a schema field
@@ -146,6 +289,8 @@ impl FieldSchema {
             min_items: None,
             max_items: None,
             example: None,
+            sensitive: None,
+            encrypted: None,
         }
     }
 
@@ -177,6 +322,27 @@ impl FieldSchema {
         FieldSchema::new(name, SchemaDataType::Timestamp)
     }
 
+    /// A String field holding an `IpAddr`
+    pub fn ip_addr(name: &str) -> Self {
+        let mut schema = FieldSchema::string(name);
+        schema.pattern = Some(IP_ADDR_PATTERN.clone());
+        schema
+    }
+
+    /// A String field holding a `SocketAddr`
+    pub fn socket_addr(name: &str) -> Self {
+        let mut schema = FieldSchema::string(name);
+        schema.pattern = Some(SOCKET_ADDR_PATTERN.clone());
+        schema
+    }
+
+    /// A String field holding a `PathBuf`
+    pub fn path(name: &str) -> Self {
+        let mut schema = FieldSchema::string(name);
+        schema.pattern = Some(PATH_PATTERN.to_string());
+        schema
+    }
+
     pub fn object(
         name: &str,
         properties: HashMap<String, Box<FieldSchema>>,
@@ -199,4 +365,135 @@ impl FieldSchema {
             ..FieldSchema::new(name, SchemaDataType::Array)
         }
     }
+
+    /// Set the default value, written with the locale-independent formats in
+    /// `utils::format`. Integer millis for a `Timestamp` field are written as
+    /// an RFC 3339 string.
+    pub fn with_default(mut self, value: impl ToArcValue) -> anyhow::Result<Self> {
+        self.default_value = Some(self.format_for_schema(&value.to_arc_value())?);
+        Ok(self)
+    }
+
+    /// Set the example value, formatted like `with_default`
+    pub fn with_example(mut self, value: impl ToArcValue) -> anyhow::Result<Self> {
+        self.example = Some(self.format_for_schema(&value.to_arc_value())?);
+        Ok(self)
+    }
+
+    fn format_for_schema(&self, value: &ArcValueType) -> anyhow::Result<String> {
+        if self.data_type == SchemaDataType::Timestamp {
+            if let Some(number) = Number::from_value(value) {
+                return format::format_timestamp_millis(number.to_checked()?);
+            }
+        }
+        format::format_value(value)
+    }
+
+    /// Parse the default value into a value of the field's data type.
+    /// Objects, arrays and untyped fields take a JSON default.
+    pub fn default_as_value(&self) -> anyhow::Result<Option<ArcValueType>> {
+        let Some(default) = self.default_value.as_deref() else {
+            return Ok(None);
+        };
+        let invalid = |e: &dyn std::fmt::Display| {
+            anyhow!(
+                "Invalid default '{}' for field '{}' ({:?}): {}",
+                default,
+                self.name,
+                self.data_type,
+                e
+            )
+        };
+        let value = match &self.data_type {
+            SchemaDataType::String | SchemaDataType::Timestamp | SchemaDataType::Binary => {
+                ArcValueType::new_primitive(default.to_string())
+            }
+            SchemaDataType::Int32 => {
+                ArcValueType::new_primitive(default.parse::<i32>().map_err(|e| invalid(&e))?)
+            }
+            SchemaDataType::Int64 => {
+                ArcValueType::new_primitive(default.parse::<i64>().map_err(|e| invalid(&e))?)
+            }
+            SchemaDataType::Float => {
+                ArcValueType::new_primitive(default.parse::<f32>().map_err(|e| invalid(&e))?)
+            }
+            SchemaDataType::Double => {
+                ArcValueType::new_primitive(default.parse::<f64>().map_err(|e| invalid(&e))?)
+            }
+            SchemaDataType::Boolean => {
+                ArcValueType::new_primitive(default.parse::<bool>().map_err(|e| invalid(&e))?)
+            }
+            SchemaDataType::Object
+            | SchemaDataType::Array
+            | SchemaDataType::Reference(_)
+            | SchemaDataType::Union(_)
+            | SchemaDataType::Any => ArcValueType::from_json_value(
+                serde_json::from_str(default).map_err(|e| invalid(&e))?,
+            ),
+        };
+        Ok(Some(value))
+    }
+
+    /// Parse the default value as a duration ("30s", "5m")
+    pub fn default_duration(&self) -> anyhow::Result<Option<Duration>> {
+        self.default_value
+            .as_deref()
+            .map(time::parse_duration)
+            .transpose()
+    }
+
+    /// Parse the default value as a byte size ("64MiB", "1024")
+    pub fn default_size(&self) -> anyhow::Result<Option<u64>> {
+        self.default_value
+            .as_deref()
+            .map(size::parse_size)
+            .transpose()
+    }
+
+    /// Clamp a number into the field's `minimum`/`maximum` range. Exclusive
+    /// bounds clamp to the nearest value of `T` strictly inside them (one
+    /// step inward for integers, the adjacent float otherwise); NaN is
+    /// returned unchanged. For integer types fractional bounds round inward
+    /// (a minimum of 1.5 clamps to 2).
+    pub fn clamp<T: Numeric>(&self, value: T) -> T {
+        let number = value.to_number();
+        let exclusive_min = self.exclusive_minimum.unwrap_or(false);
+        let exclusive_max = self.exclusive_maximum.unwrap_or(false);
+        let outside =
+            |bound: f64, exclusive: bool, past: Ordering| match number.partial_cmp_f64(bound) {
+                Some(Ordering::Equal) => exclusive,
+                order => order == Some(past),
+            };
+        match (self.minimum, self.maximum) {
+            (Some(min), _) if outside(min, exclusive_min, Ordering::Less) => {
+                nearest_inside(min, exclusive_min, true)
+            }
+            (_, Some(max)) if outside(max, exclusive_max, Ordering::Greater) => {
+                nearest_inside(max, exclusive_max, false)
+            }
+            _ => value,
+        }
+    }
+}
+
+// The value of `T` closest to `bound` on its allowed side: above it for a
+// minimum, below it for a maximum. Narrowing rounds to the nearest `T`, so at
+// most one step inward is needed to get past the bound.
+fn nearest_inside<T: Numeric>(bound: f64, exclusive: bool, above: bool) -> T {
+    let rounded = match (T::INTEGER, above) {
+        (true, true) => bound.ceil(),
+        (true, false) => bound.floor(),
+        (false, _) => bound,
+    };
+    let candidate: T = Number::Float(rounded).to_saturating();
+    let inside = match candidate.to_number().partial_cmp_f64(bound) {
+        Some(Ordering::Equal) => !exclusive,
+        Some(order) => (order == Ordering::Greater) == above,
+        None => true,
+    };
+    if inside {
+        candidate
+    } else {
+        candidate.step(above)
+    }
 }
@@ -0,0 +1,113 @@
+// runar_common/src/types/signed.rs
+//
+// Signed capability advertisements.
+//
+// The signed payload is the canonical encoding of the metadata: the compact
+// encoding with schemas (see `types::compact`), which writes fields in a
+// fixed order and schema properties sorted by name, so equal metadata always
+// gives equal bytes. The signature covers a domain tag followed by the key ID
+// and the payload, so a signature over other data, or made with another key
+// ID, does not verify.
+//
+// Key handling and the signature algorithm are left to the node through the
+// `MetadataSigner` and `MetadataVerifier` traits.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::schemas::ServiceMetadata;
+use crate::errors::{ErrorCode, RunarError};
+
+/// Domain tag prefixed to every signed message
+pub const METADATA_SIGNATURE_DOMAIN: &[u8] = b"runar-service-metadata-v1";
+
+/// Signs service metadata with a node key
+pub trait MetadataSigner: Send + Sync {
+    /// ID of the key, sent along so verifiers can look up the public key
+    fn key_id(&self) -> &str;
+
+    /// Sign `message`
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Checks signatures of service metadata
+pub trait MetadataVerifier: Send + Sync {
+    /// Fail unless `signature` is a valid signature of `message` by `key_id`
+    fn verify(&self, key_id: &str, message: &[u8], signature: &[u8]) -> Result<()>;
+}
+
+/// Service metadata with a signature over its canonical encoding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedServiceMetadata {
+    #[serde(with = "serde_bytes")]
+    payload: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    signature: Vec<u8>,
+    key_id: String,
+}
+
+impl SignedServiceMetadata {
+    /// The canonical encoding of `metadata` that gets signed
+    pub fn canonical_bytes(metadata: &ServiceMetadata) -> Vec<u8> {
+        metadata.to_compact_bytes()
+    }
+
+    /// Sign `metadata` with `signer`
+    pub fn sign(metadata: &ServiceMetadata, signer: &dyn MetadataSigner) -> Result<Self> {
+        let payload = Self::canonical_bytes(metadata);
+        let key_id = signer.key_id().to_string();
+        let signature = signer.sign(&signing_message(&key_id, &payload))?;
+        Ok(SignedServiceMetadata {
+            payload,
+            signature,
+            key_id,
+        })
+    }
+
+    /// Check the signature and decode the metadata. Fails with an
+    /// `Unauthorized` `RunarError` if the signature does not verify.
+    pub fn verify(&self, verifier: &dyn MetadataVerifier) -> Result<ServiceMetadata> {
+        verifier
+            .verify(
+                &self.key_id,
+                &signing_message(&self.key_id, &self.payload),
+                &self.signature,
+            )
+            .map_err(|e| {
+                RunarError::new(
+                    ErrorCode::Unauthorized,
+                    format!(
+                        "Service metadata signature by key '{}' is invalid: {}",
+                        self.key_id, e
+                    ),
+                )
+            })?;
+        ServiceMetadata::from_compact_bytes(&self.payload)
+    }
+
+    /// The canonical metadata bytes that were signed
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// The signature
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    /// ID of the signing key
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+}
+
+// Domain tag, key ID length and key ID, then the payload
+fn signing_message(key_id: &str, payload: &[u8]) -> Vec<u8> {
+    let mut message =
+        Vec::with_capacity(METADATA_SIGNATURE_DOMAIN.len() + 4 + key_id.len() + payload.len());
+    message.extend_from_slice(METADATA_SIGNATURE_DOMAIN);
+    message.extend_from_slice(&(key_id.len() as u32).to_le_bytes());
+    message.extend_from_slice(key_id.as_bytes());
+    message.extend_from_slice(payload);
+    message
+}
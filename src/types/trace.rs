@@ -0,0 +1,214 @@
+// runar_common/src/types/trace.rs
+//
+// W3C Trace Context (https://www.w3.org/TR/trace-context/) propagation.
+//
+// A `TraceContext` carries the `traceparent` header (trace ID, parent span ID
+// and flags) and the opaque vendor `tracestate` header. The HTTP gateway
+// parses them from incoming requests, nodes pass them along in
+// `RequestEnvelope`s, and browser peers send them back as the same strings.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::encoding::to_hex;
+
+/// The only traceparent version this implementation writes
+pub const TRACEPARENT_VERSION: u8 = 0;
+
+/// Flag bit marking a trace as sampled
+pub const FLAG_SAMPLED: u8 = 0x01;
+
+/// Maximum number of list members in a tracestate header
+pub const MAX_TRACESTATE_MEMBERS: usize = 32;
+
+/// Trace and span identifiers of the current operation
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TraceContext {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    flags: u8,
+    tracestate: Option<String>,
+}
+
+impl TraceContext {
+    /// Start a new sampled trace with random IDs
+    pub fn new_root() -> Self {
+        TraceContext {
+            trace_id: random_nonzero(),
+            span_id: random_nonzero(),
+            flags: FLAG_SAMPLED,
+            tracestate: None,
+        }
+    }
+
+    /// Parse a `traceparent` header, e.g.
+    /// `00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01`
+    pub fn parse(traceparent: &str) -> Result<Self> {
+        let invalid = || anyhow!("Invalid traceparent '{}'", traceparent);
+        let parts: Vec<&str> = traceparent.trim().split('-').collect();
+        let [version, trace_id, span_id, flags, rest @ ..] = parts.as_slice() else {
+            return Err(invalid());
+        };
+        let version = parse_hex::<1>(version).ok_or_else(invalid)?[0];
+        // Version 0xff is forbidden; version 0 has exactly four fields, later
+        // versions may append more, which are ignored
+        if version == 0xff || (version == TRACEPARENT_VERSION && !rest.is_empty()) {
+            return Err(invalid());
+        }
+        let trace_id = parse_hex::<16>(trace_id)
+            .filter(|id| id.iter().any(|b| *b != 0))
+            .ok_or_else(invalid)?;
+        let span_id = parse_hex::<8>(span_id)
+            .filter(|id| id.iter().any(|b| *b != 0))
+            .ok_or_else(invalid)?;
+        let flags = parse_hex::<1>(flags).ok_or_else(invalid)?[0];
+        Ok(TraceContext {
+            trace_id,
+            span_id,
+            flags,
+            tracestate: None,
+        })
+    }
+
+    /// Parse the `traceparent` and optional `tracestate` headers
+    pub fn from_headers(traceparent: &str, tracestate: Option<&str>) -> Result<Self> {
+        let context = Self::parse(traceparent)?;
+        match tracestate {
+            Some(state) => context.with_tracestate(state),
+            None => Ok(context),
+        }
+    }
+
+    /// Attach a validated `tracestate` header (empty clears it)
+    pub fn with_tracestate(mut self, tracestate: &str) -> Result<Self> {
+        let members: Vec<&str> = tracestate
+            .split(',')
+            .map(str::trim)
+            .filter(|member| !member.is_empty())
+            .collect();
+        if members.len() > MAX_TRACESTATE_MEMBERS {
+            return Err(anyhow!(
+                "tracestate has {} members (max {})",
+                members.len(),
+                MAX_TRACESTATE_MEMBERS
+            ));
+        }
+        for member in &members {
+            let valid = member
+                .split_once('=')
+                .is_some_and(|(key, value)| !key.is_empty() && !value.is_empty());
+            if !valid
+                || member
+                    .chars()
+                    .any(|c| !c.is_ascii() || c.is_ascii_control())
+            {
+                return Err(anyhow!("Invalid tracestate member '{}'", member));
+            }
+        }
+        self.tracestate = (!members.is_empty()).then(|| members.join(","));
+        Ok(self)
+    }
+
+    /// A context for a child operation: same trace, new span ID
+    pub fn child(&self) -> Self {
+        TraceContext {
+            span_id: random_nonzero(),
+            ..self.clone()
+        }
+    }
+
+    /// Mark the trace as sampled or not
+    pub fn with_sampled(mut self, sampled: bool) -> Self {
+        if sampled {
+            self.flags |= FLAG_SAMPLED;
+        } else {
+            self.flags &= !FLAG_SAMPLED;
+        }
+        self
+    }
+
+    /// The 16-byte trace ID
+    pub fn trace_id(&self) -> [u8; 16] {
+        self.trace_id
+    }
+
+    /// The 8-byte ID of the current span
+    pub fn span_id(&self) -> [u8; 8] {
+        self.span_id
+    }
+
+    /// The trace ID as 32 lowercase hex digits
+    pub fn trace_id_hex(&self) -> String {
+        to_hex(&self.trace_id)
+    }
+
+    /// The span ID as 16 lowercase hex digits
+    pub fn span_id_hex(&self) -> String {
+        to_hex(&self.span_id)
+    }
+
+    /// The trace flags
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Whether the caller sampled (records) the trace
+    pub fn is_sampled(&self) -> bool {
+        self.flags & FLAG_SAMPLED != 0
+    }
+
+    /// The `traceparent` header value
+    pub fn traceparent(&self) -> String {
+        format!(
+            "{:02x}-{}-{}-{:02x}",
+            TRACEPARENT_VERSION,
+            self.trace_id_hex(),
+            self.span_id_hex(),
+            self.flags
+        )
+    }
+
+    /// The `tracestate` header value, if any
+    pub fn tracestate(&self) -> Option<&str> {
+        self.tracestate.as_deref()
+    }
+}
+
+impl fmt::Display for TraceContext {
+    /// Formats as the `traceparent` header value
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.traceparent())
+    }
+}
+
+impl FromStr for TraceContext {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+fn random_nonzero<const N: usize>() -> [u8; N] {
+    loop {
+        let bytes: [u8; N] = std::array::from_fn(|_| rand::random());
+        if bytes.iter().any(|b| *b != 0) {
+            return bytes;
+        }
+    }
+}
+
+// Lowercase hex of exactly N bytes
+fn parse_hex<const N: usize>(text: &str) -> Option<[u8; N]> {
+    if text.len() != N * 2 || !text.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+        return None;
+    }
+    let mut bytes = [0u8; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&text[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
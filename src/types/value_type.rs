@@ -7,25 +7,51 @@
 // See documentation in mod.rs and rust-docs/specs/ for rationale.
 
 use std::any::Any;
+use std::cell::RefCell;
 use std::clone::Clone;
 use std::cmp::{Eq, PartialEq};
 use std::collections::HashMap;
 use std::fmt::{self, Debug};
-use std::marker::Copy;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use anyhow::{anyhow, Result};
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 
+use super::convert::FromArcValue;
 use super::erased_arc::ErasedArc;
-use crate::logging::{Component, Logger};
+use super::fingerprint;
+use super::ids::NodeId;
+use super::provenance::Provenance;
+use super::raw_json::RawJson;
+use super::schemas::FieldSchema;
+use super::visit::{is_value_list, is_value_map};
+use crate::logging::Logger;
+use crate::utils::integrity::ChecksumAlgorithm;
+pub use crate::wire::ValueCategory;
+use crate::wire::{self, hex_snippet, WireError};
+
+use super::codec::{self, CodecId};
+
+/// Type-erased deserializer function stored in the registry
+pub type DeserializerFn =
+    dyn Fn(&[u8], CodecId) -> Result<Box<dyn Any + Send + Sync>> + Send + Sync;
+
+/// Type-erased serializer function stored in the registry
+type SerializerFn = dyn Fn(&dyn Any, CodecId, &mut Vec<u8>) -> Result<()> + Send + Sync;
+
+/// Measures the encoded size of a registered value, giving up (None) once it
+/// passes the given limit
+type SizeEstimatorFn = dyn Fn(&dyn Any, u64) -> Option<u64> + Send + Sync;
+
+/// Decodes a payload into a typed ErasedArc (see `ArcValueType::materialize`)
+type MaterializerFn = dyn Fn(&[u8], CodecId) -> Result<ErasedArc> + Send + Sync;
 
 /// Wrapper struct for deserializer function that implements Debug
 #[derive(Clone)]
 pub struct DeserializerFnWrapper {
     // The actual deserializer function
-    pub func: Arc<dyn Fn(&[u8]) -> Result<Box<dyn Any + Send + Sync>> + Send + Sync>,
+    pub func: Arc<DeserializerFn>,
 }
 
 impl std::fmt::Debug for DeserializerFnWrapper {
@@ -37,15 +63,15 @@ impl std::fmt::Debug for DeserializerFnWrapper {
 impl DeserializerFnWrapper {
     pub fn new<F>(func: F) -> Self
     where
-        F: Fn(&[u8]) -> Result<Box<dyn Any + Send + Sync>> + Send + Sync + 'static,
+        F: Fn(&[u8], CodecId) -> Result<Box<dyn Any + Send + Sync>> + Send + Sync + 'static,
     {
         DeserializerFnWrapper {
             func: Arc::new(func),
         }
     }
 
-    pub fn call(&self, bytes: &[u8]) -> Result<Box<dyn Any + Send + Sync>> {
-        (self.func)(bytes)
+    pub fn call(&self, bytes: &[u8], codec: CodecId) -> Result<Box<dyn Any + Send + Sync>> {
+        (self.func)(bytes, codec)
     }
 }
 
@@ -60,7 +86,96 @@ pub struct LazyDataWithOffset {
     pub start_offset: usize,
     /// End offset of the relevant data within the buffer
     pub end_offset: usize,
-    // NOTE: We no longer store the deserializer function here, as we use direct bincode
+    /// Codec the payload was encoded with
+    pub codec: CodecId,
+    /// Where to record materialization of this value (if the registry has metrics enabled)
+    pub metrics: Option<Arc<MaterializationMetrics>>,
+    // NOTE: We no longer store the deserializer function here; the payload is decoded with `codec`
+}
+
+impl LazyDataWithOffset {
+    /// The serialized payload of this value
+    pub fn payload(&self) -> &[u8] {
+        &self.original_buffer[self.start_offset..self.end_offset]
+    }
+
+    /// Whether the payload is at most `max_copy_bytes` and shares its buffer
+    /// with other data (so copying it out would release memory)
+    pub fn should_detach(&self, max_copy_bytes: usize) -> bool {
+        let len = self.end_offset - self.start_offset;
+        len <= max_copy_bytes && len < self.original_buffer.len()
+    }
+
+    /// Copy the payload into its own buffer, releasing the original buffer
+    pub fn copy_payload(&self) -> Self {
+        let buffer: Arc<[u8]> = Arc::from(self.payload());
+        Self {
+            type_name: self.type_name.clone(),
+            end_offset: buffer.len(),
+            original_buffer: buffer,
+            start_offset: 0,
+            codec: self.codec,
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+/// Materialization counters for one type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MaterializationStats {
+    /// Number of lazy values of the type that were decoded
+    pub count: u64,
+    /// Total payload bytes decoded
+    pub bytes: u64,
+}
+
+/// Counts, per type name, how often lazy values are materialized and how many
+/// bytes are decoded. Enabled with `SerializerRegistry::enable_metrics`.
+#[derive(Debug, Default)]
+pub struct MaterializationMetrics {
+    by_type: Mutex<HashMap<String, MaterializationStats>>,
+}
+
+impl MaterializationMetrics {
+    fn record(&self, type_name: &str, bytes: usize) {
+        let mut by_type = self
+            .by_type
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let stats = by_type.entry(type_name.to_string()).or_default();
+        stats.count += 1;
+        stats.bytes += bytes as u64;
+    }
+
+    /// Counters for one type name (if any value of it was materialized)
+    pub fn get(&self, type_name: &str) -> Option<MaterializationStats> {
+        self.by_type
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(type_name)
+            .copied()
+    }
+
+    /// All counters, sorted by type name
+    pub fn snapshot(&self) -> Vec<(String, MaterializationStats)> {
+        let mut entries: Vec<_> = self
+            .by_type
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .map(|(name, stats)| (name.clone(), *stats))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+
+    /// Clear all counters
+    pub fn reset(&self) {
+        self.by_type
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clear();
+    }
 }
 
 impl fmt::Debug for LazyDataWithOffset {
@@ -71,27 +186,178 @@ impl fmt::Debug for LazyDataWithOffset {
             .field("data_segment_len", &(self.end_offset - self.start_offset))
             .field("start_offset", &self.start_offset)
             .field("end_offset", &self.end_offset)
+            .field("codec", &self.codec)
+            .field("metrics", &self.metrics.is_some())
             .finish()
     }
 }
 
-/// Categorizes the value for efficient dispatch
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ValueCategory {
-    Primitive,
-    List,
-    Map,
-    Struct,
-    Null,
-    /// Raw bytes (used for Vec<u8>, not for lazy deserialization)
-    Bytes,
+/// Default payload size (64 KiB) above which `serialize_async`/`deserialize_async`
+/// use `spawn_blocking`
+pub const DEFAULT_BLOCKING_THRESHOLD: usize = 64 * 1024;
+
+/// How the registry handles two types with the same simple name (the last
+/// path segment, e.g. `Status` for both `foo::Status` and `bar::Status`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SimpleNamePolicy {
+    /// The first type keeps the simple name (the original behavior)
+    #[default]
+    KeepFirst,
+    /// Registering a type whose simple name is taken fails
+    Error,
+    /// Colliding types are only resolvable by their full name
+    FullNameOnly,
+    /// The first type keeps the simple name and every colliding type is also
+    /// registered under its last two path segments (e.g. `bar::Status`)
+    Namespace,
+}
+
+/// What `deserialize_value` logs (at warn level) when a payload cannot be
+/// decoded. Payload bytes may contain user data, so only metadata is logged
+/// unless a hex prefix is explicitly allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailureLogging {
+    /// Failures are only returned as errors
+    #[default]
+    Off,
+    /// Category, claimed type name and payload length
+    Metadata,
+    /// Metadata plus a hexdump of at most this many leading payload bytes
+    /// (and the `WireError` snippet, if it is no longer than that)
+    WithPrefix(usize),
+}
+
+/// Types registered under the same simple name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimpleNameCollision {
+    /// The contested simple name
+    pub simple_name: String,
+    /// Full names of the types sharing it, in registration order
+    pub type_names: Vec<String>,
+    /// The type the simple name resolves to (`None` under `FullNameOnly`)
+    pub resolves_to: Option<String>,
+}
+
+/// Pool of scratch encoding buffers shared by batch serialization.
+/// Oversized buffers are dropped rather than kept around.
+#[derive(Default)]
+struct BufferPool {
+    buffers: Mutex<Vec<Vec<u8>>>,
+}
+
+const MAX_POOLED_BUFFERS: usize = 64;
+const MAX_POOLED_CAPACITY: usize = 1024 * 1024;
+
+impl BufferPool {
+    fn take(&self) -> PooledBuffer<'_> {
+        let buffer = self
+            .buffers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .pop()
+            .unwrap_or_default();
+        PooledBuffer { pool: self, buffer }
+    }
+}
+
+/// A buffer borrowed from a `BufferPool`, returned to it on drop
+struct PooledBuffer<'a> {
+    pool: &'a BufferPool,
+    buffer: Vec<u8>,
+}
+
+impl std::ops::Deref for PooledBuffer<'_> {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Vec<u8> {
+        &self.buffer
+    }
+}
+
+impl std::ops::DerefMut for PooledBuffer<'_> {
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.buffer
+    }
+}
+
+impl Drop for PooledBuffer<'_> {
+    fn drop(&mut self) {
+        if self.buffer.capacity() > MAX_POOLED_CAPACITY {
+            return;
+        }
+        let mut buffers = self
+            .pool
+            .buffers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if buffers.len() < MAX_POOLED_BUFFERS {
+            let mut buffer = std::mem::take(&mut self.buffer);
+            buffer.clear();
+            buffers.push(buffer);
+        }
+    }
+}
+
+/// Types sharing a simple name and the one it resolves to
+#[derive(Debug, Clone, Default)]
+struct SimpleNameEntry {
+    type_names: Vec<String>,
+    resolves_to: Option<String>,
+}
+
+/// The last path segment of a type name (`foo::Status` -> `Status`).
+/// Generic arguments are kept as written (`alloc::vec::Vec<foo::Status>`
+/// -> `Vec<foo::Status>`), so different instantiations stay distinct.
+fn simple_type_name(type_name: &str) -> &str {
+    trailing_segments(type_name, 0)
+}
+
+/// The last two path segments of a type name (`app::foo::Status` -> `foo::Status`)
+fn namespaced_type_name(type_name: &str) -> &str {
+    trailing_segments(type_name, 1)
+}
+
+// The path of `type_name` from its last `skip + 1` segments, looking only
+// at the path before any generic arguments
+fn trailing_segments(type_name: &str, skip: usize) -> &str {
+    let path_end = type_name.find('<').unwrap_or(type_name.len());
+    match type_name[..path_end].rmatch_indices("::").nth(skip) {
+        Some((index, _)) => &type_name[index + 2..],
+        None => type_name,
+    }
 }
 
 /// Registry for type-specific serialization and deserialization handlers
 pub struct SerializerRegistry {
-    serializers: FxHashMap<String, Box<dyn Fn(&dyn Any) -> Result<Vec<u8>> + Send + Sync>>,
+    serializers: FxHashMap<String, Arc<SerializerFn>>,
     deserializers: FxHashMap<String, DeserializerFnWrapper>,
+    /// Typed decoders for registered types, by full type name
+    materializers: FxHashMap<String, Arc<MaterializerFn>>,
+    /// Size estimates of registered types, for the async API
+    size_estimators: FxHashMap<String, Arc<SizeEstimatorFn>>,
     is_sealed: bool,
+    /// Trailing checksum appended to serialized values (if any)
+    checksum: Option<ChecksumAlgorithm>,
+    /// Codec used to encode payloads (decoding follows the wire header)
+    codec: CodecId,
+    /// Payload size above which the async API moves work to a blocking thread
+    blocking_threshold: usize,
+    /// Scratch buffers reused by `serialize_batch`
+    buffer_pool: BufferPool,
+    /// Lazy payloads up to this size are copied out of the buffer they were read from
+    detach_threshold: Option<usize>,
+    /// Materialization counters for values deserialized by this registry (if enabled)
+    metrics: Option<Arc<MaterializationMetrics>>,
+    /// Handling of types sharing a simple name
+    simple_name_policy: SimpleNamePolicy,
+    /// What is logged when a payload fails to deserialize
+    failure_logging: FailureLogging,
+    /// Types registered under each simple name
+    simple_names: FxHashMap<String, SimpleNameEntry>,
+    /// Layout fingerprints by full type name
+    fingerprints: FxHashMap<String, u64>,
+    /// Whether known fingerprints are written into value headers
+    embed_fingerprints: bool,
     /// Logger for SerializerRegistry operations
     logger: Arc<Logger>,
 }
@@ -102,7 +368,20 @@ impl SerializerRegistry {
         SerializerRegistry {
             serializers: FxHashMap::default(),
             deserializers: FxHashMap::default(),
+            materializers: FxHashMap::default(),
+            size_estimators: FxHashMap::default(),
             is_sealed: false,
+            checksum: None,
+            codec: CodecId::Bincode,
+            blocking_threshold: DEFAULT_BLOCKING_THRESHOLD,
+            buffer_pool: BufferPool::default(),
+            detach_threshold: None,
+            metrics: None,
+            simple_name_policy: SimpleNamePolicy::default(),
+            failure_logging: FailureLogging::default(),
+            simple_names: FxHashMap::default(),
+            fingerprints: FxHashMap::default(),
+            embed_fingerprints: false,
             logger,
         }
     }
@@ -138,6 +417,33 @@ impl SerializerRegistry {
         self.register_map::<String, i64>().unwrap();
         self.register_map::<String, f64>().unwrap();
         self.register_map::<String, bool>().unwrap();
+
+        // Maps keyed by numeric or UUID identifiers
+        self.register_keyed_maps::<i64>();
+        self.register_keyed_maps::<u64>();
+        self.register_keyed_maps::<uuid::Uuid>();
+
+        // Addresses and paths carried by configuration and discovery payloads
+        self.register::<std::net::SocketAddr>().unwrap();
+        self.register::<std::net::IpAddr>().unwrap();
+        self.register::<std::path::PathBuf>().unwrap();
+    }
+
+    fn register_keyed_maps<K>(&mut self)
+    where
+        K: 'static
+            + Serialize
+            + for<'de> Deserialize<'de>
+            + Clone
+            + Send
+            + Sync
+            + Eq
+            + std::hash::Hash,
+    {
+        self.register_map::<K, String>().unwrap();
+        self.register_map::<K, i64>().unwrap();
+        self.register_map::<K, f64>().unwrap();
+        self.register_map::<K, bool>().unwrap();
     }
 
     /// Seal the registry to prevent further modifications
@@ -150,6 +456,222 @@ impl SerializerRegistry {
         self.is_sealed
     }
 
+    /// Append a trailing checksum to every serialized value and verify it on
+    /// deserialization. Both ends of a connection must use the same setting;
+    /// the header flags checksummed values, so a payload that disagrees with
+    /// the reader's setting fails with `WireError::ChecksumMismatch`.
+    pub fn set_checksum(&mut self, checksum: Option<ChecksumAlgorithm>) {
+        self.checksum = checksum;
+    }
+
+    /// Get the checksum algorithm in use (if any)
+    pub fn checksum(&self) -> Option<ChecksumAlgorithm> {
+        self.checksum
+    }
+
+    /// Select the codec used to encode payloads. Receivers decode with the
+    /// codec named in each value's header, whatever their own setting.
+    pub fn set_codec(&mut self, codec: CodecId) -> Result<()> {
+        if !codec::is_supported(codec) {
+            return Err(anyhow!("Codec {:?} is not enabled in this build", codec));
+        }
+        self.codec = codec;
+        Ok(())
+    }
+
+    /// Get the codec used to encode payloads
+    pub fn codec(&self) -> CodecId {
+        self.codec
+    }
+
+    /// Set the payload size above which the async API offloads work to `spawn_blocking`
+    pub fn set_blocking_threshold(&mut self, bytes: usize) {
+        self.blocking_threshold = bytes;
+    }
+
+    /// Get the payload size above which the async API offloads work
+    pub fn blocking_threshold(&self) -> usize {
+        self.blocking_threshold
+    }
+
+    /// Copy lazy payloads of at most `max_copy_bytes` out of the buffer they
+    /// were deserialized from, so a small value does not keep a large network
+    /// read buffer alive. `None` (the default) always borrows the buffer.
+    pub fn set_detach_threshold(&mut self, max_copy_bytes: Option<usize>) {
+        self.detach_threshold = max_copy_bytes;
+    }
+
+    /// Get the size up to which lazy payloads are copied out of their buffer
+    pub fn detach_threshold(&self) -> Option<usize> {
+        self.detach_threshold
+    }
+
+    /// Count materializations of lazy values deserialized from now on.
+    /// Off by default, as recording takes a lock on every materialization.
+    pub fn enable_metrics(&mut self) {
+        if self.metrics.is_none() {
+            self.metrics = Some(Arc::new(MaterializationMetrics::default()));
+        }
+    }
+
+    /// Materialization counters (`None` unless `enable_metrics` was called)
+    pub fn metrics(&self) -> Option<Arc<MaterializationMetrics>> {
+        self.metrics.clone()
+    }
+
+    /// Log deserialization failures through the registry's logger
+    pub fn set_failure_logging(&mut self, logging: FailureLogging) {
+        self.failure_logging = logging;
+    }
+
+    /// Get the deserialization failure logging setting
+    pub fn failure_logging(&self) -> FailureLogging {
+        self.failure_logging
+    }
+
+    /// Choose how later registrations handle simple name collisions
+    pub fn set_simple_name_policy(&mut self, policy: SimpleNamePolicy) {
+        self.simple_name_policy = policy;
+    }
+
+    /// Get the simple name collision policy
+    pub fn simple_name_policy(&self) -> SimpleNamePolicy {
+        self.simple_name_policy
+    }
+
+    /// Simple names shared by more than one registered type, sorted by name
+    pub fn simple_name_collisions(&self) -> Vec<SimpleNameCollision> {
+        let mut collisions: Vec<_> = self
+            .simple_names
+            .iter()
+            .filter(|(_, entry)| entry.type_names.len() > 1)
+            .map(|(simple_name, entry)| SimpleNameCollision {
+                simple_name: simple_name.clone(),
+                type_names: entry.type_names.clone(),
+                resolves_to: entry.resolves_to.clone(),
+            })
+            .collect();
+        collisions.sort_by(|a, b| a.simple_name.cmp(&b.simple_name));
+        collisions
+    }
+
+    /// Check the simple name of `type_name` against the collision policy
+    /// before anything is registered
+    fn check_simple_name(&self, type_name: &str) -> Result<()> {
+        let simple_name = simple_type_name(type_name);
+        if self.simple_name_policy != SimpleNamePolicy::Error || simple_name == type_name {
+            return Ok(());
+        }
+        match self.simple_names.get(simple_name) {
+            Some(entry) if entry.type_names.iter().any(|owner| owner != type_name) => Err(anyhow!(
+                "Simple name '{}' of {} is already used by {}",
+                simple_name,
+                type_name,
+                entry.type_names.join(", ")
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Register the deserializer under the full name of the type and, as
+    /// the collision policy allows, under its simple name
+    fn insert_deserializer(&mut self, type_name: &str, deserializer: DeserializerFnWrapper) {
+        self.deserializers
+            .insert(type_name.to_string(), deserializer.clone());
+
+        let simple_name = simple_type_name(type_name);
+        if simple_name == type_name {
+            return;
+        }
+        let entry = self
+            .simple_names
+            .entry(simple_name.to_string())
+            .or_default();
+        if entry.resolves_to.as_deref() == Some(type_name) {
+            // Re-registration of the type that owns the simple name
+            self.deserializers
+                .insert(simple_name.to_string(), deserializer);
+            return;
+        }
+        if entry.type_names.iter().any(|owner| owner == type_name) {
+            return;
+        }
+        entry.type_names.push(type_name.to_string());
+        if entry.type_names.len() == 1 {
+            // Full names and custom deserializers take precedence over simple names
+            if !self.deserializers.contains_key(simple_name) {
+                entry.resolves_to = Some(type_name.to_string());
+                self.deserializers
+                    .insert(simple_name.to_string(), deserializer);
+            }
+            return;
+        }
+
+        match self.simple_name_policy {
+            SimpleNamePolicy::KeepFirst | SimpleNamePolicy::Error => {}
+            SimpleNamePolicy::FullNameOnly => {
+                if entry.resolves_to.take().is_some() {
+                    self.deserializers.remove(simple_name);
+                }
+            }
+            SimpleNamePolicy::Namespace => {
+                for owner in &entry.type_names {
+                    let namespaced = namespaced_type_name(owner);
+                    if namespaced == simple_name || self.deserializers.contains_key(namespaced) {
+                        continue;
+                    }
+                    if let Some(owner_deserializer) = self.deserializers.get(owner.as_str()) {
+                        let owner_deserializer = owner_deserializer.clone();
+                        self.deserializers
+                            .insert(namespaced.to_string(), owner_deserializer);
+                    }
+                }
+            }
+        }
+        self.logger.debug(format!(
+            "Simple name '{}' is shared by {} ({:?} policy)",
+            simple_name,
+            entry.type_names.join(", "),
+            self.simple_name_policy
+        ));
+    }
+
+    /// Freeze the current registrations and settings into a read-only view
+    /// that worker tasks can share without locking.
+    ///
+    /// The snapshot is sealed; types registered with this registry afterwards
+    /// are not visible through it. Materialization metrics are shared.
+    pub fn snapshot(&self) -> Arc<RegistrySnapshot> {
+        let mut frozen = self.fork();
+        frozen.is_sealed = true;
+        Arc::new(RegistrySnapshot {
+            registry: Arc::new(frozen),
+        })
+    }
+
+    /// An unsealed copy of the registrations and settings (metrics are shared)
+    pub(crate) fn fork(&self) -> SerializerRegistry {
+        SerializerRegistry {
+            serializers: self.serializers.clone(),
+            deserializers: self.deserializers.clone(),
+            materializers: self.materializers.clone(),
+            size_estimators: self.size_estimators.clone(),
+            is_sealed: false,
+            checksum: self.checksum,
+            codec: self.codec,
+            blocking_threshold: self.blocking_threshold,
+            buffer_pool: BufferPool::default(),
+            detach_threshold: self.detach_threshold,
+            metrics: self.metrics.clone(),
+            simple_name_policy: self.simple_name_policy,
+            failure_logging: self.failure_logging,
+            simple_names: self.simple_names.clone(),
+            fingerprints: self.fingerprints.clone(),
+            embed_fingerprints: self.embed_fingerprints,
+            logger: self.logger.clone(),
+        }
+    }
+
     /// Register a type for serialization/deserialization
     pub fn register<T: 'static + Serialize + for<'de> Deserialize<'de> + Clone + Send + Sync>(
         &mut self,
@@ -160,20 +682,15 @@ impl SerializerRegistry {
             ));
         }
 
-        // Get the full and simple type names
         let type_name = std::any::type_name::<T>();
-        let simple_name = if let Some(last_segment) = type_name.split("::").last() {
-            last_segment.to_string()
-        } else {
-            type_name.to_string()
-        };
+        self.check_simple_name(type_name)?;
 
         // Register serializer using the full type name
         self.serializers.insert(
             type_name.to_string(),
-            Box::new(|value: &dyn Any| -> Result<Vec<u8>> {
+            Arc::new(|value: &dyn Any, codec: CodecId, out: &mut Vec<u8>| -> Result<()> {
                 if let Some(typed_value) = value.downcast_ref::<T>() {
-                    bincode::serialize(typed_value)
+                    codec::encode_into_with(codec, typed_value, out)
                         .map_err(|e| anyhow!("Serialization error: {}", e))
                 } else {
                     Err(anyhow!("Type mismatch during serialization"))
@@ -182,24 +699,90 @@ impl SerializerRegistry {
         );
 
         // Create a deserializer function using DeserializerFnWrapper
-        let deserializer =
-            DeserializerFnWrapper::new(|bytes: &[u8]| -> Result<Box<dyn Any + Send + Sync>> {
-                let value: T = bincode::deserialize(bytes)?;
+        let deserializer = DeserializerFnWrapper::new(
+            |bytes: &[u8], codec: CodecId| -> Result<Box<dyn Any + Send + Sync>> {
+                let value: T = codec::decode_with(codec, bytes)?;
                 Ok(Box::new(value))
-            });
+            },
+        );
 
         // Register deserializer using both full and simple type names
-        self.deserializers
-            .insert(type_name.to_string(), deserializer.clone());
+        self.insert_deserializer(type_name, deserializer);
+        self.materializers.insert(
+            type_name.to_string(),
+            Arc::new(|bytes: &[u8], codec: CodecId| -> Result<ErasedArc> {
+                let value: T = codec::decode_with(codec, bytes)?;
+                Ok(ErasedArc::new_opaque(Arc::new(value)))
+            }),
+        );
+        self.size_estimators.insert(
+            type_name.to_string(),
+            Arc::new(|value: &dyn Any, limit: u64| {
+                codec::bincode_size_within(value.downcast_ref::<T>()?, limit)
+            }),
+        );
 
-        // Only register the simple name version if it's different and not already registered
-        if simple_name != type_name && !self.deserializers.contains_key(&simple_name) {
-            self.deserializers.insert(simple_name, deserializer);
-        }
+        Ok(())
+    }
 
+    /// Register a type together with the fingerprint of its serde layout
+    /// (see `types::type_fingerprint`), returning the fingerprint.
+    ///
+    /// Payloads of the type that carry a different fingerprint fail to
+    /// deserialize with `WireError::SchemaDrift`.
+    pub fn register_with_fingerprint<T>(&mut self) -> Result<u64>
+    where
+        T: 'static + Serialize + for<'de> Deserialize<'de> + Clone + Send + Sync,
+    {
+        let fingerprint = fingerprint::type_fingerprint::<T>()?;
+        self.register::<T>()?;
+        self.fingerprints
+            .insert(std::any::type_name::<T>().to_string(), fingerprint);
+        Ok(fingerprint)
+    }
+
+    /// Set the fingerprint of a registered type, e.g. one computed from its
+    /// schema with `types::schema_fingerprint`
+    pub fn set_type_fingerprint(&mut self, type_name: &str, fingerprint: u64) -> Result<()> {
+        if self.is_sealed {
+            return Err(anyhow!(
+                "Cannot set type fingerprints after registry is sealed"
+            ));
+        }
+        if !self.serializers.contains_key(type_name) {
+            return Err(anyhow!("No serializer registered for type: {}", type_name));
+        }
+        self.fingerprints.insert(type_name.to_string(), fingerprint);
         Ok(())
     }
 
+    /// The fingerprint of a type (if one was recorded)
+    pub fn type_fingerprint(&self, type_name: &str) -> Option<u64> {
+        self.fingerprints.get(type_name).copied()
+    }
+
+    /// Write known type fingerprints into the header of serialized values.
+    ///
+    /// Off by default, as peers that predate fingerprints cannot read such
+    /// headers. Incoming fingerprints are checked whether or not this is set.
+    pub fn set_embed_fingerprints(&mut self, embed: bool) {
+        self.embed_fingerprints = embed;
+    }
+
+    /// Whether type fingerprints are written into value headers
+    pub fn embed_fingerprints(&self) -> bool {
+        self.embed_fingerprints
+    }
+
+    // The fingerprint to write for a value of `type_name` (if embedding is on)
+    fn outgoing_fingerprint(&self, category: ValueCategory, type_name: &str) -> Option<u64> {
+        match category {
+            ValueCategory::Null | ValueCategory::Bytes | ValueCategory::Json => None,
+            _ if self.embed_fingerprints => self.type_fingerprint(type_name),
+            _ => None,
+        }
+    }
+
     /// Register a map type for serialization/deserialization
     pub fn register_map<K, V>(&mut self) -> Result<()>
     where
@@ -219,20 +802,16 @@ impl SerializerRegistry {
             ));
         }
 
-        // Get the full and simple type names
         let type_name = std::any::type_name::<HashMap<K, V>>();
-        let simple_name = if let Some(last_segment) = type_name.split("::").last() {
-            last_segment.to_string()
-        } else {
-            type_name.to_string()
-        };
+        self.check_simple_name(type_name)?;
 
         // Register serializer using the full type name
         self.serializers.insert(
             type_name.to_string(),
-            Box::new(|value: &dyn Any| -> Result<Vec<u8>> {
+            Arc::new(|value: &dyn Any, codec: CodecId, out: &mut Vec<u8>| -> Result<()> {
                 if let Some(map) = value.downcast_ref::<HashMap<K, V>>() {
-                    bincode::serialize(map).map_err(|e| anyhow!("Map serialization error: {}", e))
+                    codec::encode_into_with(codec, map, out)
+                        .map_err(|e| anyhow!("Map serialization error: {}", e))
                 } else {
                     Err(anyhow!("Type mismatch during map serialization"))
                 }
@@ -240,20 +819,28 @@ impl SerializerRegistry {
         );
 
         // Create a deserializer function using DeserializerFnWrapper
-        let deserializer =
-            DeserializerFnWrapper::new(|bytes: &[u8]| -> Result<Box<dyn Any + Send + Sync>> {
-                let map: HashMap<K, V> = bincode::deserialize(bytes)?;
+        let deserializer = DeserializerFnWrapper::new(
+            |bytes: &[u8], codec: CodecId| -> Result<Box<dyn Any + Send + Sync>> {
+                let map: HashMap<K, V> = codec::decode_with(codec, bytes)?;
                 Ok(Box::new(map))
-            });
+            },
+        );
 
         // Register deserializer using both full and simple type names
-        self.deserializers
-            .insert(type_name.to_string(), deserializer.clone());
-
-        // Only register the simple name version if it's different and not already registered
-        if simple_name != type_name && !self.deserializers.contains_key(&simple_name) {
-            self.deserializers.insert(simple_name, deserializer);
-        }
+        self.insert_deserializer(type_name, deserializer);
+        self.materializers.insert(
+            type_name.to_string(),
+            Arc::new(|bytes: &[u8], codec: CodecId| -> Result<ErasedArc> {
+                let map: HashMap<K, V> = codec::decode_with(codec, bytes)?;
+                Ok(ErasedArc::new_opaque(Arc::new(map)))
+            }),
+        );
+        self.size_estimators.insert(
+            type_name.to_string(),
+            Arc::new(|value: &dyn Any, limit: u64| {
+                codec::bincode_size_within(value.downcast_ref::<HashMap<K, V>>()?, limit)
+            }),
+        );
 
         Ok(())
     }
@@ -279,75 +866,220 @@ impl SerializerRegistry {
 
     /// Serialize a value using the appropriate registered handler
     pub fn serialize(&self, value: &dyn Any, type_name: &str) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        self.serialize_payload_into(value, type_name, &mut out)?;
+        Ok(out)
+    }
+
+    // Append the payload of a value of a registered type to `out`
+    fn serialize_payload_into(
+        &self,
+        value: &dyn Any,
+        type_name: &str,
+        out: &mut Vec<u8>,
+    ) -> Result<()> {
         if let Some(serializer) = self.serializers.get(type_name) {
-            serializer(value)
+            serializer(value, self.codec, out)
                 .map_err(|e| anyhow!("Serialization error for type {}: {}", type_name, e))
         } else {
             Err(anyhow!("No serializer registered for type: {}", type_name))
         }
     }
 
-    /// Helper to extract the header from serialized bytes (slice view)
-    fn extract_header_from_slice<'a>(
+    /// Convert a value to JSON like `ArcValueType::to_json`, decoding lazy
+    /// values first. Registered struct types are converted through their
+    /// serde form rather than rejected.
+    pub fn value_to_json(&self, value: &ArcValueType) -> Result<serde_json::Value> {
+        let mut value = value.clone();
+        value.materialize(self)?;
+        if value.category != ValueCategory::Struct {
+            return value.to_json();
+        }
+        let type_name = value.value.type_name();
+        let serializer = self
+            .serializers
+            .get(type_name)
+            .ok_or_else(|| anyhow!("No serializer registered for type: {}", type_name))?;
+        let mut bytes = Vec::new();
+        serializer(value.value.as_any()?, CodecId::Json, &mut bytes)?;
+        serde_json::from_slice(&bytes)
+            .map_err(|e| anyhow!("JSON conversion error for type {}: {}", type_name, e))
+    }
+
+    /// Helper to extract the header from serialized bytes (slice view).
+    /// Failures are reported as `WireError`s.
+    pub(crate) fn extract_header_from_slice<'a>(
         &self,
         bytes: &'a [u8],
-    ) -> Result<(ValueCategory, String, &'a [u8])> {
-        if bytes.is_empty() {
-            return Err(anyhow!("Empty byte array"));
-        }
-
-        // First byte is the category marker
-        let category = match bytes[0] {
-            0x01 => ValueCategory::Primitive,
-            0x02 => ValueCategory::List,
-            0x03 => ValueCategory::Map,
-            0x04 => ValueCategory::Struct,
-            0x05 => ValueCategory::Null,
-            0x06 => ValueCategory::Bytes,
-            _ => return Err(anyhow!("Invalid category marker: {}", bytes[0])),
-        };
+    ) -> Result<(wire::WireHeader<'a>, &'a [u8])> {
+        let header = wire::decode_header(bytes)?;
+        Ok((header, &bytes[header.data_offset..]))
+    }
 
-        // For null, no type name is needed
-        if category == ValueCategory::Null {
-            return Ok((category, String::new(), &[]));
-        }
+    /// Deserialize bytes (owned Arc) to an ArcValueType. The value's
+    /// provenance records when it was received.
+    pub fn deserialize_value(&self, bytes_arc: Arc<[u8]>) -> Result<ArcValueType> {
+        self.deserialize_logged(bytes_arc, None, Provenance::received())
+    }
 
-        // Extract the type name
-        if bytes.len() < 2 {
-            return Err(anyhow!("Byte array too short for header"));
+    /// Deserialize a value received from `source` (e.g. a peer id); the
+    /// source is included when the failure is logged, and becomes the
+    /// value's provenance origin when it is a valid node id
+    pub fn deserialize_value_from(
+        &self,
+        bytes_arc: Arc<[u8]>,
+        source: &str,
+    ) -> Result<ArcValueType> {
+        let mut provenance = Provenance::received();
+        provenance.origin = NodeId::new(source).ok();
+        self.deserialize_logged(bytes_arc, Some(source), provenance)
+    }
+
+    /// Deserialize a value and attach `provenance` to it; its origin is
+    /// included when the failure is logged
+    pub fn deserialize_value_with(
+        &self,
+        bytes_arc: Arc<[u8]>,
+        provenance: Provenance,
+    ) -> Result<ArcValueType> {
+        let source = provenance.origin.as_ref().map(|origin| origin.to_string());
+        self.deserialize_logged(bytes_arc, source.as_deref(), provenance)
+    }
+
+    fn deserialize_logged(
+        &self,
+        bytes_arc: Arc<[u8]>,
+        source: Option<&str>,
+        provenance: Provenance,
+    ) -> Result<ArcValueType> {
+        let result = self.verify_and_decode(bytes_arc.clone());
+        if let Err(error) = &result {
+            self.log_failure(&bytes_arc, source, error);
         }
+        result.map(|value| value.with_provenance(provenance))
+    }
 
-        let type_name_len = bytes[1] as usize;
-        if bytes.len() < 2 + type_name_len {
-            return Err(anyhow!("Byte array too short for type name"));
+    // Log a payload that failed to deserialize, as configured
+    pub(crate) fn log_failure(&self, bytes: &[u8], source: Option<&str>, error: &anyhow::Error) {
+        if let Some(report) = self.failure_report(bytes, source, error) {
+            self.logger.warn(report);
         }
+    }
 
-        let type_name_bytes = &bytes[2..2 + type_name_len];
-        let type_name = String::from_utf8(type_name_bytes.to_vec())
-            .map_err(|_| anyhow!("Invalid type name encoding"))?;
+    /// The log line for a payload that failed to deserialize, as configured
+    /// by `set_failure_logging` (`None` when logging is off)
+    pub fn failure_report(
+        &self,
+        bytes: &[u8],
+        source: Option<&str>,
+        error: &anyhow::Error,
+    ) -> Option<String> {
+        let prefix_len = match self.failure_logging {
+            FailureLogging::Off => return None,
+            FailureLogging::Metadata => None,
+            FailureLogging::WithPrefix(max_bytes) => Some(max_bytes),
+        };
+        let category = bytes
+            .first()
+            .filter(|marker| CodecId::from_id((*marker >> 4) & 0x03).is_some())
+            .and_then(|marker| ValueCategory::from_marker(marker & 0x07))
+            .map_or_else(
+                || "unknown".to_string(),
+                |category| format!("{:?}", category),
+            );
+        let type_name = match wire::decode_header(bytes) {
+            Ok(header) if header.category == ValueCategory::Null => "-".to_string(),
+            Ok(header) => header.type_name.to_string(),
+            Err(_) => "<unreadable>".to_string(),
+        };
 
-        // The actual data starts after the type name
-        let data_start_offset = 2 + type_name_len;
-        let data_bytes = &bytes[data_start_offset..];
+        let mut report = String::from("Failed to deserialize value");
+        if let Some(source) = source {
+            report.push_str(&format!(" from {}", source));
+        }
+        report.push_str(&format!(
+            ": {} (category: {}, type: {}, length: {} bytes",
+            error,
+            category,
+            type_name,
+            bytes.len()
+        ));
+        if let Some(max_bytes) = prefix_len {
+            let shown = &bytes[..bytes.len().min(max_bytes)];
+            report.push_str(&format!(", prefix: {}", hex::encode(shown)));
+            if shown.len() < bytes.len() {
+                report.push_str("...");
+            }
+            let snippet = error
+                .chain()
+                .find_map(|cause| cause.downcast_ref::<WireError>())
+                .and_then(WireError::snippet)
+                .filter(|_| max_bytes >= wire::SNIPPET_LEN);
+            if let Some(snippet) = snippet {
+                report.push_str(&format!(", at: [{}]", snippet));
+            }
+        }
+        report.push(')');
+        Some(report)
+    }
 
-        Ok((category, type_name, data_bytes))
+    // Verify the checksum (if any) and decode
+    fn verify_and_decode(&self, bytes_arc: Arc<[u8]>) -> Result<ArcValueType> {
+        self.check_checksum_flag(&bytes_arc)?;
+        match self.checksum {
+            // The checksum is stripped into a new buffer so lazy values never see it
+            Some(algorithm) => {
+                let data = algorithm.verify_trailing(&bytes_arc)?;
+                self.decode_value(Arc::from(data))
+            }
+            None => self.decode_value(bytes_arc),
+        }
     }
 
-    /// Deserialize bytes (owned Arc) to an ArcValueType
-    pub fn deserialize_value(&self, bytes_arc: Arc<[u8]>) -> Result<ArcValueType> {
-        if bytes_arc.is_empty() {
-            return Err(anyhow!("Empty byte array"));
+    // Reject a payload whose header disagrees with this registry about a
+    // trailing checksum, before its last bytes are read as the wrong thing
+    pub(crate) fn check_checksum_flag(&self, bytes: &[u8]) -> Result<()> {
+        let header = wire::decode_header(bytes)?;
+        if header.checksummed != self.checksum.is_some() {
+            return Err(WireError::ChecksumMismatch {
+                present: header.checksummed,
+                snippet: hex_snippet(bytes),
+            }
+            .into());
         }
+        Ok(())
+    }
 
+    /// Decode a value (without checksum) into a lazily deserialized ArcValueType
+    pub(crate) fn decode_value(&self, bytes_arc: Arc<[u8]>) -> Result<ArcValueType> {
         // Extract header info using a slice view
-        let (original_category, type_name, data_slice) =
-            self.extract_header_from_slice(&bytes_arc)?;
+        let (header, data_slice) = self.extract_header_from_slice(&bytes_arc)?;
+        let (original_category, codec) = (header.category, header.codec);
+        let type_name = header.type_name.to_string();
 
         // For null, just return a null value
         if original_category == ValueCategory::Null {
             return Ok(ArcValueType::null());
         }
 
+        if !codec::is_supported(codec) {
+            return Err(anyhow!(
+                "Value of type {} is encoded with {:?}, which is not enabled in this build",
+                type_name,
+                codec
+            ));
+        }
+
+        // Bytes are written raw (not codec encoded), so rebuild them directly
+        if original_category == ValueCategory::Bytes {
+            return Ok(ArcValueType::new_bytes(data_slice.to_vec()));
+        }
+
+        // JSON is forwarded as text and only parsed when a consumer asks for it
+        if original_category == ValueCategory::Json {
+            return Ok(ArcValueType::new_json(data_slice));
+        }
+
         self.logger.debug(format!(
             "Deserializing value with type: {} (category: {:?})",
             type_name, original_category
@@ -359,6 +1091,20 @@ impl SerializerRegistry {
             type_name
         ));
 
+        // A payload written against another layout would decode as garbage
+        if let (Some(found), Some(expected)) =
+            (header.fingerprint, self.type_fingerprint(&type_name))
+        {
+            if found != expected {
+                return Err(WireError::SchemaDrift {
+                    name: type_name,
+                    expected,
+                    found,
+                }
+                .into());
+            }
+        }
+
         // Check if a deserializer exists (even though we don't store it in LazyDataWithOffset,
         // its registration confirms the type is known)
         if self.deserializers.contains_key(&type_name) {
@@ -366,25 +1112,77 @@ impl SerializerRegistry {
             let data_start_offset = (data_slice.as_ptr() as usize) - (bytes_arc.as_ptr() as usize);
             let data_end_offset = data_start_offset + data_slice.len();
 
-            let lazy_data = LazyDataWithOffset {
+            let mut lazy_data = LazyDataWithOffset {
                 type_name: type_name.to_string(),
                 original_buffer: bytes_arc.clone(), // Clone the Arc (cheap)
                 start_offset: data_start_offset,
                 end_offset: data_end_offset,
+                codec,
+                metrics: self.metrics.clone(),
             };
+            if let Some(max_copy_bytes) = self.detach_threshold {
+                if lazy_data.should_detach(max_copy_bytes) {
+                    lazy_data = lazy_data.copy_payload();
+                }
+            }
 
             // Store Arc<LazyDataWithOffset> in value, keeping original category
             let value = ErasedArc::from_value(lazy_data);
-            return Ok(ArcValueType {
+            Ok(ArcValueType {
                 category: original_category, // Keep original category (Map, Struct, etc.)
                 value,
-            });
+                provenance: None,
+            })
         } else {
-            return Err(anyhow!(
-                "No deserializer registered for complex type, cannot create lazy value: {}",
-                type_name
-            ));
+            Err(WireError::UnknownType {
+                name: type_name,
+                snippet: hex_snippet(&bytes_arc),
+            }
+            .into())
+        }
+    }
+
+    /// Decode a lazy payload into a typed value
+    pub(crate) fn materialize_lazy(&self, lazy: &LazyDataWithOffset) -> Result<ErasedArc> {
+        let materializer = self.materializers.get(&lazy.type_name).ok_or_else(|| {
+            anyhow!(
+                "Type {} has no typed decoder (only types added with register or register_map can be materialized)",
+                lazy.type_name
+            )
+        })?;
+        let value = materializer(lazy.payload(), lazy.codec).map_err(|e| {
+            anyhow!(
+                "Failed to materialize value of type '{}': {}",
+                lazy.type_name,
+                e
+            )
+        })?;
+        if let Some(metrics) = &lazy.metrics {
+            metrics.record(&lazy.type_name, lazy.payload().len());
         }
+        Ok(value)
+    }
+
+    /// Decode a payload of a registered type and re-encode it as JSON, for
+    /// types (such as structs) that `ArcValueType::to_json` cannot render
+    pub(crate) fn payload_to_json(
+        &self,
+        type_name: &str,
+        payload: &[u8],
+        codec: CodecId,
+    ) -> Result<serde_json::Value> {
+        let deserializer = self
+            .deserializers
+            .get(type_name)
+            .ok_or_else(|| anyhow!("No deserializer registered for type: {}", type_name))?;
+        let serializer = self
+            .serializers
+            .get(type_name)
+            .ok_or_else(|| anyhow!("No serializer registered for type: {}", type_name))?;
+        let decoded = deserializer.call(payload, codec)?;
+        let mut json = Vec::new();
+        serializer(&*decoded as &dyn Any, CodecId::Json, &mut json)?;
+        serde_json::from_slice(&json).map_err(|e| anyhow!("JSON conversion error: {}", e))
     }
 
     /// Get a stored deserializer by type name
@@ -392,24 +1190,6 @@ impl SerializerRegistry {
         self.deserializers.get(type_name).cloned()
     }
 
-    /// Helper to decide if a type should be immediately deserialized
-    fn is_simple_immediate_type(&self, type_name: &str) -> bool {
-        // Simple types that should be deserialized immediately
-        type_name == "i32"
-            || type_name == "i64"
-            || type_name == "f32"
-            || type_name == "f64"
-            || type_name == "bool"
-            || type_name == "String"
-            || type_name.contains("Vec<")
-                && (type_name.contains("i32")
-                    || type_name.contains("i64")
-                    || type_name.contains("f32")
-                    || type_name.contains("f64")
-                    || type_name.contains("bool")
-                    || type_name.contains("String"))
-    }
-
     /// Print all registered deserializers for debugging
     pub fn debug_print_deserializers(&self) {
         for key in self.deserializers.keys() {
@@ -419,6 +1199,63 @@ impl SerializerRegistry {
 
     /// Serialize a value to bytes, returning an Arc<[u8]>
     pub fn serialize_value(&self, value: &ArcValueType) -> Result<Arc<[u8]>> {
+        let mut buffer = Vec::new();
+        self.serialize_into(value, &mut buffer)?;
+        Ok(Arc::from(buffer))
+    }
+
+    /// Serialize many values at once, encoding them in parallel.
+    ///
+    /// Values are encoded straight into scratch buffers that the registry
+    /// pools and reuses across batches, so once the pool is warm the only
+    /// allocation per value is the returned `Arc<[u8]>` (which needs its own
+    /// allocation, so the encoded bytes are copied into it once). Fails with
+    /// the index of the first value that could not be encoded.
+    pub fn serialize_batch(&self, values: &[ArcValueType]) -> Result<Vec<Arc<[u8]>>> {
+        let encode = |buffer: &mut PooledBuffer<'_>, (index, value): (usize, &ArcValueType)| {
+            buffer.clear();
+            self.serialize_into(value, buffer)
+                .map(|()| Arc::from(&buffer[..]))
+                .map_err(|e| anyhow!("Failed to serialize batch item {}: {}", index, e))
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            use rayon::prelude::*;
+
+            values
+                .par_iter()
+                .enumerate()
+                .map_init(|| self.buffer_pool.take(), encode)
+                .collect()
+        }
+
+        // No threads in the browser: encode sequentially with a single buffer
+        #[cfg(target_arch = "wasm32")]
+        {
+            let mut buffer = self.buffer_pool.take();
+            values
+                .iter()
+                .enumerate()
+                .map(|item| encode(&mut buffer, item))
+                .collect()
+        }
+    }
+
+    // Append the encoded value (and checksum, if enabled) to `buffer`
+    fn serialize_into(&self, value: &ArcValueType, buffer: &mut Vec<u8>) -> Result<()> {
+        let start = buffer.len();
+        self.encode_value(value, buffer)?;
+        if let Some(algorithm) = self.checksum {
+            buffer[start] |= wire::CHECKSUM_FLAG;
+            let checksum = algorithm.compute(&buffer[start..]);
+            buffer.extend_from_slice(&checksum);
+        }
+        Ok(())
+    }
+
+    /// Encode a value (without checksum) with its category and type header
+    fn encode_value(&self, value: &ArcValueType, result_vec: &mut Vec<u8>) -> Result<()> {
         // Check if the value holds LazyDataWithOffset
         if value.value.is_lazy {
             if let Ok(lazy) = value.value.get_lazy_data() {
@@ -428,32 +1265,23 @@ impl SerializerRegistry {
                     lazy.type_name, value.category
                 ));
 
-                let mut result_vec = Vec::new(); // Build into a Vec first
-
-                // Add category marker byte
-                let category_byte = match value.category {
-                    ValueCategory::Primitive => 0x01,
-                    ValueCategory::List => 0x02,
-                    ValueCategory::Map => 0x03,
-                    ValueCategory::Struct => 0x04,
-                    ValueCategory::Null => return Err(anyhow!("Cannot serialize lazy Null value")),
-                    ValueCategory::Bytes => 0x06,
-                };
-                result_vec.push(category_byte);
-
-                // Add type name length and bytes
-                let type_bytes = lazy.type_name.as_bytes();
-                if type_bytes.len() > 255 {
-                    return Err(anyhow!("Type name too long: {}", lazy.type_name));
+                if value.category == ValueCategory::Null {
+                    return Err(anyhow!("Cannot serialize lazy Null value"));
                 }
-                result_vec.push(type_bytes.len() as u8);
-                result_vec.extend_from_slice(type_bytes);
+                // The payload is copied as is, so it keeps its original codec
+                wire::encode_header_with_fingerprint(
+                    value.category,
+                    lazy.codec,
+                    &lazy.type_name,
+                    self.outgoing_fingerprint(value.category, &lazy.type_name),
+                    result_vec,
+                )?;
 
                 // Add the data bytes from the original buffer using offsets
                 result_vec
                     .extend_from_slice(&lazy.original_buffer[lazy.start_offset..lazy.end_offset]);
 
-                return Ok(Arc::from(result_vec)); // Convert Vec to Arc<[u8]>
+                return Ok(());
             } else {
                 return Err(anyhow!(
                     "Value marked as lazy, but failed to extract LazyDataWithOffset"
@@ -468,46 +1296,40 @@ impl SerializerRegistry {
             value.category
         ));
 
-        let mut result_vec = Vec::new(); // Build into a Vec first
-        let category_byte = match value.category {
-            ValueCategory::Primitive => 0x01,
-            ValueCategory::List => 0x02,
-            ValueCategory::Map => 0x03,
-            ValueCategory::Struct => 0x04,
-            ValueCategory::Null => 0x05,
-            ValueCategory::Bytes => 0x06,
-        };
-        result_vec.push(category_byte);
-
-        // Handle Null separately as it has no type name or data
+        // Null has no type name or data
         if value.category == ValueCategory::Null {
-            return Ok(Arc::from(result_vec)); // Convert Vec to Arc<[u8]>
+            wire::encode_header(ValueCategory::Null, "", result_vec)?;
+            return Ok(());
         }
 
-        // Add type name length and type name (for non-Null)
         let type_name = value.value.type_name();
-        let type_bytes = type_name.as_bytes();
-        if type_bytes.len() > 255 {
-            return Err(anyhow!("Type name too long: {}", type_name));
-        }
-        result_vec.push(type_bytes.len() as u8);
-        result_vec.extend_from_slice(type_bytes);
-
-        // Get the actual data bytes to append
-        let data_bytes = match value.category {
+        // Bytes and JSON payloads are written raw rather than through the codec
+        let codec = match value.category {
+            ValueCategory::Bytes | ValueCategory::Json => CodecId::Bincode,
+            _ => self.codec,
+        };
+        wire::encode_header_with_fingerprint(
+            value.category,
+            codec,
+            type_name,
+            self.outgoing_fingerprint(value.category, type_name),
+            result_vec,
+        )?;
+
+        // Append the payload right after the header
+        match value.category {
             ValueCategory::Primitive
             | ValueCategory::List
             | ValueCategory::Map
             | ValueCategory::Struct => {
                 // Use the registered serializer
                 let any_ref = value.value.as_any()?;
-                self.serialize(any_ref, type_name)? // Returns Vec<u8>
+                self.serialize_payload_into(any_ref, type_name, result_vec)?;
             }
             ValueCategory::Bytes => {
-                // Directly get the Vec<u8> bytes
+                // Directly copy the Vec<u8> bytes
                 if let Ok(bytes_arc) = value.value.as_arc::<Vec<u8>>() {
-                    // Need to clone the inner Vec<u8> if we are returning an owned buffer section
-                    bytes_arc.to_vec()
+                    result_vec.extend_from_slice(&bytes_arc);
                 } else {
                     return Err(anyhow!(
                         "Value has Bytes category but doesn't contain Arc<Vec<u8>> (actual: {})",
@@ -515,17 +1337,190 @@ impl SerializerRegistry {
                     ));
                 }
             }
-            ValueCategory::Null => unreachable!(), // Handled above
-        };
-        result_vec.extend_from_slice(&data_bytes);
+            ValueCategory::Json => result_vec.extend_from_slice(value.as_raw_json()?.as_bytes()),
+            ValueCategory::Null => unreachable!(), // Handled above
+        }
+
+        Ok(())
+    }
+}
+
+// Run CPU-heavy work on tokio's blocking pool. The browser has no blocking
+// pool, so on wasm32 the work simply runs inline.
+#[cfg(not(target_arch = "wasm32"))]
+async fn run_blocking<T, F>(work: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| anyhow!("Blocking serialization task failed: {}", e))?
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn run_blocking<T, F>(work: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    work()
+}
+
+// Async facade: small payloads are handled inline, large ones on the blocking pool
+impl SerializerRegistry {
+    /// Serialize a value without blocking the async runtime on large payloads.
+    ///
+    /// The size of eagerly held structs, lists and maps is measured (as
+    /// bincode, up to the blocking threshold) before choosing; values that
+    /// cannot be measured are encoded on the blocking pool.
+    pub async fn serialize_async(self: &Arc<Self>, value: &ArcValueType) -> Result<Arc<[u8]>> {
+        match self.payload_size_hint(value, self.blocking_threshold) {
+            Some(size) if size < self.blocking_threshold => self.serialize_value(value),
+            _ => {
+                let registry = Arc::clone(self);
+                let value = value.clone();
+                run_blocking(move || registry.serialize_value(&value)).await
+            }
+        }
+    }
+
+    /// Deserialize bytes without blocking the async runtime on large payloads
+    pub async fn deserialize_async(self: &Arc<Self>, bytes: Arc<[u8]>) -> Result<ArcValueType> {
+        if bytes.len() < self.blocking_threshold {
+            return self.deserialize_value(bytes);
+        }
+        let registry = Arc::clone(self);
+        run_blocking(move || registry.deserialize_value(bytes)).await
+    }
+
+    // Encoded payload size when it can be known without encoding. Structured
+    // values are measured only up to `limit`; None when they are larger or
+    // cannot be measured.
+    fn payload_size_hint(&self, value: &ArcValueType, limit: usize) -> Option<usize> {
+        if let Some(len) = value.lazy_len() {
+            return Some(len);
+        }
+        if is_value_list(value) {
+            let items = value.value.as_arc::<Vec<ArcValueType>>().ok()?;
+            return self.items_size_hint(items.iter(), limit);
+        }
+        if is_value_map(value) {
+            let entries = value.value.as_arc::<HashMap<String, ArcValueType>>().ok()?;
+            let keys_len: usize = entries.keys().map(String::len).sum();
+            let remaining = limit.checked_sub(keys_len)?;
+            let values_len = self.items_size_hint(entries.values(), remaining)?;
+            return Some(keys_len + values_len);
+        }
+        match value.category {
+            ValueCategory::Null => Some(0),
+            ValueCategory::Primitive => match value.expect_type::<String>(ValueCategory::Primitive)
+            {
+                Ok(()) => value.value.as_arc::<String>().ok().map(|text| text.len()),
+                Err(_) => Some(0),
+            },
+            ValueCategory::Bytes => value.as_bytes_ref().ok().map(|bytes| bytes.len()),
+            ValueCategory::Json => value.as_raw_json().ok().map(|json| json.as_bytes().len()),
+            ValueCategory::List | ValueCategory::Map | ValueCategory::Struct => {
+                let estimate = self.size_estimators.get(value.value.type_name())?;
+                estimate(value.value.as_any().ok()?, limit as u64).map(|size| size as usize)
+            }
+        }
+    }
+
+    // Total size of nested values, each counted with its type name as the
+    // header, or None once it passes `limit`
+    fn items_size_hint<'a>(
+        &self,
+        items: impl Iterator<Item = &'a ArcValueType>,
+        limit: usize,
+    ) -> Option<usize> {
+        let mut total = 0usize;
+        for item in items {
+            let header = item.value.type_name().len() + 4;
+            let remaining = limit.checked_sub(total + header)?;
+            total += header + self.payload_size_hint(item, remaining)?;
+            if total > limit {
+                return None;
+            }
+        }
+        Some(total)
+    }
+}
+
+/// Immutable view of a `SerializerRegistry` taken with
+/// `SerializerRegistry::snapshot`.
+///
+/// Derefs to a sealed registry, so all read-only operations
+/// (`serialize_value`, `deserialize_value`, ...) are available directly.
+#[derive(Clone)]
+pub struct RegistrySnapshot {
+    registry: Arc<SerializerRegistry>,
+}
+
+impl RegistrySnapshot {
+    /// The frozen registry, for APIs taking `&Arc<SerializerRegistry>`
+    /// (`serialize_async`, `deserialize_async`, `scope`)
+    pub fn registry(&self) -> &Arc<SerializerRegistry> {
+        &self.registry
+    }
+}
+
+impl std::ops::Deref for RegistrySnapshot {
+    type Target = SerializerRegistry;
+
+    fn deref(&self) -> &SerializerRegistry {
+        &self.registry
+    }
+}
+
+impl fmt::Debug for RegistrySnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegistrySnapshot")
+            .field("serializers", &self.registry.serializers.len())
+            .field("deserializers", &self.registry.deserializers.len())
+            .field("codec", &self.registry.codec)
+            .finish()
+    }
+}
+
+thread_local! {
+    /// Registry used by the serde impls of ArcValueType (see `SerializerRegistry::scope`)
+    static SCOPED_REGISTRY: RefCell<Option<Arc<SerializerRegistry>>> = const { RefCell::new(None) };
+}
+
+// Scoped registry for serde: ArcValueType fields of other types are encoded
+// with the registry wire format
+impl SerializerRegistry {
+    /// Run `f` with this registry available to `ArcValueType`'s serde impls.
+    ///
+    /// Structs embedding an `ArcValueType` can only be (de)serialized inside a
+    /// scope, e.g. `registry.scope(|| serde_json::to_string(&message))`. The
+    /// scope is per thread and scopes can be nested.
+    pub fn scope<R>(self: &Arc<Self>, f: impl FnOnce() -> R) -> R {
+        // Restores the outer scope even if `f` panics
+        struct Restore(Option<Arc<SerializerRegistry>>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                SCOPED_REGISTRY.with(|scoped| *scoped.borrow_mut() = previous);
+            }
+        }
+
+        let previous = SCOPED_REGISTRY.with(|scoped| scoped.borrow_mut().replace(Arc::clone(self)));
+        let _restore = Restore(previous);
+        f()
+    }
 
-        Ok(Arc::from(result_vec)) // Convert final Vec to Arc<[u8]>
+    /// The registry of the innermost `scope` on this thread (if any)
+    pub fn scoped() -> Option<Arc<SerializerRegistry>> {
+        SCOPED_REGISTRY.with(|scoped| scoped.borrow().clone())
     }
 }
 
 /// A type-erased value container with Arc preservation
-/// Note: This type is NOT serializable because it contains an ErasedArc field.
-/// Any attempt to serialize/deserialize ArcValueType will skip the value field.
+/// Note: serde (de)serialization goes through the registry wire format and
+/// requires a registry scope (see `SerializerRegistry::scope`).
 #[derive(Debug, Clone)]
 pub struct ArcValueType {
     /// Categorizes the value for dispatch
@@ -533,6 +1528,8 @@ pub struct ArcValueType {
     /// The contained type-erased value
     /// Note: ErasedArc is type-erased and requires custom serde impl. Only registered types are supported.
     pub value: ErasedArc,
+    /// Where the value came from (see `types::provenance`)
+    pub(crate) provenance: Option<Arc<Provenance>>,
 }
 
 impl PartialEq for ArcValueType {
@@ -549,7 +1546,11 @@ impl Eq for ArcValueType {}
 impl ArcValueType {
     /// Create a new ArcValueType
     pub fn new(value: ErasedArc, category: ValueCategory) -> Self {
-        Self { category, value }
+        Self {
+            category,
+            value,
+            provenance: None,
+        }
     }
 
     /// Create a new primitive value
@@ -558,15 +1559,22 @@ impl ArcValueType {
         Self {
             category: ValueCategory::Primitive,
             value: ErasedArc::new(arc),
+            provenance: None,
         }
     }
 
-    /// Create a new struct value
+    /// Create a new struct value (the value is moved into a new Arc)
     pub fn from_struct<T: 'static + fmt::Debug + Send + Sync>(value: T) -> Self {
-        let arc = Arc::new(value);
+        Self::from_struct_arc(Arc::new(value))
+    }
+
+    /// Create a new struct value from an existing Arc without copying the struct.
+    /// `as_struct_ref` on this value (or any clone of it) returns the same Arc.
+    pub fn from_struct_arc<T: 'static + fmt::Debug + Send + Sync>(arc: Arc<T>) -> Self {
         Self {
             category: ValueCategory::Struct,
             value: ErasedArc::new(arc),
+            provenance: None,
         }
     }
 
@@ -576,6 +1584,7 @@ impl ArcValueType {
         Self {
             category: ValueCategory::List,
             value: ErasedArc::new(arc),
+            provenance: None,
         }
     }
 
@@ -594,6 +1603,7 @@ impl ArcValueType {
         Self {
             category: ValueCategory::Map,
             value: ErasedArc::new(arc),
+            provenance: None,
         }
     }
 
@@ -606,11 +1616,39 @@ impl ArcValueType {
         Self::new_map(map)
     }
 
+    /// Create a new bytes value
+    pub fn new_bytes(bytes: Vec<u8>) -> Self {
+        Self {
+            category: ValueCategory::Bytes,
+            value: ErasedArc::new(Arc::new(bytes)),
+            provenance: None,
+        }
+    }
+
+    /// Create a JSON value from raw JSON bytes; they are parsed only when accessed
+    pub fn new_json(bytes: impl Into<Vec<u8>>) -> Self {
+        Self::from_raw_json(RawJson::new(bytes))
+    }
+
+    /// Create a JSON value from an already parsed JSON tree
+    pub fn from_json_value(value: serde_json::Value) -> Self {
+        Self::from_raw_json(RawJson::from_value(value))
+    }
+
+    fn from_raw_json(json: RawJson) -> Self {
+        Self {
+            category: ValueCategory::Json,
+            value: ErasedArc::new(Arc::new(json)),
+            provenance: None,
+        }
+    }
+
     /// Create a null value
     pub fn null() -> Self {
         Self {
             category: ValueCategory::Null,
             value: ErasedArc::new(Arc::new(())),
+            provenance: None,
         }
     }
 
@@ -619,8 +1657,383 @@ impl ArcValueType {
         self.category == ValueCategory::Null
     }
 
+    /// Extract the value as `T` and apply `f`, or return `None` for null.
+    ///
+    /// Replaces the `if !value.is_null() { ... }` pattern in handlers; a
+    /// non-null value of the wrong type is still an error.
+    pub fn map_if_present<T, U>(&self, f: impl FnOnce(T) -> U) -> Result<Option<U>>
+    where
+        T: FromArcValue,
+    {
+        if self.is_null() {
+            return Ok(None);
+        }
+        T::from_arc_value(self.clone()).map(|value| Some(f(value)))
+    }
+
+    /// Replace a null value with the default declared by `schema`.
+    ///
+    /// Non-null values are returned unchanged. A null without a schema default
+    /// stays null unless the schema marks the field as not nullable.
+    pub fn or_null_default(self, schema: &FieldSchema) -> Result<ArcValueType> {
+        if !self.is_null() {
+            return Ok(self);
+        }
+        match schema.default_as_value()? {
+            Some(default) => Ok(default),
+            None if schema.nullable == Some(false) => Err(anyhow!(
+                "Field '{}' is null, not nullable and has no default",
+                schema.name
+            )),
+            None => Ok(self),
+        }
+    }
+
+    /// Build a value tree from a JSON value.
+    /// Objects become `HashMap<String, ArcValueType>` maps, arrays become
+    /// `Vec<ArcValueType>` lists, and integers that fit in i64 stay integral.
+    pub fn from_json(json: serde_json::Value) -> Self {
+        match json {
+            serde_json::Value::Null => Self::null(),
+            serde_json::Value::Bool(b) => Self::new_primitive(b),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => Self::new_primitive(i),
+                None => Self::new_primitive(n.as_f64().unwrap_or(f64::NAN)),
+            },
+            serde_json::Value::String(s) => Self::new_primitive(s),
+            serde_json::Value::Array(items) => {
+                Self::new_list(items.into_iter().map(Self::from_json).collect::<Vec<_>>())
+            }
+            serde_json::Value::Object(fields) => Self::new_map(
+                fields
+                    .into_iter()
+                    .map(|(k, v)| (k, Self::from_json(v)))
+                    .collect::<HashMap<String, ArcValueType>>(),
+            ),
+        }
+    }
+
+    /// Convert the value tree to JSON.
+    /// Supports null, bool/integer/float/String primitives, bytes (as base64), and
+    /// lists and string-keyed maps of those or of nested values. Structs are opaque
+    /// to the registry and cannot be converted.
+    pub fn to_json(&self) -> Result<serde_json::Value> {
+        use base64::Engine;
+
+        let type_name = self.stored_type_name()?;
+        let mut value = self.clone();
+        macro_rules! try_types {
+            ($($t:ty),*) => {
+                $(
+                    if type_name == std::any::type_name::<$t>() {
+                        let typed = value.as_type_ref::<$t>()?;
+                        return serde_json::to_value(&*typed)
+                            .map_err(|e| anyhow!("JSON conversion error: {}", e));
+                    }
+                )*
+            };
+        }
+
+        match self.category {
+            ValueCategory::Null => return Ok(serde_json::Value::Null),
+            ValueCategory::Json => return self.as_raw_json()?.parse().cloned(),
+            ValueCategory::Bytes => {
+                let bytes = self.as_bytes_ref()?;
+                return Ok(serde_json::Value::String(
+                    base64::engine::general_purpose::STANDARD.encode(bytes.as_slice()),
+                ));
+            }
+            ValueCategory::Struct => {
+                return Err(anyhow!(
+                    "Struct values cannot be converted to JSON (type: {})",
+                    type_name
+                ))
+            }
+            ValueCategory::List => {
+                if type_name == std::any::type_name::<Vec<ArcValueType>>() {
+                    let items = value.as_type_ref::<Vec<ArcValueType>>()?;
+                    return items
+                        .iter()
+                        .map(ArcValueType::to_json)
+                        .collect::<Result<Vec<_>>>()
+                        .map(serde_json::Value::Array);
+                }
+                try_types!(
+                    Vec<bool>,
+                    Vec<i32>,
+                    Vec<i64>,
+                    Vec<u32>,
+                    Vec<u64>,
+                    Vec<f32>,
+                    Vec<f64>,
+                    Vec<String>
+                );
+            }
+            ValueCategory::Map => {
+                if type_name == std::any::type_name::<HashMap<String, ArcValueType>>() {
+                    let entries = value.as_type_ref::<HashMap<String, ArcValueType>>()?;
+                    return entries
+                        .iter()
+                        .map(|(k, v)| Ok((k.clone(), v.to_json()?)))
+                        .collect::<Result<serde_json::Map<_, _>>>()
+                        .map(serde_json::Value::Object);
+                }
+                try_types!(
+                    HashMap<String, bool>, HashMap<String, i32>, HashMap<String, i64>,
+                    HashMap<String, f64>, HashMap<String, String>
+                );
+                // Non-string keys become JSON object keys in their string form
+                try_types!(
+                    HashMap<i64, String>, HashMap<i64, i64>, HashMap<i64, f64>, HashMap<i64, bool>,
+                    HashMap<u64, String>, HashMap<u64, i64>, HashMap<u64, f64>, HashMap<u64, bool>,
+                    HashMap<uuid::Uuid, String>, HashMap<uuid::Uuid, i64>,
+                    HashMap<uuid::Uuid, f64>, HashMap<uuid::Uuid, bool>
+                );
+            }
+            ValueCategory::Primitive => {
+                try_types!(bool, i8, i16, i32, i64, u8, u16, u32, u64, f32, f64, String);
+                try_types!(std::net::SocketAddr, std::net::IpAddr, std::path::PathBuf);
+            }
+        }
+        Err(anyhow!(
+            "Cannot convert value of type {} to JSON",
+            type_name
+        ))
+    }
+
+    /// Render the value as an indented tree with a stable layout, for golden
+    /// and snapshot tests.
+    ///
+    /// Map entries are sorted by key, so the output does not depend on
+    /// `HashMap` iteration order. Scalars are written as JSON literals, bytes
+    /// as hex, and structs (which the registry cannot inspect) by type name.
+    pub fn to_debug_tree(&self) -> String {
+        let mut out = String::new();
+        self.write_debug_tree(&mut out, 0);
+        out
+    }
+
+    fn write_debug_tree(&self, out: &mut String, indent: usize) {
+        use std::fmt::Write;
+
+        let type_name = self
+            .stored_type_name()
+            .unwrap_or_else(|_| "<unknown>".to_string());
+        let mut value = self.clone();
+        match self.category {
+            ValueCategory::Bytes => {
+                if let Ok(bytes) = value.as_bytes_ref() {
+                    let _ = write!(out, "Bytes[{}] {}", bytes.len(), hex::encode(&*bytes));
+                    return;
+                }
+            }
+            ValueCategory::List if type_name == std::any::type_name::<Vec<ArcValueType>>() => {
+                if let Ok(items) = value.as_type_ref::<Vec<ArcValueType>>() {
+                    write_tree_items(
+                        out,
+                        indent,
+                        ('[', ']'),
+                        items.iter().map(|item| (None, item)),
+                    );
+                    return;
+                }
+            }
+            ValueCategory::Map
+                if type_name == std::any::type_name::<HashMap<String, ArcValueType>>() =>
+            {
+                if let Ok(entries) = value.as_type_ref::<HashMap<String, ArcValueType>>() {
+                    let mut sorted: Vec<_> = entries.iter().collect();
+                    sorted.sort_by(|a, b| a.0.cmp(b.0));
+                    write_tree_items(
+                        out,
+                        indent,
+                        ('{', '}'),
+                        sorted.into_iter().map(|(k, v)| (Some(k.as_str()), v)),
+                    );
+                    return;
+                }
+            }
+            _ => {}
+        }
+        match self.to_json() {
+            Ok(json) => write_json_tree(out, indent, &json),
+            Err(_) => {
+                let _ = write!(out, "{:?}<{}>", self.category, type_name);
+            }
+        }
+    }
+
+    /// The serialized type name if this value has not been deserialized yet
+    pub fn lazy_type_name(&self) -> Option<&str> {
+        self.value.lazy_type_name()
+    }
+
+    /// The size of the serialized payload if this value has not been deserialized yet
+    pub fn lazy_len(&self) -> Option<usize> {
+        self.value.lazy_len()
+    }
+
+    /// Whether this value has been deserialized (always true for locally created values)
+    pub fn is_materialized(&self) -> bool {
+        self.value.is_materialized()
+    }
+
+    /// Decode lazy data now using `registry`'s deserializers, so the value
+    /// can afterwards be read through `&self` (e.g. shared across threads)
+    /// without further decoding.
+    ///
+    /// Unlike the `as_*_ref` accessors this does not need the concrete type.
+    /// Nested lists and maps of values are materialized as well; decoding
+    /// those requires a registry scope (see `SerializerRegistry::scope`).
+    pub fn materialize(&mut self, registry: &SerializerRegistry) -> Result<()> {
+        if let Ok(lazy) = self.value.try_get_lazy_data() {
+            self.value = registry.materialize_lazy(&lazy)?;
+        }
+        if self
+            .expect_type::<Vec<ArcValueType>>(ValueCategory::List)
+            .is_ok()
+        {
+            let items = self.as_type_ref::<Vec<ArcValueType>>()?;
+            if !items.iter().all(ArcValueType::is_fully_materialized) {
+                drop(items);
+                self.update_list(|items: &mut Vec<ArcValueType>| {
+                    items
+                        .iter_mut()
+                        .try_for_each(|item| item.materialize(registry))
+                })??;
+            }
+        } else if self
+            .expect_type::<HashMap<String, ArcValueType>>(ValueCategory::Map)
+            .is_ok()
+        {
+            let entries = self.as_type_ref::<HashMap<String, ArcValueType>>()?;
+            if !entries.values().all(ArcValueType::is_fully_materialized) {
+                drop(entries);
+                self.update_map(|entries| {
+                    entries
+                        .values_mut()
+                        .try_for_each(|entry| entry.materialize(registry))
+                })??;
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether this value and every value nested in its lists and maps has
+    /// been deserialized
+    pub fn is_fully_materialized(&self) -> bool {
+        if !self.is_materialized() {
+            return false;
+        }
+        if self
+            .expect_type::<Vec<ArcValueType>>(ValueCategory::List)
+            .is_ok()
+        {
+            if let Ok(items) = self.value.as_arc::<Vec<ArcValueType>>() {
+                return items.iter().all(ArcValueType::is_fully_materialized);
+            }
+        } else if self
+            .expect_type::<HashMap<String, ArcValueType>>(ValueCategory::Map)
+            .is_ok()
+        {
+            if let Ok(entries) = self.value.as_arc::<HashMap<String, ArcValueType>>() {
+                return entries.values().all(ArcValueType::is_fully_materialized);
+            }
+        }
+        true
+    }
+
+    /// Copy the value into a tree that shares no buffers with the original.
+    ///
+    /// Lazy payloads are copied out of the receive buffer they point into, so
+    /// the copy does not keep that (possibly multi-MB) buffer alive; they stay
+    /// lazy, as decoding them needs the concrete type. Nested lists and maps of
+    /// values, strings, bytes and JSON are copied. Other materialized values
+    /// are immutable behind their Arc and are shared with the original.
+    pub fn deep_clone_owned(&self) -> Result<ArcValueType> {
+        let mut copy = self.deep_clone_value()?;
+        copy.provenance = self.provenance.clone();
+        Ok(copy)
+    }
+
+    fn deep_clone_value(&self) -> Result<ArcValueType> {
+        if let Ok(lazy) = self.value.try_get_lazy_data() {
+            return Ok(Self::from_lazy(self.category, lazy.copy_payload()));
+        }
+        match self.category {
+            ValueCategory::Null => return Ok(ArcValueType::null()),
+            ValueCategory::Bytes => {
+                return Ok(ArcValueType::new_bytes(self.as_bytes_ref()?.to_vec()))
+            }
+            ValueCategory::Json => {
+                return Ok(ArcValueType::new_json(self.as_raw_json()?.as_bytes()))
+            }
+            _ => {}
+        }
+
+        let mut value = self.clone();
+        if self
+            .expect_type::<Vec<ArcValueType>>(ValueCategory::List)
+            .is_ok()
+        {
+            let items = value.as_type_ref::<Vec<ArcValueType>>()?;
+            let copied = items
+                .iter()
+                .map(ArcValueType::deep_clone_owned)
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(ArcValueType::new_list(copied));
+        }
+        if self
+            .expect_type::<HashMap<String, ArcValueType>>(ValueCategory::Map)
+            .is_ok()
+        {
+            let entries = value.as_type_ref::<HashMap<String, ArcValueType>>()?;
+            let copied = entries
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), v.deep_clone_owned()?)))
+                .collect::<Result<HashMap<_, _>>>()?;
+            return Ok(ArcValueType::new_map(copied));
+        }
+        if self.expect_type::<String>(ValueCategory::Primitive).is_ok() {
+            let text = value.as_type_ref::<String>()?;
+            return Ok(ArcValueType::new_primitive(String::clone(&text)));
+        }
+        Ok(value)
+    }
+
+    /// Copy a lazy payload of at most `max_copy_bytes` out of the buffer it
+    /// was deserialized from, so this value no longer keeps that buffer alive.
+    /// Returns whether the payload was copied.
+    pub fn detach_from_buffer(&mut self, max_copy_bytes: usize) -> bool {
+        match self.value.try_get_lazy_data() {
+            Ok(lazy) if lazy.should_detach(max_copy_bytes) => {
+                self.value = ErasedArc::from_value(lazy.copy_payload());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Wrap lazy data in a value of the given category
+    fn from_lazy(category: ValueCategory, lazy: LazyDataWithOffset) -> Self {
+        Self {
+            category,
+            value: ErasedArc::from_value(lazy),
+            provenance: None,
+        }
+    }
+
+    /// Get the full type name of the stored value, looking through lazy data
+    pub(crate) fn stored_type_name(&self) -> Result<String> {
+        if self.value.is_lazy {
+            Ok(self.value.get_lazy_data()?.type_name.clone())
+        } else {
+            Ok(self.value.type_name().to_string())
+        }
+    }
+
     /// Get value as a reference of the specified type
-    pub fn as_type_ref<T: 'static>(&mut self) -> Result<Arc<T>>
+    pub fn as_type_ref<T>(&mut self) -> Result<Arc<T>>
     where
         T: 'static + Clone + for<'de> Deserialize<'de> + fmt::Debug + Send + Sync,
     {
@@ -629,6 +2042,8 @@ impl ArcValueType {
             let original_buffer_clone: Arc<[u8]>;
             let start_offset_val: usize;
             let end_offset_val: usize;
+            let metrics: Option<Arc<MaterializationMetrics>>;
+            let codec: CodecId;
 
             {
                 let lazy_data_arc = self
@@ -639,6 +2054,8 @@ impl ArcValueType {
                 original_buffer_clone = lazy_data_arc.original_buffer.clone();
                 start_offset_val = lazy_data_arc.start_offset;
                 end_offset_val = lazy_data_arc.end_offset;
+                metrics = lazy_data_arc.metrics.clone();
+                codec = lazy_data_arc.codec;
             }
 
             // Perform type name check before deserialization
@@ -652,7 +2069,7 @@ impl ArcValueType {
             }
 
             let data_slice = &original_buffer_clone[start_offset_val..end_offset_val];
-            let deserialized_value: T = bincode::deserialize(data_slice).map_err(|e| {
+            let deserialized_value: T = codec::decode_with(codec, data_slice).map_err(|e| {
                 anyhow!(
                     "Failed to deserialize lazy struct data for type '{}' into {}: {}",
                     type_name_clone,
@@ -661,6 +2078,10 @@ impl ArcValueType {
                 )
             })?;
 
+            if let Some(metrics) = metrics {
+                metrics.record(&type_name_clone, end_offset_val - start_offset_val);
+            }
+
             // Replace internal lazy value with the eager one
             self.value = ErasedArc::new(Arc::new(deserialized_value));
             // is_lazy is now false for self.value
@@ -668,8 +2089,24 @@ impl ArcValueType {
         self.value.as_arc::<T>()
     }
 
+    /// Get bytes as a reference
+    pub fn as_bytes_ref(&self) -> Result<Arc<Vec<u8>>> {
+        if self.category != ValueCategory::Bytes {
+            return Err(anyhow!("Value is not bytes"));
+        }
+        self.value.as_arc::<Vec<u8>>()
+    }
+
+    /// Get the raw JSON held by a Json-category value
+    pub fn as_raw_json(&self) -> Result<Arc<RawJson>> {
+        if self.category != ValueCategory::Json {
+            return Err(anyhow!("Value is not JSON"));
+        }
+        self.value.as_arc::<RawJson>()
+    }
+
     /// Get list as a reference of the specified element type
-    pub fn as_list_ref<T: 'static>(&mut self) -> Result<Arc<Vec<T>>>
+    pub fn as_list_ref<T>(&mut self) -> Result<Arc<Vec<T>>>
     where
         T: 'static + Clone + for<'de> Deserialize<'de> + fmt::Debug + Send + Sync,
     {
@@ -682,6 +2119,8 @@ impl ArcValueType {
             let original_buffer_clone: Arc<[u8]>;
             let start_offset_val: usize;
             let end_offset_val: usize;
+            let metrics: Option<Arc<MaterializationMetrics>>;
+            let codec: CodecId;
 
             {
                 let lazy_data_arc = self
@@ -692,6 +2131,8 @@ impl ArcValueType {
                 original_buffer_clone = lazy_data_arc.original_buffer.clone();
                 start_offset_val = lazy_data_arc.start_offset;
                 end_offset_val = lazy_data_arc.end_offset;
+                metrics = lazy_data_arc.metrics.clone();
+                codec = lazy_data_arc.codec;
             }
 
             // Perform type name check before deserialization
@@ -705,14 +2146,19 @@ impl ArcValueType {
             }
 
             let data_slice = &original_buffer_clone[start_offset_val..end_offset_val];
-            let deserialized_value: Vec<T> = bincode::deserialize(data_slice).map_err(|e| {
-                anyhow!(
-                    "Failed to deserialize lazy struct data for type '{}' into {}: {}",
-                    type_name_clone,
-                    std::any::type_name::<T>(),
-                    e
-                )
-            })?;
+            let deserialized_value: Vec<T> =
+                codec::decode_with(codec, data_slice).map_err(|e| {
+                    anyhow!(
+                        "Failed to deserialize lazy struct data for type '{}' into {}: {}",
+                        type_name_clone,
+                        std::any::type_name::<T>(),
+                        e
+                    )
+                })?;
+
+            if let Some(metrics) = metrics {
+                metrics.record(&type_name_clone, end_offset_val - start_offset_val);
+            }
 
             // Replace internal lazy value with the eager one
             self.value = ErasedArc::new(Arc::new(deserialized_value));
@@ -752,6 +2198,8 @@ impl ArcValueType {
             let original_buffer_clone: Arc<[u8]>;
             let start_offset_val: usize;
             let end_offset_val: usize;
+            let metrics: Option<Arc<MaterializationMetrics>>;
+            let codec: CodecId;
 
             {
                 let lazy_data_arc = self
@@ -762,6 +2210,8 @@ impl ArcValueType {
                 original_buffer_clone = lazy_data_arc.original_buffer.clone();
                 start_offset_val = lazy_data_arc.start_offset;
                 end_offset_val = lazy_data_arc.end_offset;
+                metrics = lazy_data_arc.metrics.clone();
+                codec = lazy_data_arc.codec;
             }
 
             // Perform type name check before deserialization
@@ -776,13 +2226,17 @@ impl ArcValueType {
 
             let data_slice = &original_buffer_clone[start_offset_val..end_offset_val];
             let deserialized_map: HashMap<K, V> =
-                bincode::deserialize(data_slice).map_err(|e| {
+                codec::decode_with(codec, data_slice).map_err(|e| {
                     anyhow!(
                     "Failed to deserialize lazy map data for type '{}' into HashMap<{}, {}>: {}",
                     type_name_clone, std::any::type_name::<K>(), std::any::type_name::<V>(), e
                 )
                 })?;
 
+            if let Some(metrics) = metrics {
+                metrics.record(&type_name_clone, end_offset_val - start_offset_val);
+            }
+
             // Replace internal lazy value with the eager one
             self.value = ErasedArc::new(Arc::new(deserialized_map));
             // is_lazy is now false for self.value
@@ -795,8 +2249,145 @@ impl ArcValueType {
         )
     }
 
+    /// Mutate a `HashMap<String, ArcValueType>` map value in place.
+    /// The map is only cloned when other values still share it (`Arc::make_mut`
+    /// semantics); lazily deserialized maps are materialized first.
+    pub fn update_map<R>(
+        &mut self,
+        update: impl FnOnce(&mut HashMap<String, ArcValueType>) -> R,
+    ) -> Result<R> {
+        self.expect_type::<HashMap<String, ArcValueType>>(ValueCategory::Map)?;
+        let mut map = self.as_map_ref::<String, ArcValueType>()?;
+        // Release our own reference so make_mut only copies when the map is shared elsewhere.
+        // If `update` panics the value is left as null rather than half-updated.
+        let provenance = self.provenance.take();
+        *self = ArcValueType::null();
+        let result = update(Arc::make_mut(&mut map));
+        *self = ArcValueType {
+            category: ValueCategory::Map,
+            value: ErasedArc::new(map),
+            provenance,
+        };
+        Ok(result)
+    }
+
+    /// Mutate a `Vec<T>` list value in place, with the same copy-on-write
+    /// behaviour as [`ArcValueType::update_map`].
+    pub fn update_list<T, R>(&mut self, update: impl FnOnce(&mut Vec<T>) -> R) -> Result<R>
+    where
+        T: 'static + Clone + for<'de> Deserialize<'de> + fmt::Debug + Send + Sync,
+    {
+        self.check_list_type::<T>()?;
+        let mut list = self.as_list_ref::<T>()?;
+        let provenance = self.provenance.take();
+        *self = ArcValueType::null();
+        let result = update(Arc::make_mut(&mut list));
+        *self = ArcValueType {
+            category: ValueCategory::List,
+            value: ErasedArc::new(list),
+            provenance,
+        };
+        Ok(result)
+    }
+
+    /// Append an element to a `Vec<T>` list value
+    pub fn push<T>(&mut self, item: T) -> Result<()>
+    where
+        T: 'static + Clone + for<'de> Deserialize<'de> + fmt::Debug + Send + Sync,
+    {
+        self.update_list(|list: &mut Vec<T>| list.push(item))
+    }
+
+    /// Append all elements of `items` to a `Vec<T>` list value
+    pub fn extend<T>(&mut self, items: impl IntoIterator<Item = T>) -> Result<()>
+    where
+        T: 'static + Clone + for<'de> Deserialize<'de> + fmt::Debug + Send + Sync,
+    {
+        self.update_list(|list: &mut Vec<T>| list.extend(items))
+    }
+
+    /// Get a copy of the element at `index`, or `None` if it is out of bounds
+    pub fn get_index<T>(&mut self, index: usize) -> Result<Option<T>>
+    where
+        T: 'static + Clone + for<'de> Deserialize<'de> + fmt::Debug + Send + Sync,
+    {
+        self.check_list_type::<T>()?;
+        Ok(self.as_list_ref::<T>()?.get(index).cloned())
+    }
+
+    /// Replace the element at `index`, returning the previous element
+    pub fn set_index<T>(&mut self, index: usize, item: T) -> Result<T>
+    where
+        T: 'static + Clone + for<'de> Deserialize<'de> + fmt::Debug + Send + Sync,
+    {
+        // Check bounds first so an out-of-range write never copies a shared list
+        let len = {
+            self.check_list_type::<T>()?;
+            self.as_list_ref::<T>()?.len()
+        };
+        if index >= len {
+            return Err(anyhow!(
+                "List index {} out of bounds (length {})",
+                index,
+                len
+            ));
+        }
+        self.update_list(|list: &mut Vec<T>| std::mem::replace(&mut list[index], item))
+    }
+
+    /// Copy the elements in `range` into a new list value
+    pub fn slice<T>(&mut self, range: impl std::ops::RangeBounds<usize>) -> Result<ArcValueType>
+    where
+        T: 'static + Clone + for<'de> Deserialize<'de> + fmt::Debug + Send + Sync,
+    {
+        use std::ops::Bound;
+
+        self.check_list_type::<T>()?;
+        let list = self.as_list_ref::<T>()?;
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => list.len(),
+        };
+        if start > end || end > list.len() {
+            return Err(anyhow!(
+                "List slice {}..{} out of bounds (length {})",
+                start,
+                end,
+                list.len()
+            ));
+        }
+        Ok(ArcValueType::new_list(list[start..end].to_vec()))
+    }
+
+    // Typed helpers dispatch on the exact stored type so that, for example,
+    // a `Vec<i32>` is never reinterpreted as a `Vec<i64>`.
+    pub(crate) fn expect_type<T: 'static>(&self, category: ValueCategory) -> Result<()> {
+        let expected = std::any::type_name::<T>();
+        let stored = self.stored_type_name()?;
+        if self.category != category || stored != expected {
+            return Err(anyhow!(
+                "Expected a {:?} value of type {}, found {:?} ({})",
+                category,
+                expected,
+                self.category,
+                stored
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_list_type<T: 'static>(&self) -> Result<()> {
+        self.expect_type::<Vec<T>>(ValueCategory::List)
+    }
+
     /// Get value as the specified type (makes a clone)
-    pub fn as_type<T: 'static + Clone>(&mut self) -> Result<T>
+    pub fn as_type<T>(&mut self) -> Result<T>
     where
         T: 'static + Clone + for<'de> Deserialize<'de> + fmt::Debug + Send + Sync,
     {
@@ -804,6 +2395,46 @@ impl ArcValueType {
         Ok((*arc).clone())
     }
 
+    /// Consume the value and return it as the specified type.
+    /// The inner value is moved out without cloning when this was its only holder.
+    pub fn into_type<T>(mut self) -> Result<T>
+    where
+        T: 'static + Clone + for<'de> Deserialize<'de> + fmt::Debug + Send + Sync,
+    {
+        let arc = self.as_type_ref::<T>()?;
+        drop(self);
+        Ok(Arc::try_unwrap(arc).unwrap_or_else(|shared| (*shared).clone()))
+    }
+
+    /// Consume a map value and return the map, avoiding a copy when possible
+    pub fn into_map<K, V>(mut self) -> Result<HashMap<K, V>>
+    where
+        K: 'static
+            + Clone
+            + Serialize
+            + for<'de> Deserialize<'de>
+            + fmt::Debug
+            + Eq
+            + std::hash::Hash
+            + Send
+            + Sync,
+        V: 'static + Clone + Serialize + for<'de> Deserialize<'de> + fmt::Debug + Send + Sync,
+    {
+        let arc = self.as_map_ref::<K, V>()?;
+        drop(self);
+        Ok(Arc::try_unwrap(arc).unwrap_or_else(|shared| (*shared).clone()))
+    }
+
+    /// Consume a list value and return the elements, avoiding a copy when possible
+    pub fn into_list<T>(mut self) -> Result<Vec<T>>
+    where
+        T: 'static + Clone + for<'de> Deserialize<'de> + fmt::Debug + Send + Sync,
+    {
+        let arc = self.as_list_ref::<T>()?;
+        drop(self);
+        Ok(Arc::try_unwrap(arc).unwrap_or_else(|shared| (*shared).clone()))
+    }
+
     /// Get struct as a reference of the specified type.
     /// If the value is lazy, it will be deserialized and made eager in-place.
     pub fn as_struct_ref<T>(&mut self) -> Result<Arc<T>>
@@ -822,6 +2453,8 @@ impl ArcValueType {
             let original_buffer_clone: Arc<[u8]>;
             let start_offset_val: usize;
             let end_offset_val: usize;
+            let metrics: Option<Arc<MaterializationMetrics>>;
+            let codec: CodecId;
 
             {
                 let lazy_data_arc = self
@@ -832,6 +2465,8 @@ impl ArcValueType {
                 original_buffer_clone = lazy_data_arc.original_buffer.clone();
                 start_offset_val = lazy_data_arc.start_offset;
                 end_offset_val = lazy_data_arc.end_offset;
+                metrics = lazy_data_arc.metrics.clone();
+                codec = lazy_data_arc.codec;
             }
 
             // Perform type name check before deserialization
@@ -845,7 +2480,7 @@ impl ArcValueType {
             }
 
             let data_slice = &original_buffer_clone[start_offset_val..end_offset_val];
-            let deserialized_struct: T = bincode::deserialize(data_slice).map_err(|e| {
+            let deserialized_struct: T = codec::decode_with(codec, data_slice).map_err(|e| {
                 anyhow!(
                     "Failed to deserialize lazy struct data for type '{}' into {}: {}",
                     type_name_clone,
@@ -854,6 +2489,10 @@ impl ArcValueType {
                 )
             })?;
 
+            if let Some(metrics) = metrics {
+                metrics.record(&type_name_clone, end_offset_val - start_offset_val);
+            }
+
             // Replace internal lazy value with the eager one
             self.value = ErasedArc::new(Arc::new(deserialized_struct));
             // is_lazy is now false for self.value
@@ -872,22 +2511,123 @@ impl ArcValueType {
     }
 }
 
-// Implement Serialize and Deserialize for ArcValueType, skipping the value field
+/// Write the bracketed, indented items of a `to_debug_tree` list or map
+fn write_tree_items<'a>(
+    out: &mut String,
+    indent: usize,
+    (open, close): (char, char),
+    items: impl Iterator<Item = (Option<&'a str>, &'a ArcValueType)>,
+) {
+    let mut items = items.peekable();
+    out.push(open);
+    if items.peek().is_none() {
+        out.push(close);
+        return;
+    }
+    out.push('\n');
+    for (key, item) in items {
+        push_indent(out, indent + 1);
+        if let Some(key) = key {
+            out.push_str(&serde_json::Value::from(key).to_string());
+            out.push_str(": ");
+        }
+        item.write_debug_tree(out, indent + 1);
+        out.push_str(",\n");
+    }
+    push_indent(out, indent);
+    out.push(close);
+}
+
+/// Write a JSON value in the `to_debug_tree` layout (object keys sorted)
+fn write_json_tree(out: &mut String, indent: usize, json: &serde_json::Value) {
+    match json {
+        serde_json::Value::Array(items) if !items.is_empty() => {
+            out.push_str("[\n");
+            for item in items {
+                push_indent(out, indent + 1);
+                write_json_tree(out, indent + 1, item);
+                out.push_str(",\n");
+            }
+            push_indent(out, indent);
+            out.push(']');
+        }
+        serde_json::Value::Object(entries) if !entries.is_empty() => {
+            let mut sorted: Vec<_> = entries.iter().collect();
+            sorted.sort_by(|a, b| a.0.cmp(b.0));
+            out.push_str("{\n");
+            for (key, item) in sorted {
+                push_indent(out, indent + 1);
+                out.push_str(&serde_json::Value::from(key.as_str()).to_string());
+                out.push_str(": ");
+                write_json_tree(out, indent + 1, item);
+                out.push_str(",\n");
+            }
+            push_indent(out, indent);
+            out.push('}');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+fn push_indent(out: &mut String, indent: usize) {
+    for _ in 0..indent {
+        out.push_str("  ");
+    }
+}
+
+// Implement Serialize and Deserialize for ArcValueType as registry encoded bytes
+use serde::de::{self, SeqAccess, Visitor};
+use serde::ser::Error as _;
 use serde::{Deserializer, Serializer};
 
+const NO_SCOPE_ERROR: &str =
+    "ArcValueType can only be (de)serialized inside SerializerRegistry::scope";
+
 impl Serialize for ArcValueType {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        self.category.serialize(serializer)
+        let registry =
+            SerializerRegistry::scoped().ok_or_else(|| S::Error::custom(NO_SCOPE_ERROR))?;
+        let bytes = registry.serialize_value(self).map_err(S::Error::custom)?;
+        serializer.serialize_bytes(&bytes)
     }
 }
 
 impl<'de> Deserialize<'de> for ArcValueType {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        let category = ValueCategory::deserialize(deserializer)?;
-        Ok(ArcValueType {
-            category,
-            value: ErasedArc::from_value(()), // placeholder
-        })
+        let registry =
+            SerializerRegistry::scoped().ok_or_else(|| de::Error::custom(NO_SCOPE_ERROR))?;
+        let bytes = deserializer.deserialize_bytes(WireBytesVisitor)?;
+        registry
+            .deserialize_value(Arc::from(bytes))
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Accepts the encoded value as bytes, or as a sequence of bytes for formats
+/// without a native byte type (e.g. JSON)
+struct WireBytesVisitor;
+
+impl<'de> Visitor<'de> for WireBytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("registry encoded value bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        Ok(bytes.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, bytes: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(bytes)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
     }
 }
 
@@ -926,6 +2666,12 @@ impl fmt::Display for ArcValueType {
                         write!(f, "{}", fl)
                     } else if let Some(b) = any_val.downcast_ref::<bool>() {
                         write!(f, "{}", b)
+                    } else if let Some(addr) = any_val.downcast_ref::<std::net::SocketAddr>() {
+                        write!(f, "{}", addr)
+                    } else if let Some(ip) = any_val.downcast_ref::<std::net::IpAddr>() {
+                        write!(f, "{}", ip)
+                    } else if let Some(path) = any_val.downcast_ref::<std::path::PathBuf>() {
+                        write!(f, "\"{}\"", path.display())
                     } else {
                         write!(f, "Primitive<{}>", self.value.type_name())
                     }
@@ -951,6 +2697,10 @@ impl fmt::Display for ArcValueType {
                         write!(f, "Bytes<Error Retrieving Size>")
                     }
                 }
+                ValueCategory::Json => match self.as_raw_json() {
+                    Ok(json) => write!(f, "Json(size: {} bytes)", json.as_bytes().len()),
+                    Err(_) => write!(f, "Json<Error Retrieving Size>"),
+                },
             }
         }
     }
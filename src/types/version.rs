@@ -0,0 +1,270 @@
+// runar_common/src/types/version.rs
+//
+// Semantic versions (https://semver.org) and compatibility rules.
+//
+// Versions order numerically (0.10.0 > 0.9.0) and pre-releases order before
+// the release they precede. Build metadata is kept but ignored for ordering
+// and equality, as the spec requires.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// A semantic version: MAJOR.MINOR.PATCH[-PRERELEASE][+BUILD]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Dot-separated pre-release identifiers (e.g. "alpha.1"), empty for releases
+    pub pre: Vec<String>,
+    /// Build metadata (e.g. "git.abc123"), ignored for comparisons
+    pub build: Option<String>,
+}
+
+impl Version {
+    /// Create a release version
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre: Vec::new(),
+            build: None,
+        }
+    }
+
+    /// Parse a version string; a leading 'v' is accepted
+    pub fn parse(s: &str) -> Result<Self> {
+        let trimmed = s.trim();
+        let body = trimmed.strip_prefix('v').unwrap_or(trimmed);
+        let (body, build) = match body.split_once('+') {
+            Some((body, build)) => {
+                validate_identifiers(build, s)?;
+                (body, Some(build.to_string()))
+            }
+            None => (body, None),
+        };
+        let (core, pre) = match body.split_once('-') {
+            Some((core, pre)) => {
+                validate_identifiers(pre, s)?;
+                (core, pre.split('.').map(str::to_string).collect())
+            }
+            None => (body, Vec::new()),
+        };
+
+        let numbers = core
+            .split('.')
+            .map(|part| parse_number(part, s))
+            .collect::<Result<Vec<_>>>()?;
+        let [major, minor, patch] = numbers[..] else {
+            return Err(anyhow!(
+                "Invalid version '{}': expected MAJOR.MINOR.PATCH",
+                s
+            ));
+        };
+        Ok(Self {
+            major,
+            minor,
+            patch,
+            pre,
+            build,
+        })
+    }
+
+    /// Parse a version string written before versions had to be semver.
+    ///
+    /// Strict versions parse as with `parse`. Missing minor and patch
+    /// numbers are taken as zero ("1.0" and "v2" read as 1.0.0 and 2.0.0).
+    /// Anything else (e.g. "latest") reads as 0.0.0 with the original text
+    /// kept as build metadata, so it sorts before every real version.
+    pub fn parse_lenient(s: &str) -> Self {
+        if let Ok(version) = Self::parse(s) {
+            return version;
+        }
+        let trimmed = s.trim();
+        let body = trimmed.strip_prefix('v').unwrap_or(trimmed);
+        let numbers = body
+            .split('.')
+            .map(|part| part.parse::<u64>().ok())
+            .collect::<Option<Vec<_>>>();
+        match numbers.as_deref() {
+            Some(&[major]) => Self::new(major, 0, 0),
+            Some(&[major, minor]) => Self::new(major, minor, 0),
+            Some(&[major, minor, patch]) => Self::new(major, minor, patch),
+            _ => {
+                let build: String = trimmed
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+                    .collect();
+                Self {
+                    build: (!build.is_empty()).then_some(build),
+                    ..Self::new(0, 0, 0)
+                }
+            }
+        }
+    }
+
+    /// Deserialize a version with `parse_lenient`, for use with
+    /// `#[serde(deserialize_with = "Version::deserialize_lenient")]`
+    pub fn deserialize_lenient<'de, D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(Self::parse_lenient(&s))
+    }
+
+    /// Check whether this is a pre-release version
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    /// Caret compatibility (`^required`): this version can be used where `required`
+    /// is expected. The left-most non-zero component must match and this version
+    /// must not be older. For example 1.4.0 is compatible with 1.2.0, but 0.3.0 is
+    /// not compatible with 0.2.0.
+    pub fn is_compatible_with(&self, required: &Version) -> bool {
+        if self < required {
+            return false;
+        }
+        if required.major > 0 {
+            self.major == required.major
+        } else if required.minor > 0 {
+            self.major == 0 && self.minor == required.minor
+        } else {
+            self.major == 0 && self.minor == 0 && self.patch == required.patch
+        }
+    }
+
+    /// Tilde compatibility (`~required`): same major and minor, not older
+    pub fn is_tilde_compatible_with(&self, required: &Version) -> bool {
+        self >= required && self.major == required.major && self.minor == required.minor
+    }
+}
+
+fn parse_number(part: &str, original: &str) -> Result<u64> {
+    if part.is_empty() || (part.len() > 1 && part.starts_with('0')) {
+        return Err(anyhow!(
+            "Invalid version '{}': bad number '{}'",
+            original,
+            part
+        ));
+    }
+    part.parse()
+        .map_err(|_| anyhow!("Invalid version '{}': bad number '{}'", original, part))
+}
+
+fn validate_identifiers(identifiers: &str, original: &str) -> Result<()> {
+    let valid = identifiers
+        .split('.')
+        .all(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'));
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Invalid version '{}': bad identifiers '{}'",
+            original,
+            identifiers
+        ))
+    }
+}
+
+/// Compare pre-release identifier lists; an empty list (a release) sorts last
+fn compare_pre(a: &[String], b: &[String]) -> Ordering {
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => return Ordering::Equal,
+        (true, false) => return Ordering::Greater,
+        (false, true) => return Ordering::Less,
+        (false, false) => {}
+    }
+    for (x, y) in a.iter().zip(b) {
+        let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => x.cmp(y),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| compare_pre(&self.pre, &other.pre))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Build metadata is excluded to stay consistent with Eq
+        (self.major, self.minor, self.patch, &self.pre).hash(state);
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre.join("."))?;
+        }
+        if let Some(build) = &self.build {
+            write!(f, "+{}", build)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Version {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for Version {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        Self::parse(&s)
+    }
+}
+
+impl TryFrom<&str> for Version {
+    type Error = anyhow::Error;
+
+    fn try_from(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl From<Version> for String {
+    fn from(version: Version) -> Self {
+        version.to_string()
+    }
+}
@@ -0,0 +1,166 @@
+// runar_common/src/types/visit.rs
+//
+// Walking and rewriting value trees.
+//
+// `ArcValueType::transform` walks nested `Vec<ArcValueType>` lists and
+// `HashMap<String, ArcValueType>` maps depth-first, calling the visitor on
+// the way down (`enter`) and on the way up (`leave`). Redaction, migration
+// and unit conversion passes implement `ValueVisitor` instead of writing
+// their own recursion. Map entries are visited in key order so passes are
+// deterministic.
+//
+// Lazy values are decoded as they are reached. Lists and maps are always
+// decoded; other lazy values (structs) are materialized when a registry
+// scope is active (see `SerializerRegistry::scope`) and otherwise reach the
+// visitor still lazy.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+
+use super::value_type::{ArcValueType, SerializerRegistry, ValueCategory};
+
+/// One step from a value to a nested value
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    /// Entry of a map
+    Key(String),
+    /// Item of a list
+    Index(usize),
+}
+
+/// What to do with a node after `ValueVisitor::enter`
+#[derive(Debug, Clone)]
+pub enum Visit {
+    /// Walk into the node's children, then call `leave`
+    Continue,
+    /// Keep the node as it is, without visiting its children or calling `leave`
+    Skip,
+    /// Put this value in place of the node; it is not visited
+    Replace(ArcValueType),
+}
+
+/// Callbacks for `ArcValueType::transform`. `path` leads from the root to
+/// the current node and is empty for the root.
+pub trait ValueVisitor {
+    /// Called before a node's children are visited
+    fn enter(&mut self, _path: &[PathSegment], _value: &ArcValueType) -> Result<Visit> {
+        Ok(Visit::Continue)
+    }
+
+    /// Called after a node's children were transformed, with the rebuilt
+    /// node; returns the value to keep in its place
+    fn leave(&mut self, _path: &[PathSegment], value: ArcValueType) -> Result<ArcValueType> {
+        Ok(value)
+    }
+}
+
+/// A closure is a visitor that only rewrites nodes in `leave`
+impl<F> ValueVisitor for F
+where
+    F: FnMut(&[PathSegment], ArcValueType) -> Result<ArcValueType>,
+{
+    fn leave(&mut self, path: &[PathSegment], value: ArcValueType) -> Result<ArcValueType> {
+        self(path, value)
+    }
+}
+
+/// Render a path as `$.a.b[2].c` for messages; the root alone is `$`
+pub fn format_path(path: &[PathSegment]) -> String {
+    let mut out = String::from("$");
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) => {
+                out.push('.');
+                out.push_str(key);
+            }
+            PathSegment::Index(index) => {
+                out.push_str(&format!("[{}]", index));
+            }
+        }
+    }
+    out
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathSegment::Key(key) => f.write_str(key),
+            PathSegment::Index(index) => write!(f, "{}", index),
+        }
+    }
+}
+
+impl ArcValueType {
+    /// Walk the value tree with `visitor`, returning the rewritten tree (see
+    /// the `types::visit` module docs)
+    pub fn transform<V: ValueVisitor + ?Sized>(self, visitor: &mut V) -> Result<ArcValueType> {
+        let mut path = Vec::new();
+        transform_node(self, visitor, &mut path)
+    }
+}
+
+fn transform_node<V: ValueVisitor + ?Sized>(
+    mut value: ArcValueType,
+    visitor: &mut V,
+    path: &mut Vec<PathSegment>,
+) -> Result<ArcValueType> {
+    let at = |path: &[PathSegment], e: anyhow::Error| anyhow!("At {}: {}", format_path(path), e);
+
+    if !value.is_materialized() && !is_value_list(&value) && !is_value_map(&value) {
+        if let Some(registry) = SerializerRegistry::scoped() {
+            value.materialize(&registry).map_err(|e| at(path, e))?;
+        }
+    }
+
+    match visitor.enter(path, &value)? {
+        Visit::Continue => {}
+        Visit::Skip => return Ok(value),
+        Visit::Replace(replacement) => return Ok(replacement),
+    }
+
+    let value = if is_value_list(&value) {
+        let items = value.into_list::<ArcValueType>().map_err(|e| at(path, e))?;
+        let mut transformed = Vec::with_capacity(items.len());
+        for (index, item) in items.into_iter().enumerate() {
+            path.push(PathSegment::Index(index));
+            transformed.push(transform_node(item, visitor, path)?);
+            path.pop();
+        }
+        ArcValueType::new_list(transformed)
+    } else if is_value_map(&value) {
+        let mut entries: Vec<(String, ArcValueType)> = value
+            .into_map::<String, ArcValueType>()
+            .map_err(|e| at(path, e))?
+            .into_iter()
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut transformed = HashMap::with_capacity(entries.len());
+        for (key, entry) in entries {
+            path.push(PathSegment::Key(key));
+            let entry = transform_node(entry, visitor, path)?;
+            let Some(PathSegment::Key(key)) = path.pop() else {
+                unreachable!("path segment pushed above");
+            };
+            transformed.insert(key, entry);
+        }
+        ArcValueType::new_map(transformed)
+    } else {
+        value
+    };
+
+    visitor.leave(path, value)
+}
+
+pub(super) fn is_value_list(value: &ArcValueType) -> bool {
+    value
+        .expect_type::<Vec<ArcValueType>>(ValueCategory::List)
+        .is_ok()
+}
+
+pub(super) fn is_value_map(value: &ArcValueType) -> bool {
+    value
+        .expect_type::<HashMap<String, ArcValueType>>(ValueCategory::Map)
+        .is_ok()
+}
@@ -1,19 +1,23 @@
 //! VMap module for runar_common
-//! Provides a convenient wrapper for working with maps with string keys
+//! Provides a convenient wrapper for working with maps keyed by strings
+//! (the default) or by numeric/UUID identifiers
 
 use crate::types::ArcValueType;
+use anyhow::Result;
+use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::fmt;
-use std::sync::Arc;
+use std::hash::Hash;
 
-/// VMap wrapper for easier map manipulation with string keys and generic values
+/// VMap wrapper for easier map manipulation with generic values.
+/// Keys are `String` unless another key type (e.g. `i64`, `u64`, `Uuid`) is given.
 #[derive(Clone)]
-pub struct VMap<T> {
-    pub inner: HashMap<String, T>,
+pub struct VMap<T, K = String> {
+    pub inner: HashMap<K, T>,
 }
 
 // Manual Debug implementation that doesn't require T: Debug
-impl<T> fmt::Debug for VMap<T> {
+impl<T, K: fmt::Debug> fmt::Debug for VMap<T, K> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("VMap")
             .field("keys", &self.inner.keys().collect::<Vec<_>>())
@@ -22,7 +26,7 @@ impl<T> fmt::Debug for VMap<T> {
     }
 }
 
-impl<T> VMap<T> {
+impl<T, K: Eq + Hash> VMap<T, K> {
     /// Create a new empty VMap
     pub fn new() -> Self {
         VMap {
@@ -31,56 +35,160 @@ impl<T> VMap<T> {
     }
 
     /// Create a VMap from an existing HashMap
-    pub fn from_hashmap(map: HashMap<String, T>) -> Self {
+    pub fn from_hashmap(map: HashMap<K, T>) -> Self {
         VMap { inner: map }
     }
 
     /// Get a value by key
-    pub fn get(&self, key: &str) -> Option<&T> {
+    pub fn get<Q>(&self, key: &Q) -> Option<&T>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
         self.inner.get(key)
     }
 
     /// Insert a value
-    pub fn insert<K: Into<String>>(&mut self, key: K, value: T) {
+    pub fn insert<Q: Into<K>>(&mut self, key: Q, value: T) {
         self.inner.insert(key.into(), value);
     }
 
     /// Convert to inner HashMap
-    pub fn into_inner(self) -> HashMap<String, T> {
+    pub fn into_inner(self) -> HashMap<K, T> {
         self.inner
     }
 
     /// Get reference to inner HashMap
-    pub fn as_hashmap(&self) -> &HashMap<String, T> {
+    pub fn as_hashmap(&self) -> &HashMap<K, T> {
         &self.inner
     }
 }
 
-impl<T> From<HashMap<String, T>> for VMap<T> {
-    fn from(map: HashMap<String, T>) -> Self {
+impl<T, K> From<HashMap<K, T>> for VMap<T, K> {
+    fn from(map: HashMap<K, T>) -> Self {
         VMap { inner: map }
     }
 }
 
-impl<T> From<VMap<T>> for HashMap<String, T> {
-    fn from(vmap: VMap<T>) -> Self {
+impl<T, K> From<VMap<T, K>> for HashMap<K, T> {
+    fn from(vmap: VMap<T, K>) -> Self {
         vmap.inner
     }
 }
 
-impl<T> Default for VMap<T> {
+impl<T, K: Eq + Hash> Default for VMap<T, K> {
     fn default() -> Self {
         Self::new()
     }
 }
 // Extension methods for ArcValueType conversions
-impl<T> VMap<T>
+impl<T, K> VMap<T, K>
 where
     T: 'static + Clone + Send + Sync + fmt::Debug,
-    HashMap<String, T>: 'static + Send + Sync,
+    K: 'static + Send + Sync + fmt::Debug,
 {
     /// Convert VMap to an ArcValueType with Map category
     pub fn to_arc_value_type(self) -> ArcValueType {
         ArcValueType::from_map(self.inner)
     }
 }
+
+/// Whether a map key was set, set to null, or left out.
+///
+/// Actions often treat "field omitted" (keep the current value) differently
+/// from "field set to null" (clear it), which `Option` cannot express.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntryState<T> {
+    /// The key holds a non-null value
+    Present(T),
+    /// The key is present with a null value
+    ExplicitNull,
+    /// The key is not in the map
+    Missing,
+}
+
+impl<T> EntryState<T> {
+    /// Whether the key holds a non-null value
+    pub fn is_present(&self) -> bool {
+        matches!(self, EntryState::Present(_))
+    }
+
+    /// Whether the key is present with a null value
+    pub fn is_null(&self) -> bool {
+        matches!(self, EntryState::ExplicitNull)
+    }
+
+    /// Whether the key is not in the map
+    pub fn is_missing(&self) -> bool {
+        matches!(self, EntryState::Missing)
+    }
+
+    /// The value, if present (null and missing both give `None`)
+    pub fn present(self) -> Option<T> {
+        match self {
+            EntryState::Present(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Borrow the value
+    pub fn as_ref(&self) -> EntryState<&T> {
+        match self {
+            EntryState::Present(value) => EntryState::Present(value),
+            EntryState::ExplicitNull => EntryState::ExplicitNull,
+            EntryState::Missing => EntryState::Missing,
+        }
+    }
+
+    /// Convert a present value
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> EntryState<U> {
+        match self {
+            EntryState::Present(value) => EntryState::Present(f(value)),
+            EntryState::ExplicitNull => EntryState::ExplicitNull,
+            EntryState::Missing => EntryState::Missing,
+        }
+    }
+
+    /// Convert a present value with a fallible conversion
+    pub fn try_map<U>(self, f: impl FnOnce(T) -> Result<U>) -> Result<EntryState<U>> {
+        Ok(match self {
+            EntryState::Present(value) => EntryState::Present(f(value)?),
+            EntryState::ExplicitNull => EntryState::ExplicitNull,
+            EntryState::Missing => EntryState::Missing,
+        })
+    }
+}
+
+impl<K: Eq + Hash> VMap<ArcValueType, K> {
+    /// Look up a key, telling a null value apart from a missing key
+    pub fn entry_state<Q>(&self, key: &Q) -> EntryState<&ArcValueType>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self.inner.get(key) {
+            Some(value) if value.is_null() => EntryState::ExplicitNull,
+            Some(value) => EntryState::Present(value),
+            None => EntryState::Missing,
+        }
+    }
+}
+
+impl ArcValueType {
+    /// Look up a key of a `HashMap<String, ArcValueType>` map value, telling
+    /// a null value apart from a missing key.
+    ///
+    /// A null map (e.g. an action called without parameters) has every key
+    /// missing; other non-map values are an error.
+    pub fn entry_state(&mut self, key: &str) -> Result<EntryState<ArcValueType>> {
+        if self.is_null() {
+            return Ok(EntryState::Missing);
+        }
+        let map = self.as_map_ref::<String, ArcValueType>()?;
+        Ok(match map.get(key) {
+            Some(value) if value.is_null() => EntryState::ExplicitNull,
+            Some(value) => EntryState::Present(value.clone()),
+            None => EntryState::Missing,
+        })
+    }
+}
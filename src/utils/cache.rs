@@ -0,0 +1,169 @@
+// runar_common/src/utils/cache.rs
+//
+// Bounded LRU cache for decoded `ArcValueType` values.
+//
+// Values are cheap to clone (they are Arc-backed), so the cache hands out
+// clones and never blocks readers for longer than a map lookup. Entries are
+// keyed either by a request key or by the BLAKE3 hash of the encoded bytes.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+
+use crate::types::{ArcValueType, SerializerRegistry};
+use crate::utils::integrity::blake3_hash;
+
+/// Key derived from the content of an encoded payload
+pub type ContentHash = [u8; 32];
+
+/// Cache counters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+struct Entries<K> {
+    values: HashMap<K, (ArcValueType, u64)>,
+    // Last-use tick -> key, oldest first
+    recency: BTreeMap<u64, K>,
+    tick: u64,
+}
+
+/// Bounded least-recently-used cache of values
+pub struct ValueCache<K = ContentHash> {
+    capacity: usize,
+    entries: Mutex<Entries<K>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl<K: Eq + Hash + Clone> ValueCache<K> {
+    /// Create a cache holding at most `capacity` values (at least one)
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: Mutex::new(Entries {
+                values: HashMap::new(),
+                recency: BTreeMap::new(),
+                tick: 0,
+            }),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    /// Look up a value, marking it as recently used
+    pub fn get(&self, key: &K) -> Option<ArcValueType> {
+        let mut entries = self.lock();
+        let entries = &mut *entries;
+        entries.tick += 1;
+        let tick = entries.tick;
+        match entries.values.get_mut(key) {
+            Some((value, last_used)) => {
+                entries.recency.remove(last_used);
+                entries.recency.insert(tick, key.clone());
+                *last_used = tick;
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(value.clone())
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Insert a value, evicting the least recently used entry if the cache is full
+    pub fn insert(&self, key: K, value: ArcValueType) {
+        let mut entries = self.lock();
+        entries.tick += 1;
+        let tick = entries.tick;
+        if let Some((_, last_used)) = entries.values.insert(key.clone(), (value, tick)) {
+            entries.recency.remove(&last_used);
+        }
+        entries.recency.insert(tick, key);
+
+        while entries.values.len() > self.capacity {
+            let Some((_, oldest)) = entries.recency.pop_first() else {
+                break;
+            };
+            entries.values.remove(&oldest);
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Get a cached value or compute and cache it.
+    /// The cache is not locked while `compute` runs, so concurrent misses may compute twice.
+    pub fn get_or_insert_with(
+        &self,
+        key: K,
+        compute: impl FnOnce() -> Result<ArcValueType>,
+    ) -> Result<ArcValueType> {
+        if let Some(value) = self.get(&key) {
+            return Ok(value);
+        }
+        let value = compute()?;
+        self.insert(key, value.clone());
+        Ok(value)
+    }
+
+    /// Remove a value
+    pub fn remove(&self, key: &K) -> Option<ArcValueType> {
+        let mut entries = self.lock();
+        let (value, last_used) = entries.values.remove(key)?;
+        entries.recency.remove(&last_used);
+        Some(value)
+    }
+
+    /// Remove all values (counters are kept)
+    pub fn clear(&self) {
+        let mut entries = self.lock();
+        entries.values.clear();
+        entries.recency.clear();
+    }
+
+    /// Get the number of cached values
+    pub fn len(&self) -> usize {
+        self.lock().values.len()
+    }
+
+    /// Check whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the cache counters
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            len: self.len(),
+            capacity: self.capacity,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries<K>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl ValueCache<ContentHash> {
+    /// Deserialize `bytes` through the registry, reusing the cached value when
+    /// identical bytes were decoded before
+    pub fn decode(&self, registry: &SerializerRegistry, bytes: Arc<[u8]>) -> Result<ArcValueType> {
+        let key = blake3_hash(&bytes);
+        self.get_or_insert_with(key, || registry.deserialize_value(bytes))
+    }
+}
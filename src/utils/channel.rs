@@ -0,0 +1,202 @@
+// runar_common/src/utils/channel.rs
+//
+// Bounded async channels with consistent backpressure semantics.
+//
+// `send` waits for capacity, `try_send` drops the message when the queue is
+// full. Both record queue depth and drop counts, and the first time the
+// queue saturates a warning is logged. The warning is re-armed once the
+// queue drains below half its capacity.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+use crate::logging::Logger;
+
+/// Point-in-time statistics of a bounded channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelStats {
+    /// Messages currently queued
+    pub depth: usize,
+    /// Highest depth observed
+    pub max_depth: usize,
+    /// Configured capacity
+    pub capacity: usize,
+    /// Messages accepted into the queue
+    pub sent: u64,
+    /// Messages dropped because the queue was full
+    pub dropped: u64,
+}
+
+struct Shared {
+    name: String,
+    capacity: usize,
+    logger: Arc<Logger>,
+    max_depth: AtomicUsize,
+    sent: AtomicU64,
+    dropped: AtomicU64,
+    saturated: AtomicBool,
+}
+
+impl Shared {
+    fn record_sent(&self, depth: usize) {
+        self.sent.fetch_add(1, Ordering::Relaxed);
+        self.max_depth.fetch_max(depth, Ordering::Relaxed);
+        if depth >= self.capacity {
+            self.warn_saturated();
+        }
+    }
+
+    fn record_dropped(&self) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+        self.warn_saturated();
+    }
+
+    fn record_received(&self, depth: usize) {
+        if depth <= self.capacity / 2 {
+            self.saturated.store(false, Ordering::Relaxed);
+        }
+    }
+
+    fn warn_saturated(&self) {
+        if !self.saturated.swap(true, Ordering::Relaxed) {
+            self.logger.warn(format!(
+                "Channel '{}' is saturated (capacity {}, dropped so far {})",
+                self.name,
+                self.capacity,
+                self.dropped.load(Ordering::Relaxed)
+            ));
+        }
+    }
+}
+
+/// Create a bounded channel named `name` (used in log messages)
+pub fn bounded<T>(
+    name: impl Into<String>,
+    capacity: usize,
+    logger: Arc<Logger>,
+) -> (BoundedSender<T>, BoundedReceiver<T>) {
+    let (tx, rx) = mpsc::channel(capacity);
+    let shared = Arc::new(Shared {
+        name: name.into(),
+        capacity,
+        logger,
+        max_depth: AtomicUsize::new(0),
+        sent: AtomicU64::new(0),
+        dropped: AtomicU64::new(0),
+        saturated: AtomicBool::new(false),
+    });
+    (
+        BoundedSender {
+            inner: tx,
+            shared: shared.clone(),
+        },
+        BoundedReceiver { inner: rx, shared },
+    )
+}
+
+/// Sending half of a bounded channel
+pub struct BoundedSender<T> {
+    inner: mpsc::Sender<T>,
+    shared: Arc<Shared>,
+}
+
+impl<T> Clone for BoundedSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> BoundedSender<T> {
+    /// Send a message, waiting for capacity if the queue is full
+    pub async fn send(&self, value: T) -> Result<()> {
+        if self.inner.capacity() == 0 {
+            self.shared.warn_saturated();
+        }
+        self.inner
+            .send(value)
+            .await
+            .map_err(|_| anyhow!("Channel '{}' is closed", self.shared.name))?;
+        self.shared.record_sent(self.depth());
+        Ok(())
+    }
+
+    /// Send a message without waiting; returns Ok(false) if it was dropped because the queue is full
+    pub fn try_send(&self, value: T) -> Result<bool> {
+        match self.inner.try_send(value) {
+            Ok(()) => {
+                self.shared.record_sent(self.depth());
+                Ok(true)
+            }
+            Err(TrySendError::Full(_)) => {
+                self.shared.record_dropped();
+                Ok(false)
+            }
+            Err(TrySendError::Closed(_)) => {
+                Err(anyhow!("Channel '{}' is closed", self.shared.name))
+            }
+        }
+    }
+
+    /// Get the number of queued messages
+    pub fn depth(&self) -> usize {
+        self.inner.max_capacity() - self.inner.capacity()
+    }
+
+    /// Check whether the receiver has been dropped
+    pub fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+
+    /// Get the channel statistics
+    pub fn stats(&self) -> ChannelStats {
+        stats(&self.shared, self.depth())
+    }
+}
+
+/// Receiving half of a bounded channel
+pub struct BoundedReceiver<T> {
+    inner: mpsc::Receiver<T>,
+    shared: Arc<Shared>,
+}
+
+impl<T> BoundedReceiver<T> {
+    /// Receive the next message; None once all senders are dropped and the queue is empty
+    pub async fn recv(&mut self) -> Option<T> {
+        let value = self.inner.recv().await;
+        self.shared.record_received(self.inner.len());
+        value
+    }
+
+    /// Receive a message if one is queued
+    pub fn try_recv(&mut self) -> Option<T> {
+        let value = self.inner.try_recv().ok();
+        self.shared.record_received(self.inner.len());
+        value
+    }
+
+    /// Get the number of queued messages
+    pub fn depth(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Get the channel statistics
+    pub fn stats(&self) -> ChannelStats {
+        stats(&self.shared, self.depth())
+    }
+}
+
+fn stats(shared: &Shared, depth: usize) -> ChannelStats {
+    ChannelStats {
+        depth,
+        max_depth: shared.max_depth.load(Ordering::Relaxed),
+        capacity: shared.capacity,
+        sent: shared.sent.load(Ordering::Relaxed),
+        dropped: shared.dropped.load(Ordering::Relaxed),
+    }
+}
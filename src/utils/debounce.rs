@@ -0,0 +1,131 @@
+// runar_common/src/utils/debounce.rs
+//
+// Per-key debouncing of bursty updates (e.g. metadata change events).
+//
+// The first update for a key opens a window; later updates within the window
+// replace the pending value. When the window closes the latest value is
+// emitted once, so subscribers see at most one value per key per window.
+// The debouncer is driven by the caller: `drain_due` returns the values whose
+// window has closed and `next_deadline` says when to call it again.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::Duration;
+
+use crate::utils::time::Instant;
+
+struct Pending<V> {
+    value: V,
+    due: Instant,
+}
+
+/// Coalesces updates per key and emits at most one value per key per window
+pub struct Debouncer<K, V> {
+    window: Duration,
+    pending: HashMap<K, Pending<V>>,
+    coalesced: u64,
+}
+
+impl<K: Eq + Hash + Clone, V> Debouncer<K, V> {
+    /// Create a debouncer emitting each key at most once per `window`
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: HashMap::new(),
+            coalesced: 0,
+        }
+    }
+
+    /// The debounce window
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Record an update for `key`
+    pub fn push(&mut self, key: K, value: V) {
+        self.push_at(key, value, Instant::now());
+    }
+
+    /// Record an update for `key` as of `now`. Returns true if the update
+    /// replaced a pending value instead of opening a new window.
+    pub fn push_at(&mut self, key: K, value: V, now: Instant) -> bool {
+        match self.pending.get_mut(&key) {
+            Some(pending) => {
+                pending.value = value;
+                self.coalesced += 1;
+                true
+            }
+            None => {
+                let due = now + self.window;
+                self.pending.insert(key, Pending { value, due });
+                false
+            }
+        }
+    }
+
+    /// Take the values whose window has closed
+    pub fn drain_due(&mut self) -> Vec<(K, V)> {
+        self.drain_due_at(Instant::now())
+    }
+
+    /// Take the values whose window has closed as of `now`, oldest first
+    pub fn drain_due_at(&mut self, now: Instant) -> Vec<(K, V)> {
+        let due: Vec<K> = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| pending.due <= now)
+            .map(|(key, _)| key.clone())
+            .collect();
+        let mut emitted: Vec<_> = due
+            .into_iter()
+            .filter_map(|key| {
+                let pending = self.pending.remove(&key)?;
+                Some((pending.due, key, pending.value))
+            })
+            .collect();
+        emitted.sort_by_key(|(due, _, _)| *due);
+        emitted
+            .into_iter()
+            .map(|(_, key, value)| (key, value))
+            .collect()
+    }
+
+    /// Take every pending value regardless of its window (e.g. on shutdown)
+    pub fn flush(&mut self) -> Vec<(K, V)> {
+        let mut emitted: Vec<_> = self
+            .pending
+            .drain()
+            .map(|(key, pending)| (pending.due, key, pending.value))
+            .collect();
+        emitted.sort_by_key(|(due, _, _)| *due);
+        emitted
+            .into_iter()
+            .map(|(_, key, value)| (key, value))
+            .collect()
+    }
+
+    /// When the earliest pending window closes (`None` if nothing is pending)
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.pending.values().map(|pending| pending.due).min()
+    }
+
+    /// Number of keys with a pending value
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Number of updates merged into an already pending value
+    pub fn coalesced(&self) -> u64 {
+        self.coalesced
+    }
+}
+
+impl<K, V> std::fmt::Debug for Debouncer<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Debouncer")
+            .field("window", &self.window)
+            .field("pending", &self.pending.len())
+            .field("coalesced", &self.coalesced)
+            .finish()
+    }
+}
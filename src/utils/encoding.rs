@@ -0,0 +1,73 @@
+// runar_common/src/utils/encoding.rs
+//
+// Text encodings for binary data (keys, hashes, signatures).
+//
+// All crates should go through these helpers so that the same bytes always
+// produce the same string representation across the stack.
+
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+
+use crate::types::ArcValueType;
+
+pub use multibase::Base;
+
+/// Encode bytes as standard (padded) base64
+pub fn to_base64(bytes: &[u8]) -> String {
+    STANDARD.encode(bytes)
+}
+
+/// Decode standard (padded) base64
+pub fn from_base64(encoded: &str) -> Result<Vec<u8>> {
+    STANDARD
+        .decode(encoded)
+        .map_err(|e| anyhow!("Invalid base64: {}", e))
+}
+
+/// Encode bytes as URL-safe base64 without padding
+pub fn to_base64_url(bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Decode URL-safe base64 without padding
+pub fn from_base64_url(encoded: &str) -> Result<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(encoded)
+        .map_err(|e| anyhow!("Invalid base64url: {}", e))
+}
+
+/// Encode bytes as lowercase hex
+pub fn to_hex(bytes: &[u8]) -> String {
+    hex::encode(bytes)
+}
+
+/// Decode hex (either case)
+pub fn from_hex(encoded: &str) -> Result<Vec<u8>> {
+    hex::decode(encoded).map_err(|e| anyhow!("Invalid hex: {}", e))
+}
+
+/// Encode bytes as a multibase string (the first character identifies the base)
+pub fn to_multibase(base: Base, bytes: &[u8]) -> String {
+    multibase::encode(base, bytes)
+}
+
+/// Decode a multibase string, returning the base it was encoded with
+pub fn from_multibase(encoded: &str) -> Result<(Base, Vec<u8>)> {
+    multibase::decode(encoded).map_err(|e| anyhow!("Invalid multibase: {}", e))
+}
+
+/// Decode standard base64 into a Bytes-category value
+pub fn bytes_value_from_base64(encoded: &str) -> Result<ArcValueType> {
+    from_base64(encoded).map(ArcValueType::new_bytes)
+}
+
+/// Decode hex into a Bytes-category value
+pub fn bytes_value_from_hex(encoded: &str) -> Result<ArcValueType> {
+    from_hex(encoded).map(ArcValueType::new_bytes)
+}
+
+/// Decode a multibase string into a Bytes-category value
+pub fn bytes_value_from_multibase(encoded: &str) -> Result<ArcValueType> {
+    from_multibase(encoded).map(|(_, bytes)| ArcValueType::new_bytes(bytes))
+}
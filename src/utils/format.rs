@@ -0,0 +1,113 @@
+// runar_common/src/utils/format.rs
+//
+// Locale-independent, round-trippable text for numbers, timestamps and byte
+// sizes, used in log lines and in schema `example`/`default` strings.
+//
+// Numbers use '.' as the decimal separator and no digit grouping, timestamps
+// are RFC 3339 in UTC with milliseconds, and sizes use the largest binary unit
+// that divides them exactly. Each format has a parser that reads it back to
+// the same value, so services never have to guess whether "3,14" or a
+// local-time stamp was meant.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use super::num::{Number, Numeric};
+use super::time::{self, SystemTime};
+use crate::types::ArcValueType;
+
+/// Format a number so that parsing it back gives the same value: integers as
+/// plain digits, floats in their shortest exact form ("0.1", "1e300", "NaN")
+pub fn format_number<T: Numeric>(value: T) -> String {
+    match value.to_number() {
+        Number::Int(i) => i.to_string(),
+        Number::UInt(u) => u.to_string(),
+        // Whole floats print without the ".0" that `{:?}` would add; -0.0
+        // keeps it so its sign survives the round trip
+        Number::Float(f) if f.fract() == 0.0 && f.abs() < 1e15 && !is_negative_zero(f) => {
+            format!("{}", f as i64)
+        }
+        Number::Float(f) => format!("{:?}", f),
+    }
+}
+
+fn is_negative_zero(f: f64) -> bool {
+    f == 0.0 && f.is_sign_negative()
+}
+
+/// Parse a number written by `format_number`. Commas are rejected rather than
+/// read as decimal or grouping separators.
+pub fn parse_number(input: &str) -> Result<f64> {
+    let trimmed = input.trim();
+    if trimmed.contains(',') {
+        return Err(anyhow!(
+            "Invalid number '{}': use '.' as the decimal separator and no grouping",
+            input
+        ));
+    }
+    trimmed
+        .parse()
+        .map_err(|_| anyhow!("Invalid number '{}'", input))
+}
+
+/// Format a time as RFC 3339 in UTC with milliseconds
+/// ("2024-03-01T12:00:00.250Z"). Times past the latest representable date
+/// (year 262142) are clamped to it.
+pub fn format_timestamp(at: SystemTime) -> String {
+    let latest = DateTime::<Utc>::MAX_UTC.timestamp_millis() as u64;
+    to_rfc3339(time::to_epoch_millis(at).min(latest)).unwrap_or_default()
+}
+
+/// Format milliseconds since the UNIX epoch like `format_timestamp`, failing
+/// for values past the latest representable date
+pub fn format_timestamp_millis(millis: u64) -> Result<String> {
+    to_rfc3339(millis).ok_or_else(|| anyhow!("Timestamp {}ms is out of range", millis))
+}
+
+fn to_rfc3339(millis: u64) -> Option<String> {
+    let millis = i64::try_from(millis).ok()?;
+    DateTime::<Utc>::from_timestamp_millis(millis)
+        .map(|at| at.to_rfc3339_opts(SecondsFormat::Millis, true))
+}
+
+/// Parse an RFC 3339 timestamp with any UTC offset. Times before the UNIX
+/// epoch are rejected.
+pub fn parse_timestamp(input: &str) -> Result<SystemTime> {
+    let parsed = DateTime::parse_from_rfc3339(input.trim())
+        .map_err(|e| anyhow!("Invalid timestamp '{}': {}", input, e))?;
+    let millis = u64::try_from(parsed.timestamp_millis())
+        .map_err(|_| anyhow!("Invalid timestamp '{}': before the UNIX epoch", input))?;
+    Ok(time::from_epoch_millis(millis))
+}
+
+/// Format a byte size with the largest binary unit that divides it exactly
+/// ("1536MiB", "4KiB", "1000"). Unlike `size::format_size`, nothing is
+/// rounded, so `size::parse_size` reads back the same number of bytes.
+pub fn format_size_exact(bytes: u64) -> String {
+    const UNITS: &[(&str, u64)] = &[
+        ("TiB", 1 << 40),
+        ("GiB", 1 << 30),
+        ("MiB", 1 << 20),
+        ("KiB", 1 << 10),
+    ];
+    UNITS
+        .iter()
+        .find(|(_, unit)| bytes > 0 && bytes.is_multiple_of(*unit))
+        .map(|(name, unit)| format!("{}{}", bytes / unit, name))
+        .unwrap_or_else(|| bytes.to_string())
+}
+
+/// Format a scalar value for a log line or schema string: numbers with
+/// `format_number`, strings as-is, and anything else as compact JSON
+pub fn format_value(value: &ArcValueType) -> Result<String> {
+    if let Some(number) = Number::from_value(value) {
+        return Ok(match number {
+            Number::Float(f) => format_number(f),
+            other => other.to_string(),
+        });
+    }
+    match value.to_json()? {
+        serde_json::Value::String(s) => Ok(s),
+        other => Ok(other.to_string()),
+    }
+}
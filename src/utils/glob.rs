@@ -0,0 +1,291 @@
+// runar_common/src/utils/glob.rs
+//
+// Compiled glob patterns shared by log component filters, topic segment
+// matching and capability queries.
+//
+// Syntax:
+//   *        any run of characters, including none
+//   ?        exactly one character
+//   [abc]    one of the listed characters; ranges like [a-z] are allowed
+//   [!a-z]   one character not in the class ([^a-z] works too)
+//   \x       the character x taken literally
+//
+// Patterns without wildcards compare by equality, and patterns whose only
+// wildcard is a trailing `*` by prefix, so the common cases never run the
+// general matcher.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// Characters with a special meaning in a glob
+pub const GLOB_META: &[char] = &['*', '?', '[', '\\'];
+
+/// Check whether `text` contains glob wildcards
+pub fn has_wildcards(text: &str) -> bool {
+    text.contains(GLOB_META)
+}
+
+/// Compile `text` if it is a glob, or return None for literal text. Text
+/// with wildcards that does not parse (e.g. "a[") counts as literal.
+pub fn compile_if_pattern(text: &str) -> Option<Glob> {
+    has_wildcards(text).then(|| Glob::new(text).ok()).flatten()
+}
+
+/// A compiled glob pattern
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Glob {
+    source: String,
+    matcher: Matcher,
+}
+
+#[derive(Clone)]
+enum Matcher {
+    Literal(String),
+    Prefix(String),
+    Tokens(Vec<Token>),
+}
+
+#[derive(Clone)]
+enum Token {
+    Char(char),
+    One,
+    Any,
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+impl Token {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Token::Char(expected) => *expected == c,
+            Token::One => true,
+            Token::Any => false,
+            Token::Class { negated, ranges } => {
+                ranges.iter().any(|(low, high)| (*low..=*high).contains(&c)) != *negated
+            }
+        }
+    }
+}
+
+impl Glob {
+    /// Compile a pattern, failing on an unterminated class or escape
+    pub fn new(pattern: &str) -> Result<Self> {
+        let tokens = parse(pattern)?;
+        let literal_len = tokens
+            .iter()
+            .take_while(|token| matches!(token, Token::Char(_)))
+            .count();
+        let literal = || {
+            tokens[..literal_len]
+                .iter()
+                .map(|token| match token {
+                    Token::Char(c) => *c,
+                    _ => unreachable!(),
+                })
+                .collect::<String>()
+        };
+        let matcher = if literal_len == tokens.len() {
+            Matcher::Literal(literal())
+        } else if literal_len == tokens.len() - 1 && matches!(tokens[literal_len], Token::Any) {
+            Matcher::Prefix(literal())
+        } else {
+            Matcher::Tokens(tokens)
+        };
+        Ok(Glob {
+            source: pattern.to_string(),
+            matcher,
+        })
+    }
+
+    /// A pattern matching exactly `text`, wildcards included
+    pub fn literal(text: &str) -> Self {
+        Glob {
+            source: escape(text),
+            matcher: Matcher::Literal(text.to_string()),
+        }
+    }
+
+    /// The pattern as written
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Check whether the pattern has no wildcards
+    pub fn is_literal(&self) -> bool {
+        matches!(self.matcher, Matcher::Literal(_))
+    }
+
+    /// The text every match starts with
+    pub fn literal_prefix(&self) -> String {
+        match &self.matcher {
+            Matcher::Literal(text) | Matcher::Prefix(text) => text.clone(),
+            Matcher::Tokens(tokens) => tokens
+                .iter()
+                .map_while(|token| match token {
+                    Token::Char(c) => Some(*c),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Check whether the whole of `text` matches the pattern
+    pub fn matches(&self, text: &str) -> bool {
+        match &self.matcher {
+            Matcher::Literal(literal) => text == literal,
+            Matcher::Prefix(prefix) => text.starts_with(prefix.as_str()),
+            Matcher::Tokens(tokens) => {
+                let chars: Vec<char> = text.chars().collect();
+                match_tokens(tokens, &chars)
+            }
+        }
+    }
+}
+
+// Iterative matcher that backtracks to the most recent `*` on a mismatch
+fn match_tokens(tokens: &[Token], text: &[char]) -> bool {
+    let (mut t, mut c) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while c < text.len() {
+        match tokens.get(t) {
+            Some(Token::Any) => {
+                backtrack = Some((t, c));
+                t += 1;
+            }
+            Some(token) if token.matches(text[c]) => {
+                t += 1;
+                c += 1;
+            }
+            _ => match backtrack {
+                Some((star, consumed)) => {
+                    t = star + 1;
+                    c = consumed + 1;
+                    backtrack = Some((star, consumed + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    tokens[t..].iter().all(|token| matches!(token, Token::Any))
+}
+
+fn parse(pattern: &str) -> Result<Vec<Token>> {
+    let invalid = |reason: &str| anyhow!("Invalid glob '{}': {}", pattern, reason);
+    let mut tokens = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '*' => {
+                // Consecutive stars match the same as one
+                if matches!(tokens.last(), Some(Token::Any)) {
+                    continue;
+                }
+                Token::Any
+            }
+            '?' => Token::One,
+            '\\' => Token::Char(chars.next().ok_or_else(|| invalid("trailing '\\'"))?),
+            '[' => {
+                let mut negated = false;
+                let mut ranges = Vec::new();
+                let mut first = true;
+                loop {
+                    let c = chars.next().ok_or_else(|| invalid("unterminated '['"))?;
+                    match c {
+                        '!' | '^' if first => negated = true,
+                        ']' if !ranges.is_empty() => break,
+                        _ => {
+                            let low = if c == '\\' {
+                                chars.next().ok_or_else(|| invalid("trailing '\\'"))?
+                            } else {
+                                c
+                            };
+                            let mut lookahead = chars.clone();
+                            let high = match (lookahead.next(), lookahead.next()) {
+                                (Some('-'), Some(high)) if high != ']' => {
+                                    chars = lookahead;
+                                    high
+                                }
+                                _ => low,
+                            };
+                            if high < low {
+                                return Err(invalid("class range is reversed"));
+                            }
+                            ranges.push((low, high));
+                        }
+                    }
+                    first = false;
+                }
+                Token::Class { negated, ranges }
+            }
+            other => Token::Char(other),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Escape `text` so it matches itself as a glob
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if GLOB_META.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+impl fmt::Debug for Glob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Glob").field(&self.source).finish()
+    }
+}
+
+impl fmt::Display for Glob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl PartialEq for Glob {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for Glob {}
+
+impl std::hash::Hash for Glob {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.source.hash(state);
+    }
+}
+
+impl FromStr for Glob {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for Glob {
+    type Error = anyhow::Error;
+
+    fn try_from(pattern: String) -> Result<Self> {
+        Self::new(&pattern)
+    }
+}
+
+impl From<Glob> for String {
+    fn from(glob: Glob) -> Self {
+        glob.source
+    }
+}
@@ -0,0 +1,129 @@
+// runar_common/src/utils/integrity.rs
+//
+// Checksums and hashes for detecting truncated or corrupted payloads.
+//
+// CRC32C is cheap and hardware-accelerated on most CPUs, which makes it the
+// right choice for per-message checks. BLAKE3 is a cryptographic hash for
+// content addressing and for cases where tampering matters.
+
+use anyhow::Result;
+
+use crate::errors::{ErrorCode, RunarError};
+use crate::utils::encoding::to_hex;
+
+/// Compute the CRC32C (Castagnoli) checksum of data
+pub fn crc32c(data: &[u8]) -> u32 {
+    crc32c::crc32c(data)
+}
+
+/// Check data against an expected CRC32C checksum
+pub fn verify_crc32c(data: &[u8], expected: u32) -> bool {
+    crc32c(data) == expected
+}
+
+/// Compute the BLAKE3 hash of data
+pub fn blake3_hash(data: &[u8]) -> [u8; 32] {
+    *blake3::hash(data).as_bytes()
+}
+
+/// Compute the BLAKE3 hash of data as lowercase hex
+pub fn blake3_hex(data: &[u8]) -> String {
+    to_hex(&blake3_hash(data))
+}
+
+/// Check data against an expected BLAKE3 hash (constant-time comparison)
+pub fn verify_blake3(data: &[u8], expected: &[u8; 32]) -> bool {
+    blake3::hash(data) == blake3::Hash::from(*expected)
+}
+
+/// Checksum appended to the end of a payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    /// 4-byte little-endian CRC32C
+    Crc32c,
+    /// 32-byte BLAKE3 hash
+    Blake3,
+}
+
+impl ChecksumAlgorithm {
+    /// Get the size of the checksum in bytes
+    pub fn size(&self) -> usize {
+        match self {
+            ChecksumAlgorithm::Crc32c => 4,
+            ChecksumAlgorithm::Blake3 => 32,
+        }
+    }
+
+    /// Compute the checksum bytes of data
+    pub fn compute(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            ChecksumAlgorithm::Crc32c => crc32c(data).to_le_bytes().to_vec(),
+            ChecksumAlgorithm::Blake3 => blake3_hash(data).to_vec(),
+        }
+    }
+
+    /// Append the checksum of `data` to itself
+    pub fn append_to(&self, data: &mut Vec<u8>) {
+        let checksum = self.compute(data);
+        data.extend_from_slice(&checksum);
+    }
+
+    /// Verify a trailing checksum and return the data without it.
+    /// Fails with a `Serialization` `RunarError` on truncation or mismatch.
+    pub fn verify_trailing<'a>(&self, data: &'a [u8]) -> Result<&'a [u8]> {
+        self.verify_trailing_chunked(data, usize::MAX, |_| Ok(()))
+    }
+
+    /// Like `verify_trailing`, hashing `chunk_size` bytes at a time and
+    /// calling `between` with each chunk's length before it is hashed, so
+    /// long payloads can be abandoned part way
+    pub fn verify_trailing_chunked<'a>(
+        &self,
+        data: &'a [u8],
+        chunk_size: usize,
+        mut between: impl FnMut(usize) -> Result<()>,
+    ) -> Result<&'a [u8]> {
+        if data.len() < self.size() {
+            return Err(RunarError::new(
+                ErrorCode::Serialization,
+                format!(
+                    "Payload truncated: {} bytes is shorter than the {:?} checksum",
+                    data.len(),
+                    self
+                ),
+            )
+            .into());
+        }
+        let (payload, checksum) = data.split_at(data.len() - self.size());
+        let chunks = payload.chunks(chunk_size.max(1));
+        let computed = match self {
+            ChecksumAlgorithm::Crc32c => {
+                let mut crc = 0;
+                for chunk in chunks {
+                    between(chunk.len())?;
+                    crc = crc32c::crc32c_append(crc, chunk);
+                }
+                crc.to_le_bytes().to_vec()
+            }
+            ChecksumAlgorithm::Blake3 => {
+                let mut hasher = blake3::Hasher::new();
+                for chunk in chunks {
+                    between(chunk.len())?;
+                    hasher.update(chunk);
+                }
+                hasher.finalize().as_bytes().to_vec()
+            }
+        };
+        if computed != checksum {
+            return Err(RunarError::new(
+                ErrorCode::Serialization,
+                format!(
+                    "Payload {:?} checksum mismatch (corrupted or truncated)",
+                    self
+                ),
+            )
+            .into());
+        }
+        Ok(payload)
+    }
+}
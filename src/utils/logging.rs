@@ -74,6 +74,7 @@ pub fn debug_log_with_data<T: std::fmt::Debug>(component: Component, message: &s
 }
 
 // Initialize logging with component filters
+#[cfg(not(target_arch = "wasm32"))]
 pub fn init_logging() {
     // Set up env_logger with custom format
     let env = env_logger::Env::default()
@@ -87,7 +88,7 @@ pub fn init_logging() {
             writeln!(
                 buf,
                 "{} {} [{}] {}",
-                chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+                super::format::format_timestamp(super::time::SystemTime::now()),
                 level_style.value(record.level()),
                 record.target(),
                 record.args()
@@ -101,7 +102,14 @@ pub fn get_test_filter() -> String {
     std::env::var("RUNAR_TEST_LOG").unwrap_or_else(|_| "debug".to_string())
 }
 
+// Initialize logging in the browser: debug level, written to the console
+#[cfg(target_arch = "wasm32")]
+pub fn init_logging() {
+    let _ = crate::logging::console::init(log::LevelFilter::Debug);
+}
+
 // Configure logging for tests
+#[cfg(not(target_arch = "wasm32"))]
 pub fn configure_test_logging() {
     let filter = get_test_filter();
     let env = env_logger::Env::default().filter_or("RUST_LOG", filter);
@@ -113,7 +121,7 @@ pub fn configure_test_logging() {
             writeln!(
                 buf,
                 "{} {} [{}] {}",
-                chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+                super::format::format_timestamp(super::time::SystemTime::now()),
                 level_style.value(record.level()),
                 record.target(),
                 record.args()
@@ -5,9 +5,68 @@
 // Value converters and extractors
 pub mod value_converters;
 
+// LRU cache for decoded values
+pub mod cache;
+
+// Bounded channels with backpressure tracking
+pub mod channel;
+
+// Per-key debouncing of bursty updates
+pub mod debounce;
+
+// Base64, hex and multibase encoding
+pub mod encoding;
+
+// Checksums and hashes for payload integrity
+pub mod integrity;
+
+// Locale-independent formatting of numbers, timestamps and sizes
+pub mod format;
+
+// Compiled glob patterns for filters, topics and capability queries
+pub mod glob;
+
 // Logging utilities
 pub mod logging;
 
+// Checked and saturating numeric conversions
+pub mod num;
+
+// Service, action and topic path handling
+pub mod paths;
+
+// Rate limiting
+pub mod rate_limit;
+
+// Retry with backoff
+pub mod retry;
+
+// Action path routing with parameters
+pub mod router;
+
+// Periodic background tasks (native only)
+#[cfg(not(target_arch = "wasm32"))]
+pub mod scheduler;
+
+// Byte size parsing and formatting
+pub mod size;
+
+// Topic pattern subscription table
+pub mod subscriptions;
+
+// ArcValueType persistence in key-value stores
+pub mod storage;
+
+// Background tasks with logging and duration metrics (native only)
+#[cfg(not(target_arch = "wasm32"))]
+pub mod task;
+
+// Epoch timestamps and duration formatting
+pub mod time;
+
+// Observable value cells
+pub mod watch;
+
 // Re-export everything from submodules
 pub use logging::*;
 pub use value_converters::*;
@@ -0,0 +1,316 @@
+// runar_common/src/utils/num.rs
+//
+// Panic-free conversions between the numeric primitives an ArcValueType can
+// hold.
+//
+// `checked` fails with an `InvalidInput` `RunarError` when the value does not
+// fit the target type (out of range, a fraction for an integer target, or
+// NaN/infinity for an integer target); `saturating` clamps to the nearest
+// representable value instead. Floats are compared exactly, so
+// `checked::<f64, i64>(9.3e18)` fails rather than wrapping.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use anyhow::Result;
+
+use crate::errors::{ErrorCode, RunarError};
+use crate::types::ArcValueType;
+
+// 2^127 and 2^128, the bounds of i128 and u128 as exact floats
+const TWO_POW_127: f64 = 170_141_183_460_469_231_731_687_303_715_884_105_728.0;
+const TWO_POW_128: f64 = 340_282_366_920_938_463_463_374_607_431_768_211_456.0;
+
+/// A numeric value of any supported primitive type
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    /// A signed integer
+    Int(i128),
+    /// A `u128`, whose range `Int` cannot hold
+    UInt(u128),
+    /// A floating point number
+    Float(f64),
+}
+
+impl Number {
+    /// Read a numeric primitive held by `value`, if it holds one
+    pub fn from_value(value: &ArcValueType) -> Option<Number> {
+        let type_name = value.stored_type_name().ok()?;
+        let mut value = value.clone();
+        macro_rules! try_types {
+            ($($t:ty),*) => {
+                $(
+                    if type_name == std::any::type_name::<$t>() {
+                        return value.as_type_ref::<$t>().ok().map(|n| n.to_number());
+                    }
+                )*
+            };
+        }
+        try_types!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64);
+        None
+    }
+
+    /// Convert a JSON number without going through `f64` for integers
+    pub fn from_json(number: &serde_json::Number) -> Number {
+        if let Some(i) = number.as_i64() {
+            Number::Int(i as i128)
+        } else if let Some(u) = number.as_u64() {
+            Number::Int(u as i128)
+        } else {
+            Number::Float(number.as_f64().unwrap_or(f64::NAN))
+        }
+    }
+
+    /// The value as the nearest `f64`
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Number::Int(i) => i as f64,
+            Number::UInt(u) => u as f64,
+            Number::Float(f) => f,
+        }
+    }
+
+    /// Convert to `U`, failing if the value does not fit
+    pub fn to_checked<U: Numeric>(self) -> Result<U> {
+        U::from_number_checked(self).ok_or_else(|| out_of_range(self, U::NAME))
+    }
+
+    /// Convert to `U`, clamping to its range. NaN becomes zero for integer
+    /// targets.
+    pub fn to_saturating<U: Numeric>(self) -> U {
+        U::from_number_saturating(self)
+    }
+
+    /// Compare exactly with a float bound; `None` if either side is NaN
+    pub fn partial_cmp_f64(&self, bound: f64) -> Option<Ordering> {
+        if bound.is_nan() {
+            return None;
+        }
+        match *self {
+            Number::Float(f) => f.partial_cmp(&bound),
+            Number::Int(i) => Some(if bound >= TWO_POW_127 {
+                Ordering::Less
+            } else if bound < -TWO_POW_127 {
+                Ordering::Greater
+            } else {
+                let whole = bound.trunc();
+                i.cmp(&(whole as i128)).then(fraction_order(bound, whole))
+            }),
+            Number::UInt(u) => Some(if bound >= TWO_POW_128 {
+                Ordering::Less
+            } else if bound < 0.0 {
+                Ordering::Greater
+            } else {
+                let whole = bound.trunc();
+                u.cmp(&(whole as u128)).then(fraction_order(bound, whole))
+            }),
+        }
+    }
+
+    /// Check that the value lies within optional bounds; exclusive bounds
+    /// reject the bound itself
+    pub fn in_range(
+        &self,
+        minimum: Option<f64>,
+        exclusive_minimum: bool,
+        maximum: Option<f64>,
+        exclusive_maximum: bool,
+    ) -> bool {
+        let above = minimum.is_none_or(|min| match self.partial_cmp_f64(min) {
+            Some(Ordering::Greater) => true,
+            Some(Ordering::Equal) => !exclusive_minimum,
+            _ => false,
+        });
+        let below = maximum.is_none_or(|max| match self.partial_cmp_f64(max) {
+            Some(Ordering::Less) => true,
+            Some(Ordering::Equal) => !exclusive_maximum,
+            _ => false,
+        });
+        above && below
+    }
+}
+
+// How an integer equal to `whole` compares with `bound` once the fraction
+// that truncation dropped is taken into account
+fn fraction_order(bound: f64, whole: f64) -> Ordering {
+    if bound > whole {
+        Ordering::Less
+    } else if bound < whole {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Int(i) => write!(f, "{}", i),
+            Number::UInt(u) => write!(f, "{}", u),
+            Number::Float(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+/// Numeric primitives that can be converted into one another
+pub trait Numeric: Copy + Sized {
+    /// Type name used in error messages
+    const NAME: &'static str;
+
+    /// Whether the type holds only whole numbers
+    const INTEGER: bool;
+
+    /// Widen into a `Number`
+    fn to_number(self) -> Number;
+
+    /// Narrow from a `Number`, `None` if it does not fit
+    fn from_number_checked(number: Number) -> Option<Self>;
+
+    /// Narrow from a `Number`, clamping to the type's range
+    fn from_number_saturating(number: Number) -> Self;
+
+    /// The neighbouring value above (or below) this one; integers saturate
+    /// at the type's limits
+    fn step(self, up: bool) -> Self;
+}
+
+macro_rules! impl_numeric_int {
+    ($($t:ty => $wide:ident),*) => {
+        $(
+            impl Numeric for $t {
+                const NAME: &'static str = stringify!($t);
+                const INTEGER: bool = true;
+
+                fn to_number(self) -> Number {
+                    Number::$wide(self as _)
+                }
+
+                fn from_number_checked(number: Number) -> Option<Self> {
+                    match number {
+                        Number::Int(i) => <$t>::try_from(i).ok(),
+                        Number::UInt(u) => <$t>::try_from(u).ok(),
+                        Number::Float(f) => {
+                            if !f.is_finite() || f.fract() != 0.0 {
+                                None
+                            } else if f >= 0.0 {
+                                (f < TWO_POW_128)
+                                    .then(|| <$t>::try_from(f as u128).ok())
+                                    .flatten()
+                            } else {
+                                (f >= -TWO_POW_127)
+                                    .then(|| <$t>::try_from(f as i128).ok())
+                                    .flatten()
+                            }
+                        }
+                    }
+                }
+
+                fn from_number_saturating(number: Number) -> Self {
+                    match number {
+                        Number::Int(i) => <$t>::try_from(i)
+                            .unwrap_or(if i < 0 { <$t>::MIN } else { <$t>::MAX }),
+                        Number::UInt(u) => <$t>::try_from(u).unwrap_or(<$t>::MAX),
+                        // `as` truncates the fraction, clamps and maps NaN to 0
+                        Number::Float(f) => f as $t,
+                    }
+                }
+
+                fn step(self, up: bool) -> Self {
+                    if up {
+                        self.saturating_add(1)
+                    } else {
+                        self.saturating_sub(1)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_int!(
+    i8 => Int, i16 => Int, i32 => Int, i64 => Int, i128 => Int,
+    u8 => Int, u16 => Int, u32 => Int, u64 => Int, u128 => UInt
+);
+
+macro_rules! impl_numeric_float {
+    ($($t:ty),*) => {
+        $(
+            impl Numeric for $t {
+                const NAME: &'static str = stringify!($t);
+                const INTEGER: bool = false;
+
+                fn to_number(self) -> Number {
+                    Number::Float(self as f64)
+                }
+
+                fn from_number_checked(number: Number) -> Option<Self> {
+                    // NaN and infinities carry over; finite values must stay finite
+                    let converted = number.as_f64() as $t;
+                    (converted.is_finite() || !number.as_f64().is_finite()).then_some(converted)
+                }
+
+                fn from_number_saturating(number: Number) -> Self {
+                    match number {
+                        Number::Float(f) if f.is_nan() => <$t>::NAN,
+                        other => {
+                            let converted = other.as_f64() as $t;
+                            if converted.is_infinite() && other.as_f64().is_finite() {
+                                converted.signum() * <$t>::MAX
+                            } else {
+                                converted
+                            }
+                        }
+                    }
+                }
+
+                fn step(self, up: bool) -> Self {
+                    if up {
+                        self.next_up()
+                    } else {
+                        self.next_down()
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_float!(f32, f64);
+
+/// Convert between numeric primitives, failing with an `InvalidInput`
+/// `RunarError` if the value does not fit the target type
+pub fn checked<T: Numeric, U: Numeric>(value: T) -> Result<U> {
+    value.to_number().to_checked()
+}
+
+/// Convert between numeric primitives, clamping to the target's range
+pub fn saturating<T: Numeric, U: Numeric>(value: T) -> U {
+    value.to_number().to_saturating()
+}
+
+/// Coerce whatever numeric primitive `value` holds into `U`, failing if it
+/// holds no number or the number does not fit
+pub fn coerce<U: Numeric>(value: &ArcValueType) -> Result<U> {
+    let number = Number::from_value(value).ok_or_else(|| {
+        RunarError::new(
+            ErrorCode::InvalidInput,
+            format!("Expected a number, found {:?}", value.category),
+        )
+    })?;
+    number.to_checked()
+}
+
+/// Clamp `value` into optional `[minimum, maximum]` bounds. NaN is returned
+/// unchanged.
+pub fn clamp_to_range(value: f64, minimum: Option<f64>, maximum: Option<f64>) -> f64 {
+    let value = minimum.map_or(value, |min| if value < min { min } else { value });
+    maximum.map_or(value, |max| if value > max { max } else { value })
+}
+
+fn out_of_range(number: Number, target: &str) -> anyhow::Error {
+    RunarError::new(
+        ErrorCode::InvalidInput,
+        format!("{} does not fit in {}", number, target),
+    )
+    .into()
+}
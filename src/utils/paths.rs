@@ -0,0 +1,659 @@
+// runar_common/src/utils/paths.rs
+//
+// Parsing, validation, joining and wildcard matching for service, action
+// and topic paths. Paths are '/'-separated segments, e.g. "math/add".
+//
+// Topic patterns support two wildcards:
+// - `*` matches exactly one segment, or any run of characters inside a
+//   segment, e.g. `sensors/temp-*/reading`
+// - `>` matches one or more trailing segments and must be the last segment
+// Every other character in a topic is literal. A segment prefixed with `~`
+// is a full glob (see `utils::glob`), e.g. `sensors/~temp-[0-9]/reading`;
+// concrete paths cannot start a segment with `~`. Topic paths compile their
+// segments once, when they are parsed.
+//
+// Action paths may be templates: a segment `{name}` is a parameter standing
+// for any single segment, e.g. "files/{file_id}/read". The router uses the
+// same syntax (see `parse_template_segment`).
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::glob::{escape, Glob};
+use crate::types::{IStr, NetworkId};
+
+/// Separator between path segments
+pub const PATH_SEPARATOR: char = '/';
+
+/// Wildcard matching exactly one segment
+pub const SINGLE_WILDCARD: &str = "*";
+
+/// Wildcard matching one or more trailing segments
+pub const MULTI_WILDCARD: &str = ">";
+
+/// Prefix marking a topic pattern segment as a glob, e.g. `~temp-[0-9]`
+pub const GLOB_MARKER: char = '~';
+
+/// Separator between the network ID and the path in network-qualified paths
+pub const NETWORK_SEPARATOR: char = ':';
+
+/// Validate a single concrete (non-wildcard) path segment
+pub fn validate_segment(segment: &str) -> Result<()> {
+    if segment.is_empty() {
+        return Err(anyhow!("Path segment cannot be empty"));
+    }
+    if segment == SINGLE_WILDCARD || segment == MULTI_WILDCARD {
+        return Err(anyhow!(
+            "Wildcard '{}' is not allowed in a concrete path",
+            segment
+        ));
+    }
+    if segment.starts_with(GLOB_MARKER) {
+        return Err(anyhow!(
+            "Path segment '{}' cannot start with the glob marker '{}'",
+            segment,
+            GLOB_MARKER
+        ));
+    }
+    if let Some(c) = segment
+        .chars()
+        .find(|c| c.is_whitespace() || c.is_control() || *c == PATH_SEPARATOR)
+    {
+        return Err(anyhow!(
+            "Path segment '{}' contains invalid character {:?}",
+            segment,
+            c
+        ));
+    }
+    Ok(())
+}
+
+/// Opening delimiter of a template parameter segment, e.g. `{file_id}`
+pub const PARAM_OPEN: char = '{';
+
+/// Closing delimiter of a template parameter segment
+pub const PARAM_CLOSE: char = '}';
+
+/// Validate a segment of a path template, returning the parameter name for
+/// a `{name}` segment and None for a literal segment.
+///
+/// Parameter names may contain ASCII letters, digits and underscores; braces
+/// anywhere else in a segment are rejected.
+pub fn parse_template_segment(segment: &str) -> Result<Option<&str>> {
+    if !segment.contains([PARAM_OPEN, PARAM_CLOSE]) {
+        validate_segment(segment)?;
+        return Ok(None);
+    }
+    let name = segment
+        .strip_prefix(PARAM_OPEN)
+        .and_then(|rest| rest.strip_suffix(PARAM_CLOSE))
+        .filter(|name| {
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+        .ok_or_else(|| anyhow!("Invalid parameter segment '{}'", segment))?;
+    Ok(Some(name))
+}
+
+/// Split a path into segments, ignoring a single leading or trailing separator
+pub fn split_segments(path: &str) -> Vec<&str> {
+    let trimmed = path.strip_prefix(PATH_SEPARATOR).unwrap_or(path);
+    let trimmed = trimmed.strip_suffix(PATH_SEPARATOR).unwrap_or(trimmed);
+    if trimmed.is_empty() {
+        return Vec::new();
+    }
+    trimmed.split(PATH_SEPARATOR).collect()
+}
+
+/// Join path parts with the separator, trimming redundant separators between them
+pub fn join_path(parts: &[&str]) -> String {
+    parts
+        .iter()
+        .map(|p| p.trim_matches(PATH_SEPARATOR))
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Check whether a concrete path matches a wildcard pattern. A pattern with
+/// `>` anywhere but its last segment matches nothing. The pattern is compiled
+/// on every call; parse it into a `TopicPath` to match it repeatedly.
+pub fn matches_pattern(pattern: &str, path: &str) -> bool {
+    let pattern = split_segments(pattern);
+    let matchers: Vec<SegmentMatcher> =
+        pattern.iter().map(|s| SegmentMatcher::lenient(s)).collect();
+    segments_match(&pattern, &matchers, &split_segments(path))
+}
+
+fn segments_match<P: AsRef<str>, S: AsRef<str>>(
+    pattern: &[P],
+    matchers: &[SegmentMatcher],
+    path: &[S],
+) -> bool {
+    match (matchers.first(), path.first()) {
+        (None, None) => true,
+        // `>` only stands for the rest of the path as the last segment
+        (Some(SegmentMatcher::Multi), Some(_)) => matchers.len() == 1,
+        (Some(matcher), Some(segment))
+            if matcher.matches(pattern[0].as_ref(), segment.as_ref()) =>
+        {
+            segments_match(&pattern[1..], &matchers[1..], &path[1..])
+        }
+        _ => false,
+    }
+}
+
+/// Check whether a concrete segment matches a pattern segment: `*` matches
+/// any segment, `*` inside a segment any run of characters, and a segment
+/// marked with `~` matches as a glob. Anything else, including a marked
+/// segment that is not a valid glob, must be equal.
+pub fn segment_matches(pattern: &str, segment: &str) -> bool {
+    SegmentMatcher::lenient(pattern).matches(pattern, segment)
+}
+
+/// How a topic pattern segment matches, compiled when the path is parsed
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SegmentMatcher {
+    /// Matches the segment text exactly
+    Literal,
+    /// `*` on its own: any one segment
+    Single,
+    /// `>`: one or more trailing segments
+    Multi,
+    /// A segment with `*` inside it, or one marked with `~`
+    Glob(Glob),
+}
+
+impl SegmentMatcher {
+    /// Compile a pattern segment, failing on a marked segment that is not a
+    /// valid glob
+    fn compile(segment: &str) -> Result<Self> {
+        if segment == SINGLE_WILDCARD {
+            return Ok(SegmentMatcher::Single);
+        }
+        if segment == MULTI_WILDCARD {
+            return Ok(SegmentMatcher::Multi);
+        }
+        if let Some(glob) = segment.strip_prefix(GLOB_MARKER) {
+            if glob.is_empty() {
+                return Err(anyhow!("Glob segment '{}' is empty", segment));
+            }
+            return Ok(SegmentMatcher::Glob(Glob::new(glob)?));
+        }
+        if !segment.contains(SINGLE_WILDCARD) {
+            return Ok(SegmentMatcher::Literal);
+        }
+        // `*` is the only wildcard of an unmarked segment
+        let source = segment
+            .split(SINGLE_WILDCARD)
+            .map(escape)
+            .collect::<Vec<_>>()
+            .join(SINGLE_WILDCARD);
+        Ok(SegmentMatcher::Glob(Glob::new(&source)?))
+    }
+
+    // Compile a segment that was not validated, treating it as literal text
+    // when it does not compile
+    fn lenient(segment: &str) -> Self {
+        Self::compile(segment).unwrap_or(SegmentMatcher::Literal)
+    }
+
+    /// Check whether `segment` matches; `pattern` is the text this matcher
+    /// was compiled from
+    fn matches(&self, pattern: &str, segment: &str) -> bool {
+        match self {
+            SegmentMatcher::Literal => pattern == segment,
+            SegmentMatcher::Single | SegmentMatcher::Multi => true,
+            SegmentMatcher::Glob(glob) => glob.matches(segment),
+        }
+    }
+}
+
+/// Path identifying a service, e.g. "math"
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ServicePath(String);
+
+impl ServicePath {
+    /// Parse and validate a service path. A service path is a single
+    /// segment, since action and topic paths take their first segment as
+    /// the service.
+    pub fn new(path: &str) -> Result<Self> {
+        let segments = split_segments(path);
+        match segments.as_slice() {
+            [] => Err(anyhow!("Service path cannot be empty")),
+            [segment] => {
+                validate_segment(segment)?;
+                Ok(Self(segment.to_string()))
+            }
+            _ => Err(anyhow!("Service path '{}' must be a single segment", path)),
+        }
+    }
+
+    /// Get the path as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Build the path of an action provided by this service
+    pub fn action(&self, name: &str) -> Result<ActionPath> {
+        ActionPath::new(&join_path(&[&self.0, name]))
+    }
+
+    /// Build the path of a topic published by this service
+    pub fn topic(&self, name: &str) -> Result<TopicPath> {
+        TopicPath::new(&join_path(&[&self.0, name]))
+    }
+}
+
+/// Path identifying an action, e.g. "math/add".
+/// The first segment is the service path, the remainder is the action name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ActionPath {
+    service: ServicePath,
+    action: String,
+}
+
+impl ActionPath {
+    /// Parse and validate an action path
+    pub fn new(path: &str) -> Result<Self> {
+        let segments = split_segments(path);
+        if segments.len() < 2 {
+            return Err(anyhow!(
+                "Action path '{}' must contain a service and an action name",
+                path
+            ));
+        }
+        let mut params = Vec::new();
+        for segment in &segments {
+            if let Some(name) = parse_template_segment(segment)? {
+                if params.contains(&name) {
+                    return Err(anyhow!("Parameter '{}' appears twice in '{}'", name, path));
+                }
+                params.push(name);
+            }
+        }
+        if parse_template_segment(segments[0])?.is_some() {
+            return Err(anyhow!(
+                "Service segment of '{}' cannot be a parameter",
+                path
+            ));
+        }
+        Ok(Self {
+            service: ServicePath(segments[0].to_string()),
+            action: segments[1..].join("/"),
+        })
+    }
+
+    /// Get the service part of the path
+    pub fn service(&self) -> &ServicePath {
+        &self.service
+    }
+
+    /// Get the action name (everything after the service segment)
+    pub fn action_name(&self) -> &str {
+        &self.action
+    }
+
+    /// Get the path segments
+    pub fn segments(&self) -> Vec<&str> {
+        let mut segments = vec![self.service.as_str()];
+        segments.extend(self.action.split(PATH_SEPARATOR));
+        segments
+    }
+
+    /// Names of the template parameters, in path order
+    pub fn params(&self) -> Vec<&str> {
+        self.action
+            .split(PATH_SEPARATOR)
+            .filter_map(|segment| parse_template_segment(segment).ok().flatten())
+            .collect()
+    }
+
+    /// Whether the path contains template parameters
+    pub fn is_template(&self) -> bool {
+        !self.params().is_empty()
+    }
+
+    /// Fill in the template parameters, producing a concrete path.
+    /// Every parameter must be given exactly once and nothing else.
+    pub fn bind(&self, values: &[(&str, &str)]) -> Result<ActionPath> {
+        let params = self.params();
+        if let Some((name, _)) = values.iter().find(|(name, _)| !params.contains(name)) {
+            return Err(anyhow!("'{}' has no parameter '{}'", self, name));
+        }
+        let mut segments = vec![self.service.as_str()];
+        for segment in self.action.split(PATH_SEPARATOR) {
+            match parse_template_segment(segment)? {
+                Some(param) => {
+                    let value = values
+                        .iter()
+                        .find(|(name, _)| *name == param)
+                        .map(|(_, value)| *value)
+                        .ok_or_else(|| anyhow!("Missing parameter '{}' for '{}'", param, self))?;
+                    if parse_template_segment(value)?.is_some() {
+                        return Err(anyhow!(
+                            "Value '{}' for parameter '{}' is itself a parameter",
+                            value,
+                            param
+                        ));
+                    }
+                    segments.push(value);
+                }
+                None => segments.push(segment),
+            }
+        }
+        ActionPath::new(&segments.join("/"))
+    }
+
+    /// Match a concrete path against this template, returning the captured
+    /// parameters in path order (None if the path does not match)
+    pub fn extract(&self, path: &str) -> Option<Vec<(&str, String)>> {
+        let template = self.segments();
+        let segments = split_segments(path);
+        if template.len() != segments.len() {
+            return None;
+        }
+        let mut params = Vec::new();
+        for (expected, actual) in template.into_iter().zip(segments) {
+            match parse_template_segment(expected).ok()? {
+                Some(name) => {
+                    validate_segment(actual).ok()?;
+                    params.push((name, actual.to_string()));
+                }
+                None if expected == actual => {}
+                None => return None,
+            }
+        }
+        Some(params)
+    }
+}
+
+/// Path identifying an event topic, e.g. "math/added".
+/// May be a pattern containing `*`, `>` and `~`-marked glob segments.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TopicPath {
+    segments: Vec<IStr>,
+    // One matcher per segment, derived from `segments`
+    matchers: Arc<[SegmentMatcher]>,
+}
+
+impl TopicPath {
+    /// Parse and validate a topic path or pattern
+    pub fn new(path: &str) -> Result<Self> {
+        let segments = split_segments(path);
+        if segments.is_empty() {
+            return Err(anyhow!("Topic path cannot be empty"));
+        }
+        for (i, segment) in segments.iter().enumerate() {
+            match *segment {
+                SINGLE_WILDCARD => {}
+                MULTI_WILDCARD if i == segments.len() - 1 => {}
+                MULTI_WILDCARD => {
+                    return Err(anyhow!(
+                        "Wildcard '>' must be the last segment in '{}'",
+                        path
+                    ))
+                }
+                _ => validate_segment(segment.strip_prefix(GLOB_MARKER).unwrap_or(segment))?,
+            }
+        }
+        Self::from_segments(segments.into_iter().map(IStr::new).collect())
+    }
+
+    // Compile already validated segments
+    fn from_segments(segments: Vec<IStr>) -> Result<Self> {
+        let matchers = segments
+            .iter()
+            .map(|segment| SegmentMatcher::compile(segment))
+            .collect::<Result<_>>()?;
+        Ok(Self { segments, matchers })
+    }
+
+    /// Get the (interned) path segments
+    pub fn segments(&self) -> &[IStr] {
+        &self.segments
+    }
+
+    /// The compiled matcher of each segment
+    pub(crate) fn matchers(&self) -> &[SegmentMatcher] {
+        &self.matchers
+    }
+
+    /// Check whether this topic contains wildcards
+    pub fn is_pattern(&self) -> bool {
+        self.matchers.iter().any(|m| *m != SegmentMatcher::Literal)
+    }
+
+    /// Append a segment, returning a new topic path
+    pub fn join(&self, segment: &str) -> Result<Self> {
+        TopicPath::new(&join_path(&[&self.to_string(), segment]))
+    }
+
+    /// Check whether a concrete topic matches this topic (treated as a pattern)
+    pub fn matches(&self, topic: &TopicPath) -> bool {
+        segments_match(&self.segments, &self.matchers, &topic.segments)
+    }
+}
+
+// Equality, ordering and hashing go by the segments alone; the matchers are
+// derived from them
+
+impl PartialEq for TopicPath {
+    fn eq(&self, other: &Self) -> bool {
+        self.segments == other.segments
+    }
+}
+
+impl Eq for TopicPath {}
+
+impl Hash for TopicPath {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.segments.hash(state);
+    }
+}
+
+impl PartialOrd for TopicPath {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TopicPath {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.segments.cmp(&other.segments)
+    }
+}
+
+/// Path of an event published by a service, optionally qualified with the
+/// network it is published on, e.g. "auth/user_created" or
+/// "main:auth/user_created".
+///
+/// Build one with `EventPath::service("auth").event("user_created")` rather
+/// than concatenating strings; the builder validates every part.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct EventPath {
+    network: Option<NetworkId>,
+    service: ServicePath,
+    event: String,
+}
+
+impl EventPath {
+    /// Start building the path of an event published by `service`
+    pub fn service(service: impl Into<String>) -> EventPathBuilder {
+        EventPathBuilder {
+            network: None,
+            service: service.into(),
+            event: None,
+        }
+    }
+
+    /// Parse and validate an event path, with or without a network prefix
+    pub fn new(path: &str) -> Result<Self> {
+        let (network, rest) = match path.split_once(NETWORK_SEPARATOR) {
+            Some((network, rest)) if !network.contains(PATH_SEPARATOR) => {
+                (Some(NetworkId::new(network)?), rest)
+            }
+            _ => (None, path),
+        };
+        let segments = split_segments(rest);
+        if segments.len() < 2 {
+            return Err(anyhow!(
+                "Event path '{}' must contain a service and an event name",
+                path
+            ));
+        }
+        let mut builder = EventPath::service(segments[0]).event(segments[1..].join("/"));
+        builder.network = network;
+        builder.build()
+    }
+
+    /// Get the network the event is published on (if qualified)
+    pub fn network(&self) -> Option<&NetworkId> {
+        self.network.as_ref()
+    }
+
+    /// Get the service publishing the event
+    pub fn service_path(&self) -> &ServicePath {
+        &self.service
+    }
+
+    /// Get the event name (everything after the service segment)
+    pub fn event_name(&self) -> &str {
+        &self.event
+    }
+
+    /// Get the topic the event is published under (without the network)
+    pub fn topic(&self) -> TopicPath {
+        let segments = std::iter::once(self.service.as_str())
+            .chain(self.event.split(PATH_SEPARATOR))
+            .map(IStr::new)
+            .collect();
+        TopicPath::from_segments(segments).expect("event path segments are concrete")
+    }
+}
+
+/// Builder for [`EventPath`]; parts are validated by `build`
+#[derive(Debug, Clone)]
+pub struct EventPathBuilder {
+    network: Option<NetworkId>,
+    service: String,
+    event: Option<String>,
+}
+
+impl EventPathBuilder {
+    /// Set the event name; may contain several segments, e.g. "user/created"
+    pub fn event(mut self, name: impl Into<String>) -> Self {
+        self.event = Some(name.into());
+        self
+    }
+
+    /// Qualify the path with the network the event is published on
+    pub fn with_network(mut self, network: NetworkId) -> Self {
+        self.network = Some(network);
+        self
+    }
+
+    /// Validate the parts and build the path
+    pub fn build(self) -> Result<EventPath> {
+        validate_segment(&self.service)?;
+        if self.service.contains(NETWORK_SEPARATOR) {
+            return Err(anyhow!(
+                "Service name '{}' cannot contain '{}'",
+                self.service,
+                NETWORK_SEPARATOR
+            ));
+        }
+        let event = self.event.ok_or_else(|| {
+            anyhow!(
+                "Event path for service '{}' has no event name",
+                self.service
+            )
+        })?;
+        let segments = split_segments(&event);
+        if segments.is_empty() {
+            return Err(anyhow!("Event name cannot be empty"));
+        }
+        for segment in &segments {
+            validate_segment(segment)?;
+        }
+        Ok(EventPath {
+            network: self.network,
+            service: ServicePath(self.service),
+            event: segments.join("/"),
+        })
+    }
+}
+
+// Display, FromStr and String conversions shared by the path types
+
+impl fmt::Display for ServicePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl fmt::Display for ActionPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.service, self.action)
+    }
+}
+
+impl fmt::Display for EventPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(network) = &self.network {
+            write!(f, "{}{}", network, NETWORK_SEPARATOR)?;
+        }
+        write!(f, "{}/{}", self.service, self.event)
+    }
+}
+
+impl fmt::Display for TopicPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+            f.write_str(segment)?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! impl_path_conversions {
+    ($($name:ident),*) => {
+        $(
+            impl FromStr for $name {
+                type Err = anyhow::Error;
+
+                fn from_str(s: &str) -> Result<Self> {
+                    Self::new(s)
+                }
+            }
+
+            impl TryFrom<String> for $name {
+                type Error = anyhow::Error;
+
+                fn try_from(s: String) -> Result<Self> {
+                    Self::new(&s)
+                }
+            }
+
+            impl From<$name> for String {
+                fn from(path: $name) -> Self {
+                    path.to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_path_conversions!(ServicePath, ActionPath, TopicPath, EventPath);
@@ -0,0 +1,227 @@
+// runar_common/src/utils/rate_limit.rs
+//
+// Token-bucket rate limiting.
+//
+// Buckets are implemented with the generic cell rate algorithm (GCRA): the
+// whole bucket state is a single "theoretical arrival time" stored in an
+// `AtomicU64`, so acquiring tokens is one compare-and-swap and never blocks.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use crate::utils::time::Instant;
+
+/// Refill rate and burst size of a token bucket
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// Tokens added per second
+    pub per_second: f64,
+    /// Maximum number of tokens the bucket holds
+    pub burst: u32,
+}
+
+impl RateLimit {
+    /// Create a limit of `per_second` tokens with a bucket of `burst` tokens
+    pub fn new(per_second: f64, burst: u32) -> Self {
+        Self { per_second, burst }
+    }
+
+    // Nanoseconds per token, or `None` if the bucket never refills: the rate
+    // is zero, negative or NaN, or so slow that a full bucket would not fit
+    // in the clock
+    fn interval_nanos(&self) -> Option<u64> {
+        let interval = (1e9 / self.per_second).round().max(1.0);
+        let fits = interval * (self.burst.max(1) as f64) < (u64::MAX / 4) as f64;
+        (self.per_second > 0.0 && fits).then_some(interval as u64)
+    }
+}
+
+/// A single token bucket
+#[derive(Debug)]
+pub struct TokenBucket {
+    origin: Instant,
+    interval: u64,
+    capacity: u64,
+    // False if spent tokens never come back
+    refills: bool,
+    // Nanoseconds since `origin` at which the bucket will be full again
+    tat: AtomicU64,
+}
+
+impl TokenBucket {
+    /// Create a full bucket
+    pub fn new(limit: RateLimit) -> Self {
+        Self::starting_at(limit, Instant::now())
+    }
+
+    /// Create a full bucket whose clock starts at `origin`
+    pub fn starting_at(limit: RateLimit, origin: Instant) -> Self {
+        // A bucket that never refills keeps its clock at zero, so it allows
+        // `burst` tokens in total
+        let (interval, refills) = match limit.interval_nanos() {
+            Some(interval) => (interval, true),
+            None => (1, false),
+        };
+        Self {
+            origin,
+            interval,
+            capacity: interval * limit.burst as u64,
+            refills,
+            tat: AtomicU64::new(0),
+        }
+    }
+
+    /// Try to take one token
+    pub fn try_acquire(&self) -> bool {
+        self.try_acquire_n_at(1, Instant::now())
+    }
+
+    /// Try to take `n` tokens at once
+    pub fn try_acquire_n(&self, n: u32) -> bool {
+        self.try_acquire_n_at(n, Instant::now())
+    }
+
+    /// Try to take `n` tokens as of `now`
+    pub fn try_acquire_n_at(&self, n: u32, now: Instant) -> bool {
+        let now = self.nanos_since_origin(now);
+        let cost = self.interval.saturating_mul(n as u64);
+        let mut tat = self.tat.load(Ordering::Relaxed);
+        loop {
+            let new_tat = tat.max(now).saturating_add(cost);
+            if new_tat - now > self.capacity {
+                return false;
+            }
+            match self
+                .tat
+                .compare_exchange_weak(tat, new_tat, Ordering::AcqRel, Ordering::Relaxed)
+            {
+                Ok(_) => return true,
+                Err(actual) => tat = actual,
+            }
+        }
+    }
+
+    /// Get the number of whole tokens available as of `now`
+    pub fn available_at(&self, now: Instant) -> u32 {
+        let now = self.nanos_since_origin(now);
+        let used = self.tat.load(Ordering::Relaxed).saturating_sub(now);
+        ((self.capacity - used.min(self.capacity)) / self.interval) as u32
+    }
+
+    /// Get the number of whole tokens currently available
+    pub fn available(&self) -> u32 {
+        self.available_at(Instant::now())
+    }
+
+    /// Get how long until `n` tokens are available (zero if they are available
+    /// now, `Duration::MAX` if the bucket never refills)
+    pub fn wait_time(&self, n: u32) -> Duration {
+        let now = self.nanos_since_origin(Instant::now());
+        let cost = self.interval.saturating_mul(n as u64);
+        let new_tat = self
+            .tat
+            .load(Ordering::Relaxed)
+            .max(now)
+            .saturating_add(cost);
+        match (new_tat - now).saturating_sub(self.capacity) {
+            0 => Duration::ZERO,
+            _ if !self.refills => Duration::MAX,
+            wait => Duration::from_nanos(wait),
+        }
+    }
+
+    /// Check whether the bucket is full as of `now`
+    pub fn is_full_at(&self, now: Instant) -> bool {
+        self.tat.load(Ordering::Relaxed) <= self.nanos_since_origin(now)
+    }
+
+    fn nanos_since_origin(&self, now: Instant) -> u64 {
+        if !self.refills {
+            return 0;
+        }
+        now.saturating_duration_since(self.origin).as_nanos() as u64
+    }
+}
+
+/// Per-key token buckets sharing the same limit (e.g. one bucket per peer)
+#[derive(Debug)]
+pub struct RateLimiter<K> {
+    limit: RateLimit,
+    buckets: RwLock<HashMap<K, Arc<TokenBucket>>>,
+}
+
+impl<K: Eq + Hash + Clone> RateLimiter<K> {
+    /// Create a limiter applying `limit` to each key independently
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            buckets: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Get the limit applied to each key
+    pub fn limit(&self) -> RateLimit {
+        self.limit
+    }
+
+    /// Try to take one token for `key`
+    pub fn try_acquire(&self, key: &K) -> bool {
+        self.bucket(key).try_acquire()
+    }
+
+    /// Try to take `n` tokens for `key`
+    pub fn try_acquire_n(&self, key: &K, n: u32) -> bool {
+        self.bucket(key).try_acquire_n(n)
+    }
+
+    /// Get the bucket for a key, creating a full one if needed
+    pub fn bucket(&self, key: &K) -> Arc<TokenBucket> {
+        if let Some(bucket) = self
+            .buckets
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(key)
+        {
+            return bucket.clone();
+        }
+        self.buckets
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(key.clone())
+            .or_insert_with(|| Arc::new(TokenBucket::new(self.limit)))
+            .clone()
+    }
+
+    /// Forget the bucket of a key
+    pub fn remove(&self, key: &K) {
+        self.buckets
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(key);
+    }
+
+    /// Drop buckets that have refilled completely; a new bucket would behave identically
+    pub fn purge_idle(&self) {
+        let now = Instant::now();
+        self.buckets
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .retain(|_, bucket| !bucket.is_full_at(now));
+    }
+
+    /// Get the number of tracked keys
+    pub fn len(&self) -> usize {
+        self.buckets
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .len()
+    }
+
+    /// Check whether no keys are tracked
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,185 @@
+// runar_common/src/utils/retry.rs
+//
+// Retry loops with fixed or exponential backoff.
+//
+// Errors decide whether they are worth retrying through the `Retryable`
+// trait, which is implemented for `RunarError` and for `anyhow::Error`
+// values wrapping one. Use the `_if` variants to supply a custom predicate.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::future::Future;
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::errors::RunarError;
+
+/// Delay strategy between attempts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Backoff {
+    /// Wait the same amount of time before every retry
+    Fixed(Duration),
+    /// Start at `initial` and multiply by `multiplier` after each retry, capped at `max`
+    Exponential {
+        initial: Duration,
+        max: Duration,
+        multiplier: f64,
+    },
+}
+
+/// How often and how long to retry an operation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay strategy between attempts
+    pub backoff: Backoff,
+    /// Fraction of each delay (0.0 to 1.0) that is randomized
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::exponential(5, Duration::from_millis(100), Duration::from_secs(10))
+    }
+}
+
+impl RetryPolicy {
+    /// Retry with a fixed delay
+    pub fn fixed(max_attempts: u32, delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            backoff: Backoff::Fixed(delay),
+            jitter: 0.0,
+        }
+    }
+
+    /// Retry with a doubling delay starting at `initial` and capped at `max`
+    pub fn exponential(max_attempts: u32, initial: Duration, max: Duration) -> Self {
+        Self {
+            max_attempts,
+            backoff: Backoff::Exponential {
+                initial,
+                max,
+                multiplier: 2.0,
+            },
+            jitter: 0.0,
+        }
+    }
+
+    /// Set the jitter fraction (clamped to 0.0..=1.0)
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Get the delay before retry number `retry` (1 for the first retry), without jitter
+    pub fn base_delay(&self, retry: u32) -> Duration {
+        match self.backoff {
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential {
+                initial,
+                max,
+                multiplier,
+            } => {
+                let factor = multiplier.powi(retry.saturating_sub(1) as i32);
+                let secs = initial.as_secs_f64() * factor;
+                if secs.is_finite() && secs < max.as_secs_f64() {
+                    Duration::from_secs_f64(secs)
+                } else {
+                    max
+                }
+            }
+        }
+    }
+
+    /// Get the delay before retry number `retry`, with jitter applied
+    pub fn delay(&self, retry: u32) -> Duration {
+        let base = self.base_delay(retry);
+        if self.jitter <= 0.0 || base.is_zero() {
+            return base;
+        }
+        let spread = base.as_secs_f64() * self.jitter;
+        let offset = rand::thread_rng().gen_range(-spread..=spread);
+        Duration::from_secs_f64((base.as_secs_f64() + offset).max(0.0))
+    }
+}
+
+/// Errors that know whether the failed operation may be retried
+pub trait Retryable {
+    fn is_retryable(&self) -> bool;
+}
+
+impl Retryable for RunarError {
+    fn is_retryable(&self) -> bool {
+        RunarError::is_retryable(self)
+    }
+}
+
+impl Retryable for anyhow::Error {
+    fn is_retryable(&self) -> bool {
+        RunarError::find_in(self).is_some_and(RunarError::is_retryable)
+    }
+}
+
+/// Run `op` until it succeeds, fails with a non-retryable error, or runs out of attempts.
+/// The closure receives the attempt number, starting at 1.
+pub fn retry<T, E: Retryable>(
+    policy: &RetryPolicy,
+    op: impl FnMut(u32) -> Result<T, E>,
+) -> Result<T, E> {
+    retry_if(policy, op, E::is_retryable)
+}
+
+/// Like `retry`, but with a custom predicate deciding which errors are retried
+pub fn retry_if<T, E>(
+    policy: &RetryPolicy,
+    mut op: impl FnMut(u32) -> Result<T, E>,
+    should_retry: impl Fn(&E) -> bool,
+) -> Result<T, E> {
+    let mut attempt = 1;
+    loop {
+        match op(attempt) {
+            Err(e) if attempt < policy.max_attempts && should_retry(&e) => {
+                std::thread::sleep(policy.delay(attempt));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Async version of `retry`; sleeps on the tokio timer between attempts
+/// (not available on wasm32, where there is no tokio timer)
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn retry_async<T, E, F, Fut>(policy: &RetryPolicy, op: F) -> Result<T, E>
+where
+    E: Retryable,
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_async_if(policy, op, E::is_retryable).await
+}
+
+/// Async version of `retry_if`
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn retry_async_if<T, E, F, Fut>(
+    policy: &RetryPolicy,
+    mut op: F,
+    should_retry: impl Fn(&E) -> bool,
+) -> Result<T, E>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 1;
+    loop {
+        match op(attempt).await {
+            Err(e) if attempt < policy.max_attempts && should_retry(&e) => {
+                tokio::time::sleep(policy.delay(attempt)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
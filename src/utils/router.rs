@@ -0,0 +1,227 @@
+// runar_common/src/utils/router.rs
+//
+// Action path routing.
+//
+// Routes are stored in a trie of path segments. A `{name}` segment (e.g.
+// `service/{id}/get`, the `ActionPath` template syntax) is a parameter that
+// matches any single segment and captures it. Lookups return the route
+// matching the longest prefix of the requested path, preferring literal
+// segments over parameters.
+//
+// A lookup follows literal children first and backtracks into parameter
+// children, stopping at the first route that matches the whole path. Each
+// trie node is visited at most once, so when routes do not overlap a lookup
+// takes time proportional to the number of segments. Routes that offer
+// both a literal and a parameter at many positions are the worst case: a
+// path of n segments can visit up to 2^n nodes, bounded by the number of
+// registered routes sharing its prefix.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+
+use crate::utils::paths::{parse_template_segment, split_segments};
+
+struct Node<T> {
+    children: HashMap<String, Node<T>>,
+    // Parameter child: (parameter name, subtree)
+    param: Option<(String, Box<Node<T>>)>,
+    value: Option<T>,
+}
+
+impl<T> Default for Node<T> {
+    fn default() -> Self {
+        Self {
+            children: HashMap::new(),
+            param: None,
+            value: None,
+        }
+    }
+}
+
+// Best match found so far: (matched segment count, value, captured params)
+type Best<'a, T> = Option<(usize, &'a T, Vec<(&'a str, usize)>)>;
+
+impl<T> Node<T> {
+    fn lookup<'a>(
+        &'a self,
+        segments: &[&str],
+        depth: usize,
+        params: &mut Vec<(&'a str, usize)>,
+        best: &mut Best<'a, T>,
+    ) {
+        if let Some(value) = &self.value {
+            if best.as_ref().is_none_or(|(matched, _, _)| depth > *matched) {
+                *best = Some((depth, value, params.clone()));
+            }
+        }
+        let Some(segment) = segments.get(depth) else {
+            return;
+        };
+        if let Some(child) = self.children.get(*segment) {
+            child.lookup(segments, depth + 1, params, best);
+        }
+        // Nothing beats a match of the whole path found through literals
+        if best
+            .as_ref()
+            .is_some_and(|(matched, _, _)| *matched == segments.len())
+        {
+            return;
+        }
+        if let Some((name, child)) = &self.param {
+            params.push((name.as_str(), depth));
+            child.lookup(segments, depth + 1, params, best);
+            params.pop();
+        }
+    }
+}
+
+/// The result of a successful `PathRouter::route` lookup
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteMatch<'a, T> {
+    /// The value registered for the matched route
+    pub value: &'a T,
+    /// Captured parameters in path order, e.g. `[("id", "42")]`
+    pub params: Vec<(&'a str, String)>,
+    /// Segments of the path after the matched route (empty for an exact match)
+    pub rest: Vec<String>,
+}
+
+impl<T> RouteMatch<'_, T> {
+    /// The captured value of a parameter
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| *param == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the whole path was matched
+    pub fn is_exact(&self) -> bool {
+        self.rest.is_empty()
+    }
+}
+
+/// Routes action paths (e.g. "users/{id}/get") to values with longest-prefix
+/// matching. Lookups take time proportional to the number of path segments
+/// unless routes overlap at many positions (see the module notes).
+pub struct PathRouter<T> {
+    root: Node<T>,
+    len: usize,
+}
+
+impl<T> Default for PathRouter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PathRouter<T> {
+    /// Create an empty router
+    pub fn new() -> Self {
+        Self {
+            root: Node::default(),
+            len: 0,
+        }
+    }
+
+    /// Register a route, returning the value it replaces.
+    ///
+    /// A parameter must have the same name in every route that shares its
+    /// position, e.g. `users/{id}/get` and `users/{name}/put` conflict.
+    pub fn insert(&mut self, route: &str, value: T) -> Result<Option<T>> {
+        let segments = split_segments(route);
+        if segments.is_empty() {
+            return Err(anyhow!("Route cannot be empty"));
+        }
+        // Validate every segment before the trie is touched
+        let parsed = segments
+            .iter()
+            .map(|segment| {
+                parse_template_segment(segment)
+                    .map(|param| (*segment, param))
+                    .map_err(|e| anyhow!("Invalid route '{}': {}", route, e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Check parameter names along the existing part of the route, so a
+        // conflict leaves the router unchanged
+        let mut existing_node = Some(&self.root);
+        for (segment, param) in &parsed {
+            let Some(node) = existing_node else {
+                break;
+            };
+            existing_node = match (param, &node.param) {
+                (Some(name), Some((existing, _))) if existing != name => {
+                    return Err(anyhow!(
+                        "Parameter '{{{}}}' in route '{}' conflicts with '{{{}}}'",
+                        name,
+                        route,
+                        existing
+                    ));
+                }
+                (Some(_), Some((_, child))) => Some(child),
+                (Some(_), None) => None,
+                (None, _) => node.children.get(*segment),
+            };
+        }
+
+        let mut node = &mut self.root;
+        for (segment, param) in parsed {
+            node = match param {
+                Some(name) => {
+                    &mut node
+                        .param
+                        .get_or_insert_with(|| (name.to_string(), Box::default()))
+                        .1
+                }
+                None => node.children.entry(segment.to_string()).or_default(),
+            };
+        }
+        let previous = node.value.replace(value);
+        if previous.is_none() {
+            self.len += 1;
+        }
+        Ok(previous)
+    }
+
+    /// Find the route matching the longest prefix of `path`
+    pub fn route(&self, path: &str) -> Option<RouteMatch<'_, T>> {
+        let segments = split_segments(path);
+        let mut best = None;
+        self.root.lookup(&segments, 0, &mut Vec::new(), &mut best);
+        let (matched, value, params) = best?;
+        Some(RouteMatch {
+            value,
+            params: params
+                .into_iter()
+                .map(|(name, index)| (name, segments[index].to_string()))
+                .collect(),
+            rest: segments[matched..].iter().map(|s| s.to_string()).collect(),
+        })
+    }
+
+    /// Find a route matching all of `path`
+    pub fn route_exact(&self, path: &str) -> Option<RouteMatch<'_, T>> {
+        self.route(path).filter(RouteMatch::is_exact)
+    }
+
+    /// Number of registered routes
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no routes are registered
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T> fmt::Debug for PathRouter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PathRouter")
+            .field("routes", &self.len)
+            .finish()
+    }
+}
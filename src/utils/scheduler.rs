@@ -0,0 +1,240 @@
+// runar_common/src/utils/scheduler.rs
+//
+// Periodic background tasks (heartbeats, cleanup loops).
+//
+// Each task runs on its own tokio task started with `spawn_logged`. Runs can
+// be paused and resumed through the returned handle, the period can be
+// jittered so many nodes do not fire in lockstep, and every run is counted
+// and timed in the global metrics registry.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use tokio::sync::watch;
+
+use crate::logging::Logger;
+use crate::metrics::{self, MetricLabels};
+use crate::utils::task::spawn_logged;
+use crate::utils::time::format_duration;
+
+/// Counter of completed runs, labelled with the scheduler logger's component
+/// and the task name (in the `action` label)
+pub const SCHEDULER_RUNS_METRIC: &str = "runar_scheduler_runs_total";
+
+/// Histogram of run times in seconds, labelled like `SCHEDULER_RUNS_METRIC`
+pub const SCHEDULER_RUN_DURATION_METRIC: &str = "runar_scheduler_run_duration_seconds";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TaskState {
+    Running,
+    Paused,
+    Cancelled,
+}
+
+/// Handle to a task started with `Scheduler::every`
+#[derive(Clone)]
+pub struct ScheduledTask {
+    name: Arc<str>,
+    state: Arc<watch::Sender<TaskState>>,
+    runs: Arc<AtomicU64>,
+}
+
+impl ScheduledTask {
+    /// The task name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Skip runs until `resume` is called (a run in progress completes)
+    pub fn pause(&self) {
+        self.set_state(TaskState::Paused);
+    }
+
+    /// Resume a paused task; the next run is one period from now
+    pub fn resume(&self) {
+        self.set_state(TaskState::Running);
+    }
+
+    /// Stop the task for good
+    pub fn cancel(&self) {
+        self.state.send_replace(TaskState::Cancelled);
+    }
+
+    /// Whether the task is paused
+    pub fn is_paused(&self) -> bool {
+        *self.state.borrow() == TaskState::Paused
+    }
+
+    /// Whether the task has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        *self.state.borrow() == TaskState::Cancelled
+    }
+
+    /// Number of completed runs
+    pub fn runs(&self) -> u64 {
+        self.runs.load(Ordering::Relaxed)
+    }
+
+    fn set_state(&self, state: TaskState) {
+        // Cancellation is final
+        self.state.send_if_modified(|current| {
+            let changed = *current != TaskState::Cancelled && *current != state;
+            if changed {
+                *current = state;
+            }
+            changed
+        });
+    }
+}
+
+impl std::fmt::Debug for ScheduledTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScheduledTask")
+            .field("name", &self.name)
+            .field("state", &*self.state.borrow())
+            .field("runs", &self.runs())
+            .finish()
+    }
+}
+
+/// Runs closures periodically on the tokio runtime.
+/// Dropping the scheduler cancels all of its tasks.
+pub struct Scheduler {
+    logger: Logger,
+    jitter: f64,
+    tasks: Mutex<Vec<ScheduledTask>>,
+}
+
+impl Scheduler {
+    /// Create a scheduler logging through `logger`
+    pub fn new(logger: &Logger) -> Self {
+        Self {
+            logger: logger.clone(),
+            jitter: 0.0,
+            tasks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Randomize every delay by up to `fraction` of the period in either
+    /// direction (e.g. 0.1 for +/-10%), clamped to 0..=1
+    pub fn with_jitter(mut self, fraction: f64) -> Self {
+        self.jitter = fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Run `f` every `period` (the first run is one period from now) until
+    /// the task is cancelled or the scheduler is dropped.
+    /// A run that takes longer than the period delays the next one.
+    pub fn every<F, Fut>(&self, period: Duration, name: &str, mut f: F) -> ScheduledTask
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send,
+    {
+        let (state, mut state_rx) = watch::channel(TaskState::Running);
+        let task = ScheduledTask {
+            name: Arc::from(name),
+            state: Arc::new(state),
+            runs: Arc::new(AtomicU64::new(0)),
+        };
+
+        let logger = self.logger.clone();
+        let jitter = self.jitter;
+        let runs = task.runs.clone();
+        let task_name = name.to_string();
+        spawn_logged(&self.logger, name, async move {
+            let labels =
+                MetricLabels::component(logger.component()).with_action(task_name.as_str());
+            loop {
+                let state = *state_rx.borrow_and_update();
+                match state {
+                    TaskState::Cancelled => break,
+                    TaskState::Paused => {
+                        if state_rx.changed().await.is_err() {
+                            break;
+                        }
+                        continue;
+                    }
+                    TaskState::Running => {}
+                }
+                match tokio::time::timeout(jittered(period, jitter), state_rx.changed()).await {
+                    // Paused, resumed or cancelled while waiting
+                    Ok(Ok(())) => continue,
+                    Ok(Err(_)) => break,
+                    Err(_) => {}
+                }
+
+                let started = Instant::now();
+                f().await;
+                let elapsed = started.elapsed();
+                let run = runs.fetch_add(1, Ordering::Relaxed) + 1;
+                metrics::global()
+                    .counter(SCHEDULER_RUNS_METRIC, &labels)
+                    .inc();
+                metrics::global()
+                    .histogram(SCHEDULER_RUN_DURATION_METRIC, &labels)
+                    .observe(elapsed.as_secs_f64());
+                logger.debug(format!(
+                    "Scheduled task '{}' run {} took {}",
+                    task_name,
+                    run,
+                    format_duration(elapsed)
+                ));
+            }
+        });
+
+        self.lock().push(task.clone());
+        task
+    }
+
+    /// The first task registered under `name`
+    pub fn task(&self, name: &str) -> Option<ScheduledTask> {
+        self.lock().iter().find(|task| task.name() == name).cloned()
+    }
+
+    /// All tasks that have not been cancelled
+    pub fn tasks(&self) -> Vec<ScheduledTask> {
+        let mut tasks = self.lock();
+        tasks.retain(|task| !task.is_cancelled());
+        tasks.clone()
+    }
+
+    /// Cancel every task
+    pub fn shutdown(&self) {
+        for task in self.lock().drain(..) {
+            task.cancel();
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<ScheduledTask>> {
+        self.tasks
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drop for Scheduler {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl std::fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("jitter", &self.jitter)
+            .field("tasks", &*self.lock())
+            .finish()
+    }
+}
+
+// The period randomized by up to `jitter` of itself
+fn jittered(period: Duration, jitter: f64) -> Duration {
+    if jitter == 0.0 {
+        return period;
+    }
+    let factor = 1.0 + rand::thread_rng().gen_range(-jitter..=jitter);
+    period.mul_f64(factor.max(0.0))
+}
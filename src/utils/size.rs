@@ -0,0 +1,102 @@
+// runar_common/src/utils/size.rs
+//
+// Byte sizes for configuration values ("512KiB", "1.5GB", "100").
+// Decimal units (KB, MB, ...) are powers of 1000, binary units (KiB, MiB, ...)
+// powers of 1024. Units are case-insensitive and a bare number means bytes.
+
+use anyhow::{anyhow, Result};
+
+const UNITS: &[(&str, u64)] = &[
+    ("b", 1),
+    ("k", 1_000),
+    ("kb", 1_000),
+    ("m", 1_000_000),
+    ("mb", 1_000_000),
+    ("g", 1_000_000_000),
+    ("gb", 1_000_000_000),
+    ("t", 1_000_000_000_000),
+    ("tb", 1_000_000_000_000),
+    ("ki", 1 << 10),
+    ("kib", 1 << 10),
+    ("mi", 1 << 20),
+    ("mib", 1 << 20),
+    ("gi", 1 << 30),
+    ("gib", 1 << 30),
+    ("ti", 1 << 40),
+    ("tib", 1 << 40),
+];
+
+/// Parse a human-readable size into a number of bytes
+pub fn parse_size(input: &str) -> Result<u64> {
+    let trimmed = input.trim();
+    let number_len = trimmed
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(number_len);
+    let unit = unit.trim().to_ascii_lowercase();
+
+    let value: f64 = number
+        .parse()
+        .map_err(|_| anyhow!("Invalid size '{}': bad number '{}'", input, number))?;
+    let multiplier = if unit.is_empty() {
+        1
+    } else {
+        UNITS
+            .iter()
+            .find(|(name, _)| *name == unit)
+            .map(|(_, multiplier)| *multiplier)
+            .ok_or_else(|| anyhow!("Invalid size '{}': unknown unit '{}'", input, unit))?
+    };
+
+    let bytes = value * multiplier as f64;
+    if bytes.fract() != 0.0 || bytes > u64::MAX as f64 {
+        return Err(anyhow!(
+            "Invalid size '{}': not a whole number of bytes",
+            input
+        ));
+    }
+    Ok(bytes as u64)
+}
+
+/// Format a number of bytes with the largest binary unit that keeps it above 1 (e.g. "1.5 GiB")
+pub fn format_size(bytes: u64) -> String {
+    const BINARY: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < BINARY.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        let rendered = format!("{:.2}", value);
+        let rendered = rendered.trim_end_matches('0').trim_end_matches('.');
+        format!("{} {}", rendered, BINARY[unit])
+    }
+}
+
+/// Serde helpers for byte-size fields in configuration structs.
+/// Accepts a human-readable string ("64MiB") or an integer number of bytes,
+/// and serializes back to an integer.
+pub mod size_serde {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Bytes(u64),
+        Text(String),
+    }
+
+    pub fn serialize<S: Serializer>(bytes: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(*bytes)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        match Raw::deserialize(deserializer)? {
+            Raw::Bytes(bytes) => Ok(bytes),
+            Raw::Text(text) => super::parse_size(&text).map_err(serde::de::Error::custom),
+        }
+    }
+}
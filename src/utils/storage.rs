@@ -0,0 +1,192 @@
+// runar_common/src/utils/storage.rs
+//
+// Persistence of `ArcValueType` values in embedded key-value stores.
+//
+// Stored blobs have a small header so that values written by older versions
+// can be recognized and corrupted blobs are rejected instead of being
+// deserialized into garbage:
+//
+//   [magic "RV"] [format version: u8] [crc32c of payload: u32 LE] [payload]
+//
+// The payload is the `SerializerRegistry` encoding of the value.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use anyhow::{anyhow, Result};
+
+use crate::errors::{ErrorCode, RunarError};
+use crate::types::{ArcValueType, SerializerRegistry};
+use crate::utils::integrity::crc32c;
+
+/// Magic bytes at the start of every stored blob
+pub const BLOB_MAGIC: [u8; 2] = *b"RV";
+
+/// Current blob format version
+pub const BLOB_FORMAT_VERSION: u8 = 1;
+
+const HEADER_LEN: usize = 7;
+
+/// Encode a value into a self-describing, checksummed blob
+pub fn encode_blob(registry: &SerializerRegistry, value: &ArcValueType) -> Result<Vec<u8>> {
+    let payload = registry.serialize_value(value)?;
+    let mut blob = Vec::with_capacity(HEADER_LEN + payload.len());
+    blob.extend_from_slice(&BLOB_MAGIC);
+    blob.push(BLOB_FORMAT_VERSION);
+    blob.extend_from_slice(&crc32c(&payload).to_le_bytes());
+    blob.extend_from_slice(&payload);
+    Ok(blob)
+}
+
+/// Decode a blob written by `encode_blob`.
+/// Corrupted or unrecognized blobs fail with a `Serialization` `RunarError`.
+pub fn decode_blob(registry: &SerializerRegistry, blob: &[u8]) -> Result<ArcValueType> {
+    if blob.len() < HEADER_LEN || blob[..2] != BLOB_MAGIC {
+        return Err(corrupt("missing blob header"));
+    }
+    if blob[2] != BLOB_FORMAT_VERSION {
+        return Err(corrupt(&format!(
+            "unsupported blob format version {}",
+            blob[2]
+        )));
+    }
+    let expected = u32::from_le_bytes([blob[3], blob[4], blob[5], blob[6]]);
+    let payload = &blob[HEADER_LEN..];
+    let actual = crc32c(payload);
+    if actual != expected {
+        return Err(corrupt(&format!(
+            "checksum mismatch (expected {:08x}, got {:08x})",
+            expected, actual
+        )));
+    }
+    registry.deserialize_value(Arc::from(payload))
+}
+
+fn corrupt(reason: &str) -> anyhow::Error {
+    RunarError::new(
+        ErrorCode::Serialization,
+        format!("Corrupted value blob: {}", reason),
+    )
+    .into()
+}
+
+/// Minimal key-value backend interface (implemented by adapters over sled, sqlite, ...)
+pub trait KvBackend: Send + Sync {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>>;
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<()>;
+    fn remove(&self, key: &[u8]) -> Result<()>;
+}
+
+/// Adapter turning a pair of closures into a `KvBackend`; removal writes are not supported
+pub struct FnBackend<G, P> {
+    get: G,
+    put: P,
+}
+
+impl<G, P> FnBackend<G, P>
+where
+    G: Fn(&[u8]) -> Result<Option<Vec<u8>>> + Send + Sync,
+    P: Fn(&[u8], &[u8]) -> Result<()> + Send + Sync,
+{
+    /// Create a backend from read and write closures
+    pub fn new(get: G, put: P) -> Self {
+        Self { get, put }
+    }
+}
+
+impl<G, P> KvBackend for FnBackend<G, P>
+where
+    G: Fn(&[u8]) -> Result<Option<Vec<u8>>> + Send + Sync,
+    P: Fn(&[u8], &[u8]) -> Result<()> + Send + Sync,
+{
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        (self.get)(key)
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        (self.put)(key, value)
+    }
+
+    fn remove(&self, _key: &[u8]) -> Result<()> {
+        Err(anyhow!("This backend does not support removal"))
+    }
+}
+
+/// In-memory backend, mainly for tests
+#[derive(Debug, Default)]
+pub struct MemoryKv {
+    entries: RwLock<HashMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl MemoryKv {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl KvBackend for MemoryKv {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(self
+            .entries
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(key)
+            .cloned())
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<()> {
+        self.entries
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(key.to_vec(), value.to_vec());
+        Ok(())
+    }
+
+    fn remove(&self, key: &[u8]) -> Result<()> {
+        self.entries
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(key);
+        Ok(())
+    }
+}
+
+/// Reads and writes `ArcValueType` values by string key through a `KvBackend`
+pub struct ValueStore<B> {
+    registry: Arc<SerializerRegistry>,
+    backend: B,
+}
+
+impl<B: KvBackend> ValueStore<B> {
+    /// Create a store over a backend
+    pub fn new(registry: Arc<SerializerRegistry>, backend: B) -> Self {
+        Self { registry, backend }
+    }
+
+    /// Store a value under a key
+    pub fn put(&self, key: &str, value: &ArcValueType) -> Result<()> {
+        let blob = encode_blob(&self.registry, value)?;
+        self.backend.put(key.as_bytes(), &blob)
+    }
+
+    /// Load the value stored under a key
+    pub fn get(&self, key: &str) -> Result<Option<ArcValueType>> {
+        match self.backend.get(key.as_bytes())? {
+            Some(blob) => decode_blob(&self.registry, &blob)
+                .map(Some)
+                .map_err(|e| e.context(format!("Failed to load value for key '{}'", key))),
+            None => Ok(None),
+        }
+    }
+
+    /// Remove the value stored under a key
+    pub fn remove(&self, key: &str) -> Result<()> {
+        self.backend.remove(key.as_bytes())
+    }
+
+    /// Get the underlying backend
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+}
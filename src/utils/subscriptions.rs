@@ -0,0 +1,234 @@
+// runar_common/src/utils/subscriptions.rs
+//
+// Topic subscription table: wildcard topic patterns mapped to subscribers.
+//
+// Patterns are stored in a trie of path segments with separate branches for
+// the `*` and `>` wildcards, so matching a concrete topic walks the trie once
+// per segment instead of comparing it against every pattern. Glob segments
+// such as `temp-*` or `~temp-[0-9]` get a branch each and are tried in turn;
+// they reuse the globs compiled when the pattern's `TopicPath` was parsed.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::types::IStr;
+use crate::utils::glob::Glob;
+use crate::utils::paths::{SegmentMatcher, TopicPath};
+
+/// Handle returned by `SubscriptionTable::insert`, used to unsubscribe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubscriptionId(u64);
+
+impl SubscriptionId {
+    /// The numeric id (unique within its table)
+    pub fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for SubscriptionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sub-{}", self.0)
+    }
+}
+
+struct Node<T> {
+    children: HashMap<String, Node<T>>,
+    // Subtree for a `*` segment
+    single: Option<Box<Node<T>>>,
+    // Subtrees for glob segments
+    globs: Vec<(Glob, Node<T>)>,
+    // Subscribers whose pattern ends at this node
+    exact: Vec<(SubscriptionId, T)>,
+    // Subscribers whose pattern ends with `>` after this node
+    multi: Vec<(SubscriptionId, T)>,
+}
+
+impl<T> Default for Node<T> {
+    fn default() -> Self {
+        Self {
+            children: HashMap::new(),
+            single: None,
+            globs: Vec::new(),
+            exact: Vec::new(),
+            multi: Vec::new(),
+        }
+    }
+}
+
+impl<T> Node<T> {
+    fn is_empty(&self) -> bool {
+        self.children.is_empty()
+            && self.single.is_none()
+            && self.globs.is_empty()
+            && self.exact.is_empty()
+            && self.multi.is_empty()
+    }
+
+    fn collect<'a>(&'a self, segments: &[&str], out: &mut Vec<&'a (SubscriptionId, T)>) {
+        let Some((first, rest)) = segments.split_first() else {
+            out.extend(self.exact.iter());
+            return;
+        };
+        out.extend(self.multi.iter());
+        if let Some(child) = self.children.get(*first) {
+            child.collect(rest, out);
+        }
+        if let Some(single) = &self.single {
+            single.collect(rest, out);
+        }
+        for (pattern, child) in &self.globs {
+            if pattern.matches(first) {
+                child.collect(rest, out);
+            }
+        }
+    }
+
+    // Remove a subscriber, pruning nodes left empty
+    fn remove(&mut self, segments: &[(&str, &SegmentMatcher)], id: SubscriptionId) -> Option<T> {
+        let take = |entries: &mut Vec<(SubscriptionId, T)>| {
+            let index = entries.iter().position(|(entry, _)| *entry == id)?;
+            Some(entries.remove(index).1)
+        };
+        match segments.split_first() {
+            None => take(&mut self.exact),
+            Some(((_, SegmentMatcher::Multi), [])) => take(&mut self.multi),
+            Some(((_, SegmentMatcher::Single), rest)) => {
+                let single = self.single.as_mut()?;
+                let removed = single.remove(rest, id);
+                if single.is_empty() {
+                    self.single = None;
+                }
+                removed
+            }
+            Some(((_, SegmentMatcher::Glob(glob)), rest)) => {
+                let index = self.globs.iter().position(|(pattern, _)| pattern == glob)?;
+                let removed = self.globs[index].1.remove(rest, id);
+                if self.globs[index].1.is_empty() {
+                    self.globs.remove(index);
+                }
+                removed
+            }
+            Some(((segment, _), rest)) => {
+                let child = self.children.get_mut(*segment)?;
+                let removed = child.remove(rest, id);
+                if child.is_empty() {
+                    self.children.remove(*segment);
+                }
+                removed
+            }
+        }
+    }
+}
+
+/// Maps topic patterns (with `*` and `>` wildcards) to subscriber values and
+/// finds every subscriber of a concrete topic in time proportional to the
+/// number of its segments.
+pub struct SubscriptionTable<T> {
+    root: Node<T>,
+    patterns: HashMap<SubscriptionId, TopicPath>,
+    next_id: u64,
+}
+
+impl<T> Default for SubscriptionTable<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SubscriptionTable<T> {
+    /// Create an empty table
+    pub fn new() -> Self {
+        Self {
+            root: Node::default(),
+            patterns: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Subscribe `value` to every topic matching `pattern`
+    pub fn insert(&mut self, pattern: &TopicPath, value: T) -> SubscriptionId {
+        self.next_id += 1;
+        let id = SubscriptionId(self.next_id);
+        let mut node = &mut self.root;
+        for (segment, matcher) in pattern.segments().iter().zip(pattern.matchers()) {
+            match matcher {
+                // `TopicPath` only allows `>` as the last segment
+                SegmentMatcher::Multi => {
+                    node.multi.push((id, value));
+                    self.patterns.insert(id, pattern.clone());
+                    return id;
+                }
+                SegmentMatcher::Single => node = node.single.get_or_insert_with(Default::default),
+                SegmentMatcher::Glob(glob) => {
+                    let index = match node.globs.iter().position(|(p, _)| p == glob) {
+                        Some(index) => index,
+                        None => {
+                            node.globs.push((glob.clone(), Node::default()));
+                            node.globs.len() - 1
+                        }
+                    };
+                    node = &mut node.globs[index].1;
+                }
+                SegmentMatcher::Literal => {
+                    node = node.children.entry(segment.to_string()).or_default()
+                }
+            }
+        }
+        node.exact.push((id, value));
+        self.patterns.insert(id, pattern.clone());
+        id
+    }
+
+    /// Unsubscribe, returning the subscriber value
+    pub fn remove(&mut self, id: SubscriptionId) -> Option<T> {
+        let pattern = self.patterns.remove(&id)?;
+        let segments: Vec<_> = pattern
+            .segments()
+            .iter()
+            .map(IStr::as_str)
+            .zip(pattern.matchers())
+            .collect();
+        self.root.remove(&segments, id)
+    }
+
+    /// The pattern a subscription was registered with
+    pub fn pattern(&self, id: SubscriptionId) -> Option<&TopicPath> {
+        self.patterns.get(&id)
+    }
+
+    /// All subscribers of a concrete topic, in subscription order
+    pub fn matches(&self, topic: &TopicPath) -> Vec<&T> {
+        self.matches_with_ids(topic)
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect()
+    }
+
+    /// All subscribers of a concrete topic with their ids, in subscription order
+    pub fn matches_with_ids(&self, topic: &TopicPath) -> Vec<(SubscriptionId, &T)> {
+        let segments: Vec<&str> = topic.segments().iter().map(|s| s.as_str()).collect();
+        let mut found = Vec::new();
+        self.root.collect(&segments, &mut found);
+        found.sort_by_key(|(id, _)| *id);
+        found.into_iter().map(|(id, value)| (*id, value)).collect()
+    }
+
+    /// Number of subscriptions
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Whether the table has no subscriptions
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+}
+
+impl<T> fmt::Debug for SubscriptionTable<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubscriptionTable")
+            .field("subscriptions", &self.patterns.len())
+            .finish()
+    }
+}
@@ -0,0 +1,110 @@
+// runar_common/src/utils/task.rs
+//
+// Spawning background work with a standard logging and metrics envelope.
+//
+// Tasks started with `spawn_logged` log when they start, finish or panic,
+// record their run time in the global metrics registry, and make their
+// logger (with its correlation context) available through `current_logger`.
+
+use std::any::Any;
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use tokio::task::JoinHandle;
+
+use crate::logging::Logger;
+use crate::metrics::{self, MetricLabels};
+use crate::utils::time::format_duration;
+
+/// Histogram of task run times in seconds, labelled with the logger's
+/// component and the task name (in the `action` label)
+pub const TASK_DURATION_METRIC: &str = "runar_task_duration_seconds";
+
+/// Counter of panicked tasks, labelled like `TASK_DURATION_METRIC`
+pub const TASK_PANICS_METRIC: &str = "runar_task_panics_total";
+
+tokio::task_local! {
+    static CURRENT_LOGGER: Logger;
+}
+
+/// The logger of the `spawn_logged` task running on this task (if any)
+pub fn current_logger() -> Option<Logger> {
+    CURRENT_LOGGER.try_with(Logger::clone).ok()
+}
+
+/// Spawn `fut` on the tokio runtime as the task `name`.
+///
+/// The task logs its start and end through `logger`, logs panics as errors
+/// instead of losing them in the runtime, and records its duration. The
+/// handle resolves to `None` if the task panicked.
+pub fn spawn_logged<F>(logger: &Logger, name: &str, fut: F) -> JoinHandle<Option<F::Output>>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let logger = logger.clone();
+    let name = name.to_string();
+    tokio::spawn(async move {
+        let labels = MetricLabels::component(logger.component()).with_action(name.as_str());
+        logger.debug(format!("Task '{}' started", name));
+        let started = Instant::now();
+
+        let result = CURRENT_LOGGER
+            .scope(logger.clone(), CatchUnwind(Box::pin(fut)))
+            .await;
+        let elapsed = started.elapsed();
+        metrics::global()
+            .histogram(TASK_DURATION_METRIC, &labels)
+            .observe(elapsed.as_secs_f64());
+
+        match result {
+            Ok(output) => {
+                logger.debug(format!(
+                    "Task '{}' finished after {}",
+                    name,
+                    format_duration(elapsed)
+                ));
+                Some(output)
+            }
+            Err(panic) => {
+                metrics::global().counter(TASK_PANICS_METRIC, &labels).inc();
+                logger.error(format!(
+                    "Task '{}' panicked after {}: {}",
+                    name,
+                    format_duration(elapsed),
+                    panic_message(panic.as_ref())
+                ));
+                None
+            }
+        }
+    })
+}
+
+// Polls a future, turning a panic into an error
+struct CatchUnwind<F>(Pin<Box<F>>);
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let inner = self.0.as_mut();
+        match catch_unwind(AssertUnwindSafe(|| inner.poll(cx))) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Err(panic) => Poll::Ready(Err(panic)),
+        }
+    }
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message
+    } else {
+        "<non-string panic payload>"
+    }
+}
@@ -0,0 +1,287 @@
+// runar_common/src/utils/time.rs
+//
+// Time helpers: epoch timestamps, monotonic stamps and human-readable durations.
+//
+// Epoch values are plain integers because that is how they travel in
+// metadata (e.g. `ServiceMetadata::registration_time`).
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+
+// `std::time` clocks panic on wasm32-unknown-unknown, so the browser build uses
+// the JS-backed drop-in replacements. Code that needs the clock should import
+// these rather than `std::time::{Instant, SystemTime}`.
+#[cfg(not(target_arch = "wasm32"))]
+pub use std::time::{Instant, SystemTime, UNIX_EPOCH};
+#[cfg(target_arch = "wasm32")]
+pub use web_time::{Instant, SystemTime, UNIX_EPOCH};
+
+lazy_static! {
+    static ref PROCESS_START: Instant = Instant::now();
+}
+
+/// Get the current time in milliseconds since the UNIX epoch
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Get the current time in seconds since the UNIX epoch
+pub fn now_secs() -> u64 {
+    to_epoch_secs(SystemTime::now())
+}
+
+/// Get a monotonic timestamp in nanoseconds, relative to the first call in this process.
+/// Unlike epoch time it never goes backwards, so it is suitable for measuring intervals.
+pub fn monotonic_nanos() -> u64 {
+    PROCESS_START.elapsed().as_nanos() as u64
+}
+
+/// Get a monotonic timestamp in milliseconds (see `monotonic_nanos`)
+pub fn monotonic_millis() -> u64 {
+    PROCESS_START.elapsed().as_millis() as u64
+}
+
+/// Source of wall-clock time, so code that timestamps its output can be
+/// driven by a `MockClock` in tests
+pub trait Clock: Send + Sync {
+    /// The current time
+    fn now(&self) -> SystemTime;
+
+    /// The current time in milliseconds since the UNIX epoch
+    fn now_millis(&self) -> u64 {
+        to_epoch_millis(self.now())
+    }
+}
+
+/// The system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock that only moves when told to. Clones share the same time.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: Arc<Mutex<SystemTime>>,
+}
+
+impl MockClock {
+    /// Create a clock stopped at `start`
+    pub fn new(start: SystemTime) -> Self {
+        MockClock {
+            now: Arc::new(Mutex::new(start)),
+        }
+    }
+
+    /// Create a clock stopped at `millis` milliseconds since the UNIX epoch
+    pub fn at_epoch_millis(millis: u64) -> Self {
+        Self::new(from_epoch_millis(millis))
+    }
+
+    /// Move the clock to `time`
+    pub fn set(&self, time: SystemTime) {
+        *self.lock() = time;
+    }
+
+    /// Move the clock forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        *self.lock() += duration;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SystemTime> {
+        self.now
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> SystemTime {
+        *self.lock()
+    }
+}
+
+/// Convert a `SystemTime` to seconds since the UNIX epoch (0 for times before the epoch)
+pub fn to_epoch_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Convert a `SystemTime` to milliseconds since the UNIX epoch (0 for times before the epoch)
+pub fn to_epoch_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Convert seconds since the UNIX epoch to a `SystemTime`
+pub fn from_epoch_secs(secs: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+/// Convert milliseconds since the UNIX epoch to a `SystemTime`
+pub fn from_epoch_millis(millis: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(millis)
+}
+
+/// Format a duration for humans using its two largest units, e.g. "3m 12s", "2d 4h" or "250ms"
+pub fn format_duration(duration: Duration) -> String {
+    let total_secs = duration.as_secs();
+    if total_secs == 0 {
+        return format!("{}ms", duration.subsec_millis());
+    }
+
+    let units = [
+        (total_secs / 86_400, "d"),
+        (total_secs % 86_400 / 3_600, "h"),
+        (total_secs % 3_600 / 60, "m"),
+        (total_secs % 60, "s"),
+    ];
+    let first = units
+        .iter()
+        .position(|(value, _)| *value > 0)
+        .unwrap_or(units.len() - 1);
+    units[first..]
+        .iter()
+        .take(2)
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| format!("{}{}", value, unit))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse a human-readable duration such as "250ms", "5m", "1.5h" or "3m 12s".
+/// Supported units are ns, us (or µs), ms, s, m, h and d; a unit is required.
+pub fn parse_duration(input: &str) -> Result<Duration> {
+    let compact: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.is_empty() {
+        return Err(anyhow!("Empty duration"));
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = compact.as_str();
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let (number, after) = rest.split_at(number_len);
+        let unit_len = after
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(after.len());
+        let (unit, after) = after.split_at(unit_len);
+
+        let nanos_per_unit: u64 = match unit {
+            "ns" => 1,
+            "us" | "µs" => 1_000,
+            "ms" => 1_000_000,
+            "s" => 1_000_000_000,
+            "m" => 60_000_000_000,
+            "h" => 3_600_000_000_000,
+            "d" => 86_400_000_000_000,
+            "" => return Err(anyhow!("Invalid duration '{}': missing unit", input)),
+            other => {
+                return Err(anyhow!(
+                    "Invalid duration '{}': unknown unit '{}'",
+                    input,
+                    other
+                ))
+            }
+        };
+        // Whole numbers are counted exactly so formatted durations read back unchanged
+        let part = match number.parse::<u64>() {
+            Ok(value) => value
+                .checked_mul(nanos_per_unit)
+                .map(Duration::from_nanos)
+                .ok_or_else(|| anyhow!("Invalid duration '{}': too long", input))?,
+            Err(_) => {
+                let value: f64 = number.parse().map_err(|_| {
+                    anyhow!("Invalid duration '{}': bad number '{}'", input, number)
+                })?;
+                Duration::try_from_secs_f64(value * nanos_per_unit as f64 / 1e9)
+                    .map_err(|e| anyhow!("Invalid duration '{}': {}", input, e))?
+            }
+        };
+        total = total
+            .checked_add(part)
+            .ok_or_else(|| anyhow!("Invalid duration '{}': too long", input))?;
+        rest = after;
+    }
+    Ok(total)
+}
+
+/// Serde helpers for `Duration` fields in configuration structs.
+/// Accepts a human-readable string ("30s") or an integer number of seconds,
+/// and serializes back to the human-readable form.
+///
+/// ```
+/// use std::time::Duration;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct NetworkConfig {
+///     #[serde(with = "runar_common::utils::time::duration_serde")]
+///     timeout: Duration,
+/// }
+///
+/// let config: NetworkConfig = serde_json::from_str(r#"{"timeout": "1m 30s"}"#).unwrap();
+/// assert_eq!(config.timeout, Duration::from_secs(90));
+/// ```
+pub mod duration_serde {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Secs(u64),
+        Text(String),
+    }
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&super::format_duration_exact(*duration))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        match Raw::deserialize(deserializer)? {
+            Raw::Secs(secs) => Ok(Duration::from_secs(secs)),
+            Raw::Text(text) => super::parse_duration(&text).map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// Format a duration using every non-zero unit down to nanoseconds (e.g. "1h 5s"
+/// or "2m 250ms 40us"). `parse_duration` reads the result back unchanged.
+pub fn format_duration_exact(duration: Duration) -> String {
+    let total_secs = duration.as_secs();
+    let parts = [
+        (total_secs / 86_400, "d"),
+        (total_secs % 86_400 / 3_600, "h"),
+        (total_secs % 3_600 / 60, "m"),
+        (total_secs % 60, "s"),
+        (duration.subsec_millis() as u64, "ms"),
+        (duration.subsec_micros() as u64 % 1_000, "us"),
+        (duration.subsec_nanos() as u64 % 1_000, "ns"),
+    ];
+    let rendered: Vec<String> = parts
+        .iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| format!("{}{}", value, unit))
+        .collect();
+    if rendered.is_empty() {
+        "0s".to_string()
+    } else {
+        rendered.join(" ")
+    }
+}
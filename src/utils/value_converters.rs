@@ -1,8 +1,19 @@
 // runar_common/src/utils/value_converters.rs
 //
 // Utility functions for working with ArcValueType
+//
+// One helper per kind of value, so call sites do not need to know which
+// `ArcValueType` constructor and category fit. The number readers accept
+// any numeric primitive that fits (see `utils::num`).
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use anyhow::Result;
 
 use crate::types::ArcValueType;
+use crate::utils::num;
+use crate::utils::time::{self, SystemTime};
 
 /// Create a null/empty ArcValueType
 pub fn null_value() -> ArcValueType {
@@ -19,7 +30,51 @@ pub fn number_value(n: f64) -> ArcValueType {
     ArcValueType::new_primitive(n)
 }
 
+/// Create an ArcValueType from an integer
+pub fn int_value(n: i64) -> ArcValueType {
+    ArcValueType::new_primitive(n)
+}
+
 /// Create an ArcValueType from a boolean
 pub fn bool_value(b: bool) -> ArcValueType {
     ArcValueType::new_primitive(b)
 }
+
+/// Create a list of (possibly heterogeneous) values
+pub fn list_value(items: Vec<ArcValueType>) -> ArcValueType {
+    ArcValueType::new_list(items)
+}
+
+/// Create a map of (possibly heterogeneous) values
+pub fn map_value(entries: HashMap<String, ArcValueType>) -> ArcValueType {
+    ArcValueType::new_map(entries)
+}
+
+/// Create a raw bytes value
+pub fn bytes_value(bytes: Vec<u8>) -> ArcValueType {
+    ArcValueType::new_bytes(bytes)
+}
+
+/// Create a timestamp value: milliseconds since the UNIX epoch as an `i64`
+/// (0 for times before the epoch), the integer form `Timestamp` schema
+/// fields accept
+pub fn timestamp_value(at: SystemTime) -> ArcValueType {
+    ArcValueType::new_primitive(time::to_epoch_millis(at) as i64)
+}
+
+/// Create a struct value; the type must be registered with the
+/// `SerializerRegistry` to be sent over the wire
+pub fn struct_value<T: 'static + Debug + Send + Sync>(value: T) -> ArcValueType {
+    ArcValueType::from_struct(value)
+}
+
+/// Read a number from a value holding any numeric primitive
+pub fn value_as_number(value: &ArcValueType) -> Result<f64> {
+    num::coerce(value)
+}
+
+/// Read an integer from a value holding any numeric primitive whose value
+/// is a whole number within `i64`
+pub fn value_as_int(value: &ArcValueType) -> Result<i64> {
+    num::coerce(value)
+}
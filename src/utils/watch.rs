@@ -0,0 +1,229 @@
+// runar_common/src/utils/watch.rs
+//
+// Observable value cells for publishing node state (config, membership).
+//
+// A `WatchValue` holds the current value; any clone of it can publish a new
+// one, and observers obtained with `subscribe` wait for changes. Every
+// transition is logged at debug level through the cell's logger.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+use crate::logging::Logger;
+use crate::types::ArcValueType;
+
+struct Shared<T> {
+    name: String,
+    sender: watch::Sender<T>,
+    logger: Arc<Logger>,
+    describe: fn(&T) -> String,
+    version: AtomicU64,
+}
+
+/// Current value plus change notifications, shareable between producers.
+/// Observers only see the latest value; intermediate updates may be skipped.
+pub struct WatchValue<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Clone for WatchValue<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T: Clone + fmt::Debug> WatchValue<T> {
+    /// Create a cell named `name` (used in log messages) holding `initial`
+    pub fn new(name: impl Into<String>, initial: T, logger: Arc<Logger>) -> Self {
+        Self::with_describe(name, initial, logger, |value| format!("{:?}", value))
+    }
+}
+
+impl WatchValue<ArcValueType> {
+    /// Create a cell for `ArcValueType` values; transitions are logged as
+    /// JSON where the value can be converted, and by type otherwise
+    pub fn new_value(name: impl Into<String>, initial: ArcValueType, logger: Arc<Logger>) -> Self {
+        Self::with_describe(name, initial, logger, describe_value)
+    }
+
+    /// Publish `value` unless it has the same content as the current value.
+    ///
+    /// `ArcValueType` equality only recognizes the same shared value, so
+    /// values are also compared by their JSON form when both convert.
+    pub fn set_if_content_changed(&self, value: ArcValueType) -> bool {
+        self.set_unless(value, |current, value| {
+            current == value
+                || matches!(
+                    (current.to_json(), value.to_json()),
+                    (Ok(a), Ok(b)) if a == b
+                )
+        })
+    }
+}
+
+impl<T: Clone> WatchValue<T> {
+    /// Create a cell that renders values with `describe` when logging transitions
+    pub fn with_describe(
+        name: impl Into<String>,
+        initial: T,
+        logger: Arc<Logger>,
+        describe: fn(&T) -> String,
+    ) -> Self {
+        let (sender, _) = watch::channel(initial);
+        Self {
+            shared: Arc::new(Shared {
+                name: name.into(),
+                sender,
+                logger,
+                describe,
+                version: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// The cell name
+    pub fn name(&self) -> &str {
+        &self.shared.name
+    }
+
+    /// A copy of the current value
+    pub fn get(&self) -> T {
+        self.shared.sender.borrow().clone()
+    }
+
+    /// Publish a new value, returning the previous one
+    pub fn set(&self, value: T) -> T {
+        let previous = self.shared.sender.send_replace(value);
+        self.record_transition(&previous);
+        previous
+    }
+
+    /// Publish `value` only if it differs from the current value.
+    /// Returns true if it was published.
+    pub fn set_if_changed(&self, value: T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.set_unless(value, |current, value| current == value)
+    }
+
+    // Publish `value` unless `same(current, value)`
+    fn set_unless(&self, value: T, same: impl FnOnce(&T, &T) -> bool) -> bool {
+        let mut previous = None;
+        self.shared.sender.send_if_modified(|current| {
+            if same(current, &value) {
+                return false;
+            }
+            previous = Some(std::mem::replace(current, value));
+            true
+        });
+        match previous {
+            Some(previous) => {
+                self.record_transition(&previous);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Modify the current value in place and notify observers
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        let mut previous = None;
+        self.shared.sender.send_modify(|current| {
+            previous = Some(current.clone());
+            f(current);
+        });
+        if let Some(previous) = previous {
+            self.record_transition(&previous);
+        }
+    }
+
+    /// Number of values published since the cell was created
+    pub fn version(&self) -> u64 {
+        self.shared.version.load(Ordering::Relaxed)
+    }
+
+    /// Observe the cell; the observer starts at the current value
+    pub fn subscribe(&self) -> WatchReceiver<T> {
+        WatchReceiver {
+            inner: self.shared.sender.subscribe(),
+        }
+    }
+
+    /// Number of live observers
+    pub fn observer_count(&self) -> usize {
+        self.shared.sender.receiver_count()
+    }
+
+    fn record_transition(&self, previous: &T) {
+        let version = self.shared.version.fetch_add(1, Ordering::Relaxed) + 1;
+        let describe = self.shared.describe;
+        let current = describe(&self.shared.sender.borrow());
+        self.shared.logger.debug(format!(
+            "'{}' changed (v{}): {} -> {}",
+            self.shared.name,
+            version,
+            describe(previous),
+            current
+        ));
+    }
+}
+
+impl<T> fmt::Debug for WatchValue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WatchValue")
+            .field("name", &self.shared.name)
+            .field("version", &self.shared.version.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+/// Observer of a `WatchValue`
+pub struct WatchReceiver<T> {
+    inner: watch::Receiver<T>,
+}
+
+impl<T: Clone> WatchReceiver<T> {
+    /// A copy of the current value (marks it as seen)
+    pub fn get(&mut self) -> T {
+        self.inner.borrow_and_update().clone()
+    }
+
+    /// Whether a value was published since the last `get` or `changed`
+    pub fn has_changed(&self) -> bool {
+        self.inner.has_changed().unwrap_or(false)
+    }
+
+    /// Wait for the next published value; `None` once every `WatchValue`
+    /// clone has been dropped
+    pub async fn changed(&mut self) -> Option<T> {
+        self.inner.changed().await.ok()?;
+        Some(self.inner.borrow_and_update().clone())
+    }
+}
+
+impl<T> Clone for WatchReceiver<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+fn describe_value(value: &ArcValueType) -> String {
+    match value.to_json() {
+        Ok(json) => json.to_string(),
+        Err(_) => format!(
+            "{:?}<{}>",
+            value.category,
+            value
+                .stored_type_name()
+                .unwrap_or_else(|_| "<unknown>".to_string())
+        ),
+    }
+}
@@ -0,0 +1,421 @@
+// runar_common/src/wire.rs
+//
+// Value wire format definitions (categories and header layout).
+// This module only uses `core` and `alloc`, so it is available when the crate
+// is built without the `std` feature (e.g. for embedded peers).
+//
+// Header layout: [category marker][type name length][type name bytes][payload]
+// Null values consist of the category marker only. The high nibble of the
+// marker byte identifies the codec of the payload (0 = bincode), so headers
+// written before codecs were selectable decode unchanged.
+//
+// Type names up to 255 bytes use a one byte length. Longer names set
+// `LONG_TYPE_NAME_FLAG` in the marker and write the length as a LEB128
+// varint, so peers that predate long names still read every header they
+// could read before.
+//
+// When `FINGERPRINT_FLAG` is set, an eight byte little endian fingerprint of
+// the type's layout follows the type name, so receivers can reject payloads
+// written against a different version of the type.
+//
+// `CHECKSUM_FLAG` is set when a checksum trails the value (see
+// `SerializerRegistry::set_checksum`), so a receiver that disagrees about
+// checksums rejects the payload instead of misreading its last bytes. The
+// flag sits in the top bit of the codec field, so peers that predate it
+// reject such payloads as having an unknown codec.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Longest type name written with the original one byte length prefix
+pub const SHORT_TYPE_NAME_LEN: usize = 255;
+
+/// Marker bit set when a type fingerprint follows the type name
+pub const FINGERPRINT_FLAG: u8 = 0x80;
+
+/// Marker bit set when a checksum follows the encoded value
+pub const CHECKSUM_FLAG: u8 = 0x40;
+
+/// Size of the fingerprint written after the type name
+pub const FINGERPRINT_LEN: usize = 8;
+
+/// Longest type name the header can carry (a two byte varint length)
+pub const MAX_TYPE_NAME_LEN: usize = 0x3fff;
+
+/// Marker bit set when the type name length is a varint
+pub const LONG_TYPE_NAME_FLAG: u8 = 0x08;
+
+/// Number of leading bytes included in the hexdump snippet of a bad payload
+pub const SNIPPET_LEN: usize = 16;
+
+/// Categorizes the value for efficient dispatch
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueCategory {
+    Primitive,
+    List,
+    Map,
+    Struct,
+    Null,
+    /// Raw bytes (used for Vec<u8>, not for lazy deserialization)
+    Bytes,
+    /// Unparsed JSON text (see `RawJson`), parsed only when accessed
+    Json,
+}
+
+impl ValueCategory {
+    /// The marker byte written at the start of a serialized value
+    pub fn marker(self) -> u8 {
+        match self {
+            ValueCategory::Primitive => 0x01,
+            ValueCategory::List => 0x02,
+            ValueCategory::Map => 0x03,
+            ValueCategory::Struct => 0x04,
+            ValueCategory::Null => 0x05,
+            ValueCategory::Bytes => 0x06,
+            ValueCategory::Json => 0x07,
+        }
+    }
+
+    /// Look up the category for a marker byte
+    pub fn from_marker(byte: u8) -> Option<Self> {
+        match byte {
+            0x01 => Some(ValueCategory::Primitive),
+            0x02 => Some(ValueCategory::List),
+            0x03 => Some(ValueCategory::Map),
+            0x04 => Some(ValueCategory::Struct),
+            0x05 => Some(ValueCategory::Null),
+            0x06 => Some(ValueCategory::Bytes),
+            0x07 => Some(ValueCategory::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Encoding of a value payload, carried in the header so receivers can pick
+/// the matching decoder
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CodecId {
+    /// bincode 1.x (the default)
+    #[default]
+    Bincode,
+    /// JSON text
+    Json,
+    /// CBOR (RFC 8949)
+    Cbor,
+    /// MessagePack
+    MessagePack,
+}
+
+impl CodecId {
+    /// The identifier stored in bits 4-5 of the marker byte
+    pub fn id(self) -> u8 {
+        match self {
+            CodecId::Bincode => 0,
+            CodecId::Json => 1,
+            CodecId::Cbor => 2,
+            CodecId::MessagePack => 3,
+        }
+    }
+
+    /// Look up the codec for an identifier
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(CodecId::Bincode),
+            1 => Some(CodecId::Json),
+            2 => Some(CodecId::Cbor),
+            3 => Some(CodecId::MessagePack),
+            _ => None,
+        }
+    }
+}
+
+/// A decoded value header borrowing from the serialized bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WireHeader<'a> {
+    /// Category of the value
+    pub category: ValueCategory,
+    /// Codec of the payload
+    pub codec: CodecId,
+    /// Type name of the payload (empty for Null)
+    pub type_name: &'a str,
+    /// Layout fingerprint of the type, if the writer embedded one
+    pub fingerprint: Option<u64>,
+    /// Whether a checksum trails the value
+    pub checksummed: bool,
+    /// Offset of the payload within the serialized bytes
+    pub data_offset: usize,
+}
+
+/// Decode the header at the start of `bytes`
+pub fn decode_header(bytes: &[u8]) -> Result<WireHeader<'_>, WireError> {
+    let truncated = |offset: usize, needed: usize| WireError::TruncatedAt {
+        offset,
+        needed,
+        snippet: hex_snippet(bytes),
+    };
+
+    let marker = *bytes.first().ok_or_else(|| truncated(0, 1))?;
+    let bad_category = || WireError::BadCategory {
+        byte: marker,
+        snippet: hex_snippet(bytes),
+    };
+    let category = ValueCategory::from_marker(marker & 0x07).ok_or_else(bad_category)?;
+    let codec = CodecId::from_id((marker >> 4) & 0x03).ok_or_else(bad_category)?;
+    let has_fingerprint = marker & FINGERPRINT_FLAG != 0;
+    let checksummed = marker & CHECKSUM_FLAG != 0;
+    if category == ValueCategory::Null {
+        if has_fingerprint {
+            return Err(bad_category());
+        }
+        return Ok(WireHeader {
+            category,
+            codec,
+            type_name: "",
+            fingerprint: None,
+            checksummed,
+            data_offset: 1,
+        });
+    }
+
+    let (type_name_len, name_offset) = if marker & LONG_TYPE_NAME_FLAG == 0 {
+        (*bytes.get(1).ok_or_else(|| truncated(1, 1))? as usize, 2)
+    } else {
+        decode_varint_len(bytes)?
+    };
+    let name_end = name_offset + type_name_len;
+    let data_offset = if has_fingerprint {
+        name_end + FINGERPRINT_LEN
+    } else {
+        name_end
+    };
+    if bytes.len() < data_offset {
+        return Err(truncated(bytes.len(), data_offset - bytes.len()));
+    }
+    let type_name = core::str::from_utf8(&bytes[name_offset..name_end]).map_err(|_| {
+        WireError::InvalidTypeName {
+            offset: name_offset,
+            snippet: hex_snippet(bytes),
+        }
+    })?;
+    let fingerprint = has_fingerprint.then(|| {
+        let mut raw = [0u8; FINGERPRINT_LEN];
+        raw.copy_from_slice(&bytes[name_end..data_offset]);
+        u64::from_le_bytes(raw)
+    });
+
+    Ok(WireHeader {
+        category,
+        codec,
+        type_name,
+        fingerprint,
+        checksummed,
+        data_offset,
+    })
+}
+
+/// Append the header for a bincode encoded value of `category` and `type_name` to `out`
+pub fn encode_header(
+    category: ValueCategory,
+    type_name: &str,
+    out: &mut Vec<u8>,
+) -> Result<(), WireError> {
+    encode_header_with_codec(category, CodecId::Bincode, type_name, out)
+}
+
+/// Append the header for a value whose payload is encoded with `codec`
+pub fn encode_header_with_codec(
+    category: ValueCategory,
+    codec: CodecId,
+    type_name: &str,
+    out: &mut Vec<u8>,
+) -> Result<(), WireError> {
+    encode_header_with_fingerprint(category, codec, type_name, None, out)
+}
+
+/// Append a header that also carries the type's layout fingerprint (ignored for Null)
+pub fn encode_header_with_fingerprint(
+    category: ValueCategory,
+    codec: CodecId,
+    type_name: &str,
+    fingerprint: Option<u64>,
+    out: &mut Vec<u8>,
+) -> Result<(), WireError> {
+    let mut marker = category.marker() | (codec.id() << 4);
+    if category == ValueCategory::Null {
+        out.push(marker);
+        return Ok(());
+    }
+    let len = type_name.len();
+    if len > MAX_TYPE_NAME_LEN {
+        return Err(WireError::TypeNameTooLong {
+            name: String::from(type_name),
+            len,
+        });
+    }
+    if fingerprint.is_some() {
+        marker |= FINGERPRINT_FLAG;
+    }
+    if len <= SHORT_TYPE_NAME_LEN {
+        out.push(marker);
+        out.push(len as u8);
+    } else {
+        out.push(marker | LONG_TYPE_NAME_FLAG);
+        // Two LEB128 bytes, since MAX_TYPE_NAME_LEN < 2^14
+        out.push((len & 0x7f) as u8 | 0x80);
+        out.push((len >> 7) as u8);
+    }
+    out.extend_from_slice(type_name.as_bytes());
+    if let Some(fingerprint) = fingerprint {
+        out.extend_from_slice(&fingerprint.to_le_bytes());
+    }
+    Ok(())
+}
+
+// Read the varint type name length following the marker, returning the
+// length and the offset of the type name
+fn decode_varint_len(bytes: &[u8]) -> Result<(usize, usize), WireError> {
+    let mut len = 0usize;
+    for (i, shift) in [0u32, 7].into_iter().enumerate() {
+        let offset = 1 + i;
+        let byte = *bytes.get(offset).ok_or_else(|| WireError::TruncatedAt {
+            offset,
+            needed: 1,
+            snippet: hex_snippet(bytes),
+        })?;
+        len |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return Ok((len, offset + 1));
+        }
+    }
+    Err(WireError::InvalidTypeNameLength {
+        offset: 1,
+        snippet: hex_snippet(bytes),
+    })
+}
+
+/// Errors raised while reading or writing the value wire header.
+///
+/// Decoding variants carry a hexdump of the first bytes of the payload
+/// (`snippet`). Payloads may hold user data, so the hexdump is not part of
+/// the message; failure logging adds it only when payload bytes may be
+/// logged (`FailureLogging::WithPrefix`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WireError {
+    /// The first byte is not a known `ValueCategory` marker
+    BadCategory { byte: u8, snippet: String },
+    /// The type name is longer than `MAX_TYPE_NAME_LEN`
+    TypeNameTooLong { name: String, len: usize },
+    /// The payload ended before the header was complete
+    TruncatedAt {
+        offset: usize,
+        needed: usize,
+        snippet: String,
+    },
+    /// The type name is not valid UTF-8
+    InvalidTypeName { offset: usize, snippet: String },
+    /// The varint type name length is longer than `MAX_TYPE_NAME_LEN` allows
+    InvalidTypeNameLength { offset: usize, snippet: String },
+    /// No deserializer is registered for the type named in the header
+    UnknownType { name: String, snippet: String },
+    /// The payload was written against a different layout of the type
+    SchemaDrift {
+        name: String,
+        expected: u64,
+        found: u64,
+    },
+    /// The payload carries a checksum and the reader expects none, or the
+    /// other way round
+    ChecksumMismatch { present: bool, snippet: String },
+}
+
+impl WireError {
+    /// Byte offset in the payload at which decoding failed (if known)
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            WireError::BadCategory { .. } | WireError::ChecksumMismatch { .. } => Some(0),
+            WireError::TruncatedAt { offset, .. }
+            | WireError::InvalidTypeName { offset, .. }
+            | WireError::InvalidTypeNameLength { offset, .. } => Some(*offset),
+            WireError::TypeNameTooLong { .. }
+            | WireError::UnknownType { .. }
+            | WireError::SchemaDrift { .. } => None,
+        }
+    }
+
+    /// Hexdump of the first bytes of the payload that failed to decode
+    pub fn snippet(&self) -> Option<&str> {
+        match self {
+            WireError::BadCategory { snippet, .. }
+            | WireError::TruncatedAt { snippet, .. }
+            | WireError::InvalidTypeName { snippet, .. }
+            | WireError::InvalidTypeNameLength { snippet, .. }
+            | WireError::UnknownType { snippet, .. }
+            | WireError::ChecksumMismatch { snippet, .. } => Some(snippet),
+            WireError::TypeNameTooLong { .. } | WireError::SchemaDrift { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WireError::BadCategory { byte, .. } => {
+                write!(f, "bad category byte 0x{:02x} at offset 0", byte)
+            }
+            WireError::TypeNameTooLong { name, len } => write!(
+                f,
+                "type name too long: {} bytes (max {}): {}",
+                len, MAX_TYPE_NAME_LEN, name
+            ),
+            WireError::TruncatedAt { offset, needed, .. } => write!(
+                f,
+                "payload truncated at offset {}, {} more bytes needed",
+                offset, needed
+            ),
+            WireError::InvalidTypeName { offset, .. } => {
+                write!(f, "type name at offset {} is not valid UTF-8", offset)
+            }
+            WireError::InvalidTypeNameLength { offset, .. } => write!(
+                f,
+                "type name length at offset {} exceeds {} bytes",
+                offset, MAX_TYPE_NAME_LEN
+            ),
+            WireError::UnknownType { name, .. } => write!(f, "unknown type '{}'", name),
+            WireError::SchemaDrift {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "schema drift for type '{}': payload fingerprint {:016x} does not match local fingerprint {:016x}",
+                name, found, expected
+            ),
+            WireError::ChecksumMismatch { present: true, .. } => {
+                f.write_str("payload carries a checksum, but none is expected")
+            }
+            WireError::ChecksumMismatch { present: false, .. } => {
+                f.write_str("payload has no checksum, but one is required")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WireError {}
+
+/// Hexdump the first `SNIPPET_LEN` bytes of `bytes`, e.g. `04 0a 61 62 ... (120 bytes)`
+pub fn hex_snippet(bytes: &[u8]) -> String {
+    let mut snippet = String::new();
+    for byte in bytes.iter().take(SNIPPET_LEN) {
+        snippet.push_str(&format!("{:02x} ", byte));
+    }
+    if bytes.len() > SNIPPET_LEN {
+        snippet.push_str("... ");
+    }
+    snippet.push_str(&format!("({} bytes)", bytes.len()));
+    snippet
+}
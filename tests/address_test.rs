@@ -0,0 +1,76 @@
+use std::net::SocketAddr;
+
+use runar_common::types::{ArcValueType, PeerAddress, PeerId};
+
+#[test]
+fn test_parse_and_display() {
+    let cases = [
+        "/ip4/192.168.1.10/udp/4433/quic",
+        "/ip6/::1/udp/4433/quic",
+        "/dns/node-1.example.com/udp/443/quic",
+        "/relay/peer-abc",
+        "/memory/7",
+    ];
+    for case in cases {
+        let address = PeerAddress::parse(case).unwrap();
+        assert_eq!(address.to_string(), case);
+        assert_eq!(case.parse::<PeerAddress>().unwrap(), address);
+    }
+
+    let socket: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+    assert_eq!(
+        PeerAddress::parse("/ip4/10.0.0.1/udp/9000/quic").unwrap(),
+        PeerAddress::from(socket)
+    );
+    assert_eq!(
+        PeerAddress::parse("/dns/Node.Example.COM/udp/1/quic").unwrap(),
+        PeerAddress::Dns {
+            name: "node.example.com".to_string(),
+            port: 1
+        }
+    );
+    assert_eq!(
+        PeerAddress::parse("/relay/peer-abc").unwrap(),
+        PeerAddress::Relay(PeerId::new("peer-abc").unwrap())
+    );
+
+    let dns = PeerAddress::parse("/dns/a.example/udp/443/quic").unwrap();
+    assert_eq!(dns.port(), Some(443));
+    assert!(dns.needs_resolution());
+    assert_eq!(PeerAddress::Memory(1).port(), None);
+}
+
+#[test]
+fn test_invalid_addresses() {
+    for case in [
+        "",
+        "ip4/1.2.3.4/udp/1/quic",
+        "/ip4/::1/udp/1/quic",
+        "/ip6/1.2.3.4/udp/1/quic",
+        "/ip4/1.2.3.4/udp/70000/quic",
+        "/ip4/1.2.3.4/tcp/1",
+        "/dns/-bad.example/udp/1/quic",
+        "/dns/a..b/udp/1/quic",
+        "/relay/",
+        "/memory/x",
+        "/memory/1/extra",
+    ] {
+        assert!(
+            PeerAddress::parse(case).is_err(),
+            "{} should not parse",
+            case
+        );
+    }
+}
+
+#[test]
+fn test_serde_and_value_conversion() {
+    let address = PeerAddress::parse("/ip6/fe80::1/udp/4433/quic").unwrap();
+    let json = serde_json::to_string(&address).unwrap();
+    assert_eq!(json, "\"/ip6/fe80::1/udp/4433/quic\"");
+    assert_eq!(serde_json::from_str::<PeerAddress>(&json).unwrap(), address);
+    assert!(serde_json::from_str::<PeerAddress>("\"/carrier-pigeon/1\"").is_err());
+
+    let value = ArcValueType::from(address.clone());
+    assert_eq!(PeerAddress::try_from(value).unwrap(), address);
+}
@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float64Type, Int64Type};
+use arrow_schema::{DataType, Field, Schema};
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{ArcValueType, NodeId, SerializerRegistry};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Event {
+    id: i64,
+    kind: String,
+    latency: f64,
+    tags: Vec<String>,
+}
+
+fn events(n: i64) -> Vec<Event> {
+    (0..n)
+        .map(|id| Event {
+            id,
+            kind: if id % 2 == 0 { "read" } else { "write" }.to_string(),
+            latency: id as f64 / 2.0,
+            tags: vec![format!("t{}", id)],
+        })
+        .collect()
+}
+
+#[test]
+fn test_to_arrow_batch_infers_schema() -> Result<()> {
+    let value = ArcValueType::new_list(events(3));
+    let batch = value.to_arrow_batch::<Event>()?;
+    assert_eq!(batch.num_rows(), 3);
+
+    let schema = batch.schema();
+    assert_eq!(schema.field_with_name("id")?.data_type(), &DataType::Int64);
+    assert_eq!(schema.field_with_name("kind")?.data_type(), &DataType::Utf8);
+    let ids = batch
+        .column_by_name("id")
+        .unwrap()
+        .as_primitive::<Int64Type>();
+    assert_eq!(ids.values(), &[0, 1, 2]);
+    let latency = batch
+        .column_by_name("latency")
+        .unwrap()
+        .as_primitive::<Float64Type>();
+    assert_eq!(latency.value(2), 1.0);
+    let tags = batch.column_by_name("tags").unwrap().as_list::<i32>();
+    assert_eq!(tags.value(1).as_string::<i32>().value(0), "t1");
+    Ok(())
+}
+
+#[test]
+fn test_to_arrow_batch_from_lazy_list_with_schema() -> Result<()> {
+    let mut registry = SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )));
+    registry.register::<Vec<Event>>()?;
+    let bytes = registry.serialize_value(&ArcValueType::new_list(events(1000)))?;
+    let value = registry.deserialize_value(bytes)?;
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("kind", DataType::Utf8, false),
+    ]));
+    let batch = value.to_arrow_batch_with_schema::<Event>(schema.clone())?;
+    assert_eq!(batch.num_rows(), 1000);
+    assert_eq!(batch.schema(), schema);
+    assert_eq!(batch.column(1).as_string::<i32>().value(999), "write");
+
+    let empty = ArcValueType::new_list(Vec::<Event>::new());
+    assert_eq!(
+        empty
+            .to_arrow_batch_with_schema::<Event>(schema)?
+            .num_rows(),
+        0
+    );
+    Ok(())
+}
+
+#[test]
+fn test_to_arrow_batch_rejects_other_types() {
+    assert!(ArcValueType::new_list(vec![1i64])
+        .to_arrow_batch::<Event>()
+        .is_err());
+    assert!(ArcValueType::new_primitive(1i64)
+        .to_arrow_batch::<Event>()
+        .is_err());
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use runar_common::logging::{
+    set_audit_sink, AuditDecision, AuditRecord, AuditSink, Component, FileAuditSink, Logger,
+    MemoryAuditSink,
+};
+use runar_common::types::NodeId;
+use runar_common::utils::paths::ActionPath;
+
+#[test]
+fn test_record_fields_and_json() -> Result<()> {
+    let record = AuditRecord::new(
+        "admin@example.com",
+        ActionPath::new("users/delete")?,
+        AuditDecision::Denied,
+    )
+    .with_correlation_id("req-1")
+    .with_payload(b"{\"id\":42}");
+
+    assert_eq!(record.actor(), "admin@example.com");
+    assert_eq!(record.action().to_string(), "users/delete");
+    assert_eq!(record.decision(), AuditDecision::Denied);
+    assert_eq!(record.correlation_id(), Some("req-1"));
+    assert_eq!(record.payload_digest().unwrap().len(), 64);
+    assert_eq!(record.node_id(), None);
+
+    let json: serde_json::Value = serde_json::from_str(&record.to_json_line())?;
+    assert_eq!(json["decision"], "denied");
+    assert_eq!(json["action"], "users/delete");
+    let parsed: AuditRecord = serde_json::from_str(&record.to_json_line())?;
+    assert_eq!(parsed, record);
+    Ok(())
+}
+
+#[test]
+fn test_file_sink_appends_json_lines() -> Result<()> {
+    let path = std::env::temp_dir().join(format!("runar-audit-{}.log", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let sink = FileAuditSink::open(&path)?;
+    let action = ActionPath::new("config/set")?;
+    sink.write(&AuditRecord::new(
+        "alice",
+        action.clone(),
+        AuditDecision::Allowed,
+    ))?;
+    sink.write(&AuditRecord::new("bob", action, AuditDecision::Denied))?;
+
+    let contents = std::fs::read_to_string(&path)?;
+    std::fs::remove_file(&path)?;
+    let actors: Vec<String> = contents
+        .lines()
+        .map(|line| serde_json::from_str::<AuditRecord>(line).map(|r| r.actor().to_string()))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(actors, vec!["alice", "bob"]);
+    Ok(())
+}
+
+// The only test setting the global audit sink
+#[test]
+fn test_logger_audit_bypasses_level_filtering() -> Result<()> {
+    let sink = MemoryAuditSink::new();
+    set_audit_sink(Arc::new(sink.clone()));
+    log::set_max_level(log::LevelFilter::Off);
+
+    let logger = Logger::new_root(Component::Node, NodeId::new("node-1")?)
+        .with_component(Component::Service)
+        .with_correlation_id("cid-7");
+    logger.audit(AuditRecord::new(
+        "admin",
+        ActionPath::new("users/delete")?,
+        AuditDecision::Allowed,
+    ))?;
+    logger.audit(
+        AuditRecord::new(
+            "admin",
+            ActionPath::new("users/create")?,
+            AuditDecision::Allowed,
+        )
+        .with_correlation_id("explicit"),
+    )?;
+
+    let records = sink.records();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].node_id(), Some("node-1"));
+    assert_eq!(records[0].correlation_id(), Some("cid-7"));
+    assert_eq!(records[1].correlation_id(), Some("explicit"));
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{ArcValueType, NodeId, SerializerRegistry};
+use runar_common::utils::cache::{CacheStats, ValueCache};
+
+fn value(n: i64) -> ArcValueType {
+    ArcValueType::new_primitive(n)
+}
+
+#[test]
+fn test_lru_eviction() -> Result<()> {
+    let cache: ValueCache<&str> = ValueCache::new(2);
+    cache.insert("a", value(1));
+    cache.insert("b", value(2));
+
+    // Touch "a" so that "b" becomes the least recently used entry
+    assert_eq!(cache.get(&"a").unwrap().as_type::<i64>()?, 1);
+    cache.insert("c", value(3));
+
+    assert!(cache.get(&"b").is_none());
+    assert!(cache.get(&"a").is_some());
+    assert!(cache.get(&"c").is_some());
+    assert_eq!(
+        cache.stats(),
+        CacheStats {
+            hits: 3,
+            misses: 1,
+            evictions: 1,
+            len: 2,
+            capacity: 2,
+        }
+    );
+
+    // Replacing a key does not evict anything
+    cache.insert("c", value(30));
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get(&"c").unwrap().as_type::<i64>()?, 30);
+
+    assert!(cache.remove(&"a").is_some());
+    cache.clear();
+    assert!(cache.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_get_or_insert_with() -> Result<()> {
+    let cache: ValueCache<String> = ValueCache::new(4);
+    let mut computed = 0;
+    for _ in 0..3 {
+        cache.get_or_insert_with("key".to_string(), || {
+            computed += 1;
+            Ok(value(7))
+        })?;
+    }
+    assert_eq!(computed, 1);
+    assert!(cache
+        .get_or_insert_with("bad".to_string(), || Err(anyhow!("decode failed")))
+        .is_err());
+    assert!(cache.get(&"bad".to_string()).is_none());
+    Ok(())
+}
+
+#[test]
+fn test_decode_by_content_hash() -> Result<()> {
+    let registry = SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node")?,
+    )));
+    let bytes = registry.serialize_value(&ArcValueType::new_primitive("meta".to_string()))?;
+
+    let cache = ValueCache::new(8);
+    let mut first = cache.decode(&registry, bytes.clone())?;
+    let second = cache.decode(&registry, Arc::from(bytes.to_vec()))?;
+    assert_eq!(first.as_type::<String>()?, "meta");
+    assert_eq!(second.category, first.category);
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.len), (1, 1, 1));
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use runar_common::types::{
+    ActionMetadata, CapabilityDelta, EventMetadata, ServiceMetadata, Version,
+};
+
+fn action(name: &str, description: &str) -> ActionMetadata {
+    ActionMetadata {
+        name: name.to_string(),
+        description: description.to_string(),
+        input_schema: None,
+        output_schema: None,
+    }
+}
+
+fn event(path: &str) -> EventMetadata {
+    EventMetadata {
+        path: path.to_string(),
+        description: String::new(),
+        data_schema: None,
+    }
+}
+
+fn metadata(actions: Vec<ActionMetadata>, events: Vec<EventMetadata>) -> ServiceMetadata {
+    ServiceMetadata {
+        network_id: "default".to_string(),
+        service_path: "math".to_string(),
+        name: "Math".to_string(),
+        version: Version::new(1, 0, 0),
+        description: "Arithmetic".to_string(),
+        actions,
+        events,
+        registration_time: 100,
+        last_start_time: Some(200),
+    }
+}
+
+#[test]
+fn test_diff_and_apply() {
+    let old = metadata(
+        vec![action("add", "Add"), action("sub", "Subtract")],
+        vec![event("math/overflow")],
+    );
+    let mut new = metadata(
+        vec![action("add", "Add two numbers"), action("mul", "Multiply")],
+        vec![event("math/overflow"), event("math/result")],
+    );
+    new.version = Version::new(1, 1, 0);
+    new.last_start_time = Some(300);
+
+    let delta = ServiceMetadata::diff(&old, &new);
+    assert_eq!(delta.service_path, "math");
+    assert_eq!(delta.actions_added, vec![action("mul", "Multiply")]);
+    assert_eq!(delta.actions_removed, vec!["sub"]);
+    assert_eq!(
+        delta.actions_changed,
+        vec![action("add", "Add two numbers")]
+    );
+    assert_eq!(delta.events_added, vec![event("math/result")]);
+    assert!(delta.events_removed.is_empty() && delta.events_changed.is_empty());
+    assert_eq!(delta.version, Some(Version::new(1, 1, 0)));
+    assert_eq!(delta.description, None);
+    assert_eq!(delta.last_start_time, Some(300));
+
+    let mut applied = old.clone();
+    applied.apply(&delta).unwrap();
+    assert_eq!(applied, new);
+
+    // The delta round-trips through the wire format
+    let json = serde_json::to_string(&delta).unwrap();
+    assert_eq!(
+        serde_json::from_str::<CapabilityDelta>(&json).unwrap(),
+        delta
+    );
+
+    assert!(ServiceMetadata::diff(&new, &new).is_empty());
+}
+
+#[test]
+fn test_apply_rejects_mismatched_delta() {
+    let old = metadata(vec![action("add", "Add")], Vec::new());
+    let new = metadata(
+        vec![action("add", "Add"), action("mul", "Multiply")],
+        Vec::new(),
+    );
+    let delta = ServiceMetadata::diff(&old, &new);
+
+    // Applying twice would add "mul" again
+    let mut target = new.clone();
+    assert!(target.apply(&delta).is_err());
+    assert_eq!(target, new);
+
+    let mut other = old.clone();
+    other.service_path = "strings".to_string();
+    assert!(other.apply(&delta).is_err());
+
+    let removal = CapabilityDelta {
+        service_path: "math".to_string(),
+        actions_removed: vec!["div".to_string()],
+        ..Default::default()
+    };
+    let mut target = old.clone();
+    let err = target.apply(&removal).unwrap_err();
+    assert_eq!(err.to_string(), "Cannot remove unknown action 'div'");
+}
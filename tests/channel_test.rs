@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::NodeId;
+use runar_common::utils::channel::bounded;
+
+fn test_logger() -> Arc<Logger> {
+    Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    ))
+}
+
+#[tokio::test]
+async fn test_try_send_drops_when_full() -> Result<()> {
+    let (tx, mut rx) = bounded("events", 2, test_logger());
+    assert!(tx.try_send(1)?);
+    assert!(tx.try_send(2)?);
+    assert!(!tx.try_send(3)?);
+
+    let stats = tx.stats();
+    assert_eq!(stats.depth, 2);
+    assert_eq!(stats.max_depth, 2);
+    assert_eq!(stats.capacity, 2);
+    assert_eq!(stats.sent, 2);
+    assert_eq!(stats.dropped, 1);
+
+    assert_eq!(rx.recv().await, Some(1));
+    assert_eq!(rx.try_recv(), Some(2));
+    assert_eq!(rx.try_recv(), None);
+    assert_eq!(rx.stats().depth, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_waits_for_capacity() -> Result<()> {
+    let (tx, mut rx) = bounded("requests", 1, test_logger());
+    tx.send("a").await?;
+
+    let sender = tx.clone();
+    let pending = tokio::spawn(async move { sender.send("b").await });
+    assert_eq!(rx.recv().await, Some("a"));
+    pending.await??;
+    assert_eq!(rx.recv().await, Some("b"));
+    assert_eq!(tx.stats().sent, 2);
+    assert_eq!(tx.stats().dropped, 0);
+
+    drop(rx);
+    assert!(tx.is_closed());
+    assert!(tx.send("c").await.is_err());
+    assert!(tx.try_send("d").is_err());
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{
+    ArcValueType, ChunkedPayload, ChunkingConfig, NodeId, SerializerRegistry,
+};
+
+fn registry() -> SerializerRegistry {
+    SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )))
+}
+
+// Deterministic pseudo-random content
+fn noise(len: usize, seed: u64) -> Vec<u8> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as u8
+        })
+        .collect()
+}
+
+#[test]
+fn test_config_validation() {
+    assert!(ChunkingConfig::new(1024, 4096, 16384).is_ok());
+    assert!(ChunkingConfig::new(1024, 3000, 16384).is_err());
+    assert!(ChunkingConfig::new(8192, 4096, 16384).is_err());
+    assert!(ChunkingConfig::new(0, 4096, 16384).is_err());
+}
+
+#[test]
+fn test_boundaries_respect_limits() -> Result<()> {
+    let config = ChunkingConfig::new(512, 2048, 8192)?;
+    let data = noise(200_000, 7);
+    let ranges = config.boundaries(&data);
+    assert_eq!(ranges.first().unwrap().start, 0);
+    assert_eq!(ranges.last().unwrap().end, data.len());
+    for pair in ranges.windows(2) {
+        assert_eq!(pair[0].end, pair[1].start);
+    }
+    for range in &ranges[..ranges.len() - 1] {
+        assert!(range.len() >= 512 && range.len() <= 8192);
+    }
+
+    // Content without cut points is split at the maximum size
+    let zeros = vec![0u8; 20_000];
+    let ranges = config.boundaries(&zeros);
+    assert_eq!(ranges[0].len(), 8192);
+    Ok(())
+}
+
+#[test]
+fn test_edit_only_changes_nearby_chunks() -> Result<()> {
+    let registry = registry();
+    let config = ChunkingConfig::default();
+    let original = noise(1 << 20, 42);
+    let mut edited = original.clone();
+    edited.splice(500_000..500_010, b"inserted!!!!!!".iter().copied());
+
+    let before = registry.serialize_chunked(&ArcValueType::new_bytes(original), &config)?;
+    let after = registry.serialize_chunked(&ArcValueType::new_bytes(edited.clone()), &config)?;
+    assert!(after.manifest.chunks.len() > 50);
+
+    let changed = after.manifest.changed_since(&before.manifest);
+    assert!(!changed.is_empty());
+    assert!(changed.len() <= 3, "{} chunks changed", changed.len());
+
+    // The receiver combines the chunks it had with the changed ones
+    let mut store: HashMap<_, _> = before.chunks.clone();
+    for info in &changed {
+        store.insert(info.hash, after.chunk(&info.hash).unwrap());
+    }
+    let value =
+        registry.deserialize_chunked(&after.manifest, &config, |hash| store.get(hash).cloned())?;
+    assert_eq!(*value.as_bytes_ref()?, edited);
+    Ok(())
+}
+
+#[test]
+fn test_assemble_detects_missing_and_corrupt_chunks() -> Result<()> {
+    let config = ChunkingConfig::new(64, 256, 1024)?;
+    let payload = ChunkedPayload::from_bytes(&noise(10_000, 3), &config);
+    let manifest = &payload.manifest;
+    assert_eq!(manifest.total_len, 10_000);
+    assert_eq!(manifest.missing(|_| false).len(), payload.chunks.len());
+
+    let first = manifest.chunks[0].hash;
+    let missing = manifest.assemble(&config, |hash| {
+        if *hash == first {
+            None
+        } else {
+            payload.chunk(hash)
+        }
+    });
+    assert!(missing.unwrap_err().to_string().contains("Missing chunk 0"));
+
+    let corrupt = manifest.assemble(&config, |hash| {
+        let chunk = payload.chunk(hash)?;
+        if *hash == first {
+            let mut bytes = chunk.to_vec();
+            bytes[0] ^= 0xff;
+            return Some(Arc::from(bytes));
+        }
+        Some(chunk)
+    });
+    assert!(corrupt.unwrap_err().to_string().contains("does not match"));
+
+    assert_eq!(
+        manifest
+            .assemble(&config, |hash| payload.chunk(hash))?
+            .len(),
+        10_000
+    );
+    Ok(())
+}
+
+#[test]
+fn test_assemble_checks_claimed_lengths_before_allocating() -> Result<()> {
+    let config = ChunkingConfig::new(64, 256, 1024)?;
+    let payload = ChunkedPayload::from_bytes(&noise(10_000, 5), &config);
+
+    let mut inflated = payload.manifest.clone();
+    inflated.total_len = u64::MAX;
+    let err = inflated.assemble(&config, |_| panic!("no chunk is read"));
+    assert!(err
+        .unwrap_err()
+        .to_string()
+        .contains("chunks add up to 10000"));
+
+    let small = config.with_max_assembled_len(4096);
+    let err = payload
+        .manifest
+        .assemble(&small, |_| panic!("no chunk is read"));
+    assert!(err.unwrap_err().to_string().contains("exceeds the limit"));
+
+    assert!(ChunkingConfig::new(64, 256, u32::MAX as usize + 1).is_err());
+    Ok(())
+}
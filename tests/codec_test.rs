@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::codec::{decode_with, encode_with, Codec, JsonCodec};
+use runar_common::types::{ArcValueType, CodecId, NodeId, SerializerRegistry, ValueCategory};
+use runar_common::wire::decode_header;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Reading {
+    sensor: String,
+    value: f64,
+}
+
+fn registry(codec: CodecId) -> SerializerRegistry {
+    let mut registry = SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )));
+    registry.register::<Reading>().unwrap();
+    registry.set_codec(codec).unwrap();
+    registry
+}
+
+#[test]
+fn test_codecs_round_trip() -> Result<()> {
+    let reading = Reading {
+        sensor: "t1".to_string(),
+        value: 21.5,
+    };
+    for codec in [
+        CodecId::Bincode,
+        CodecId::Json,
+        CodecId::Cbor,
+        CodecId::MessagePack,
+    ] {
+        let bytes = encode_with(codec, &reading)?;
+        assert_eq!(decode_with::<Reading>(codec, &bytes)?, reading);
+    }
+    assert_eq!(
+        JsonCodec::encode(&reading)?,
+        br#"{"sensor":"t1","value":21.5}"#
+    );
+    assert_eq!(JsonCodec::ID, CodecId::Json);
+    Ok(())
+}
+
+#[test]
+fn test_receiver_selects_codec_from_header() -> Result<()> {
+    // The receiver keeps the default codec and decodes whatever it is sent
+    let receiver = registry(CodecId::Bincode);
+    for codec in [CodecId::Json, CodecId::Cbor, CodecId::MessagePack] {
+        let sender = registry(codec);
+        let value = ArcValueType::from_struct(Reading {
+            sensor: "t2".to_string(),
+            value: -3.0,
+        });
+        let bytes = sender.serialize_value(&value)?;
+        let header = decode_header(&bytes)?;
+        assert_eq!(header.codec, codec);
+        assert_eq!(header.category, ValueCategory::Struct);
+
+        let mut decoded = receiver.deserialize_value(bytes.clone())?;
+        // Forwarding a lazy value keeps the sender's encoding
+        assert_eq!(receiver.serialize_value(&decoded)?, bytes);
+        assert_eq!(decoded.as_struct_ref::<Reading>()?.value, -3.0);
+
+        let map = ArcValueType::new_map(HashMap::from([("a".to_string(), 1i64)]));
+        let mut decoded = receiver.deserialize_value(sender.serialize_value(&map)?)?;
+        assert_eq!(decoded.as_map_ref::<String, i64>()?["a"], 1);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_bincode_headers_are_unchanged() -> Result<()> {
+    let bincode = registry(CodecId::Bincode);
+    let bytes = bincode.serialize_value(&ArcValueType::new_primitive(7i64))?;
+    assert_eq!(bytes[0], ValueCategory::Primitive.marker());
+
+    // Raw payloads are not codec encoded, whatever the registry codec
+    let json = registry(CodecId::Json);
+    let bytes = json.serialize_value(&ArcValueType::new_bytes(vec![1, 2]))?;
+    assert_eq!(decode_header(&bytes)?.codec, CodecId::Bincode);
+    Ok(())
+}
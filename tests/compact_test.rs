@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use runar_common::types::compact::{decode_services, encode_services};
+use runar_common::types::{
+    ActionMetadata, EventMetadata, FieldSchema, SchemaDataType, ServiceMetadata, Version,
+};
+
+fn user_schema(name: &str) -> FieldSchema {
+    let mut properties = HashMap::new();
+    let mut id = FieldSchema::string("id");
+    id.description = Some("Unique user identifier".to_string());
+    id.pattern = Some("^[a-z0-9-]+$".to_string());
+    properties.insert("id".to_string(), Box::new(id));
+    let mut age = FieldSchema::integer("age");
+    age.minimum = Some(0.0);
+    age.maximum = Some(150.0);
+    properties.insert("age".to_string(), Box::new(age));
+    let mut tags = FieldSchema::new("tags", SchemaDataType::Array);
+    tags.items = Some(Box::new(FieldSchema::string("tag")));
+    tags.max_items = Some(16);
+    properties.insert("tags".to_string(), Box::new(tags));
+    let mut email = FieldSchema::string("email");
+    email.sensitive = Some(true);
+    email.encrypted = Some(true);
+    email.nullable = Some(true);
+    properties.insert("email".to_string(), Box::new(email));
+    let mut schema = FieldSchema::object(name, properties, Some(vec!["id".to_string()]));
+    schema.description = Some("A user account".to_string());
+    schema
+}
+
+fn service(path: &str) -> ServiceMetadata {
+    let actions = ["get", "create", "update", "delete", "list"]
+        .iter()
+        .map(|name| ActionMetadata {
+            name: name.to_string(),
+            description: format!("{} users", name),
+            input_schema: Some(user_schema("user")),
+            output_schema: Some(FieldSchema::new(
+                "result",
+                SchemaDataType::Union(vec![
+                    SchemaDataType::Reference("User".to_string()),
+                    SchemaDataType::Any,
+                ]),
+            )),
+        })
+        .collect();
+    let events = ["created", "deleted"]
+        .iter()
+        .map(|name| EventMetadata {
+            path: format!("{}/{}", path, name),
+            description: format!("A user was {}", name),
+            data_schema: Some(user_schema("user")),
+        })
+        .collect();
+    ServiceMetadata {
+        network_id: "network-7f3a9c".to_string(),
+        service_path: path.to_string(),
+        name: "Users".to_string(),
+        version: Version::parse("2.1.0-beta.1").unwrap(),
+        description: "User accounts".to_string(),
+        actions,
+        events,
+        registration_time: 1_700_000_000,
+        last_start_time: Some(1_700_000_100),
+    }
+}
+
+#[test]
+fn test_compact_round_trip() {
+    let metadata = service("users");
+    let decoded = ServiceMetadata::from_compact_bytes(&metadata.to_compact_bytes()).unwrap();
+    assert_eq!(decoded, metadata);
+
+    let mut never_started = metadata.clone();
+    never_started.last_start_time = None;
+    let bytes = never_started.to_compact_bytes();
+    assert_eq!(
+        ServiceMetadata::from_compact_bytes(&bytes).unwrap(),
+        never_started
+    );
+
+    // Without schemas only the schemas are lost
+    let slim =
+        ServiceMetadata::from_compact_bytes(&metadata.to_compact_bytes_without_schemas()).unwrap();
+    assert_eq!(slim.actions.len(), 5);
+    assert!(slim.actions.iter().all(|a| a.input_schema.is_none()));
+    assert_eq!(slim.events[1].path, "users/deleted");
+    assert_eq!(slim.version, metadata.version);
+
+    let services = vec![service("users"), service("admins")];
+    assert_eq!(
+        decode_services(&encode_services(&services, true)).unwrap(),
+        services
+    );
+}
+
+#[test]
+fn test_compact_size_target() {
+    let metadata = service("users");
+    let bincode_len = bincode::serialize(&metadata).unwrap().len();
+    let compact_len = metadata.to_compact_bytes().len();
+    let slim_len = metadata.to_compact_bytes_without_schemas().len();
+
+    assert!(
+        compact_len * 2 < bincode_len,
+        "compact {} vs bincode {}",
+        compact_len,
+        bincode_len
+    );
+    assert!(
+        slim_len * 5 < bincode_len,
+        "without schemas {} vs bincode {}",
+        slim_len,
+        bincode_len
+    );
+}
+
+#[test]
+fn test_compact_rejects_bad_input() {
+    let bytes = service("users").to_compact_bytes();
+    assert!(ServiceMetadata::from_compact_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(ServiceMetadata::from_compact_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+
+    let mut wrong_version = bytes.clone();
+    wrong_version[0] = 9;
+    assert!(ServiceMetadata::from_compact_bytes(&wrong_version).is_err());
+
+    // A huge count is rejected instead of allocated
+    assert!(decode_services(&[1, 0, 1, 0xff, 0xff, 0xff, 0xff, 0x0f]).is_err());
+}
@@ -0,0 +1,156 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use runar_common::config::{ConfigLayer, ConfigLoader};
+use runar_common::types::ValueCategory;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct NetworkConfig {
+    port: u16,
+    host: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct NodeConfig {
+    name: String,
+    network: NetworkConfig,
+    debug: bool,
+}
+
+fn defaults() -> NodeConfig {
+    NodeConfig {
+        name: "node".to_string(),
+        network: NetworkConfig {
+            port: 8000,
+            host: "127.0.0.1".to_string(),
+        },
+        debug: false,
+    }
+}
+
+fn write_temp_file(name: &str, contents: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("runar_config_test_{}_{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_layer_precedence() -> Result<()> {
+    let file = write_temp_file(
+        "precedence.toml",
+        "name = \"from-file\"\n[network]\nport = 9000\n",
+    );
+    std::env::set_var("RUNAR_PRECEDENCE_TEST_NETWORK__PORT", "9500");
+    std::env::set_var("RUNAR_PRECEDENCE_TEST_DEBUG", "true");
+
+    let config = ConfigLoader::new()
+        .with_defaults(&defaults())?
+        .with_file(&file)
+        .with_env_prefix("RUNAR_PRECEDENCE_TEST")
+        .load()?;
+
+    let typed: NodeConfig = config.extract()?;
+    assert_eq!(typed.name, "from-file");
+    assert_eq!(typed.network.port, 9500);
+    assert_eq!(typed.network.host, "127.0.0.1");
+    assert!(typed.debug);
+
+    assert_eq!(config.source_of("name"), Some(ConfigLayer::File));
+    assert_eq!(
+        config.source_of("network.port"),
+        Some(ConfigLayer::Environment)
+    );
+    assert_eq!(
+        config.source_of("network.host"),
+        Some(ConfigLayer::Defaults)
+    );
+    assert_eq!(config.source_of("missing"), None);
+
+    config.set_override("network.port", 10000)?;
+    assert_eq!(config.get::<u16>("network.port")?, Some(10000));
+    assert_eq!(
+        config.source_of("network.port"),
+        Some(ConfigLayer::Override)
+    );
+
+    std::fs::remove_file(file)?;
+    Ok(())
+}
+
+#[test]
+fn test_json_file_and_value_export() -> Result<()> {
+    let file = write_temp_file("export.json", r#"{"network": {"host": "0.0.0.0"}}"#);
+    let config = ConfigLoader::new()
+        .with_defaults(&defaults())?
+        .with_file(&file)
+        .load()?;
+
+    assert_eq!(
+        config.get::<String>("network.host")?.as_deref(),
+        Some("0.0.0.0")
+    );
+    assert_eq!(config.get_or("network.timeout", 30u64)?, 30);
+
+    let mut value = config.to_value();
+    assert_eq!(value.category, ValueCategory::Map);
+    let map = value.as_map_ref::<String, runar_common::types::ArcValueType>()?;
+    let mut name = map.get("name").unwrap().clone();
+    assert_eq!(name.as_type::<String>()?, "node");
+
+    std::fs::remove_file(file)?;
+    Ok(())
+}
+
+#[test]
+fn test_change_notifications() -> Result<()> {
+    let config = ConfigLoader::new().with_defaults(&defaults())?.load()?;
+    let mut changes = config.subscribe();
+    let revision = config.revision();
+
+    config.set_override("debug", true)?;
+    let change = changes.try_recv()?;
+    assert_eq!(change.keys, vec!["debug".to_string()]);
+    assert_eq!(change.revision, revision + 1);
+
+    // Setting the same value again is not a change
+    config.set_override("debug", true)?;
+    assert!(changes.try_recv().is_err());
+
+    config.clear_overrides()?;
+    assert_eq!(changes.try_recv()?.keys, vec!["debug".to_string()]);
+    assert_eq!(config.get::<bool>("debug")?, Some(false));
+    Ok(())
+}
+
+#[test]
+fn test_unsupported_file_format() {
+    let result = ConfigLoader::new().with_file("config.yaml").load();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_duration_and_size_values() -> Result<()> {
+    let path = write_temp_file(
+        "units.toml",
+        "[network]\ntimeout = \"1m 30s\"\nretry_after = 5\nbuffer = \"1.5KiB\"\nbad = \"fast\"\n",
+    );
+    let config = ConfigLoader::new().with_file(&path).load()?;
+    std::fs::remove_file(&path)?;
+
+    assert_eq!(
+        config.get_duration("network.timeout")?,
+        Some(Duration::from_secs(90))
+    );
+    assert_eq!(
+        config.get_duration("network.retry_after")?,
+        Some(Duration::from_secs(5))
+    );
+    assert_eq!(config.get_size("network.buffer")?, Some(1536));
+    assert_eq!(config.get_duration("network.missing")?, None);
+    assert!(config.get_duration("network.bad").is_err());
+    assert!(config.get_size("network.bad").is_err());
+    Ok(())
+}
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Result;
+use runar_common::impl_arc_value_struct;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{
+    ArcValueType, FieldSchema, FromArcValue, NodeId, SchemaRegistry, SerializerRegistry,
+    ToArcValue, ValueCategory,
+};
+use runar_common::vmap;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Reading {
+    sensor: String,
+    celsius: f64,
+}
+
+impl_arc_value_struct!(Reading);
+
+fn wrap(value: impl ToArcValue) -> ArcValueType {
+    value.to_arc_value()
+}
+
+#[test]
+fn test_primitive_round_trips() -> Result<()> {
+    assert_eq!(i64::from_arc_value(wrap(42i64))?, 42);
+    assert!(bool::from_arc_value(wrap(true))?);
+    assert_eq!(String::from_arc_value(wrap("text"))?, "text");
+
+    // Numbers convert when they fit; other stored types must match exactly
+    assert_eq!(i32::from_arc_value(wrap(42i64))?, 42);
+    assert!(i32::from_arc_value(wrap(5_000_000_000i64)).is_err());
+    assert!(bool::from_arc_value(wrap(1i32)).is_err());
+    assert!(i32::from_arc_value(wrap(vec![1i32])).is_err());
+
+    // String literals from vmap! can still be read as Strings
+    let mut map = vmap! { "name" => "node-1" };
+    let name = map.as_map_ref::<String, ArcValueType>()?["name"].clone();
+    assert_eq!(String::from_arc_value(name)?, "node-1");
+    Ok(())
+}
+
+#[test]
+fn test_collections_and_options() -> Result<()> {
+    assert_eq!(Vec::<u32>::from_arc_value(wrap(vec![1u32, 2]))?, vec![1, 2]);
+
+    let scores = HashMap::from([("a".to_string(), 1.5f64)]);
+    let value = wrap(scores.clone());
+    assert_eq!(value.category, ValueCategory::Map);
+    assert_eq!(HashMap::<String, f64>::from_arc_value(value)?, scores);
+
+    let none: Option<i32> = None;
+    assert_eq!(wrap(none).category, ValueCategory::Null);
+    assert_eq!(Option::<i32>::from_arc_value(ArcValueType::null())?, None);
+    assert_eq!(Option::<i32>::from_arc_value(wrap(Some(5)))?, Some(5));
+    Ok(())
+}
+
+#[test]
+fn test_structs_via_registry() -> Result<()> {
+    let mut registry = SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )));
+    registry.register::<Reading>()?;
+
+    let reading = Reading {
+        sensor: "t1".to_string(),
+        celsius: 21.5,
+    };
+    let bytes = registry.serialize_value(&wrap(reading.clone()))?;
+    let decoded = registry.deserialize_value(bytes)?;
+    assert_eq!(Reading::from_arc_value(decoded)?, reading);
+    assert!(Reading::from_arc_value(wrap(1i32)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_from_and_try_from() -> Result<()> {
+    let value: ArcValueType = 7u16.into();
+    assert_eq!(value.category, ValueCategory::Primitive);
+    assert_eq!(u16::try_from(value)?, 7);
+
+    let text: ArcValueType = "hello".into();
+    let text: String = text.try_into()?;
+    assert_eq!(text, "hello");
+
+    let list: ArcValueType = vec![1.5f32, 2.5].into();
+    assert_eq!(Vec::<f32>::try_from(list)?, vec![1.5, 2.5]);
+
+    let map: ArcValueType = HashMap::from([("k".to_string(), 1i64)]).into();
+    let map: HashMap<String, i64> = map.try_into()?;
+    assert_eq!(map["k"], 1);
+
+    let wrong: Result<bool> = ArcValueType::from(1u8).try_into();
+    assert!(wrong.is_err());
+    Ok(())
+}
+
+#[test]
+fn test_map_from_pairs() -> Result<()> {
+    let rows = vec![("alpha", 1i64), ("beta", 2i64), ("alpha", 3i64)];
+    let mut value = ArcValueType::from_pairs(rows.iter().copied());
+    assert_eq!(value.category, ValueCategory::Map);
+    let map = value.as_map_ref::<String, ArcValueType>()?;
+    assert_eq!(map.len(), 2);
+    assert_eq!(i64::from_arc_value(map["alpha"].clone())?, 3);
+
+    let mut collected: ArcValueType = rows
+        .into_iter()
+        .map(|(name, count)| (name.to_string(), count.to_string()))
+        .collect();
+    let map = collected.as_map_ref::<String, ArcValueType>()?;
+    assert_eq!(String::from_arc_value(map["beta"].clone())?, "2");
+
+    let empty: ArcValueType = std::iter::empty::<(String, bool)>().collect();
+    assert_eq!(empty.category, ValueCategory::Map);
+    Ok(())
+}
+
+#[test]
+fn test_address_and_path_values() -> Result<()> {
+    let registry = SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )));
+    let addr: SocketAddr = "[::1]:7000".parse()?;
+    let ip: IpAddr = "10.0.0.7".parse()?;
+    let path = PathBuf::from("/var/lib/runar");
+
+    let round_trip = |value: ArcValueType| -> Result<ArcValueType> {
+        registry.deserialize_value(registry.serialize_value(&value)?)
+    };
+    assert_eq!(SocketAddr::from_arc_value(round_trip(wrap(addr))?)?, addr);
+    assert_eq!(IpAddr::from_arc_value(round_trip(wrap(ip))?)?, ip);
+    assert_eq!(
+        PathBuf::from_arc_value(round_trip(wrap(path.clone()))?)?,
+        path
+    );
+    assert_eq!(wrap(addr).to_json()?, serde_json::json!("[::1]:7000"));
+    assert_eq!(wrap(ip).to_string(), "10.0.0.7");
+    assert_eq!(wrap(path.clone()).to_string(), "\"/var/lib/runar\"");
+
+    // Strings from configuration are parsed
+    assert_eq!(SocketAddr::from_arc_value(wrap("127.0.0.1:80"))?.port(), 80);
+    assert_eq!(
+        IpAddr::from_arc_value(wrap("::1"))?,
+        IpAddr::from([0u16, 0, 0, 0, 0, 0, 0, 1])
+    );
+    assert_eq!(PathBuf::from_arc_value(wrap("/var/lib/runar"))?, path);
+    let err = SocketAddr::from_arc_value(wrap("localhost")).unwrap_err();
+    assert!(err.to_string().contains("localhost"), "{}", err);
+    assert!(IpAddr::from_arc_value(wrap(7i64)).is_err());
+
+    let schemas = SchemaRegistry::new();
+    let cases = [
+        (
+            FieldSchema::socket_addr("listen"),
+            "[fe80::1%eth0]:443",
+            true,
+        ),
+        (
+            FieldSchema::socket_addr("listen"),
+            "192.168.1.20:8080",
+            true,
+        ),
+        (FieldSchema::socket_addr("listen"), "192.168.1.20", false),
+        (FieldSchema::socket_addr("listen"), "::1:80", false),
+        (FieldSchema::ip_addr("peer"), "2001:db8::ff00:42:8329", true),
+        (FieldSchema::ip_addr("peer"), "::ffff:192.0.2.1", true),
+        (FieldSchema::ip_addr("peer"), "256.1.1.1", false),
+        (FieldSchema::socket_addr("listen"), "[1:2]:80", false),
+        (FieldSchema::path("data_dir"), "relative/dir", true),
+        (FieldSchema::path("data_dir"), "", false),
+    ];
+    for (schema, text, valid) in cases {
+        let result = schemas.validate_field(&schema, &serde_json::json!(text), "$");
+        assert_eq!(result.is_ok(), valid, "{} against {}", text, schema.name);
+    }
+
+    // The pattern accepts exactly what `IpAddr` parses
+    let peer = FieldSchema::ip_addr("peer");
+    let addresses = [
+        "::",
+        "::1",
+        "1::",
+        "1:2:3:4:5:6:7:8",
+        "1:2:3:4:5:6:7::",
+        "::2:3:4:5:6:7:8",
+        "1:2:3:4:5:6:1.2.3.4",
+        "1::5:6:1.2.3.4",
+        ":",
+        "1:2",
+        ":::::::",
+        "1::2::3",
+        "1:2:3:4:5:6:7:8:9",
+        "1:2:3:4:5:6:7:8::",
+        "::1:2:3:4:5:6:7:8",
+        "1:2:3:4:5:6:7:1.2.3.4",
+        "12345::1",
+        "::1.2.3",
+    ];
+    for text in addresses {
+        let result = schemas.validate_field(&peer, &serde_json::json!(text), "$");
+        assert_eq!(result.is_ok(), text.parse::<IpAddr>().is_ok(), "{}", text);
+    }
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use std::time::{Duration, Instant};
+
+use runar_common::errors::ErrorCode;
+use runar_common::types::Deadline;
+
+#[test]
+fn test_expiry_and_check() {
+    let past = Deadline::at(Instant::now() - Duration::from_millis(5));
+    assert!(past.is_expired());
+    assert_eq!(past.remaining(), Duration::ZERO);
+    assert_eq!(past.check().unwrap_err().code(), ErrorCode::Timeout);
+
+    let future = Deadline::after(Duration::from_secs(10));
+    assert!(!future.is_expired());
+    assert!(future.check().is_ok());
+}
+
+#[test]
+fn test_child_deadlines_never_extend_parent() {
+    let parent = Deadline::after(Duration::from_secs(2));
+
+    let longer = parent.child(Duration::from_secs(60));
+    assert_eq!(longer, parent);
+
+    let shorter = parent.child(Duration::from_millis(100));
+    assert!(shorter < parent);
+
+    let with_margin = parent.child_with_margin(Duration::from_millis(500));
+    assert!(with_margin.remaining() <= Duration::from_millis(1500));
+}
+
+#[test]
+fn test_serializes_as_remaining_millis() {
+    let deadline = Deadline::after(Duration::from_secs(5));
+    let json = serde_json::to_string(&deadline).unwrap();
+    let millis: u64 = json.parse().unwrap();
+    assert!(millis > 4_000 && millis <= 5_000);
+
+    let decoded: Deadline = serde_json::from_str(&json).unwrap();
+    assert!(decoded.remaining() <= Duration::from_millis(millis));
+}
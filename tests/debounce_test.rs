@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use runar_common::utils::debounce::Debouncer;
+use runar_common::utils::time::Instant;
+
+#[test]
+fn test_coalesces_updates_within_window() {
+    let start = Instant::now();
+    let at = |ms: u64| start + Duration::from_millis(ms);
+    let mut debouncer = Debouncer::new(Duration::from_millis(100));
+
+    assert!(!debouncer.push_at("svc-a", 1, at(0)));
+    assert!(debouncer.push_at("svc-a", 2, at(30)));
+    assert!(!debouncer.push_at("svc-b", 10, at(50)));
+    assert!(debouncer.push_at("svc-a", 3, at(90)));
+    assert_eq!(debouncer.pending_len(), 2);
+    assert_eq!(debouncer.coalesced(), 2);
+    assert_eq!(debouncer.next_deadline(), Some(at(100)));
+
+    assert!(debouncer.drain_due_at(at(99)).is_empty());
+    // Only the latest value of the burst is emitted
+    assert_eq!(debouncer.drain_due_at(at(100)), vec![("svc-a", 3)]);
+    assert_eq!(debouncer.next_deadline(), Some(at(150)));
+
+    // A new update after emission opens a new window
+    debouncer.push_at("svc-a", 4, at(120));
+    assert_eq!(
+        debouncer.drain_due_at(at(300)),
+        vec![("svc-b", 10), ("svc-a", 4)]
+    );
+    assert_eq!(debouncer.next_deadline(), None);
+}
+
+#[test]
+fn test_flush_emits_everything_pending() {
+    let start = Instant::now();
+    let mut debouncer = Debouncer::new(Duration::from_secs(60));
+    debouncer.push_at(1u32, "first", start);
+    debouncer.push_at(2u32, "second", start + Duration::from_secs(1));
+    assert_eq!(debouncer.flush(), vec![(1, "first"), (2, "second")]);
+    assert_eq!(debouncer.pending_len(), 0);
+}
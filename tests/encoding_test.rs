@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{NodeId, SerializerRegistry, ValueCategory};
+use runar_common::utils::encoding::{
+    bytes_value_from_base64, bytes_value_from_hex, bytes_value_from_multibase, from_base64,
+    from_base64_url, from_hex, from_multibase, to_base64, to_base64_url, to_hex, to_multibase,
+    Base,
+};
+
+const KEY: &[u8] = &[0xde, 0xad, 0xbe, 0xef, 0x00, 0xff];
+
+#[test]
+fn test_round_trips() -> Result<()> {
+    assert_eq!(to_hex(KEY), "deadbeef00ff");
+    assert_eq!(from_hex("DEADBEEF00FF")?, KEY);
+    assert_eq!(to_base64(KEY), "3q2+7wD/");
+    assert_eq!(from_base64(&to_base64(KEY))?, KEY);
+    assert_eq!(to_base64_url(KEY), "3q2-7wD_");
+    assert_eq!(from_base64_url("3q2-7wD_")?, KEY);
+
+    let encoded = to_multibase(Base::Base58Btc, KEY);
+    assert!(encoded.starts_with('z'));
+    assert_eq!(from_multibase(&encoded)?, (Base::Base58Btc, KEY.to_vec()));
+
+    assert!(from_hex("xyz").is_err());
+    assert!(from_base64("not base64!").is_err());
+    assert!(from_multibase("").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_bytes_values() -> Result<()> {
+    for value in [
+        bytes_value_from_hex("deadbeef00ff")?,
+        bytes_value_from_base64("3q2+7wD/")?,
+        bytes_value_from_multibase(&to_multibase(Base::Base16Lower, KEY))?,
+    ] {
+        assert_eq!(value.category, ValueCategory::Bytes);
+        assert_eq!(value.as_bytes_ref()?.as_slice(), KEY);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_bytes_value_survives_serialization() -> Result<()> {
+    let registry = SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node")?,
+    )));
+    let value = bytes_value_from_hex("deadbeef00ff")?;
+    let bytes = registry.serialize_value(&value)?;
+    let decoded = registry.deserialize_value(bytes)?;
+    assert_eq!(decoded.category, ValueCategory::Bytes);
+    assert_eq!(decoded.as_bytes_ref()?.as_slice(), KEY);
+    Ok(())
+}
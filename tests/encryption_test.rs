@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use runar_common::errors::{ErrorCode, RunarError};
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{
+    decrypt_fields, encrypt_fields, ArcValueType, EncryptedField, FieldDecryptor, FieldEncryptor,
+    FieldSchema, NodeId, SchemaDataType, SerializerRegistry, ValueCategory,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+// XOR with a per-key byte; enough to tell keys apart in tests
+struct XorKey {
+    id: String,
+    byte: u8,
+}
+
+impl FieldEncryptor for XorKey {
+    fn key_id(&self) -> &str {
+        &self.id
+    }
+
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        Ok(plaintext.iter().map(|b| b ^ self.byte).collect())
+    }
+}
+
+struct XorKeyring(HashMap<String, u8>);
+
+impl FieldDecryptor for XorKeyring {
+    fn decrypt(&self, key_id: &str, ciphertext: &[u8]) -> Result<Vec<u8>> {
+        let byte = self
+            .0
+            .get(key_id)
+            .ok_or_else(|| anyhow!("unknown key '{}'", key_id))?;
+        Ok(ciphertext.iter().map(|b| b ^ byte).collect())
+    }
+}
+
+fn registry() -> Arc<SerializerRegistry> {
+    let mut registry = SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )));
+    registry.register::<Vec<ArcValueType>>().unwrap();
+    registry
+        .register::<HashMap<String, ArcValueType>>()
+        .unwrap();
+    Arc::new(registry)
+}
+
+fn patient_schema() -> FieldSchema {
+    let mut ssn = FieldSchema::string("ssn");
+    ssn.encrypted = Some(true);
+    let mut address = FieldSchema::object("address", HashMap::new(), None);
+    address.encrypted = Some(true);
+    let mut note = FieldSchema::string("note");
+    note.encrypted = Some(true);
+    let mut visit_properties = HashMap::new();
+    visit_properties.insert("note".to_string(), Box::new(note));
+    visit_properties.insert("ward".to_string(), Box::new(FieldSchema::string("ward")));
+    let visit = FieldSchema::object("visit", visit_properties, None);
+
+    let mut properties = HashMap::new();
+    properties.insert("id".to_string(), Box::new(FieldSchema::string("id")));
+    properties.insert("ssn".to_string(), Box::new(ssn));
+    properties.insert("address".to_string(), Box::new(address));
+    properties.insert(
+        "visits".to_string(),
+        Box::new(FieldSchema::array("visits", Box::new(visit))),
+    );
+    FieldSchema::object("patient", properties, None)
+}
+
+fn patient() -> ArcValueType {
+    ArcValueType::from_json(json!({
+        "id": "p-1",
+        "ssn": "123-45-6789",
+        "address": {"city": "Oslo", "zip": "0150"},
+        "visits": [{"note": "flu", "ward": "A"}, {"note": null, "ward": "B"}],
+    }))
+}
+
+#[test]
+fn test_encrypts_only_marked_fields() -> Result<()> {
+    let registry = registry();
+    let key = XorKey {
+        id: "k1".to_string(),
+        byte: 0x5a,
+    };
+    let encrypted =
+        registry.scope(|| encrypt_fields(patient(), &patient_schema(), &registry, &key))?;
+
+    let mut entries = encrypted.clone().into_map::<String, ArcValueType>()?;
+    assert_eq!(entries["ssn"].category, ValueCategory::Bytes);
+    assert_eq!(entries["address"].category, ValueCategory::Bytes);
+    let field = EncryptedField::from_value(&entries["ssn"]).unwrap();
+    assert_eq!(field.key_id(), "k1");
+    let id: String = entries.get_mut("id").unwrap().as_type()?;
+    assert_eq!(id, "p-1");
+
+    // Routable parts stay readable; null fields are not encrypted
+    let mut visits = entries.remove("visits").unwrap();
+    let visits = visits.as_list_ref::<ArcValueType>()?;
+    let mut first = visits[0].clone().into_map::<String, ArcValueType>()?;
+    assert!(EncryptedField::from_value(&first["note"]).is_some());
+    assert_eq!(first.get_mut("ward").unwrap().as_type::<String>()?, "A");
+    let second = visits[1].clone().into_map::<String, ArcValueType>()?;
+    assert!(second["note"].is_null());
+    Ok(())
+}
+
+#[test]
+fn test_round_trip_and_key_rotation() -> Result<()> {
+    let registry = registry();
+    let schema = patient_schema();
+    let old_key = XorKey {
+        id: "k1".to_string(),
+        byte: 0x5a,
+    };
+    let new_key = XorKey {
+        id: "k2".to_string(),
+        byte: 0x33,
+    };
+    let keyring = XorKeyring(HashMap::from([
+        ("k1".to_string(), 0x5a),
+        ("k2".to_string(), 0x33),
+    ]));
+
+    let old = registry.scope(|| encrypt_fields(patient(), &schema, &registry, &old_key))?;
+    let new = registry.scope(|| encrypt_fields(patient(), &schema, &registry, &new_key))?;
+    for encrypted in [old, new] {
+        // Through the wire and back
+        let bytes = registry.scope(|| registry.serialize_value(&encrypted))?;
+        let received = registry.scope(|| registry.deserialize_value(bytes))?;
+        let decrypted = registry.scope(|| {
+            decrypt_fields(received, &schema, &registry, &keyring).and_then(|value| value.to_json())
+        })?;
+        assert_eq!(decrypted, patient().to_json()?);
+    }
+
+    // One field at a time
+    let encrypted = registry.scope(|| encrypt_fields(patient(), &schema, &registry, &new_key))?;
+    let entries = encrypted.into_map::<String, ArcValueType>()?;
+    let mut ssn = EncryptedField::from_value(&entries["ssn"])
+        .unwrap()
+        .decrypt(&registry, &keyring)?;
+    assert_eq!(ssn.as_type::<String>()?, "123-45-6789");
+    Ok(())
+}
+
+#[test]
+fn test_missing_key_is_unauthorized() -> Result<()> {
+    let registry = registry();
+    let key = XorKey {
+        id: "k9".to_string(),
+        byte: 1,
+    };
+    let encrypted =
+        registry.scope(|| encrypt_fields(patient(), &patient_schema(), &registry, &key))?;
+    let keyring = XorKeyring(HashMap::new());
+
+    let err = registry
+        .scope(|| decrypt_fields(encrypted, &patient_schema(), &registry, &keyring))
+        .unwrap_err();
+    let runar = err.downcast_ref::<RunarError>().expect("RunarError");
+    assert_eq!(runar.code, ErrorCode::Unauthorized);
+    assert!(format!("{:#}", err).contains("$.address"), "{:#}", err);
+
+    assert!(EncryptedField::from_bytes(b"plain bytes").is_none());
+    assert!(EncryptedField::from_bytes(b"RNENC1\x05\x00ab").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_plain_bytes_with_magic_prefix_are_left_alone() -> Result<()> {
+    let registry = registry();
+    let mut ssn = FieldSchema::string("ssn");
+    ssn.encrypted = Some(true);
+    let mut properties = HashMap::new();
+    properties.insert("ssn".to_string(), Box::new(ssn));
+    properties.insert(
+        "blob".to_string(),
+        Box::new(FieldSchema::new("blob", SchemaDataType::Binary)),
+    );
+    let schema = FieldSchema::object("record", properties, None);
+    let key = XorKey {
+        id: "k1".to_string(),
+        byte: 0x5a,
+    };
+    let keyring = XorKeyring(HashMap::from([("k1".to_string(), 0x5a)]));
+
+    // Parses as an encrypted field under a key the keyring does not have
+    let blob = b"RNENC1\x02\x00k9payload".to_vec();
+    assert!(EncryptedField::from_bytes(&blob).is_some());
+    let record = ArcValueType::new_map(HashMap::from([
+        (
+            "ssn".to_string(),
+            ArcValueType::new_primitive("123-45-6789".to_string()),
+        ),
+        ("blob".to_string(), ArcValueType::new_bytes(blob.clone())),
+    ]));
+
+    let encrypted = registry.scope(|| encrypt_fields(record, &schema, &registry, &key))?;
+    let decrypted = registry.scope(|| decrypt_fields(encrypted, &schema, &registry, &keyring))?;
+    let mut entries = decrypted.into_map::<String, ArcValueType>()?;
+    assert_eq!(*entries["blob"].as_bytes_ref()?, blob);
+    assert_eq!(
+        entries.get_mut("ssn").unwrap().as_type::<String>()?,
+        "123-45-6789"
+    );
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Patient {
+    id: String,
+    ssn: String,
+}
+
+// A struct without a JSON form
+#[derive(Debug)]
+struct Opaque;
+
+#[test]
+fn test_encrypts_fields_inside_structs() -> Result<()> {
+    let mut registry = SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )));
+    registry.register::<Patient>()?;
+    registry.register::<HashMap<String, ArcValueType>>()?;
+    let registry = Arc::new(registry);
+    let key = XorKey {
+        id: "k1".to_string(),
+        byte: 0x5a,
+    };
+    let patient = Patient {
+        id: "p-1".to_string(),
+        ssn: "123-45-6789".to_string(),
+    };
+
+    // Eager and received (lazy) structs both come back as maps
+    let eager = ArcValueType::from_struct(patient.clone());
+    let bytes = registry.serialize_value(&eager)?;
+    let received = registry.deserialize_value(bytes)?;
+    for value in [eager, received] {
+        let encrypted =
+            registry.scope(|| encrypt_fields(value, &patient_schema(), &registry, &key))?;
+        let mut entries = encrypted.into_map::<String, ArcValueType>()?;
+        assert!(EncryptedField::from_value(&entries["ssn"]).is_some());
+        assert_eq!(entries.get_mut("id").unwrap().as_type::<String>()?, "p-1");
+    }
+
+    // Struct types the registry cannot turn into JSON are rejected, not
+    // passed through with readable fields
+    let opaque = ArcValueType::from_struct(Opaque);
+    let err = encrypt_fields(opaque, &patient_schema(), &registry, &key).unwrap_err();
+    assert_eq!(RunarError::code_of(&err), ErrorCode::InvalidInput);
+    Ok(())
+}
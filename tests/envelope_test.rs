@@ -0,0 +1,146 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use runar_common::errors::RunarError;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{
+    ArcValueType, CorrelationId, Deadline, EventEnvelope, FieldSchema, NodeId, Priority, QosHints,
+    RequestEnvelope, ResponseEnvelope, SchemaRef, SchemaRegistry, SerializerRegistry,
+};
+use runar_common::utils::paths::{ActionPath, TopicPath};
+
+fn create_test_registry() -> SerializerRegistry {
+    SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )))
+}
+
+#[test]
+fn test_request_round_trip() -> Result<()> {
+    let registry = create_test_registry();
+    let correlation_id = CorrelationId::generate();
+    let request = RequestEnvelope::new(
+        correlation_id,
+        ActionPath::new("math/add")?,
+        ArcValueType::new_primitive(5i32),
+    )
+    .with_deadline(Deadline::after(Duration::from_secs(30)));
+
+    let bytes = request.to_bytes(&registry)?;
+    let mut decoded = RequestEnvelope::from_bytes(&registry, &bytes)?;
+
+    assert_eq!(decoded.correlation_id, correlation_id);
+    assert_eq!(decoded.action_path.to_string(), "math/add");
+    let remaining = decoded.deadline.unwrap().remaining();
+    assert!(remaining > Duration::from_secs(25) && remaining <= Duration::from_secs(30));
+    assert!(!decoded.is_expired());
+    assert_eq!(decoded.payload.as_type::<i32>()?, 5);
+    Ok(())
+}
+
+#[test]
+fn test_response_round_trip() -> Result<()> {
+    let registry = create_test_registry();
+    let correlation_id = CorrelationId::generate();
+    let request = RequestEnvelope::new(
+        correlation_id,
+        ActionPath::new("math/div")?,
+        ArcValueType::null(),
+    );
+
+    let ok = request.respond(ArcValueType::new_primitive("done".to_string()));
+    let decoded = ResponseEnvelope::from_bytes(&registry, &ok.to_bytes(&registry)?)?;
+    assert_eq!(decoded.correlation_id, correlation_id);
+    assert_eq!(decoded.into_result().unwrap().as_type::<String>()?, "done");
+
+    let failed = request.respond_err(RunarError::invalid_input("division by zero"));
+    let decoded = ResponseEnvelope::from_bytes(&registry, &failed.to_bytes(&registry)?)?;
+    assert!(decoded.is_err());
+    assert_eq!(
+        decoded.into_result().unwrap_err(),
+        RunarError::invalid_input("division by zero")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_event_round_trip_and_validation() -> Result<()> {
+    let registry = create_test_registry();
+    let mut schemas = SchemaRegistry::new();
+    let schema = schemas.register("temperature", 1, FieldSchema::double("celsius"))?;
+
+    let correlation_id = CorrelationId::generate();
+    let event = EventEnvelope::new(
+        TopicPath::new("sensors/temperature")?,
+        NodeId::new("node-1")?,
+        ArcValueType::new_primitive(21.5f64),
+    )
+    .with_correlation_id(correlation_id)
+    .with_schema(schema.clone());
+    event.validate(&schemas)?;
+
+    let bytes = event.to_bytes(&registry)?;
+    let mut decoded = EventEnvelope::from_bytes(&registry, &bytes)?;
+    assert_eq!(decoded.topic.to_string(), "sensors/temperature");
+    assert_eq!(decoded.publisher.as_str(), "node-1");
+    assert_eq!(decoded.timestamp_millis, event.timestamp_millis);
+    assert_eq!(decoded.correlation_id, Some(correlation_id));
+    assert_eq!(decoded.schema, Some(schema.clone()));
+    decoded.validate(&schemas)?;
+    assert_eq!(decoded.payload.as_type::<f64>()?, 21.5);
+
+    let wrong = EventEnvelope::new(
+        TopicPath::new("sensors/temperature")?,
+        NodeId::new("node-1")?,
+        ArcValueType::new_primitive("hot".to_string()),
+    );
+    wrong.validate(&schemas)?;
+    assert!(wrong.with_schema(schema).validate(&schemas).is_err());
+    assert!(event
+        .with_schema(SchemaRef::new("unknown", 1))
+        .validate(&schemas)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_qos_hints_travel_in_headers() -> Result<()> {
+    let registry = create_test_registry();
+    let qos = QosHints::new()
+        .with_priority(Priority::High)
+        .with_ttl(Duration::from_secs(5))
+        .idempotent();
+
+    let request = RequestEnvelope::new(
+        CorrelationId::generate(),
+        ActionPath::new("math/add")?,
+        ArcValueType::null(),
+    )
+    .with_qos(qos);
+    let decoded = RequestEnvelope::from_bytes(&registry, &request.to_bytes(&registry)?)?;
+    assert_eq!(decoded.qos, Some(qos));
+
+    let event = EventEnvelope::new(
+        TopicPath::new("math/result")?,
+        NodeId::new("node-1")?,
+        ArcValueType::null(),
+    );
+    assert_eq!(event.qos, None);
+    let mut event = event.with_qos(QosHints::new().with_ttl(Duration::from_secs(5)));
+    let decoded = EventEnvelope::from_bytes(&registry, &event.to_bytes(&registry)?)?;
+    assert_eq!(decoded.qos, event.qos);
+    assert!(!decoded.is_expired());
+
+    event.timestamp_millis -= 10_000;
+    assert!(event.is_expired());
+
+    assert!(Priority::Critical > Priority::High && Priority::Low < Priority::Normal);
+    assert_eq!(QosHints::default().priority, Priority::Normal);
+    assert_eq!("HIGH".parse::<Priority>()?, Priority::High);
+    assert!(qos.is_expired_at(1_000, 6_001));
+    assert!(!qos.is_expired_at(1_000, 6_000));
+    assert!(!QosHints::new().is_expired_at(0, u64::MAX));
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{ArcValueType, ErasedArc, NodeId, SerializerRegistry};
+
+#[test]
+fn test_try_unwrap_moves_unique_value() -> Result<()> {
+    let values: Vec<u64> = (0..1024).collect();
+    let buffer = values.as_ptr();
+    let erased = ErasedArc::from_value(values);
+    assert!(erased.is_unique());
+
+    let unwrapped = erased.try_unwrap::<Vec<u64>>()?;
+    // Same allocation: the vector was moved out, not cloned
+    assert_eq!(unwrapped.as_ptr(), buffer);
+    assert_eq!(unwrapped.len(), 1024);
+    Ok(())
+}
+
+#[test]
+fn test_try_unwrap_clones_shared_value() -> Result<()> {
+    let shared = Arc::new(vec![1u8, 2, 3]);
+    let erased = ErasedArc::new(shared.clone());
+    assert!(!erased.is_unique());
+
+    let unwrapped = erased.try_unwrap::<Vec<u8>>()?;
+    assert_eq!(unwrapped, *shared);
+    assert_ne!(unwrapped.as_ptr(), shared.as_ptr());
+    assert_eq!(Arc::strong_count(&shared), 1);
+    Ok(())
+}
+
+#[test]
+fn test_try_unwrap_rejects_wrong_and_lazy_types() -> Result<()> {
+    let erased = ErasedArc::from_value(String::from("text"));
+    assert!(erased.clone().try_unwrap::<i32>().is_err());
+    assert_eq!(erased.try_unwrap::<String>()?, "text");
+
+    let registry = SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )));
+    let bytes = registry.serialize_value(&ArcValueType::new_list(vec![1i32, 2]))?;
+    let lazy = registry.deserialize_value(bytes)?;
+    let error = lazy.value.try_unwrap::<Vec<i32>>().unwrap_err();
+    assert!(error.to_string().contains("lazy"));
+    Ok(())
+}
+
+mod billing {
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Status(pub u64);
+}
+
+mod shipping {
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Status(pub String);
+}
+
+#[test]
+fn test_types_sharing_a_name_are_not_confused() -> Result<()> {
+    let erased = ErasedArc::from_value(billing::Status(7));
+    assert!(erased.as_arc::<shipping::Status>().is_err());
+    assert!(erased.clone().try_unwrap::<shipping::Status>().is_err());
+    assert_eq!(erased.try_unwrap::<billing::Status>()?, billing::Status(7));
+    Ok(())
+}
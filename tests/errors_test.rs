@@ -0,0 +1,224 @@
+use anyhow::{anyhow, Context, Result};
+use runar_common::errors::catalog::render_template;
+use runar_common::errors::codes::SERVICE_CODES_START;
+use runar_common::errors::{
+    ErrorCode, ErrorCodeRegistry, ResultExt, RunarError, StaticCatalog, StatusClass,
+};
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{NodeId, ValueCategory, WireError};
+
+fn test_logger() -> Logger {
+    Logger::new_root(Component::Custom("Test"), NodeId::new("test-node").unwrap())
+}
+
+#[test]
+fn test_with_code_wraps_message() {
+    let result: Result<()> = Err(anyhow!("user 42 missing"));
+    let err = result.with_code(ErrorCode::NotFound).unwrap_err();
+
+    assert_eq!(err.code(), ErrorCode::NotFound);
+    assert_eq!(err.message(), "user 42 missing");
+    assert_eq!(err.to_string(), "[not_found] user 42 missing");
+}
+
+#[test]
+fn test_to_value_err_produces_string_value() -> Result<()> {
+    let result: Result<i32, RunarError> = Err(RunarError::invalid_input("bad id"));
+    let mut value = result.to_value_err().unwrap_err();
+
+    assert_eq!(value.category, ValueCategory::Primitive);
+    assert_eq!(value.as_type::<String>()?, "[invalid_input] bad id");
+    Ok(())
+}
+
+#[test]
+fn test_log_err_passes_result_through() {
+    let logger = test_logger();
+
+    let ok: Result<i32, String> = Ok(7);
+    assert_eq!(ok.log_err(&logger, "loading").unwrap(), 7);
+
+    let err: Result<i32, String> = Err("boom".to_string());
+    let chained = err
+        .log_err(&logger, "loading")
+        .with_code(ErrorCode::Internal)
+        .unwrap_err();
+    assert_eq!(chained, RunarError::internal("boom"));
+}
+
+#[test]
+fn test_status_class_mapping() {
+    let err = RunarError::not_found("no such action");
+    assert_eq!(err.status_class(), StatusClass::NotFound);
+    assert_eq!(err.http_status(), 404);
+
+    assert_eq!(
+        ErrorCode::Serialization.status_class(),
+        StatusClass::BadRequest
+    );
+    assert_eq!(ErrorCode::Timeout.status_class().http_status(), 504);
+}
+
+#[test]
+fn test_status_class_http_round_trip() {
+    assert_eq!(StatusClass::from_http_status(200), None);
+    assert_eq!(
+        StatusClass::from_http_status(503),
+        Some(StatusClass::Unavailable)
+    );
+    assert_eq!(
+        StatusClass::from_http_status(418),
+        Some(StatusClass::BadRequest)
+    );
+
+    let err = RunarError::from_http_status(401, "token expired");
+    assert_eq!(err.code(), ErrorCode::Unauthorized);
+    assert_eq!(err.status_class().http_status(), 401);
+}
+
+#[test]
+fn test_anyhow_interop_keeps_codes() {
+    // RunarError -> anyhow -> RunarError is lossless
+    let original = RunarError::new(ErrorCode::Conflict, "version 3 is stale");
+    let wrapped: anyhow::Error = original.clone().into();
+    assert_eq!(
+        wrapped.downcast_ref::<RunarError>().map(|e| e.code),
+        Some(ErrorCode::Conflict)
+    );
+    assert_eq!(RunarError::from(wrapped), original);
+
+    // Context added on the way up is kept in the message, the code survives
+    let with_context = Err::<(), _>(anyhow::Error::from(original))
+        .context("updating profile")
+        .unwrap_err();
+    assert_eq!(RunarError::code_of(&with_context), ErrorCode::Conflict);
+    let converted = RunarError::from(with_context);
+    assert_eq!(converted.code, ErrorCode::Conflict);
+    assert_eq!(converted.message, "updating profile: version 3 is stale");
+
+    // Errors without a RunarError inside
+    let wire: anyhow::Error = WireError::BadCategory {
+        byte: 0xff,
+        snippet: "ff".to_string(),
+    }
+    .into();
+    assert_eq!(RunarError::from(wire).code, ErrorCode::Serialization);
+    let plain = RunarError::from(anyhow!("disk full"));
+    assert_eq!(plain, RunarError::internal("disk full"));
+
+    // `?` converts anyhow errors in functions returning RunarError
+    fn load() -> std::result::Result<(), RunarError> {
+        Err(anyhow!(RunarError::not_found("no such key"))).context("loading config")?;
+        Ok(())
+    }
+    let err = load().unwrap_err();
+    assert_eq!(err.code, ErrorCode::NotFound);
+    assert_eq!(err.message, "loading config: no such key");
+}
+
+#[test]
+fn test_numeric_error_codes() -> Result<()> {
+    let registry = ErrorCodeRegistry::new();
+    assert_eq!(
+        ErrorCode::from_number(ErrorCode::Timeout.number()),
+        Some(ErrorCode::Timeout)
+    );
+    assert_eq!(registry.lookup(3).unwrap().name, "not_found");
+
+    let billing = SERVICE_CODES_START..SERVICE_CODES_START + 100;
+    registry.reserve("billing", billing.clone())?;
+    registry.reserve("billing", billing.clone())?;
+    let err = registry
+        .reserve(
+            "shipping",
+            SERVICE_CODES_START + 50..SERVICE_CODES_START + 150,
+        )
+        .unwrap_err();
+    assert_eq!(RunarError::code_of(&err), ErrorCode::Conflict);
+    assert_eq!(
+        registry.owner_of(SERVICE_CODES_START + 7).as_deref(),
+        Some("billing")
+    );
+
+    let declined = registry.register("billing", "card_declined", 100_001, ErrorCode::Forbidden)?;
+    registry.register("billing", "card_declined", 100_001, ErrorCode::Forbidden)?;
+    let reused = registry
+        .register("billing", "card_expired", 100_001, ErrorCode::Forbidden)
+        .unwrap_err();
+    assert_eq!(RunarError::code_of(&reused), ErrorCode::Conflict);
+    let outside = registry
+        .register("billing", "refund_failed", 200_000, ErrorCode::Internal)
+        .unwrap_err();
+    assert_eq!(RunarError::code_of(&outside), ErrorCode::InvalidInput);
+
+    // Numbers travel with the error
+    let error = registry.error("billing", "card_declined", "insufficient funds")?;
+    assert_eq!(error.code, ErrorCode::Forbidden);
+    assert_eq!(error.number(), declined.number);
+    let json = serde_json::to_value(&error)?;
+    assert_eq!(json["number"], 100_001);
+    assert_eq!(serde_json::from_value::<RunarError>(json)?, error);
+    let plain = serde_json::to_value(RunarError::not_found("gone"))?;
+    assert_eq!(plain["number"], 3);
+    assert!(plain.get("params").is_none());
+
+    // Errors from peers that do not send numbers get their code's
+    let old: RunarError = serde_json::from_str(r#"{"code":"NotFound","message":"x"}"#)?;
+    assert_eq!(old, RunarError::not_found("x"));
+    assert_eq!(old.number(), ErrorCode::NotFound.number());
+
+    // and survive a trip through anyhow with context
+    let wrapped = anyhow::Error::from(error).context("charging order 17");
+    assert_eq!(RunarError::from(wrapped).number(), 100_001);
+    Ok(())
+}
+
+#[test]
+fn test_localized_messages() -> Result<()> {
+    let catalog = StaticCatalog::new()
+        .with_message("en", 100_404, "Order {order} was not found")
+        .with_message("de", 100_404, "Bestellung {order} wurde nicht gefunden")
+        .with_message("de-CH", 100_404, "Bstellig {order} isch nöd gfunde")
+        .with_message(
+            "en",
+            ErrorCode::Timeout.number(),
+            "Please try again in {retry_after}s",
+        );
+
+    let error = RunarError::not_found("order lookup failed: no row for id 17")
+        .with_number(100_404)
+        .with_param("order", 17);
+    assert_eq!(error.localized(&catalog, "en"), "Order 17 was not found");
+    assert_eq!(
+        error.localized(&catalog, "de_CH"),
+        "Bstellig 17 isch nöd gfunde"
+    );
+    // Falls back to the language, then to the developer message
+    assert_eq!(
+        error.localized(&catalog, "de-AT"),
+        "Bestellung 17 wurde nicht gefunden"
+    );
+    assert_eq!(error.localized(&catalog, "fr"), error.message);
+
+    // Errors without their own template use their code's
+    let timeout = RunarError::new(ErrorCode::Timeout, "upstream slow").with_param("retry_after", 5);
+    assert_eq!(
+        timeout.localized(&catalog, "en-GB"),
+        "Please try again in 5s"
+    );
+
+    // Parameters travel with the error
+    let received: RunarError = serde_json::from_value(serde_json::to_value(&error)?)?;
+    assert_eq!(
+        received.params().get("order").map(String::as_str),
+        Some("17")
+    );
+    assert_eq!(received.localized(&catalog, "en"), "Order 17 was not found");
+
+    let params = |name: &str| (name == "n").then_some("3");
+    assert_eq!(
+        render_template("{n} of {{n}} and {missing}", params),
+        "3 of {n} and {missing}"
+    );
+    Ok(())
+}
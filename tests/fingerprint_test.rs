@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{
+    schema_fingerprint, type_fingerprint, type_layout, ArcValueType, FieldSchema, NodeId,
+    SchemaDataType, SerializerRegistry, WireError,
+};
+use runar_common::wire::{decode_header, encode_header_with_fingerprint, CodecId, ValueCategory};
+use serde::{Deserialize, Serialize};
+
+mod v1 {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub enum Unit {
+        Celsius,
+        Fahrenheit,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct Reading {
+        pub sensor: String,
+        pub value: f64,
+        pub unit: Unit,
+        pub tags: Vec<String>,
+        pub previous: Option<Box<Reading>>,
+    }
+}
+
+mod v2 {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct Reading {
+        pub sensor: String,
+        pub value: f32,
+    }
+}
+
+mod moved {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct Reading {
+        pub sensor: String,
+        pub value: f32,
+    }
+}
+
+fn registry() -> SerializerRegistry {
+    SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )))
+}
+
+#[test]
+fn test_type_layout() -> Result<()> {
+    assert_eq!(
+        type_layout::<v2::Reading>()?,
+        "struct Reading{sensor:string,value:f32}"
+    );
+
+    let layout = type_layout::<v1::Reading>()?;
+    assert!(layout.starts_with(
+        "struct Reading{sensor:string,value:f64,unit:enum Unit[Celsius|Fahrenheit],tags:seq<string>,previous:option<"
+    ));
+
+    // Only the layout counts, not where the type lives
+    assert_eq!(
+        type_fingerprint::<v2::Reading>()?,
+        type_fingerprint::<moved::Reading>()?
+    );
+    assert_ne!(
+        type_fingerprint::<v1::Reading>()?,
+        type_fingerprint::<v2::Reading>()?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_schema_fingerprint() {
+    let schema = FieldSchema::object(
+        "reading",
+        HashMap::from([
+            (
+                "sensor".to_string(),
+                Box::new(FieldSchema::string("sensor")),
+            ),
+            ("value".to_string(), Box::new(FieldSchema::double("value"))),
+        ]),
+        None,
+    );
+    let base = schema_fingerprint(&schema);
+
+    let mut described = schema.clone();
+    described.description = Some("A sensor reading".to_string());
+    assert_eq!(schema_fingerprint(&described), base);
+
+    let mut changed = schema.clone();
+    changed.properties.as_mut().unwrap().insert(
+        "value".to_string(),
+        Box::new(FieldSchema::new("value", SchemaDataType::Int32)),
+    );
+    assert_ne!(schema_fingerprint(&changed), base);
+}
+
+#[test]
+fn test_header_carries_fingerprint() {
+    let mut bytes = Vec::new();
+    encode_header_with_fingerprint(
+        ValueCategory::Struct,
+        CodecId::Bincode,
+        "sensor::Reading",
+        Some(0x0123_4567_89ab_cdef),
+        &mut bytes,
+    )
+    .unwrap();
+    bytes.push(42);
+
+    let header = decode_header(&bytes).unwrap();
+    assert_eq!(header.type_name, "sensor::Reading");
+    assert_eq!(header.codec, CodecId::Bincode);
+    assert_eq!(header.fingerprint, Some(0x0123_4567_89ab_cdef));
+    assert_eq!(&bytes[header.data_offset..], &[42]);
+
+    assert!(matches!(
+        decode_header(&bytes[..bytes.len() - 4]),
+        Err(WireError::TruncatedAt { .. })
+    ));
+}
+
+#[test]
+fn test_schema_drift_is_rejected() -> Result<()> {
+    let mut sender = registry();
+    let fingerprint = sender.register_with_fingerprint::<v2::Reading>()?;
+    sender.set_embed_fingerprints(true);
+    let reading = v2::Reading {
+        sensor: "t1".to_string(),
+        value: 21.5,
+    };
+    let bytes = sender.serialize_value(&ArcValueType::from_struct(reading.clone()))?;
+    assert_eq!(decode_header(&bytes)?.fingerprint, Some(fingerprint));
+
+    // Same layout on the receiving side
+    let mut receiver = registry();
+    receiver.register_with_fingerprint::<v2::Reading>()?;
+    let mut value = receiver.deserialize_value(bytes.clone())?;
+    assert_eq!(*value.as_struct_ref::<v2::Reading>()?, reading);
+
+    // Receivers that do not know the fingerprint skip the check
+    let mut plain = registry();
+    plain.register::<v2::Reading>()?;
+    assert!(plain.deserialize_value(bytes.clone()).is_ok());
+
+    // The receiver's copy of the type has changed
+    let mut drifted = registry();
+    drifted.register::<v2::Reading>()?;
+    drifted.set_type_fingerprint(
+        std::any::type_name::<v2::Reading>(),
+        type_fingerprint::<v1::Reading>()?,
+    )?;
+    let error = drifted.deserialize_value(bytes).unwrap_err();
+    assert!(error.to_string().contains("schema drift"));
+    assert!(matches!(
+        error.downcast_ref::<WireError>(),
+        Some(WireError::SchemaDrift { found, .. }) if *found == fingerprint
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_fingerprints_are_opt_in() -> Result<()> {
+    let mut registry = registry();
+    registry.register_with_fingerprint::<v2::Reading>()?;
+    assert!(!registry.embed_fingerprints());
+
+    let value = ArcValueType::from_struct(v2::Reading {
+        sensor: "t1".to_string(),
+        value: 1.0,
+    });
+    let bytes = registry.serialize_value(&value)?;
+    assert_eq!(decode_header(&bytes)?.fingerprint, None);
+
+    assert!(registry.set_type_fingerprint("not::Registered", 1).is_err());
+    Ok(())
+}
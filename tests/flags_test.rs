@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use runar_common::flags::{FlagChange, FlagRegistry, FlagSource, FlagValue};
+use runar_common::types::ArcValueType;
+
+#[test]
+fn test_defaults_and_runtime_changes() -> Result<()> {
+    let flags = FlagRegistry::with_env_prefix(None);
+    flags.register_bool("network.fast_path", false, "Use the experimental fast path")?;
+    flags.register_int("network.max_peers", 32, "Maximum number of peers")?;
+
+    assert!(!flags.is_enabled("network.fast_path"));
+    assert_eq!(flags.get_int("network.max_peers"), Some(32));
+    assert!(!flags.is_enabled("unknown"));
+
+    let mut changes = flags.subscribe();
+    flags.set("network.fast_path", FlagValue::Bool(true))?;
+    flags.set_from_str("network.max_peers", "64")?;
+    assert!(flags.is_enabled("network.fast_path"));
+    assert_eq!(
+        flags.state("network.max_peers").unwrap().source,
+        FlagSource::Runtime
+    );
+    assert_eq!(
+        changes.try_recv()?,
+        FlagChange {
+            name: "network.fast_path".to_string(),
+            old: FlagValue::Bool(false),
+            new: FlagValue::Bool(true),
+        }
+    );
+    assert_eq!(changes.try_recv()?.new, FlagValue::Int(64));
+
+    flags.reset("network.max_peers")?;
+    assert_eq!(flags.get_int("network.max_peers"), Some(32));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_operations() -> Result<()> {
+    let flags = FlagRegistry::with_env_prefix(None);
+    flags.register_bool("feature", true, "")?;
+    assert!(flags.register_bool("feature", true, "").is_err());
+    assert!(flags.register_bool("Bad Name", true, "").is_err());
+    assert!(flags.set("feature", FlagValue::Int(1)).is_err());
+    assert!(flags.set_from_str("feature", "maybe").is_err());
+    assert!(flags.set("missing", FlagValue::Bool(true)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_env_override() -> Result<()> {
+    let flags = FlagRegistry::with_env_prefix(Some("FLAGS_TEST"));
+    assert_eq!(
+        flags.env_var_name("gossip.fan-out").as_deref(),
+        Some("FLAGS_TEST_GOSSIP_FAN_OUT")
+    );
+    std::env::set_var("FLAGS_TEST_GOSSIP_FAN_OUT", "8");
+    std::env::set_var("FLAGS_TEST_GOSSIP_ENABLED", "on");
+    flags.register_int("gossip.fan-out", 3, "")?;
+    flags.register_bool("gossip.enabled", false, "")?;
+
+    assert_eq!(flags.get_int("gossip.fan-out"), Some(8));
+    assert!(flags.is_enabled("gossip.enabled"));
+    let state = flags.state("gossip.fan-out").unwrap();
+    assert_eq!(state.source, FlagSource::Environment);
+    assert_eq!(state.default, FlagValue::Int(3));
+
+    std::env::set_var("FLAGS_TEST_BROKEN", "yes please");
+    assert!(flags.register_bool("broken", false, "").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_as_value() -> Result<()> {
+    let flags = FlagRegistry::with_env_prefix(None);
+    flags.register_bool("a", true, "")?;
+    flags.register_int("b", 7, "")?;
+    assert_eq!(flags.snapshot().len(), 2);
+
+    let mut value = flags.to_value();
+    let map = value.as_map_ref::<String, ArcValueType>()?;
+    let map: HashMap<_, _> = map.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    assert!(map["a"].clone().as_type::<bool>()?);
+    assert_eq!(map["b"].clone().as_type::<i64>()?, 7);
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use runar_common::types::{ArcValueType, FieldSchema};
+use runar_common::utils::format::{
+    format_number, format_size_exact, format_timestamp, format_timestamp_millis, format_value,
+    parse_number, parse_timestamp,
+};
+use runar_common::utils::size::parse_size;
+use runar_common::utils::time::from_epoch_millis;
+
+#[test]
+fn test_numbers_round_trip() {
+    assert_eq!(format_number(2.75f64), "2.75");
+    assert_eq!(format_number(2.0f64), "2");
+    assert_eq!(format_number(-0.5f32), "-0.5");
+    assert_eq!(format_number(1e300f64), "1e300");
+    assert_eq!(format_number(1_000_000i64), "1000000");
+    assert_eq!(format_number(u64::MAX), "18446744073709551615");
+
+    for value in [0.1, 1.0 / 3.0, -2.5e-8, 1e15, 123456789.125, f64::MAX] {
+        assert_eq!(parse_number(&format_number(value)).unwrap(), value);
+    }
+    assert!(parse_number(&format_number(f64::NAN)).unwrap().is_nan());
+    assert_eq!(format_number(-0.0f64), "-0.0");
+    assert!(parse_number(&format_number(-0.0f64))
+        .unwrap()
+        .is_sign_negative());
+
+    let err = parse_number("3,14").unwrap_err().to_string();
+    assert!(err.contains("decimal separator"), "{}", err);
+    assert!(parse_number("1,000").is_err());
+    assert!(parse_number("ten").is_err());
+}
+
+#[test]
+fn test_timestamps_round_trip() {
+    let at = from_epoch_millis(1_709_294_400_250);
+    assert_eq!(format_timestamp(at), "2024-03-01T12:00:00.250Z");
+    assert_eq!(
+        format_timestamp_millis(0).unwrap(),
+        "1970-01-01T00:00:00.000Z"
+    );
+    assert!(format_timestamp_millis(u64::MAX).is_err());
+    assert!(format_timestamp_millis(i64::MAX as u64).is_err());
+    assert_eq!(parse_timestamp(&format_timestamp(at)).unwrap(), at);
+
+    // Offsets are normalised to UTC
+    assert_eq!(
+        parse_timestamp("2024-03-01T13:00:00.250+01:00").unwrap(),
+        at
+    );
+    assert!(parse_timestamp("2024-03-01 12:00:00").is_err());
+    assert!(parse_timestamp("1969-12-31T23:59:59Z").is_err());
+}
+
+#[test]
+fn test_sizes_round_trip() {
+    assert_eq!(format_size_exact(0), "0");
+    assert_eq!(format_size_exact(1000), "1000");
+    assert_eq!(format_size_exact(4096), "4KiB");
+    assert_eq!(format_size_exact(1_610_612_736), "1536MiB");
+    assert_eq!(format_size_exact(2 << 40), "2TiB");
+
+    for bytes in [0, 1, 1023, 1024, 1_500_000, 1_610_612_736, u64::MAX] {
+        assert_eq!(parse_size(&format_size_exact(bytes)).unwrap(), bytes);
+    }
+}
+
+#[test]
+fn test_schema_default_and_example() {
+    let ratio = FieldSchema::double("ratio")
+        .with_default(0.25f64)
+        .unwrap()
+        .with_example(3.5f64)
+        .unwrap();
+    assert_eq!(ratio.default_value.as_deref(), Some("0.25"));
+    assert_eq!(ratio.example.as_deref(), Some("3.5"));
+    let mut default = ratio.default_as_value().unwrap().unwrap();
+    assert_eq!(default.as_type::<f64>().unwrap(), 0.25);
+
+    let created = FieldSchema::timestamp("created")
+        .with_example(1_709_294_400_250i64)
+        .unwrap();
+    assert_eq!(created.example.as_deref(), Some("2024-03-01T12:00:00.250Z"));
+
+    let name = FieldSchema::string("name").with_default("anon").unwrap();
+    assert_eq!(name.default_value.as_deref(), Some("anon"));
+    assert_eq!(
+        format_value(&ArcValueType::new_primitive(true)).unwrap(),
+        "true"
+    );
+    assert_eq!(
+        format_value(&ArcValueType::new_primitive(7u8)).unwrap(),
+        "7"
+    );
+}
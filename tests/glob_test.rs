@@ -0,0 +1,138 @@
+use runar_common::types::{ActionMetadata, EventMetadata, ServiceMetadata, Version};
+use runar_common::utils::glob::{compile_if_pattern, escape, Glob};
+use runar_common::utils::paths::{ServicePath, TopicPath};
+
+fn glob(pattern: &str) -> Glob {
+    Glob::new(pattern).unwrap()
+}
+
+#[test]
+fn test_glob_matching() {
+    let cases = [
+        ("registry", "registry", true),
+        ("registry", "registry2", false),
+        ("net*", "network", true),
+        ("net*", "net", true),
+        ("net*", "inet", false),
+        ("*_changed", "price_changed", true),
+        ("*_changed", "price_changed_late", false),
+        ("a*b*c", "axxbyyc", true),
+        ("a*b*c", "axxbyy", false),
+        ("file-?", "file-1", true),
+        ("file-?", "file-10", false),
+        ("v[0-9]", "v7", true),
+        ("v[0-9]", "vx", false),
+        ("v[!0-9]", "vx", true),
+        ("v[^0-9]", "v7", false),
+        ("[]x]", "]", true),
+        ("a\\*", "a*", true),
+        ("a\\*", "ab", false),
+        ("*", "", true),
+        ("ünï*", "ünïcode", true),
+        ("?", "ü", true),
+    ];
+    for (pattern, text, expected) in cases {
+        assert_eq!(
+            glob(pattern).matches(text),
+            expected,
+            "{} ~ {}",
+            pattern,
+            text
+        );
+    }
+
+    for invalid in ["[abc", "x\\", "[z-a]"] {
+        assert!(
+            Glob::new(invalid).is_err(),
+            "{} should not compile",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn test_glob_helpers() {
+    assert!(glob("exact").is_literal());
+    assert!(!glob("pre*").is_literal());
+    assert_eq!(glob("runar_*::net").literal_prefix(), "runar_");
+
+    let literal = Glob::literal("a*[b]");
+    assert!(literal.matches("a*[b]"));
+    assert!(!literal.matches("ax[b]"));
+    assert_eq!(literal.as_str(), escape("a*[b]"));
+    assert_eq!(Glob::new(literal.as_str()).unwrap(), literal);
+
+    assert!(compile_if_pattern("plain").is_none());
+    assert!(compile_if_pattern("a[").is_none());
+    assert!(compile_if_pattern("temp-*").is_some());
+
+    let json = serde_json::to_string(&glob("log_*")).unwrap();
+    assert_eq!(json, "\"log_*\"");
+    assert_eq!(serde_json::from_str::<Glob>(&json).unwrap(), glob("log_*"));
+    assert!(serde_json::from_str::<Glob>("\"[oops\"").is_err());
+}
+
+#[test]
+fn test_topic_segment_globs() {
+    let pattern = TopicPath::new("sensors/temp-*/reading").unwrap();
+    assert!(pattern.is_pattern());
+    assert!(pattern.matches(&TopicPath::new("sensors/temp-kitchen/reading").unwrap()));
+    assert!(!pattern.matches(&TopicPath::new("sensors/humidity/reading").unwrap()));
+    assert!(!TopicPath::new("sensors/temp/reading").unwrap().is_pattern());
+
+    // `?`, `[` and `\` are literal unless the segment is marked with `~`
+    let concrete = TopicPath::new("sensors/temp?[1]\\x/reading").unwrap();
+    assert!(!concrete.is_pattern());
+    assert!(concrete.matches(&concrete));
+    assert!(!concrete.matches(&TopicPath::new("sensors/tempA1x/reading").unwrap()));
+    let star = TopicPath::new("sensors/t?-*/reading").unwrap();
+    assert!(star.matches(&TopicPath::new("sensors/t?-1/reading").unwrap()));
+    assert!(!star.matches(&TopicPath::new("sensors/tx-1/reading").unwrap()));
+
+    let marked = TopicPath::new("sensors/~temp-[0-9]/reading").unwrap();
+    assert!(marked.is_pattern());
+    assert!(marked.matches(&TopicPath::new("sensors/temp-7/reading").unwrap()));
+    assert!(!marked.matches(&TopicPath::new("sensors/temp-x/reading").unwrap()));
+    assert!(TopicPath::new("sensors/~temp-[0-9/reading").is_err());
+    assert!(TopicPath::new("sensors/~/reading").is_err());
+    assert!(ServicePath::new("~sensors").is_err());
+}
+
+#[test]
+fn test_capability_queries() {
+    let action = |name: &str| ActionMetadata {
+        name: name.to_string(),
+        description: String::new(),
+        input_schema: None,
+        output_schema: None,
+    };
+    let event = |path: &str| EventMetadata {
+        path: path.to_string(),
+        description: String::new(),
+        data_schema: None,
+    };
+    let service = ServiceMetadata {
+        network_id: "main".to_string(),
+        service_path: "users".to_string(),
+        name: "Users".to_string(),
+        version: Version::new(1, 0, 0),
+        description: String::new(),
+        actions: vec![
+            action("get_user"),
+            action("get_users"),
+            action("delete_user"),
+        ],
+        events: vec![event("users/created"), event("users/deleted")],
+        registration_time: 0,
+        last_start_time: None,
+    };
+
+    let getters: Vec<&str> = service
+        .actions_matching(&glob("get_*"))
+        .into_iter()
+        .map(|action| action.name.as_str())
+        .collect();
+    assert_eq!(getters, vec!["get_user", "get_users"]);
+    assert_eq!(service.events_matching(&glob("users/*ed")).len(), 2);
+    assert_eq!(service.events_matching(&glob("users/c*")).len(), 1);
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use runar_common::health::{
+    ComponentHealth, HealthAggregator, HealthCheck, HealthStatus, NodeHealth,
+};
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{NodeId, SerializerRegistry, ValueCategory};
+
+struct FixedCheck {
+    name: &'static str,
+    result: ComponentHealth,
+}
+
+#[async_trait]
+impl HealthCheck for FixedCheck {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    async fn check(&self) -> ComponentHealth {
+        self.result.clone()
+    }
+}
+
+fn fixed(name: &'static str, result: ComponentHealth) -> Arc<dyn HealthCheck> {
+    Arc::new(FixedCheck { name, result })
+}
+
+#[tokio::test]
+async fn test_worst_critical_status_wins() {
+    let mut aggregator = HealthAggregator::new();
+    aggregator.register(fixed("db", ComponentHealth::healthy()));
+    aggregator.register(fixed("network", ComponentHealth::degraded("1 of 3 peers")));
+
+    let report = aggregator.check_all().await;
+    assert_eq!(report.status, HealthStatus::Degraded);
+    assert_eq!(report.checks.len(), 2);
+    assert_eq!(
+        report.checks["network"].message.as_deref(),
+        Some("1 of 3 peers")
+    );
+
+    aggregator.register(fixed("storage", ComponentHealth::unhealthy("disk full")));
+    assert_eq!(aggregator.check_all().await.status, HealthStatus::Unhealthy);
+}
+
+#[tokio::test]
+async fn test_non_critical_failure_only_degrades() {
+    let mut aggregator = HealthAggregator::new();
+    aggregator.register(fixed("db", ComponentHealth::healthy()));
+    aggregator.register_non_critical(fixed(
+        "metrics",
+        ComponentHealth::unhealthy("exporter down"),
+    ));
+
+    let report = aggregator.check_all().await;
+    assert_eq!(report.status, HealthStatus::Degraded);
+    assert!(!report.is_healthy());
+    assert!(HealthAggregator::new().check_all().await.is_healthy());
+}
+
+#[tokio::test]
+async fn test_report_serializes_as_value() -> Result<()> {
+    let mut aggregator = HealthAggregator::new();
+    aggregator.register(fixed("db", ComponentHealth::healthy()));
+    let report = aggregator.check_all().await;
+
+    let mut registry = SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node")?,
+    )));
+    registry.register::<NodeHealth>()?;
+
+    let value = report.to_value();
+    assert_eq!(value.category, ValueCategory::Struct);
+    let bytes = registry.serialize_value(&value)?;
+    let mut decoded = registry.deserialize_value(bytes)?;
+    assert_eq!(*decoded.as_struct_ref::<NodeHealth>()?, report);
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{ArcValueType, CorrelationId, NetworkId, NodeId, PeerId, ServiceId};
+
+#[test]
+fn test_id_validation() {
+    assert!(NodeId::new("node-1").is_ok());
+    assert!(NodeId::new("").is_err());
+    assert!(NetworkId::new("net one").is_err());
+    assert!(ServiceId::new("math/add").is_err());
+    assert!(PeerId::new("x".repeat(300)).is_err());
+
+    let parsed: NetworkId = "mainnet".parse().unwrap();
+    assert_eq!(parsed.as_str(), "mainnet");
+    assert_eq!(parsed.to_string(), "mainnet");
+}
+
+#[test]
+fn test_id_serde() -> Result<()> {
+    let id = ServiceId::new("math")?;
+    let json = serde_json::to_string(&id)?;
+    assert_eq!(json, "\"math\"");
+    assert_eq!(serde_json::from_str::<ServiceId>(&json)?, id);
+
+    // Invalid ids are rejected when deserializing
+    assert!(serde_json::from_str::<ServiceId>("\"\"").is_err());
+
+    let mut by_peer: HashMap<PeerId, u32> = HashMap::new();
+    by_peer.insert(PeerId::new("peer-a")?, 1);
+    assert_eq!(by_peer.get(&PeerId::new("peer-a")?), Some(&1));
+    Ok(())
+}
+
+#[test]
+fn test_id_value_conversion() -> Result<()> {
+    let id = NodeId::new("node-7")?;
+    let value: ArcValueType = id.clone().into();
+    let back = NodeId::try_from(value)?;
+    assert_eq!(back, id);
+
+    let bad = ArcValueType::new_primitive(42i32);
+    assert!(NodeId::try_from(bad).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_logger_carries_node_id() -> Result<()> {
+    let logger = Logger::new_root(Component::Node, NodeId::new("node-9")?);
+    let child = logger.with_component(Component::Service);
+    assert_eq!(child.node_id(), &NodeId::new("node-9")?);
+    Ok(())
+}
+
+#[test]
+fn test_correlation_id_generation_is_sortable() -> Result<()> {
+    let ids: Vec<CorrelationId> = (0..100).map(|_| CorrelationId::generate()).collect();
+    assert!(ids.windows(2).all(|w| w[0] < w[1]));
+
+    let text: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    assert!(text.windows(2).all(|w| w[0] < w[1]));
+    assert!(text.iter().all(|t| t.len() == 26));
+    Ok(())
+}
+
+#[test]
+fn test_correlation_id_parsing() -> Result<()> {
+    let id = CorrelationId::generate();
+    let text = id.to_string();
+    assert_eq!(text.parse::<CorrelationId>()?, id);
+    assert_eq!(CorrelationId::parse(&text.to_lowercase())?, id);
+    assert!(id.timestamp_millis() > 1_600_000_000_000);
+
+    assert!(CorrelationId::parse("too-short").is_err());
+    assert!(CorrelationId::parse("8ZZZZZZZZZZZZZZZZZZZZZZZZZ").is_err());
+    assert!(CorrelationId::parse("0000000000000000000000000U").is_err());
+
+    let json = serde_json::to_string(&id)?;
+    assert_eq!(serde_json::from_str::<CorrelationId>(&json)?, id);
+    Ok(())
+}
+
+#[test]
+fn test_logger_accepts_correlation_id() -> Result<()> {
+    let id = CorrelationId::generate();
+    let logger = Logger::new_root(Component::Node, NodeId::new("node-1")?)
+        .with_component(Component::Service)
+        .with_correlation_id(id);
+    assert_eq!(logger.correlation_id(), Some(id.to_string().as_str()));
+    Ok(())
+}
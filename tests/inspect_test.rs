@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{
+    inspect, inspect_with, ArcValueType, CodecId, NodeId, SerializerRegistry,
+};
+use runar_common::wire::ValueCategory;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Reading {
+    sensor: String,
+    celsius: f64,
+}
+
+fn registry() -> SerializerRegistry {
+    SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )))
+}
+
+fn reading() -> ArcValueType {
+    ArcValueType::from_struct(Reading {
+        sensor: "t1".to_string(),
+        celsius: 21.5,
+    })
+}
+
+#[test]
+fn test_inspect_default_types() -> Result<()> {
+    let value = ArcValueType::new_map(HashMap::from([
+        ("name".to_string(), "probe".to_string()),
+        ("site".to_string(), "north".to_string()),
+    ]));
+    let bytes = registry().serialize_value(&value)?;
+
+    let report = inspect(&bytes);
+    assert_eq!(report.category, Some(ValueCategory::Map));
+    assert_eq!(report.codec, Some(CodecId::Bincode));
+    assert!(report.registered);
+    assert_eq!(report.total_len, bytes.len());
+    assert_eq!(report.header_len + report.payload_len, bytes.len());
+    assert_eq!(report.error, None);
+    let content: serde_json::Value = serde_json::from_str(report.content.as_deref().unwrap())?;
+    assert_eq!(content["site"], "north");
+
+    let text = report.to_string();
+    assert!(text.contains("category:    Map"));
+    assert!(text.contains("(registered)"));
+    assert!(text.contains("\"name\": \"probe\""));
+    Ok(())
+}
+
+#[test]
+fn test_inspect_registered_struct() -> Result<()> {
+    let mut registry = registry();
+    registry.register::<Reading>()?;
+    let bytes = registry.serialize_value(&reading())?;
+
+    let report = inspect_with(&bytes, &registry);
+    assert_eq!(report.category, Some(ValueCategory::Struct));
+    assert_eq!(
+        report.type_name.as_deref(),
+        Some(std::any::type_name::<Reading>())
+    );
+    let content: serde_json::Value = serde_json::from_str(report.content.as_deref().unwrap())?;
+    assert_eq!(content["celsius"], 21.5);
+
+    // Without the type, bincode cannot be decoded, but the header still is
+    let unknown = inspect(&bytes);
+    assert!(!unknown.registered);
+    assert_eq!(unknown.content, None);
+    assert!(unknown.error.unwrap().contains("not registered"));
+    Ok(())
+}
+
+#[test]
+fn test_inspect_self_describing_codec() -> Result<()> {
+    let mut registry = registry();
+    registry.register::<Reading>()?;
+    registry.set_codec(CodecId::Json)?;
+    let bytes = registry.serialize_value(&reading())?;
+
+    let report = inspect(&bytes);
+    assert!(!report.registered);
+    assert_eq!(report.codec, Some(CodecId::Json));
+    let content: serde_json::Value = serde_json::from_str(report.content.as_deref().unwrap())?;
+    assert_eq!(content["sensor"], "t1");
+    Ok(())
+}
+
+#[test]
+fn test_inspect_bytes_and_garbage() -> Result<()> {
+    let bytes = registry().serialize_value(&ArcValueType::new_bytes((0u8..20).collect()))?;
+    let report = inspect(&bytes);
+    assert_eq!(report.category, Some(ValueCategory::Bytes));
+    let dump = report.content.unwrap();
+    assert!(dump.starts_with("00000000  00 01 02"));
+    assert!(dump.contains("\n00000010  10 11 12 13"));
+
+    let report = inspect(&[0xf8, 0x00]);
+    assert_eq!(report.category, None);
+    assert!(report.error.as_deref().unwrap().contains("bad category"));
+    assert!(report.to_string().contains("<unreadable>"));
+    Ok(())
+}
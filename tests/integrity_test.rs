@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use runar_common::errors::{ErrorCode, RunarError};
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{ArcValueType, NodeId, SerializerRegistry};
+use runar_common::utils::integrity::{
+    blake3_hash, blake3_hex, crc32c, verify_blake3, verify_crc32c, ChecksumAlgorithm,
+};
+use runar_common::wire::{decode_header, WireError};
+
+fn create_test_registry() -> SerializerRegistry {
+    SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )))
+}
+
+#[test]
+fn test_hash_helpers() {
+    // Standard CRC32C check value
+    assert_eq!(crc32c(b"123456789"), 0xe306_9283);
+    assert!(verify_crc32c(b"123456789", 0xe306_9283));
+    assert!(!verify_crc32c(b"123456780", 0xe306_9283));
+
+    assert_eq!(
+        blake3_hex(b""),
+        "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+    );
+    let hash = blake3_hash(b"payload");
+    assert!(verify_blake3(b"payload", &hash));
+    assert!(!verify_blake3(b"payloaD", &hash));
+}
+
+#[test]
+fn test_trailing_checksum() -> Result<()> {
+    for algorithm in [ChecksumAlgorithm::Crc32c, ChecksumAlgorithm::Blake3] {
+        let mut data = b"some bytes".to_vec();
+        algorithm.append_to(&mut data);
+        assert_eq!(data.len(), 10 + algorithm.size());
+        assert_eq!(algorithm.verify_trailing(&data)?, b"some bytes");
+
+        data[0] ^= 1;
+        assert!(algorithm.verify_trailing(&data).is_err());
+        assert!(algorithm.verify_trailing(&[1, 2]).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_registry_checksum_option() -> Result<()> {
+    let mut registry = create_test_registry();
+    registry.set_checksum(Some(ChecksumAlgorithm::Crc32c));
+    assert_eq!(registry.checksum(), Some(ChecksumAlgorithm::Crc32c));
+
+    let value = ArcValueType::new_list(vec![1i64, 2, 3]);
+    let bytes = registry.serialize_value(&value)?;
+    let plain = create_test_registry().serialize_value(&value)?;
+    assert_eq!(bytes.len(), plain.len() + 4);
+
+    let mut decoded = registry.deserialize_value(bytes.clone())?;
+    assert_eq!(*decoded.as_list_ref::<i64>()?, vec![1, 2, 3]);
+
+    // A decoded lazy value re-serializes with a fresh checksum
+    let reencoded = registry.serialize_value(&registry.deserialize_value(bytes.clone())?)?;
+    assert_eq!(reencoded, bytes);
+
+    let truncated: Arc<[u8]> = Arc::from(&bytes[..bytes.len() - 2]);
+    let error = registry.deserialize_value(truncated).unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<RunarError>().unwrap().code,
+        ErrorCode::Serialization
+    );
+    Ok(())
+}
+
+#[test]
+fn test_checksum_presence_is_checked() -> Result<()> {
+    let mut checked = create_test_registry();
+    checked.set_checksum(Some(ChecksumAlgorithm::Crc32c));
+    let plain = create_test_registry();
+    let value = ArcValueType::new_list(vec![1i64, 2, 3]);
+
+    let with_checksum = checked.serialize_value(&value)?;
+    assert!(decode_header(&with_checksum)?.checksummed);
+    let error = plain.deserialize_value(with_checksum).unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<WireError>(),
+        Some(WireError::ChecksumMismatch { present: true, .. })
+    ));
+
+    let without_checksum = plain.serialize_value(&value)?;
+    assert!(!decode_header(&without_checksum)?.checksummed);
+    let error = checked.deserialize_value(without_checksum).unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<WireError>(),
+        Some(WireError::ChecksumMismatch { present: false, .. })
+    ));
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use runar_common::types::{global_interner, IStr, Interner};
+use runar_common::utils::paths::TopicPath;
+
+#[test]
+fn test_interning_shares_allocations() {
+    let a = IStr::new("sensors/temperature");
+    let b = IStr::from("sensors/temperature".to_string());
+    assert!(IStr::ptr_eq(&a, &b));
+    assert_eq!(a, b);
+    assert_eq!(a, "sensors/temperature");
+    assert_eq!(a.len(), "sensors/temperature".len());
+    assert_ne!(a, IStr::new("sensors/humidity"));
+    assert!(global_interner().len() >= 2);
+}
+
+#[test]
+fn test_separate_pools_compare_by_content() {
+    let pool = Interner::new();
+    let local = pool.intern("topic");
+    let global = IStr::new("topic");
+    assert!(!IStr::ptr_eq(&local, &global));
+    assert_eq!(local, global);
+
+    let mut counts = HashMap::new();
+    *counts.entry(local.clone()).or_insert(0) += 1;
+    *counts.entry(global).or_insert(0) += 1;
+    assert_eq!(counts[&local], 2);
+
+    assert_eq!(pool.len(), 1);
+    drop(counts);
+    drop(local);
+    pool.purge_unused();
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn test_ordering_display_and_serde() {
+    let mut names = vec![IStr::new("b"), IStr::new("a"), IStr::new("c")];
+    names.sort();
+    assert_eq!(names, vec!["a", "b", "c"]);
+    assert_eq!(format!("{} {:?}", names[0], names[1]), "a \"b\"");
+
+    let json = serde_json::to_string(&names).unwrap();
+    assert_eq!(json, r#"["a","b","c"]"#);
+    let decoded: Vec<IStr> = serde_json::from_str(&json).unwrap();
+    assert!(IStr::ptr_eq(&decoded[0], &names[0]));
+}
+
+#[test]
+fn test_topic_segments_are_interned() {
+    let first = TopicPath::new("chat/rooms/general").unwrap();
+    let second = TopicPath::new("chat/rooms/random").unwrap();
+    assert!(IStr::ptr_eq(&first.segments()[0], &second.segments()[0]));
+    assert_eq!(first.to_string(), "chat/rooms/general");
+}
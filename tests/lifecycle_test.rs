@@ -0,0 +1,104 @@
+use runar_common::types::{ArcValueType, ServiceLifecycle, ServiceMetadata, ServiceState, Version};
+use runar_common::utils::time::from_epoch_secs;
+use serde_json::json;
+
+fn metadata() -> ServiceMetadata {
+    ServiceMetadata {
+        network_id: "default".to_string(),
+        service_path: "math".to_string(),
+        name: "Math".to_string(),
+        version: Version::new(1, 0, 0),
+        description: String::new(),
+        actions: Vec::new(),
+        events: Vec::new(),
+        registration_time: 100,
+        last_start_time: None,
+    }
+}
+
+#[test]
+fn test_transitions() {
+    use ServiceState::*;
+    assert!(Created.can_transition_to(&Initializing));
+    assert!(Running.can_transition_to(&Paused));
+    assert!(Paused.can_transition_to(&Running));
+    assert!(Stopped.can_transition_to(&Initializing));
+    assert!(Running.can_transition_to(&ServiceState::failed("boom")));
+    assert!(ServiceState::failed("boom").can_transition_to(&Initializing));
+
+    assert!(!Created.can_transition_to(&Running));
+    assert!(!Stopped.can_transition_to(&Running));
+    assert!(!Stopped.can_transition_to(&ServiceState::failed("late")));
+    assert!(!Paused.can_transition_to(&Paused));
+}
+
+#[test]
+fn test_lifecycle_records_start_time() {
+    let mut lifecycle = ServiceLifecycle::created_at(from_epoch_secs(100));
+    lifecycle
+        .transition_at(ServiceState::Initializing, from_epoch_secs(110))
+        .unwrap();
+    lifecycle
+        .transition_at(ServiceState::Running, from_epoch_secs(120))
+        .unwrap();
+    lifecycle
+        .transition_at(ServiceState::Paused, from_epoch_secs(130))
+        .unwrap();
+    lifecycle
+        .transition_at(ServiceState::Running, from_epoch_secs(140))
+        .unwrap();
+
+    // Resuming from Paused is not a new start
+    assert_eq!(lifecycle.last_started_at(), Some(from_epoch_secs(120)));
+    assert_eq!(lifecycle.since(), from_epoch_secs(140));
+
+    let err = lifecycle
+        .transition_at(ServiceState::Created, from_epoch_secs(150))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid service state transition from running to created"
+    );
+    assert_eq!(lifecycle.state(), &ServiceState::Running);
+
+    let mut metadata = metadata();
+    lifecycle.apply_to(&mut metadata);
+    assert_eq!(metadata.last_start_time, Some(120));
+}
+
+#[test]
+fn test_state_values() {
+    let failed = ServiceState::failed("db unreachable");
+    assert_eq!(failed.to_string(), "failed: db unreachable");
+    assert_eq!(
+        failed.to_value().to_json().unwrap(),
+        json!({"state": "failed", "error": "db unreachable"})
+    );
+    assert_eq!(
+        ServiceState::from_value(&failed.to_value()).unwrap(),
+        failed
+    );
+    assert_eq!(
+        ServiceState::from_value(&ArcValueType::from(ServiceState::Paused)).unwrap(),
+        ServiceState::Paused
+    );
+    assert!(
+        ServiceState::from_value(&ArcValueType::from_json(json!({"state": "dozing"}))).is_err()
+    );
+
+    let mut lifecycle = ServiceLifecycle::created_at(from_epoch_secs(1));
+    lifecycle
+        .transition_at(ServiceState::failed("bad config"), from_epoch_secs(2))
+        .unwrap();
+    let value = lifecycle.to_value();
+    assert_eq!(
+        value.to_json().unwrap(),
+        json!({
+            "state": "failed",
+            "error": "bad config",
+            "since_millis": 2_000,
+            "last_start_time": null,
+        })
+    );
+    assert_eq!(ServiceLifecycle::from_value(&value).unwrap(), lifecycle);
+}
@@ -0,0 +1,58 @@
+use log::LevelFilter;
+use runar_common::logging::{self, Component, Logger, NodeLogCapture, RunarLogBackend};
+use runar_common::types::NodeId;
+
+fn node_logger(id: &str) -> Logger {
+    Logger::new_root(Component::Node, NodeId::new(id).unwrap())
+}
+
+// The only test installing the global backend
+#[test]
+fn test_capture_interleaves_nodes() {
+    let capture = NodeLogCapture::new();
+    logging::install(RunarLogBackend::new(LevelFilter::Debug).with_sink(capture.clone())).unwrap();
+
+    let (a, b, c) = (
+        node_logger("node-a"),
+        node_logger("node-b"),
+        node_logger("node-c"),
+    );
+    a.info("started");
+    b.info("started");
+    a.with_component(Component::Network).info("announce sent");
+    c.info("started");
+    b.with_component(Component::NetworkDiscovery)
+        .info("peer node-a discovered");
+    c.debug("peer node-a discovered");
+    log::info!(target: "hyper", "untagged");
+
+    assert_eq!(capture.nodes(), vec!["node-a", "node-b", "node-c"]);
+    assert_eq!(
+        capture.messages_for("node-a"),
+        vec!["started", "announce sent"]
+    );
+    assert_eq!(
+        capture.for_node("node-b")[1].component.as_deref(),
+        Some("NetworkDiscovery")
+    );
+    assert_eq!(capture.position("node-c", "started"), Some(3));
+    assert_eq!(capture.position("node-c", "missing"), None);
+
+    capture.assert_before(("node-a", "announce"), ("node-b", "discovered"));
+    capture.assert_node_order("node-b", &["started", "discovered"]);
+    assert!(std::panic::catch_unwind(|| {
+        capture.assert_before(("node-c", "discovered"), ("node-a", "started"))
+    })
+    .is_err());
+    assert!(std::panic::catch_unwind(|| {
+        capture.assert_node_order("node-a", &["announce", "started"])
+    })
+    .is_err());
+
+    let timeline = capture.timeline();
+    assert!(timeline.starts_with("#0    INFO  [node-a][runar_common::logging] started\n"));
+    assert!(timeline.contains("[-][hyper] untagged"));
+
+    capture.clear();
+    assert!(capture.records().is_empty());
+}
@@ -0,0 +1,423 @@
+use std::sync::Arc;
+
+use log::{Level, LevelFilter, Log, Record};
+use runar_common::logging::{
+    self, Component, EventLogSink, FileSink, LogFormat, LogRecord, LogSink, Logger, MemorySink,
+    RunarLogBackend,
+};
+use runar_common::types::{ArcValueType, NodeId, ValueCategory};
+use runar_common::utils::channel;
+use runar_common::utils::rate_limit::RateLimit;
+use runar_common::utils::time::MockClock;
+use serde_json::json;
+
+fn log_record(
+    backend: &RunarLogBackend,
+    level: Level,
+    target: &str,
+    kvs: &[(&str, &str)],
+    message: &str,
+) {
+    backend.log(
+        &Record::builder()
+            .level(level)
+            .target(target)
+            .key_values(&kvs)
+            .args(format_args!("{}", message))
+            .build(),
+    );
+}
+
+#[test]
+fn test_logger_tags_become_fields() {
+    let sink = MemorySink::new();
+    let backend = RunarLogBackend::new(LevelFilter::Info).with_sink(sink.clone());
+
+    log_record(
+        &backend,
+        Level::Info,
+        "runar_common::logging",
+        &[("node_id", "node-1"), ("component", "Service.Registry")],
+        "[node-1][Service.Registry] service started",
+    );
+
+    let records = sink.records();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].node_id.as_deref(), Some("node-1"));
+    assert_eq!(records[0].component.as_deref(), Some("Service.Registry"));
+    assert_eq!(records[0].message, "service started");
+    assert!(sink.lines()[0].ends_with("INFO  [node-1][Service.Registry] service started"));
+}
+
+#[test]
+fn test_third_party_records_get_node_id() {
+    let sink = MemorySink::new();
+    let backend = RunarLogBackend::new(LevelFilter::Info)
+        .with_node_id(NodeId::new("node-2").unwrap())
+        .with_format(LogFormat::Json)
+        .with_sink(sink.clone());
+
+    log_record(
+        &backend,
+        Level::Warn,
+        "hyper::proto",
+        &[],
+        "connection reset",
+    );
+
+    let line: serde_json::Value = serde_json::from_str(&sink.lines()[0]).unwrap();
+    assert_eq!(line["node_id"], "node-2");
+    assert_eq!(line["target"], "hyper::proto");
+    assert_eq!(line["component"], serde_json::Value::Null);
+    assert_eq!(line["level"], "WARN");
+    assert_eq!(line["message"], "connection reset");
+    assert!(line["timestamp"].as_str().unwrap().ends_with('Z'));
+}
+
+#[test]
+fn test_mock_clock_gives_stable_output() {
+    let clock = MockClock::at_epoch_millis(1_704_067_200_250);
+    let sink = MemorySink::new();
+    let backend = RunarLogBackend::new(LevelFilter::Info)
+        .with_clock(clock.clone())
+        .with_sink(sink.clone());
+
+    log_record(
+        &backend,
+        Level::Info,
+        "runar_common::logging",
+        &[("node_id", "node-1"), ("component", "Registry")],
+        "[node-1][Registry] ready",
+    );
+    clock.advance(std::time::Duration::from_secs(61));
+    log_record(&backend, Level::Warn, "hyper::proto", &[], "slow peer");
+
+    assert_eq!(
+        sink.lines(),
+        vec![
+            "2024-01-01T00:00:00.250Z INFO  [node-1][Registry] ready",
+            "2024-01-01T00:01:01.250Z WARN  [hyper::proto] slow peer",
+        ]
+    );
+}
+
+// Records lines as a sink writing to a terminal would get them
+#[derive(Default)]
+struct TerminalSink(std::sync::Mutex<Vec<String>>);
+
+impl LogSink for TerminalSink {
+    fn write(&self, _record: &LogRecord, line: &str) {
+        self.0.lock().unwrap().push(line.to_string());
+    }
+
+    fn is_terminal(&self) -> bool {
+        true
+    }
+}
+
+#[test]
+fn test_pretty_format_aligns_columns() {
+    let sink = MemorySink::new();
+    let terminal = Arc::new(TerminalSink::default());
+    let backend = RunarLogBackend::new(LevelFilter::Info)
+        .with_clock(MockClock::at_epoch_millis(1_704_067_200_250))
+        .with_format(LogFormat::Pretty)
+        .with_sink(sink.clone())
+        .with_shared_sink(terminal.clone());
+
+    log_record(
+        &backend,
+        Level::Info,
+        "runar_common::logging",
+        &[("node_id", "node-1"), ("component", "Registry")],
+        "[node-1][Registry] ready",
+    );
+    log_record(
+        &backend,
+        Level::Error,
+        "runar_common::logging",
+        &[
+            ("node_id", "node-1"),
+            ("component", "Service.Registry|action=a/b"),
+        ],
+        "[node-1][Service.Registry|action=a/b] failed",
+    );
+
+    let lines = sink.lines();
+    assert_eq!(
+        lines,
+        vec![
+            "00:00:00.250 INFO  Registry             node-1 ready",
+            "00:00:00.250 ERROR Service.Registry|ac… node-1 failed",
+        ]
+    );
+
+    // Only sinks writing to a terminal get colors
+    let lines = terminal.0.lock().unwrap().clone();
+    assert_eq!(
+        lines[0],
+        "\x1b[2m00:00:00.250\x1b[0m \x1b[32mINFO \x1b[0m Registry             \x1b[2mnode-1\x1b[0m ready"
+    );
+    assert_eq!(
+        lines[1],
+        "\x1b[2m00:00:00.250\x1b[0m \x1b[1;31mERROR\x1b[0m Service.Registry|ac… \x1b[2mnode-1\x1b[0m failed"
+    );
+    assert_eq!(
+        backend.config_as_value().to_json().unwrap()["format"],
+        "pretty"
+    );
+}
+
+#[test]
+fn test_warnings_published_as_events() {
+    let node = NodeId::new("node-1").unwrap();
+    let (tx, mut rx) = channel::bounded(
+        "log-events",
+        16,
+        Arc::new(Logger::new_root(Component::System, node.clone())),
+    );
+    let sink = EventLogSink::new(node.clone(), tx).with_rate_limit(RateLimit::new(0.0, 2));
+    let backend = RunarLogBackend::new(LevelFilter::Info)
+        .with_clock(MockClock::at_epoch_millis(1_000))
+        .with_sink(sink);
+
+    log_record(&backend, Level::Info, "app", &[], "not published");
+    log_record(
+        &backend,
+        Level::Warn,
+        "runar_common::logging",
+        &[("node_id", "node-1"), ("component", "Network")],
+        "[node-1][Network] peer slow",
+    );
+    log_record(&backend, Level::Error, "hyper", &[], "reset");
+    log_record(&backend, Level::Error, "hyper", &[], "rate limited");
+
+    let warn = rx.try_recv().unwrap();
+    assert_eq!(warn.topic.to_string(), "system/logs/warn");
+    assert_eq!(warn.publisher, node);
+    assert_eq!(warn.timestamp_millis, 1_000);
+    assert_eq!(
+        warn.payload.to_json().unwrap(),
+        json!({
+            "level": "WARN",
+            "target": "runar_common::logging",
+            "node_id": "node-1",
+            "component": "Network",
+            "message": "peer slow",
+            "timestamp_millis": 1_000,
+            "suppressed": 0,
+        })
+    );
+
+    let error = rx.try_recv().unwrap();
+    assert_eq!(error.topic.to_string(), "system/logs/error");
+    assert_eq!(error.payload.to_json().unwrap()["message"], "reset");
+    assert!(rx.try_recv().is_none());
+}
+
+#[test]
+fn test_level_overrides() {
+    let sink = MemorySink::new();
+    let backend = RunarLogBackend::new(LevelFilter::Info)
+        .with_level_for("hyper", LevelFilter::Error)
+        .with_level_for("Registry", LevelFilter::Debug)
+        .with_sink(sink.clone());
+    assert_eq!(backend.max_level(), LevelFilter::Debug);
+
+    log_record(&backend, Level::Warn, "hyper::proto", &[], "dropped");
+    log_record(&backend, Level::Warn, "hyperlocal", &[], "kept");
+    log_record(
+        &backend,
+        Level::Debug,
+        "runar_common::logging",
+        &[
+            ("node_id", "n"),
+            ("component", "Service.Registry|action=a/b"),
+        ],
+        "kept",
+    );
+    log_record(
+        &backend,
+        Level::Debug,
+        "runar_common::logging",
+        &[("node_id", "n"), ("component", "Network")],
+        "dropped",
+    );
+
+    let messages: Vec<String> = sink.records().into_iter().map(|r| r.message).collect();
+    assert_eq!(messages, vec!["kept", "kept"]);
+}
+
+#[test]
+fn test_level_override_globs() {
+    let sink = MemorySink::new();
+    let backend = RunarLogBackend::new(LevelFilter::Warn)
+        .with_level_for("runar_*", LevelFilter::Debug)
+        .with_level_for("Net*", LevelFilter::Trace)
+        .with_sink(sink.clone());
+
+    log_record(&backend, Level::Debug, "runar_node::p2p", &[], "kept");
+    log_record(&backend, Level::Debug, "tokio::runtime", &[], "dropped");
+    log_record(
+        &backend,
+        Level::Trace,
+        "app",
+        &[("node_id", "n"), ("component", "Service.Network")],
+        "kept",
+    );
+    log_record(
+        &backend,
+        Level::Trace,
+        "app",
+        &[("node_id", "n"), ("component", "Service.Registry")],
+        "dropped",
+    );
+
+    let messages: Vec<String> = sink.records().into_iter().map(|r| r.message).collect();
+    assert_eq!(messages, vec!["kept", "kept"]);
+}
+
+#[test]
+fn test_file_sink_appends_lines() {
+    let path = std::env::temp_dir().join(format!("runar-log-{}.log", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let backend = RunarLogBackend::new(LevelFilter::Info).with_sink(FileSink::open(&path).unwrap());
+
+    log_record(&backend, Level::Info, "app", &[], "first");
+    log_record(&backend, Level::Error, "app", &[], "second");
+    backend.flush();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].ends_with("INFO  [app] first"));
+    assert!(lines[1].ends_with("ERROR [app] second"));
+}
+
+#[test]
+fn test_runtime_config_value() -> anyhow::Result<()> {
+    let sink = MemorySink::new();
+    let backend = RunarLogBackend::new(LevelFilter::Info)
+        .with_level_for("hyper", LevelFilter::Error)
+        .with_sink(sink.clone());
+
+    let config = backend.config_as_value();
+    assert_eq!(config.category, ValueCategory::Map);
+    assert_eq!(
+        config.to_json()?,
+        json!({
+            "level": "info",
+            "format": "text",
+            "levels": {"hyper": "error"},
+            "sinks": {"memory": true},
+            "sampling": {},
+        })
+    );
+
+    backend.apply_config_value(&ArcValueType::from_json(json!({
+        "format": "json",
+        "levels": {"hyper": null, "Network": "debug"},
+        "sampling": {"Network": 2},
+    })))?;
+    assert_eq!(backend.format(), LogFormat::Json);
+    assert_eq!(backend.level_for(None, "hyper::proto"), LevelFilter::Info);
+    assert_eq!(backend.max_level(), LevelFilter::Debug);
+
+    for i in 0..4 {
+        log_record(
+            &backend,
+            Level::Debug,
+            "runar_common::logging",
+            &[("node_id", "n"), ("component", "Network")],
+            &format!("packet {}", i),
+        );
+    }
+    let messages: Vec<String> = sink.records().into_iter().map(|r| r.message).collect();
+    assert_eq!(messages, vec!["packet 0", "packet 2"]);
+
+    // Invalid values change nothing
+    for invalid in [
+        json!({"level": "loud"}),
+        json!({"sinks": {"file": false}}),
+        json!({"sampling": {"Network": 0}}),
+        json!({"colour": true}),
+        json!({"level": "trace", "sinks": {"memory": "off"}}),
+    ] {
+        assert!(backend
+            .apply_config_value(&ArcValueType::from_json(invalid))
+            .is_err());
+    }
+    assert_eq!(backend.config_as_value().to_json()?["level"], "info");
+
+    backend.apply_config_value(&ArcValueType::from_json(
+        json!({"sinks": {"memory": false}}),
+    ))?;
+    sink.clear();
+    log_record(&backend, Level::Error, "app", &[], "dropped");
+    assert!(sink.records().is_empty());
+    Ok(())
+}
+
+// The only test installing the global backend
+#[test]
+fn test_install_unifies_logger_and_log_output() {
+    let sink = MemorySink::new();
+    let backend = RunarLogBackend::new(LevelFilter::Info)
+        .with_node_id(NodeId::new("node-3").unwrap())
+        .with_sink(sink.clone());
+    logging::install(backend).unwrap();
+    assert!(logging::installed().is_some());
+    assert!(logging::install(RunarLogBackend::new(LevelFilter::Info)).is_err());
+
+    let logger = Logger::new_root(Component::Node, NodeId::new("node-3").unwrap())
+        .with_component(Component::Registry);
+    logger.info("registered service");
+    log::info!(target: "tokio_tungstenite", "handshake done");
+    log::debug!(target: "tokio_tungstenite", "filtered out");
+
+    let records = sink.records();
+    let ours: Vec<_> = records
+        .iter()
+        .filter(|r| r.node_id.as_deref() == Some("node-3"))
+        .collect();
+    assert_eq!(ours.len(), 2);
+    assert_eq!(ours[0].component.as_deref(), Some("Registry"));
+    assert_eq!(ours[0].message, "registered service");
+    assert_eq!(ours[1].target, "tokio_tungstenite");
+    assert_eq!(ours[1].message, "handshake done");
+
+    // The admin interface reconfigures the installed backend
+    assert_eq!(
+        logging::config_as_value().unwrap().to_json().unwrap()["level"],
+        "info"
+    );
+    logging::apply_config_value(&ArcValueType::from_json(json!({"level": "debug"}))).unwrap();
+    assert_eq!(log::max_level(), LevelFilter::Debug);
+    log::debug!(target: "tokio_tungstenite", "now visible");
+    assert_eq!(sink.records().last().unwrap().message, "now visible");
+}
+
+// Turns the backend's level up to error from inside `write`
+struct QuietingSink(std::sync::OnceLock<Arc<RunarLogBackend>>);
+
+impl LogSink for QuietingSink {
+    fn write(&self, _record: &LogRecord, _line: &str) {
+        if let Some(backend) = self.0.get() {
+            backend
+                .apply_config_value(&ArcValueType::from_json(json!({"level": "error"})))
+                .unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_sinks_run_without_holding_the_backend_lock() {
+    let quieting = Arc::new(QuietingSink(std::sync::OnceLock::new()));
+    let backend =
+        Arc::new(RunarLogBackend::new(LevelFilter::Info).with_shared_sink(quieting.clone()));
+    let _ = quieting.0.set(backend.clone());
+
+    log_record(&backend, Level::Warn, "app", &[], "reconfigures");
+    assert_eq!(backend.max_level(), LevelFilter::Error);
+}
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use runar_common::logging::{Component, Logger, LoggerFactory};
+use runar_common::types::NodeId;
+
+// A library that only knows about the factory
+struct Cache {
+    logger: Logger,
+}
+
+impl Cache {
+    fn new(loggers: &dyn LoggerFactory) -> Self {
+        Cache {
+            logger: loggers.logger_for(Component::Custom("Cache")),
+        }
+    }
+}
+
+#[test]
+fn test_root_logger_is_a_factory() {
+    let root = Logger::new_root(Component::Node, NodeId::new("node-1").unwrap());
+    let cache = Cache::new(&root);
+    assert_eq!(cache.logger.component(), Component::Custom("Cache"));
+    assert_eq!(cache.logger.node_id().as_str(), "node-1");
+
+    let shared: Arc<dyn LoggerFactory> = Arc::new(root.with_component(Component::Service));
+    let registry = shared.logger_for(Component::Registry);
+    assert_eq!(registry.component(), Component::Registry);
+    assert_eq!(registry.node_id().as_str(), "node-1");
+}
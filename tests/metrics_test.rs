@@ -0,0 +1,176 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use runar_common::logging::{Component, Logger};
+use runar_common::metrics::{
+    snapshot_as_value, snapshot_schema, MetricLabels, MetricsRegistry, ACTION_DURATION_METRIC,
+};
+use runar_common::types::{NodeId, SchemaRegistry, ValueCategory};
+use runar_common::utils::paths::ActionPath;
+
+fn action_labels() -> MetricLabels {
+    MetricLabels::component(Component::Service)
+        .with_service("math")
+        .with_action("add")
+}
+
+#[test]
+fn test_counter_and_gauge() {
+    let registry = MetricsRegistry::new();
+    let counter = registry.counter("requests_total", &action_labels());
+    counter.inc();
+    counter.add(4);
+
+    // The same name and labels return the same metric
+    assert_eq!(
+        registry.counter("requests_total", &action_labels()).get(),
+        5
+    );
+
+    let gauge = registry.gauge("connections", &MetricLabels::component(Component::Network));
+    gauge.set(3.0);
+    gauge.inc();
+    gauge.dec();
+    gauge.add(-1.5);
+    assert_eq!(gauge.get(), 1.5);
+}
+
+#[test]
+fn test_histogram_buckets() {
+    let registry = MetricsRegistry::new();
+    let histogram =
+        registry.histogram_with_buckets("latency_seconds", &action_labels(), &[0.1, 1.0, 0.5]);
+    histogram.observe(0.05);
+    histogram.observe(0.7);
+    histogram.observe(3.0);
+
+    assert_eq!(histogram.count(), 3);
+    assert!((histogram.sum() - 3.75).abs() < 1e-9);
+    assert_eq!(
+        histogram.cumulative_buckets(),
+        vec![(0.1, 1), (0.5, 1), (1.0, 2), (f64::INFINITY, 3)]
+    );
+}
+
+#[test]
+fn test_concurrent_updates() {
+    let registry = Arc::new(MetricsRegistry::new());
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let registry = registry.clone();
+            thread::spawn(move || {
+                let counter = registry.counter("hits", &MetricLabels::component(Component::Node));
+                let gauge = registry.gauge("level", &MetricLabels::component(Component::Node));
+                for _ in 0..1000 {
+                    counter.inc();
+                    gauge.add(1.0);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let snapshot = registry.snapshot();
+    assert_eq!(snapshot.counters[0].value, 8000);
+    assert_eq!(snapshot.gauges[0].value, 8000.0);
+}
+
+#[test]
+fn test_prometheus_export() {
+    let registry = MetricsRegistry::new();
+    registry.counter("requests_total", &action_labels()).add(2);
+    registry
+        .histogram_with_buckets("latency_seconds", &action_labels(), &[0.5])
+        .observe(0.25);
+
+    let text = registry.snapshot().to_prometheus_text();
+    assert!(text.contains("# TYPE requests_total counter\n"));
+    assert!(
+        text.contains("requests_total{action=\"add\",component=\"Service\",service=\"math\"} 2\n")
+    );
+    assert!(text.contains("# TYPE latency_seconds histogram\n"));
+    assert!(text.contains(
+        "latency_seconds_bucket{action=\"add\",component=\"Service\",service=\"math\",le=\"+Inf\"} 1\n"
+    ));
+    assert!(text.contains(
+        "latency_seconds_count{action=\"add\",component=\"Service\",service=\"math\"} 1\n"
+    ));
+}
+
+#[test]
+fn test_snapshot_as_value_matches_schema() -> anyhow::Result<()> {
+    let registry = MetricsRegistry::new();
+    registry.counter("requests_total", &action_labels()).add(3);
+    registry
+        .gauge("connections", &MetricLabels::component(Component::Network))
+        .set(2.5);
+    registry
+        .histogram_with_buckets("latency_seconds", &action_labels(), &[0.1, 1.0])
+        .observe(0.5);
+
+    let value = registry.snapshot_as_value();
+    assert_eq!(value.category, ValueCategory::Map);
+    let json = value.to_json()?;
+    SchemaRegistry::new().validate_field(&snapshot_schema(), &json, "$")?;
+
+    assert_eq!(json["counters"][0]["name"], "requests_total");
+    assert_eq!(json["counters"][0]["labels"]["action"], "add");
+    assert_eq!(json["counters"][0]["value"], 3);
+    assert_eq!(json["gauges"][0]["labels"]["component"], "Network");
+    assert!(json["gauges"][0]["labels"].get("service").is_none());
+    assert_eq!(json["gauges"][0]["value"], 2.5);
+    assert_eq!(
+        json["histograms"][0]["buckets"],
+        serde_json::json!([{"le": 0.1, "count": 0}, {"le": 1.0, "count": 1}])
+    );
+    assert_eq!(json["histograms"][0]["count"], 1);
+
+    // The process-wide registry renders the same way
+    assert_eq!(snapshot_as_value().category, ValueCategory::Map);
+    Ok(())
+}
+
+#[test]
+fn test_time_action_records_latency() -> anyhow::Result<()> {
+    let registry = MetricsRegistry::new();
+    let path = ActionPath::new("math/add")?;
+    let logger = Logger::new_root(Component::Node, NodeId::new("node-1")?);
+
+    {
+        let _timer = registry
+            .time_action(&path)
+            .with_logger(&logger)
+            .with_slow_threshold(Duration::ZERO);
+        thread::sleep(Duration::from_millis(2));
+    }
+    let elapsed = registry.time_action(&path).finish();
+    assert!(elapsed < Duration::from_secs(1));
+
+    let histogram = registry.histogram(ACTION_DURATION_METRIC, &action_labels());
+    assert_eq!(histogram.count(), 2);
+    assert!(histogram.sum() >= 0.002);
+    Ok(())
+}
+
+#[test]
+fn test_prometheus_help_and_escaping() {
+    let registry = MetricsRegistry::new();
+    registry.describe("queue-depth", "Items waiting\nin \\queue");
+    registry
+        .gauge(
+            "queue-depth",
+            &MetricLabels::component(Component::Custom("Queue")).with_service("a\"b\\c\nd"),
+        )
+        .set(f64::NAN);
+
+    let text = registry.to_prometheus_text();
+    assert_eq!(
+        text,
+        "# HELP queue_depth Items waiting\\nin \\\\queue\n\
+         # TYPE queue_depth gauge\n\
+         queue_depth{component=\"Queue\",service=\"a\\\"b\\\\c\\nd\"} NaN\n"
+    );
+}
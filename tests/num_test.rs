@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use runar_common::errors::{ErrorCode, RunarError};
+use runar_common::types::{ArcValueType, FieldSchema, FromArcValue, SchemaRegistry};
+use runar_common::utils::num::{checked, clamp_to_range, coerce, saturating, Number, Numeric};
+use runar_common::utils::value_converters::{value_as_int, value_as_number};
+use serde_json::json;
+
+fn error_code(err: anyhow::Error) -> ErrorCode {
+    err.downcast_ref::<RunarError>().expect("RunarError").code
+}
+
+#[test]
+fn test_checked_conversions() {
+    assert_eq!(checked::<i64, i32>(42).unwrap(), 42);
+    assert_eq!(checked::<u64, i64>(i64::MAX as u64).unwrap(), i64::MAX);
+    assert_eq!(checked::<f64, i64>(-3.0).unwrap(), -3);
+    assert_eq!(checked::<i32, f64>(7).unwrap(), 7.0);
+    assert_eq!(checked::<u128, u128>(u128::MAX).unwrap(), u128::MAX);
+
+    let err = checked::<i64, i32>(i64::from(i32::MAX) + 1).unwrap_err();
+    assert_eq!(error_code(err), ErrorCode::InvalidInput);
+    assert!(checked::<i32, u32>(-1).is_err());
+    assert!(checked::<u128, i64>(u128::MAX).is_err());
+    assert!(checked::<f64, i32>(1.5).is_err());
+    assert!(checked::<f64, i64>(f64::NAN).is_err());
+    assert!(checked::<f64, i64>(f64::INFINITY).is_err());
+    // 2^63 is exactly representable as f64 but one past i64::MAX
+    assert!(checked::<f64, i64>(9_223_372_036_854_775_808.0).is_err());
+    assert!(checked::<f64, f32>(1e300).is_err());
+    assert!(checked::<f64, f32>(f64::NAN).unwrap().is_nan());
+
+    let message = checked::<i32, u8>(300).unwrap_err().to_string();
+    assert!(message.contains("300 does not fit in u8"), "{}", message);
+}
+
+#[test]
+fn test_saturating_conversions() {
+    assert_eq!(saturating::<i64, i32>(i64::MAX), i32::MAX);
+    assert_eq!(saturating::<i64, i32>(i64::MIN), i32::MIN);
+    assert_eq!(saturating::<i32, u8>(-5), 0);
+    assert_eq!(saturating::<u128, i64>(u128::MAX), i64::MAX);
+    assert_eq!(saturating::<f64, i32>(2.9), 2);
+    assert_eq!(saturating::<f64, u32>(-1.0), 0);
+    assert_eq!(saturating::<f64, i64>(f64::NAN), 0);
+    assert_eq!(saturating::<f64, f32>(1e300), f32::MAX);
+    assert_eq!(saturating::<f64, f32>(-1e300), f32::MIN);
+}
+
+#[test]
+fn test_coerce_value() {
+    assert_eq!(
+        coerce::<i64>(&ArcValueType::new_primitive(5i32)).unwrap(),
+        5
+    );
+    assert_eq!(
+        coerce::<f64>(&ArcValueType::new_primitive(5u8)).unwrap(),
+        5.0
+    );
+    assert_eq!(
+        coerce::<u16>(&ArcValueType::new_primitive(8.0f32)).unwrap(),
+        8
+    );
+    assert!(coerce::<u8>(&ArcValueType::new_primitive(256i64)).is_err());
+
+    let err = coerce::<i64>(&ArcValueType::new_primitive("5".to_string())).unwrap_err();
+    assert_eq!(error_code(err), ErrorCode::InvalidInput);
+    assert_eq!(Number::from_value(&ArcValueType::null()), None);
+}
+
+#[test]
+fn test_exact_range_checks() {
+    let big = Number::Int(i64::MAX as i128);
+    // i64::MAX rounds up to 2^63 as f64, but the comparison stays exact
+    assert!(big.in_range(None, false, Some(9_223_372_036_854_775_808.0), true));
+    assert!(Number::Int(3).in_range(Some(2.5), true, Some(3.0), false));
+    assert!(!Number::Int(3).in_range(None, false, Some(3.0), true));
+    assert!(!Number::Int(2).in_range(Some(2.5), false, None, false));
+    assert!(Number::UInt(u128::MAX).in_range(Some(-1.0), true, None, false));
+    assert!(!Number::Float(f64::NAN).in_range(Some(0.0), false, None, false));
+
+    assert_eq!(clamp_to_range(15.0, Some(0.0), Some(10.0)), 10.0);
+    assert_eq!(clamp_to_range(-1.0, Some(0.0), None), 0.0);
+    assert!(clamp_to_range(f64::NAN, Some(0.0), Some(1.0)).is_nan());
+}
+
+#[test]
+fn test_schema_range() {
+    let mut age = FieldSchema::integer("age");
+    age.minimum = Some(0.0);
+    age.maximum = Some(150.0);
+    assert_eq!(age.clamp(200i32), 150);
+    assert_eq!(age.clamp(-4i64), 0);
+    assert_eq!(age.clamp(42u8), 42);
+    assert_eq!(age.clamp(150.5f64), 150.0);
+
+    let mut ratio = FieldSchema::integer("ratio");
+    ratio.minimum = Some(1.5);
+    ratio.maximum = Some(9.5);
+    assert_eq!(ratio.clamp(0i32), 2);
+    assert_eq!(ratio.clamp(12u8), 9);
+    assert_eq!(ratio.clamp(0.0f64), 1.5);
+
+    let mut id = FieldSchema::long("id");
+    id.maximum = Some(9_007_199_254_740_992.0);
+    let mut properties = HashMap::new();
+    properties.insert("id".to_string(), Box::new(id));
+    let mut schemas = SchemaRegistry::new();
+    let record = schemas
+        .register("record", 1, FieldSchema::object("record", properties, None))
+        .unwrap();
+
+    // 2^53 + 1 is above the maximum even though it casts to 2^53 as f64
+    assert!(schemas
+        .validate_json(&record, &json!({ "id": 9_007_199_254_740_992u64 }))
+        .is_ok());
+    assert!(schemas
+        .validate_json(&record, &json!({ "id": 9_007_199_254_740_993u64 }))
+        .is_err());
+}
+
+#[test]
+fn test_schema_clamp_respects_exclusive_bounds() {
+    let mut score = FieldSchema::integer("score");
+    score.minimum = Some(0.0);
+    score.maximum = Some(10.0);
+    score.exclusive_minimum = Some(true);
+    score.exclusive_maximum = Some(true);
+    let in_range = |n: Number| n.in_range(Some(0.0), true, Some(10.0), true);
+
+    assert_eq!(score.clamp(-3i32), 1);
+    assert_eq!(score.clamp(0u8), 1);
+    assert_eq!(score.clamp(10i64), 9);
+    assert_eq!(score.clamp(5i32), 5);
+    assert_eq!(score.clamp(0.0f64), 0.0f64.next_up());
+    assert_eq!(score.clamp(12.0f64), 10.0f64.next_down());
+    assert!(score.clamp(f64::NAN).is_nan());
+    for x in [-3.0, 0.0, 10.0, 99.0] {
+        assert!(in_range(score.clamp(x as i32).to_number()));
+        assert!(in_range(score.clamp(x).to_number()));
+        assert!(in_range(score.clamp(x as f32).to_number()));
+    }
+
+    // A bound that f32 rounds below still clamps to a value above it
+    let mut share = FieldSchema::double("share");
+    share.minimum = Some(0.7);
+    share.exclusive_minimum = Some(true);
+    assert!((0.7f32 as f64) < 0.7);
+    let clamped = share.clamp(0.0f32);
+    assert!(clamped.to_number().in_range(Some(0.7), true, None, false));
+    assert_eq!(clamped, 0.7f32.next_up());
+
+    // Fractional exclusive bounds on integers behave like inclusive ones
+    let mut half = FieldSchema::integer("half");
+    half.minimum = Some(1.5);
+    half.exclusive_minimum = Some(true);
+    assert_eq!(half.clamp(0i32), 2);
+}
+
+#[test]
+fn test_conversions_widen_numbers() -> anyhow::Result<()> {
+    let small = ArcValueType::new_primitive(7i32);
+    assert_eq!(i64::from_arc_value(small.clone())?, 7);
+    assert_eq!(f64::from_arc_value(small.clone())?, 7.0);
+    assert_eq!(value_as_int(&small)?, 7);
+    assert_eq!(value_as_number(&ArcValueType::new_primitive(2.5f32))?, 2.5);
+
+    let err = u8::from_arc_value(ArcValueType::new_primitive(300i64)).unwrap_err();
+    assert_eq!(error_code(err), ErrorCode::InvalidInput);
+    assert!(value_as_int(&ArcValueType::new_primitive(2.5f64)).is_err());
+    assert!(i64::from_arc_value(ArcValueType::new_primitive("7".to_string())).is_err());
+    Ok(())
+}
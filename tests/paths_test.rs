@@ -0,0 +1,155 @@
+use anyhow::Result;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{ActionMetadata, EventMetadata, NetworkId, NodeId};
+use runar_common::utils::paths::{
+    join_path, matches_pattern, ActionPath, EventPath, ServicePath, TopicPath,
+};
+use runar_common::utils::router::PathRouter;
+
+#[test]
+fn test_parse_and_display() -> Result<()> {
+    let service = ServicePath::new("/math/")?;
+    assert_eq!(service.as_str(), "math");
+
+    let action = ActionPath::new("math/ops/add")?;
+    assert_eq!(action.service().as_str(), "math");
+    assert_eq!(action.action_name(), "ops/add");
+    assert_eq!(action.segments(), vec!["math", "ops", "add"]);
+    assert_eq!(action.to_string(), "math/ops/add");
+
+    let topic: TopicPath = "math/added".parse()?;
+    assert_eq!(topic.segments(), &["math".to_string(), "added".to_string()]);
+    Ok(())
+}
+
+#[test]
+fn test_validation() {
+    assert!(ServicePath::new("").is_err());
+    assert!(ServicePath::new("math/ops").is_err());
+    assert!(ActionPath::new("math").is_err());
+    assert!(ActionPath::new("math//add").is_err());
+    assert!(ActionPath::new("math/*").is_err());
+    assert!(TopicPath::new("math/>/x").is_err());
+    assert!(TopicPath::new("math/a b").is_err());
+}
+
+#[test]
+fn test_joining() -> Result<()> {
+    assert_eq!(join_path(&["/math/", "add", ""]), "math/add");
+
+    let service = ServicePath::new("math")?;
+    assert_eq!(service.action("add")?.to_string(), "math/add");
+    let nested = service.action("ops/add")?;
+    assert_eq!(nested.service().as_str(), "math");
+    assert_eq!(nested.action_name(), "ops/add");
+    assert_eq!(
+        service.topic("added")?.join("v1")?.to_string(),
+        "math/added/v1"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_wildcard_matching() -> Result<()> {
+    assert!(matches_pattern("math/*", "math/added"));
+    assert!(!matches_pattern("math/*", "math/added/v1"));
+    assert!(matches_pattern("math/>", "math/added/v1"));
+    assert!(!matches_pattern("math/>", "math"));
+    assert!(!matches_pattern("a/>/c", "a/b"));
+    assert!(!matches_pattern("a/>/c", "a/x/y/z"));
+    assert!(!matches_pattern("a/>/c", "a/b/c"));
+    assert!(matches_pattern("*/added", "stats/added"));
+    assert!(!matches_pattern("math/added", "math/removed"));
+
+    let pattern = TopicPath::new("users/*/>")?;
+    assert!(pattern.is_pattern());
+    assert!(pattern.matches(&TopicPath::new("users/42/profile/updated")?));
+    assert!(!pattern.matches(&TopicPath::new("users/42")?));
+    Ok(())
+}
+
+#[test]
+fn test_event_path_builder() -> Result<()> {
+    let path = EventPath::service("auth").event("user_created").build()?;
+    assert_eq!(path.to_string(), "auth/user_created");
+    assert_eq!(path.network(), None);
+    assert_eq!(path.topic(), TopicPath::new("auth/user_created")?);
+
+    let network = NetworkId::new("main")?;
+    let path = EventPath::service("auth")
+        .event("user/created")
+        .with_network(network.clone())
+        .build()?;
+    assert_eq!(path.to_string(), "main:auth/user/created");
+    assert_eq!(path.network(), Some(&network));
+    assert_eq!(path.service_path().as_str(), "auth");
+    assert_eq!(path.event_name(), "user/created");
+    assert_eq!(EventPath::new("main:auth/user/created")?, path);
+
+    assert!(EventPath::service("auth").build().is_err());
+    assert!(EventPath::service("auth").event("").build().is_err());
+    assert!(EventPath::service("auth")
+        .event("user created")
+        .build()
+        .is_err());
+    assert!(EventPath::service("a/b").event("x").build().is_err());
+    assert!(EventPath::service("a:b").event("x").build().is_err());
+    assert!(EventPath::service("auth").event("*").build().is_err());
+    assert!(EventPath::new("auth").is_err());
+
+    let json = serde_json::to_string(&path)?;
+    assert_eq!(serde_json::from_str::<EventPath>(&json)?, path);
+    assert!(serde_json::from_str::<EventPath>("\"auth\"").is_err());
+
+    let metadata = EventMetadata::new(&path, "A user was created");
+    assert_eq!(metadata.path, "main:auth/user/created");
+    assert_eq!(metadata.event_path()?, path);
+
+    let logger = Logger::new_root(Component::Service, NodeId::new("node-1")?).with_event_path(path);
+    assert_eq!(logger.event_path(), Some("main:auth/user/created"));
+    Ok(())
+}
+
+#[test]
+fn test_action_path_templates() -> Result<()> {
+    let template = ActionPath::new("files/{file_id}/read")?;
+    assert!(template.is_template());
+    assert_eq!(template.params(), vec!["file_id"]);
+    assert!(!ActionPath::new("files/read")?.is_template());
+
+    let bound = template.bind(&[("file_id", "42")])?;
+    assert_eq!(bound.to_string(), "files/42/read");
+    assert!(!bound.is_template());
+    assert!(template.bind(&[]).is_err());
+    assert!(template.bind(&[("file_id", "42"), ("other", "x")]).is_err());
+    assert!(template.bind(&[("file_id", "a/b")]).is_err());
+    assert!(template.bind(&[("file_id", "{x}")]).is_err());
+
+    assert_eq!(
+        template.extract("files/42/read"),
+        Some(vec![("file_id", "42".to_string())])
+    );
+    assert_eq!(template.extract("files/42/write"), None);
+    assert_eq!(template.extract("files/42"), None);
+
+    assert!(ActionPath::new("{service}/read").is_err());
+    assert!(ActionPath::new("files/{id}/{id}").is_err());
+    assert!(ActionPath::new("files/{file-id}").is_err());
+    assert!(ActionPath::new("files/id}").is_err());
+
+    let metadata = ActionMetadata {
+        name: "{file_id}/read".to_string(),
+        description: "Read a file".to_string(),
+        input_schema: None,
+        output_schema: None,
+    };
+    let path = metadata.action_path(&ServicePath::new("files")?)?;
+    assert_eq!(path, template);
+
+    // The router accepts the same template syntax
+    let mut router = PathRouter::new();
+    router.insert(&path.to_string(), "read")?;
+    let found = router.route_exact("files/42/read").unwrap();
+    assert_eq!(found.params, template.extract("files/42/read").unwrap());
+    Ok(())
+}
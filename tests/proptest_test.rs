@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use proptest::prelude::*;
+use runar_common::logging::{Component, Logger};
+use runar_common::testing::{arb_registered_value, arb_value_tree};
+use runar_common::types::{ArcValueType, NodeId, SerializerRegistry};
+
+fn registry() -> SerializerRegistry {
+    SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )))
+}
+
+proptest! {
+    #[test]
+    fn registered_values_round_trip(value in arb_registered_value()) {
+        let registry = registry();
+        let bytes = registry.serialize_value(&value).unwrap();
+        let decoded = registry.deserialize_value(bytes).unwrap();
+        prop_assert_eq!(decoded.category, value.category);
+        prop_assert_eq!(decoded.to_json().unwrap(), value.to_json().unwrap());
+    }
+
+    #[test]
+    fn reserialization_is_stable(value in any::<ArcValueType>()) {
+        let registry = registry();
+        let bytes = registry.serialize_value(&value).unwrap();
+        let decoded = registry.deserialize_value(bytes.clone()).unwrap();
+        prop_assert_eq!(registry.serialize_value(&decoded).unwrap(), bytes);
+    }
+
+    #[test]
+    fn clones_are_equal(value in arb_value_tree(3)) {
+        prop_assert_eq!(value.clone(), value);
+    }
+
+    #[test]
+    fn value_trees_round_trip_through_json(value in arb_value_tree(3)) {
+        let json = value.to_json().unwrap();
+        let rebuilt = ArcValueType::from_json_value(json.clone());
+        prop_assert_eq!(rebuilt.to_json().unwrap(), json);
+    }
+}
@@ -0,0 +1,111 @@
+use anyhow::Result;
+use prost_reflect::prost::Message;
+use prost_reflect::prost_types::field_descriptor_proto::{Label, Type};
+use prost_reflect::prost_types::{
+    DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+};
+use prost_reflect::{DescriptorPool, DynamicMessage, Value};
+use runar_common::types::{ArcValueType, ProtobufBridge};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Reading {
+    sensor: String,
+    value: f64,
+    sequence: i64,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Extended {
+    sensor: String,
+    unit: String,
+}
+
+fn field(name: &str, number: i32, kind: Type, label: Label) -> FieldDescriptorProto {
+    FieldDescriptorProto {
+        name: Some(name.to_string()),
+        number: Some(number),
+        r#type: Some(kind as i32),
+        label: Some(label as i32),
+        json_name: Some(name.to_string()),
+        ..Default::default()
+    }
+}
+
+fn descriptor_set() -> Vec<u8> {
+    let reading = DescriptorProto {
+        name: Some("Reading".to_string()),
+        field: vec![
+            field("sensor", 1, Type::String, Label::Optional),
+            field("value", 2, Type::Double, Label::Optional),
+            field("sequence", 3, Type::Int64, Label::Optional),
+            field("tags", 4, Type::String, Label::Repeated),
+        ],
+        ..Default::default()
+    };
+    let file = FileDescriptorProto {
+        name: Some("sensors.proto".to_string()),
+        package: Some("sensors".to_string()),
+        syntax: Some("proto3".to_string()),
+        message_type: vec![reading],
+        ..Default::default()
+    };
+    FileDescriptorSet { file: vec![file] }.encode_to_vec()
+}
+
+fn bridge() -> ProtobufBridge {
+    let mut bridge = ProtobufBridge::from_file_descriptor_set(&descriptor_set()).unwrap();
+    bridge.register::<Reading>("sensors.Reading").unwrap();
+    bridge
+}
+
+#[test]
+fn test_round_trip_through_protobuf() -> Result<()> {
+    let bridge = bridge();
+    let reading = Reading {
+        sensor: "t1".to_string(),
+        value: 21.5,
+        sequence: 1 << 40,
+        tags: vec!["indoor".to_string()],
+    };
+    let bytes = bridge.encode(&ArcValueType::from_struct(reading.clone()))?;
+
+    // Any protobuf implementation can read the message
+    let pool = DescriptorPool::decode(descriptor_set().as_slice())?;
+    let descriptor = pool.get_message_by_name("sensors.Reading").unwrap();
+    let message = DynamicMessage::decode(descriptor, bytes.as_slice())?;
+    assert_eq!(
+        message.get_field_by_name("sequence").unwrap().as_ref(),
+        &Value::I64(1 << 40)
+    );
+
+    let mut decoded = bridge.decode("sensors.Reading", &bytes)?;
+    assert_eq!(*decoded.as_struct_ref::<Reading>()?, reading);
+    assert_eq!(
+        bridge.descriptor_for::<Reading>().unwrap().full_name(),
+        "sensors.Reading"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_default_fields_decode() -> Result<()> {
+    let bridge = bridge();
+    let mut decoded = bridge.decode("sensors.Reading", &[])?;
+    let reading = decoded.as_struct_ref::<Reading>()?;
+    assert_eq!(reading.sensor, "");
+    assert!(reading.tags.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_mismatched_types_are_rejected() {
+    let mut bridge = bridge();
+    assert!(bridge.register::<Reading>("sensors.Missing").is_err());
+    assert!(bridge.register::<Extended>("sensors.Reading").is_err());
+
+    let unregistered = ArcValueType::new_primitive(1i64);
+    assert!(bridge.encode(&unregistered).is_err());
+    assert!(bridge.decode("sensors.Other", &[]).is_err());
+}
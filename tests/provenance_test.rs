@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{ArcValueType, NodeId, Provenance, SerializerRegistry};
+use runar_common::utils::time::{SystemTime, UNIX_EPOCH};
+
+fn registry() -> Arc<SerializerRegistry> {
+    let mut registry = SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )));
+    registry
+        .register::<HashMap<String, ArcValueType>>()
+        .unwrap();
+    Arc::new(registry)
+}
+
+#[test]
+fn test_deserialize_stamps_provenance() -> Result<()> {
+    let registry = registry();
+    let bytes = registry.serialize_value(&ArcValueType::new_primitive(42i64))?;
+
+    let before = SystemTime::now();
+    let value = registry.deserialize_value(bytes.clone())?;
+    let provenance = value.provenance().expect("provenance");
+    assert!(provenance.received_at >= before);
+    assert_eq!(provenance.origin, None);
+
+    let value = registry.deserialize_value_from(bytes.clone(), "node-7")?;
+    assert_eq!(
+        value.provenance().unwrap().origin,
+        Some(NodeId::new("node-7")?)
+    );
+    // Sources that are not node ids are only used for logging
+    let value = registry.deserialize_value_from(bytes.clone(), "10.0.0.1:4433 (peer)")?;
+    assert_eq!(value.provenance().unwrap().origin, None);
+
+    let received_at = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    let expected = Provenance::received_at(received_at)
+        .with_origin(NodeId::new("node-7")?)
+        .with_transport("quic");
+    let mut value = registry.deserialize_value_with(bytes, expected.clone())?;
+    assert_eq!(value.provenance(), Some(&expected));
+    assert_eq!(value.as_type::<i64>()?, 42);
+    Ok(())
+}
+
+#[test]
+fn test_provenance_is_local_metadata() -> Result<()> {
+    let registry = registry();
+    let provenance = Provenance::received().with_transport("memory");
+    let local = ArcValueType::new_primitive("hello".to_string());
+    assert!(local.provenance().is_none());
+
+    // Ignored by equality and not serialized
+    let stamped = local.clone().with_provenance(provenance.clone());
+    assert_eq!(stamped, local);
+    assert_eq!(
+        registry.serialize_value(&stamped)?,
+        registry.serialize_value(&local)?
+    );
+
+    // Kept by clones, deep copies and in-place updates
+    assert_eq!(stamped.clone().provenance(), Some(&provenance));
+    assert_eq!(stamped.deep_clone_owned()?.provenance(), Some(&provenance));
+    let mut map = ArcValueType::new_map(HashMap::<String, ArcValueType>::new())
+        .with_provenance(provenance.clone());
+    map.update_map(|entries| entries.insert("k".to_string(), ArcValueType::null()))?;
+    assert_eq!(map.provenance(), Some(&provenance));
+
+    assert_eq!(map.take_provenance(), Some(provenance));
+    assert!(map.provenance().is_none());
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use runar_common::utils::rate_limit::{RateLimit, RateLimiter, TokenBucket};
+
+#[test]
+fn test_bucket_allows_burst_then_refills() {
+    let start = Instant::now();
+    let bucket = TokenBucket::starting_at(RateLimit::new(10.0, 3), start);
+
+    assert_eq!(bucket.available_at(start), 3);
+    assert!(bucket.try_acquire_n_at(1, start));
+    assert!(bucket.try_acquire_n_at(2, start));
+    assert!(!bucket.try_acquire_n_at(1, start));
+
+    // 10 tokens/s -> one token every 100ms
+    let later = start + Duration::from_millis(100);
+    assert_eq!(bucket.available_at(later), 1);
+    assert!(bucket.try_acquire_n_at(1, later));
+    assert!(!bucket.try_acquire_n_at(1, later));
+
+    // Refill never exceeds the burst size
+    let much_later = start + Duration::from_secs(60);
+    assert_eq!(bucket.available_at(much_later), 3);
+    assert!(bucket.is_full_at(much_later));
+    assert!(!bucket.try_acquire_n_at(4, much_later));
+}
+
+#[test]
+fn test_non_positive_rate_allows_burst_only() {
+    let start = Instant::now();
+    for per_second in [0.0, -5.0, f64::NAN] {
+        let bucket = TokenBucket::starting_at(RateLimit::new(per_second, 5), start);
+        assert_eq!(bucket.available_at(start), 5);
+        assert!(bucket.try_acquire_n_at(5, start));
+        assert!(!bucket.try_acquire_n_at(1, start));
+
+        let much_later = start + Duration::from_secs(3600);
+        assert_eq!(bucket.available_at(much_later), 0);
+        assert!(!bucket.try_acquire_n_at(1, much_later));
+        assert_eq!(bucket.wait_time(1), Duration::MAX);
+    }
+}
+
+#[test]
+fn test_limiter_tracks_keys_independently() {
+    let limiter = RateLimiter::new(RateLimit::new(0.001, 2));
+    assert!(limiter.try_acquire(&"peer-a"));
+    assert!(limiter.try_acquire(&"peer-a"));
+    assert!(!limiter.try_acquire(&"peer-a"));
+    assert!(limiter.try_acquire_n(&"peer-b", 2));
+    assert_eq!(limiter.len(), 2);
+
+    limiter.remove(&"peer-a");
+    assert!(limiter.try_acquire(&"peer-a"));
+    assert!(limiter.bucket(&"peer-b").wait_time(1) > Duration::from_secs(1));
+}
+
+#[test]
+fn test_concurrent_acquire_never_exceeds_burst() {
+    let bucket = Arc::new(TokenBucket::new(RateLimit::new(0.001, 100)));
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let bucket = bucket.clone();
+            std::thread::spawn(move || (0..50).filter(|_| bucket.try_acquire()).count())
+        })
+        .collect();
+    let granted: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    assert_eq!(granted, 100);
+}
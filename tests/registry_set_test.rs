@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{ArcValueType, NetworkId, NodeId, RegistrySet, WireError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Invoice {
+    amount: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Heartbeat {
+    seq: u64,
+}
+
+fn registry_set() -> RegistrySet {
+    RegistrySet::new(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )))
+}
+
+#[test]
+fn test_network_types_are_isolated() -> Result<()> {
+    let billing = NetworkId::new("billing")?;
+    let public = NetworkId::new("public")?;
+    let mut set = registry_set();
+    set.network_mut(&billing).register::<Invoice>()?;
+    set.network_mut(&public);
+
+    let invoice = ArcValueType::from_struct(Invoice { amount: 42 });
+    let bytes = set.serialize_value(&billing, &invoice)?;
+    let mut decoded = set.deserialize_value(&billing, bytes.clone())?;
+    assert_eq!(decoded.as_struct_ref::<Invoice>()?.amount, 42);
+
+    // Neither another network nor an unknown one can decode the payload
+    for network in [&public, &NetworkId::new("unknown")?] {
+        let err = set.deserialize_value(network, bytes.clone()).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<WireError>(),
+            Some(WireError::UnknownType { .. })
+        ));
+        assert!(set.serialize_value(network, &invoice).is_err());
+    }
+
+    let mut networks: Vec<_> = set.networks().map(|id| id.as_str().to_string()).collect();
+    networks.sort();
+    assert_eq!(networks, vec!["billing", "public"]);
+    Ok(())
+}
+
+#[test]
+fn test_shared_types_reach_every_network() -> Result<()> {
+    let billing = NetworkId::new("billing")?;
+    let later = NetworkId::new("later")?;
+    let mut set = registry_set();
+    set.network_mut(&billing);
+    set.register_shared::<Heartbeat>()?;
+
+    let text = ArcValueType::new_primitive("hello".to_string());
+    let heartbeat = ArcValueType::from_struct(Heartbeat { seq: 7 });
+    for network in [&billing, &later] {
+        let mut decoded = set.deserialize_value(network, set.serialize_value(network, &text)?)?;
+        assert_eq!(*decoded.as_type_ref::<String>()?, "hello");
+        let bytes = set.serialize_value(network, &heartbeat)?;
+        assert_eq!(
+            set.deserialize_value(network, bytes)?
+                .as_struct_ref::<Heartbeat>()?
+                .seq,
+            7
+        );
+    }
+
+    // Networks created later fork the shared registry, including Heartbeat
+    set.network_mut(&later).register::<Invoice>()?;
+    assert!(set.get(&later).serialize_value(&heartbeat).is_ok());
+
+    set.seal();
+    assert!(set.network_mut(&billing).register::<Invoice>().is_err());
+    let sealed_later = NetworkId::new("sealed-later")?;
+    assert!(set.network_mut(&sealed_later).is_sealed());
+    assert!(set.remove(&billing).is_some());
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use anyhow::anyhow;
+use runar_common::errors::{ErrorCode, RunarError};
+use runar_common::utils::retry::{retry, retry_async, retry_if, RetryPolicy};
+
+fn unavailable() -> RunarError {
+    RunarError::new(ErrorCode::Unavailable, "peer not reachable")
+}
+
+#[test]
+fn test_exponential_delays_are_capped() {
+    let policy =
+        RetryPolicy::exponential(10, Duration::from_millis(100), Duration::from_millis(500));
+    assert_eq!(policy.base_delay(1), Duration::from_millis(100));
+    assert_eq!(policy.base_delay(2), Duration::from_millis(200));
+    assert_eq!(policy.base_delay(3), Duration::from_millis(400));
+    assert_eq!(policy.base_delay(4), Duration::from_millis(500));
+
+    let jittered = policy.with_jitter(0.5);
+    for _ in 0..20 {
+        let delay = jittered.delay(1);
+        assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(150));
+    }
+}
+
+#[test]
+fn test_retries_retryable_errors_until_success() {
+    let policy = RetryPolicy::fixed(5, Duration::ZERO);
+    let result = retry(&policy, |attempt| {
+        if attempt < 3 {
+            Err(unavailable())
+        } else {
+            Ok(attempt)
+        }
+    });
+    assert_eq!(result, Ok(3));
+}
+
+#[test]
+fn test_stops_on_non_retryable_error_and_max_attempts() {
+    let policy = RetryPolicy::fixed(5, Duration::ZERO);
+
+    let mut calls = 0;
+    let result: Result<(), RunarError> = retry(&policy, |_| {
+        calls += 1;
+        Err(RunarError::invalid_input("bad request"))
+    });
+    assert!(result.is_err());
+    assert_eq!(calls, 1);
+
+    let mut calls = 0;
+    let result: Result<(), anyhow::Error> = retry(&policy, |_| {
+        calls += 1;
+        Err(anyhow::Error::new(unavailable()))
+    });
+    assert!(result.is_err());
+    assert_eq!(calls, 5);
+
+    let mut calls = 0;
+    let result: Result<(), anyhow::Error> = retry_if(
+        &policy,
+        |_| {
+            calls += 1;
+            Err(anyhow!("flaky"))
+        },
+        |_| true,
+    );
+    assert!(result.is_err());
+    assert_eq!(calls, 5);
+}
+
+#[tokio::test]
+async fn test_async_retry() {
+    let policy = RetryPolicy::fixed(3, Duration::from_millis(1));
+    let result = retry_async(&policy, |attempt| async move {
+        if attempt < 2 {
+            Err(unavailable())
+        } else {
+            Ok("done")
+        }
+    })
+    .await;
+    assert_eq!(result, Ok("done"));
+}
@@ -0,0 +1,105 @@
+use anyhow::Result;
+use runar_common::utils::router::PathRouter;
+
+#[test]
+fn test_literal_and_parameter_routes() -> Result<()> {
+    let mut router = PathRouter::new();
+    router.insert("users/{id}/get", "get-user")?;
+    router.insert("users/me/get", "get-me")?;
+    router.insert("users/{id}/posts/{post}", "get-post")?;
+    assert_eq!(router.len(), 3);
+
+    let found = router.route_exact("users/42/get").unwrap();
+    assert_eq!(*found.value, "get-user");
+    assert_eq!(found.param("id"), Some("42"));
+
+    // Literal segments win over parameters
+    let found = router.route_exact("/users/me/get/").unwrap();
+    assert_eq!(*found.value, "get-me");
+    assert!(found.params.is_empty());
+
+    let found = router.route_exact("users/7/posts/hello").unwrap();
+    assert_eq!(
+        found.params,
+        vec![("id", "7".to_string()), ("post", "hello".to_string())]
+    );
+
+    assert!(router.route("users/7").is_none());
+    assert!(router.route_exact("users/7/delete").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_longest_prefix_match() -> Result<()> {
+    let mut router = PathRouter::new();
+    router.insert("files", 1)?;
+    router.insert("files/{bucket}", 2)?;
+    router.insert("files/public/docs", 3)?;
+
+    let found = router.route("files/public/docs/a/b").unwrap();
+    assert_eq!(*found.value, 3);
+    assert_eq!(found.rest, vec!["a", "b"]);
+
+    // Falls back to the parameter route when the literal branch runs out
+    let found = router.route("files/public/images").unwrap();
+    assert_eq!(*found.value, 2);
+    assert_eq!(found.param("bucket"), Some("public"));
+    assert_eq!(found.rest, vec!["images"]);
+    assert!(!found.is_exact());
+
+    assert_eq!(*router.route("files").unwrap().value, 1);
+    assert!(router.route("other").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_insert_validation() -> Result<()> {
+    let mut router = PathRouter::new();
+    assert_eq!(router.insert("svc/{id}/get", 1)?, None);
+    assert_eq!(router.insert("svc/{id}/get", 2)?, Some(1));
+    assert_eq!(router.len(), 1);
+
+    let err = router.insert("svc/{name}/put", 3).unwrap_err();
+    assert!(err.to_string().contains("conflicts with '{id}'"));
+    // A failed insert leaves the router unchanged
+    assert_eq!(router.len(), 1);
+    assert!(router.route("svc/x/put").is_none());
+    assert!(router.insert("", 4).is_err());
+    assert!(router.insert("svc/{}/get", 5).is_err());
+    assert!(router.insert("svc/a b", 6).is_err());
+    assert!(router.insert("svc/{a-b}/get", 7).is_err());
+    assert!(router.insert("svc/x{id}/get", 8).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_overlapping_routes() -> Result<()> {
+    // Every position offers both a literal and a parameter: the worst case
+    const DEPTH: usize = 10;
+    let mut router = PathRouter::new();
+    for mask in 0u32..1 << DEPTH {
+        let segments: Vec<String> = (0..DEPTH)
+            .map(|i| match mask & (1 << i) {
+                0 => "a".to_string(),
+                _ => format!("{{p{}}}", i),
+            })
+            .collect();
+        router.insert(&segments.join("/"), mask)?;
+    }
+    assert_eq!(router.len(), 1 << DEPTH);
+
+    let literal = ["a"; DEPTH].join("/");
+    assert_eq!(*router.route_exact(&literal).unwrap().value, 0);
+
+    // Only the first segment needs a parameter
+    let path = format!("b/{}", ["a"; DEPTH - 1].join("/"));
+    let found = router.route_exact(&path).unwrap();
+    assert_eq!(*found.value, 1);
+    assert_eq!(found.params, vec![("p0", "b".to_string())]);
+
+    // Segments past the longest route are left over
+    let found = router.route(&format!("{}/extra", literal)).unwrap();
+    assert_eq!(*found.value, 0);
+    assert_eq!(found.rest, vec!["extra"]);
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use runar_common::logging::{Component, Logger};
+use runar_common::metrics::{self, MetricLabels};
+use runar_common::types::NodeId;
+use runar_common::utils::scheduler::{Scheduler, SCHEDULER_RUNS_METRIC};
+use tokio::time::sleep;
+
+fn logger() -> Logger {
+    Logger::new_root(
+        Component::Custom("SchedulerTest"),
+        NodeId::new("test-node").unwrap(),
+    )
+}
+
+fn counting(counter: &Arc<AtomicU64>) -> impl FnMut() -> std::future::Ready<()> + Send + 'static {
+    let counter = counter.clone();
+    move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+        std::future::ready(())
+    }
+}
+
+#[tokio::test]
+async fn test_every_runs_periodically_and_records_metrics() {
+    let scheduler = Scheduler::new(&logger()).with_jitter(0.2);
+    let counter = Arc::new(AtomicU64::new(0));
+    let task = scheduler.every(Duration::from_millis(10), "heartbeat", counting(&counter));
+
+    sleep(Duration::from_millis(200)).await;
+    let runs = counter.load(Ordering::SeqCst);
+    assert!(runs >= 3, "only {} runs", runs);
+    assert!(task.runs() >= 3);
+    assert_eq!(scheduler.task("heartbeat").unwrap().name(), "heartbeat");
+
+    let labels =
+        MetricLabels::component(Component::Custom("SchedulerTest")).with_action("heartbeat");
+    assert!(
+        metrics::global()
+            .counter(SCHEDULER_RUNS_METRIC, &labels)
+            .get()
+            >= 3
+    );
+}
+
+#[tokio::test]
+async fn test_pause_resume_and_cancel() {
+    let scheduler = Scheduler::new(&logger());
+    let counter = Arc::new(AtomicU64::new(0));
+    let task = scheduler.every(Duration::from_millis(10), "cleanup", counting(&counter));
+
+    task.pause();
+    assert!(task.is_paused());
+    sleep(Duration::from_millis(30)).await;
+    let paused_at = counter.load(Ordering::SeqCst);
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(counter.load(Ordering::SeqCst), paused_at);
+
+    task.resume();
+    sleep(Duration::from_millis(150)).await;
+    assert!(counter.load(Ordering::SeqCst) > paused_at);
+
+    task.cancel();
+    // Cancellation is final
+    task.resume();
+    assert!(task.is_cancelled());
+    sleep(Duration::from_millis(30)).await;
+    let cancelled_at = counter.load(Ordering::SeqCst);
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(counter.load(Ordering::SeqCst), cancelled_at);
+    assert!(scheduler.tasks().is_empty());
+}
+
+#[tokio::test]
+async fn test_dropping_scheduler_stops_tasks() {
+    let counter = Arc::new(AtomicU64::new(0));
+    let task = {
+        let scheduler = Scheduler::new(&logger());
+        scheduler.every(Duration::from_millis(10), "short-lived", counting(&counter))
+    };
+    assert!(task.is_cancelled());
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(counter.load(Ordering::SeqCst), 0);
+}
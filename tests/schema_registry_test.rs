@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use runar_common::errors::{ErrorCode, RunarError};
+use runar_common::types::{
+    redact, redact_json, render_json_with_schema, to_json_with_schema, ArcValueType, FieldSchema,
+    SchemaDataType, SchemaRef, SchemaRegistry, REDACTED,
+};
+use serde_json::json;
+
+fn user_schema() -> FieldSchema {
+    let mut properties = HashMap::new();
+    let mut name = FieldSchema::string("name");
+    name.min_length = Some(1);
+    properties.insert("name".to_string(), Box::new(name));
+    let mut age = FieldSchema::integer("age");
+    age.minimum = Some(0.0);
+    properties.insert("age".to_string(), Box::new(age));
+    let mut email = FieldSchema::string("email");
+    email.pattern = Some("^[^@]+@[^@]+$".to_string());
+    email.nullable = Some(true);
+    properties.insert("email".to_string(), Box::new(email));
+    properties.insert(
+        "tags".to_string(),
+        Box::new(FieldSchema::array(
+            "tags",
+            Box::new(FieldSchema::string("tag")),
+        )),
+    );
+    FieldSchema::object("user", properties, Some(vec!["name".to_string()]))
+}
+
+fn error_message(result: Result<()>) -> String {
+    let error = result.unwrap_err();
+    let runar = error.downcast_ref::<RunarError>().expect("RunarError");
+    assert_eq!(runar.code, ErrorCode::InvalidInput);
+    runar.message.clone()
+}
+
+#[test]
+fn test_register_and_lookup_versions() -> Result<()> {
+    let mut schemas = SchemaRegistry::new();
+    schemas.register("user", 1, user_schema())?;
+    schemas.register("user", 1, user_schema())?;
+    assert!(schemas
+        .register("user", 1, FieldSchema::string("user"))
+        .is_err());
+    let v2 = schemas.register("user", 2, FieldSchema::string("user"))?;
+
+    assert_eq!(v2.to_string(), "user@v2");
+    assert_eq!(schemas.latest("user").unwrap().0, v2);
+    assert!(schemas.get(&SchemaRef::new("user", 1)).is_some());
+    assert!(schemas.get(&SchemaRef::new("user", 3)).is_none());
+    Ok(())
+}
+
+#[test]
+fn test_validate_json() -> Result<()> {
+    let mut schemas = SchemaRegistry::new();
+    let user = schemas.register("user", 1, user_schema())?;
+
+    schemas.validate_json(
+        &user,
+        &json!({"name": "Ada", "age": 36, "email": null, "tags": ["admin"]}),
+    )?;
+    assert_eq!(
+        error_message(schemas.validate_json(&user, &json!({"age": 3}))),
+        "$ is missing required field 'name'"
+    );
+    assert_eq!(
+        error_message(schemas.validate_json(&user, &json!({"name": "Ada", "age": -1}))),
+        "$.age must be above 0"
+    );
+    assert_eq!(
+        error_message(schemas.validate_json(&user, &json!({"name": "Ada", "tags": [1]}))),
+        "$.tags[0] must be a string"
+    );
+    assert_eq!(
+        error_message(schemas.validate_json(&user, &json!({"name": "Ada", "email": "nope"}))),
+        "$.email must match pattern '^[^@]+@[^@]+$'"
+    );
+    assert!(schemas.validate_json(&user, &json!(null)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_references_and_unions() -> Result<()> {
+    let mut schemas = SchemaRegistry::new();
+    schemas.register("user", 1, user_schema())?;
+    let team = schemas.register(
+        "team",
+        1,
+        FieldSchema::array(
+            "members",
+            Box::new(FieldSchema::new(
+                "member",
+                SchemaDataType::Reference("user".to_string()),
+            )),
+        ),
+    )?;
+    let id = schemas.register(
+        "id",
+        1,
+        FieldSchema::new(
+            "id",
+            SchemaDataType::Union(vec![SchemaDataType::Int64, SchemaDataType::String]),
+        ),
+    )?;
+
+    schemas.validate_json(&team, &json!([{"name": "Ada"}, {"name": "Linus"}]))?;
+    assert!(schemas.validate_json(&team, &json!([{"age": 1}])).is_err());
+    schemas.validate_json(&id, &json!(42))?;
+    schemas.validate_json(&id, &json!("abc"))?;
+    assert!(schemas.validate_json(&id, &json!(true)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_validate_value() -> Result<()> {
+    let mut schemas = SchemaRegistry::new();
+    let user = schemas.register("user", 1, user_schema())?;
+
+    let mut fields = HashMap::new();
+    fields.insert(
+        "name".to_string(),
+        ArcValueType::new_primitive("Ada".to_string()),
+    );
+    fields.insert("age".to_string(), ArcValueType::new_primitive(36i64));
+    fields.insert(
+        "tags".to_string(),
+        ArcValueType::new_list(vec!["admin".to_string()]),
+    );
+    let value = ArcValueType::from_map(fields);
+    assert_eq!(
+        value.to_json()?,
+        json!({"name": "Ada", "age": 36, "tags": ["admin"]})
+    );
+    schemas.validate(&user, &value)?;
+
+    let opaque = ArcValueType::from_struct(user_schema());
+    assert!(opaque.to_json().is_err());
+    assert!(schemas.validate(&user, &opaque).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_redact_sensitive_fields() -> Result<()> {
+    let mut schema = user_schema();
+    let properties = schema.properties.as_mut().unwrap();
+    properties.get_mut("email").unwrap().sensitive = Some(true);
+    properties
+        .get_mut("tags")
+        .unwrap()
+        .items
+        .as_mut()
+        .unwrap()
+        .sensitive = Some(true);
+
+    let value = ArcValueType::from_json_value(json!({
+        "name": "ada",
+        "email": "ada@example.com",
+        "tags": ["admin", "ops"],
+        "extra": "kept"
+    }));
+    let redacted = redact(&value, &schema)?;
+    assert_eq!(
+        redacted.to_json()?,
+        json!({
+            "name": "ada",
+            "email": REDACTED,
+            "tags": [REDACTED, REDACTED],
+            "extra": "kept"
+        })
+    );
+    // The original value is untouched
+    assert_eq!(value.to_json()?["email"], json!("ada@example.com"));
+
+    // Null sensitive fields stay null
+    let redacted = redact_json(&json!({"name": "ada", "email": null}), &schema);
+    assert_eq!(redacted, json!({"name": "ada", "email": null}));
+    Ok(())
+}
+
+#[test]
+fn test_redact_whole_value_and_schema_round_trip() -> Result<()> {
+    let mut secret = FieldSchema::string("token");
+    secret.sensitive = Some(true);
+    let redacted = redact(&ArcValueType::new_primitive("s3cr3t".to_string()), &secret)?;
+    assert_eq!(redacted.to_json()?, json!(REDACTED));
+
+    // Schemas serialized before the flag existed still deserialize
+    let mut json = serde_json::to_value(FieldSchema::string("name"))?;
+    json.as_object_mut().unwrap().remove("sensitive");
+    let schema: FieldSchema = serde_json::from_value(json)?;
+    assert_eq!(schema.sensitive, None);
+    Ok(())
+}
+
+#[test]
+fn test_to_json_with_schema_normalizes_representations() -> Result<()> {
+    let mut properties = HashMap::new();
+    properties.insert(
+        "avatar".to_string(),
+        Box::new(FieldSchema::new("avatar", SchemaDataType::Binary)),
+    );
+    properties.insert(
+        "created".to_string(),
+        Box::new(FieldSchema::timestamp("created")),
+    );
+    properties.insert(
+        "updated".to_string(),
+        Box::new(FieldSchema::timestamp("updated")),
+    );
+    let mut status = FieldSchema::string("status");
+    status.enum_values = Some(vec!["active".to_string(), "suspended".to_string()]);
+    properties.insert(
+        "history".to_string(),
+        Box::new(FieldSchema::array("history", Box::new(status.clone()))),
+    );
+    properties.insert("status".to_string(), Box::new(status));
+    let schema = FieldSchema::object("account", properties, None);
+
+    let mut fields = HashMap::new();
+    fields.insert("avatar".to_string(), ArcValueType::new_bytes(vec![1, 2, 3]));
+    fields.insert(
+        "created".to_string(),
+        ArcValueType::new_primitive(1_700_000_000_123i64),
+    );
+    fields.insert(
+        "updated".to_string(),
+        ArcValueType::new_primitive("2023-11-14T23:13:20+01:00".to_string()),
+    );
+    fields.insert("status".to_string(), ArcValueType::new_primitive(1i64));
+    fields.insert(
+        "history".to_string(),
+        ArcValueType::new_list(vec![
+            ArcValueType::new_primitive(0i64),
+            ArcValueType::new_primitive("suspended".to_string()),
+        ]),
+    );
+    fields.insert("note".to_string(), ArcValueType::new_primitive(7i64));
+    let value = ArcValueType::new_map(fields);
+
+    assert_eq!(
+        to_json_with_schema(&value, &schema)?,
+        json!({
+            "avatar": "AQID",
+            "created": "2023-11-14T22:13:20.123Z",
+            "updated": "2023-11-14T22:13:20.000Z",
+            "status": "suspended",
+            "history": ["active", "suspended"],
+            "note": 7
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_render_json_with_schema_keeps_unrecognized_values() {
+    let binary = FieldSchema::new("blob", SchemaDataType::Binary);
+    assert_eq!(
+        render_json_with_schema(&json!([1, 255]), &binary),
+        json!("Af8=")
+    );
+    assert_eq!(
+        render_json_with_schema(&json!([1, 256]), &binary),
+        json!([1, 256])
+    );
+
+    let timestamp = FieldSchema::timestamp("at");
+    assert_eq!(
+        render_json_with_schema(&json!("yesterday"), &timestamp),
+        json!("yesterday")
+    );
+
+    let mut level = FieldSchema::string("level");
+    level.enum_values = Some(vec!["low".to_string()]);
+    assert_eq!(render_json_with_schema(&json!(3), &level), json!(3));
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use runar_common::service_info::DEFAULT_NETWORK_ID;
+use runar_common::types::{ServiceMetadata, Version};
+use runar_common::{ServiceDescriptor, ServiceInfo};
+
+struct LegacyService;
+
+impl ServiceInfo for LegacyService {
+    fn service_name(&self) -> &str {
+        "Legacy"
+    }
+
+    fn service_path(&self) -> &str {
+        "legacy"
+    }
+
+    fn service_description(&self) -> &str {
+        "An old-style service"
+    }
+
+    fn service_version(&self) -> &str {
+        "2.1.0"
+    }
+}
+
+fn describe(info: &dyn ServiceInfo) -> String {
+    format!("{}@{}", info.service_path(), info.service_version())
+}
+
+#[test]
+fn test_builder_and_accessors() -> Result<()> {
+    let descriptor = ServiceDescriptor::builder("Math", "math")
+        .with_version("1.2.3")
+        .with_description("Arithmetic operations")
+        .with_network_id("testnet")
+        .build()?;
+
+    assert_eq!(descriptor.name(), "Math");
+    assert_eq!(descriptor.path().as_str(), "math");
+    assert_eq!(*descriptor.version(), Version::new(1, 2, 3));
+    assert_eq!(descriptor.description(), "Arithmetic operations");
+    assert_eq!(descriptor.network_id().as_str(), "testnet");
+    assert_eq!(describe(&descriptor), "math@1.2.3");
+
+    let defaults = ServiceDescriptor::builder("Echo", "echo").build()?;
+    assert_eq!(defaults.network_id().as_str(), DEFAULT_NETWORK_ID);
+    assert_eq!(defaults.version().to_string(), "0.1.0");
+    Ok(())
+}
+
+#[test]
+fn test_builder_validation() {
+    assert!(ServiceDescriptor::builder("", "math").build().is_err());
+    assert!(ServiceDescriptor::builder("Math", "").build().is_err());
+    assert!(ServiceDescriptor::builder("Math", "math")
+        .with_version("one")
+        .build()
+        .is_err());
+    assert!(ServiceDescriptor::builder("Math", "math")
+        .with_network_id("bad network")
+        .build()
+        .is_err());
+}
+
+#[test]
+fn test_conversions() -> Result<()> {
+    let descriptor = ServiceDescriptor::from_service_info(&LegacyService, "mainnet")?;
+    assert_eq!(descriptor.version().to_string(), "2.1.0");
+
+    let metadata = descriptor.to_metadata(1_700_000_000);
+    assert_eq!(metadata.network_id, "mainnet");
+    assert_eq!(metadata.service_path, "legacy");
+    assert_eq!(metadata.name, "Legacy");
+    assert_eq!(metadata.description, "An old-style service");
+    assert_eq!(metadata.registration_time, 1_700_000_000);
+    assert!(metadata.actions.is_empty() && metadata.last_start_time.is_none());
+
+    let metadata: ServiceMetadata = descriptor.into();
+    assert!(metadata.registration_time > 0);
+    Ok(())
+}
+
+#[test]
+fn test_shared_user_services_implement_service_info() -> Result<()> {
+    let shared: Arc<LegacyService> = Arc::new(LegacyService);
+    assert_eq!(describe(&shared), "legacy@2.1.0");
+
+    let erased: Arc<dyn ServiceInfo + Send + Sync> = shared;
+    assert_eq!(describe(&erased), "legacy@2.1.0");
+    let descriptor = ServiceDescriptor::from_service_info(&erased, "mainnet")?;
+    assert_eq!(descriptor.name(), "Legacy");
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use anyhow::{anyhow, Result};
+use runar_common::errors::{ErrorCode, RunarError};
+use runar_common::types::{
+    ActionMetadata, MetadataSigner, MetadataVerifier, ServiceMetadata, SignedServiceMetadata,
+    Version,
+};
+use runar_common::utils::integrity::blake3_hash;
+
+// Keyed hash standing in for a real signature scheme
+struct TestKey {
+    id: &'static str,
+    secret: &'static [u8],
+}
+
+impl MetadataSigner for TestKey {
+    fn key_id(&self) -> &str {
+        self.id
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        Ok(blake3_hash(&[self.secret, message].concat()).to_vec())
+    }
+}
+
+struct TestKeys(Vec<TestKey>);
+
+impl MetadataVerifier for TestKeys {
+    fn verify(&self, key_id: &str, message: &[u8], signature: &[u8]) -> Result<()> {
+        let key = self
+            .0
+            .iter()
+            .find(|key| key.id == key_id)
+            .ok_or_else(|| anyhow!("unknown key"))?;
+        if key.sign(message)? == signature {
+            Ok(())
+        } else {
+            Err(anyhow!("bad signature"))
+        }
+    }
+}
+
+fn metadata() -> ServiceMetadata {
+    ServiceMetadata {
+        network_id: "default".to_string(),
+        service_path: "math".to_string(),
+        name: "Math".to_string(),
+        version: Version::new(1, 2, 0),
+        description: "Arithmetic".to_string(),
+        actions: vec![ActionMetadata {
+            name: "add".to_string(),
+            description: "Add two numbers".to_string(),
+            input_schema: None,
+            output_schema: None,
+        }],
+        events: Vec::new(),
+        registration_time: 100,
+        last_start_time: None,
+    }
+}
+
+fn keys() -> TestKeys {
+    TestKeys(vec![
+        TestKey {
+            id: "node-a",
+            secret: b"secret-a",
+        },
+        TestKey {
+            id: "node-b",
+            secret: b"secret-b",
+        },
+    ])
+}
+
+#[test]
+fn test_sign_and_verify() {
+    let signer = TestKey {
+        id: "node-a",
+        secret: b"secret-a",
+    };
+    let signed = SignedServiceMetadata::sign(&metadata(), &signer).unwrap();
+    assert_eq!(signed.key_id(), "node-a");
+    assert_eq!(
+        signed.payload(),
+        SignedServiceMetadata::canonical_bytes(&metadata()).as_slice()
+    );
+    assert_eq!(signed.verify(&keys()).unwrap(), metadata());
+
+    // Signing is deterministic and survives serialization
+    let again = SignedServiceMetadata::sign(&metadata(), &signer).unwrap();
+    assert_eq!(again, signed);
+    let bytes = bincode::serialize(&signed).unwrap();
+    let decoded: SignedServiceMetadata = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(decoded.verify(&keys()).unwrap(), metadata());
+}
+
+#[test]
+fn test_tampering_is_rejected() {
+    let signer = TestKey {
+        id: "node-a",
+        secret: b"secret-a",
+    };
+    let signed = SignedServiceMetadata::sign(&metadata(), &signer).unwrap();
+
+    let mut value = serde_json::to_value(&signed).unwrap();
+    // Change one payload byte
+    let payload = value["payload"].as_array_mut().unwrap();
+    let byte = payload.iter().position(|b| b == 100).unwrap();
+    payload[byte] = serde_json::json!(101);
+    let tampered: SignedServiceMetadata = serde_json::from_value(value).unwrap();
+    let err = tampered.verify(&keys()).unwrap_err();
+    let runar = err.downcast_ref::<RunarError>().unwrap();
+    assert_eq!(runar.code, ErrorCode::Unauthorized);
+
+    // The same signature claimed for another key does not verify
+    let mut value = serde_json::to_value(&signed).unwrap();
+    value["key_id"] = serde_json::json!("node-b");
+    let rekeyed: SignedServiceMetadata = serde_json::from_value(value).unwrap();
+    assert!(rekeyed.verify(&keys()).is_err());
+
+    let unknown = TestKey {
+        id: "node-z",
+        secret: b"secret-z",
+    };
+    let signed = SignedServiceMetadata::sign(&metadata(), &unknown).unwrap();
+    assert!(signed.verify(&keys()).is_err());
+}
@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{ArcValueType, NodeId, SerializerRegistry, SimpleNamePolicy, WireError};
+use runar_common::wire::{encode_header, ValueCategory};
+
+mod foo {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct Status {
+        pub code: u32,
+    }
+}
+
+mod bar {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct Status {
+        pub reason: String,
+    }
+}
+
+fn registry(policy: SimpleNamePolicy) -> SerializerRegistry {
+    let mut registry = SerializerRegistry::new(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )));
+    registry.set_simple_name_policy(policy);
+    registry
+}
+
+// A struct value sent by a peer that only knows the type by `name`
+fn struct_bytes(name: &str, code: u32) -> Arc<[u8]> {
+    let mut bytes = Vec::new();
+    encode_header(ValueCategory::Struct, name, &mut bytes).unwrap();
+    bytes.extend(bincode::serialize(&foo::Status { code }).unwrap());
+    Arc::from(bytes)
+}
+
+fn resolves(registry: &SerializerRegistry, name: &str) -> bool {
+    match registry.deserialize_value(struct_bytes(name, 1)) {
+        Ok(_) => true,
+        Err(e) => {
+            assert!(matches!(
+                e.downcast_ref::<WireError>(),
+                Some(WireError::UnknownType { .. })
+            ));
+            false
+        }
+    }
+}
+
+#[test]
+fn test_keep_first_records_collisions() -> Result<()> {
+    let mut registry = registry(SimpleNamePolicy::KeepFirst);
+    registry.register::<foo::Status>()?;
+    registry.register::<bar::Status>()?;
+    // Re-registering does not count as a collision
+    registry.register::<foo::Status>()?;
+
+    let collisions = registry.simple_name_collisions();
+    assert_eq!(collisions.len(), 1);
+    assert_eq!(collisions[0].simple_name, "Status");
+    assert_eq!(
+        collisions[0].type_names,
+        vec![
+            std::any::type_name::<foo::Status>().to_string(),
+            std::any::type_name::<bar::Status>().to_string(),
+        ]
+    );
+    assert_eq!(
+        collisions[0].resolves_to.as_deref(),
+        Some(std::any::type_name::<foo::Status>())
+    );
+
+    let mut value = registry.deserialize_value(struct_bytes("Status", 7))?;
+    assert_eq!(value.as_struct_ref::<foo::Status>()?.code, 7);
+    Ok(())
+}
+
+#[test]
+fn test_error_policy_rejects_collisions() -> Result<()> {
+    let mut registry = registry(SimpleNamePolicy::Error);
+    registry.register::<foo::Status>()?;
+    registry.register::<foo::Status>()?;
+    let err = registry.register::<bar::Status>().unwrap_err();
+    assert!(err.to_string().contains("Simple name 'Status'"));
+
+    // Nothing was registered for the rejected type
+    let value = ArcValueType::from_struct(bar::Status {
+        reason: "down".to_string(),
+    });
+    assert!(registry.serialize_value(&value).is_err());
+    assert!(registry.simple_name_collisions().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_full_name_only_policy_drops_ambiguous_simple_names() -> Result<()> {
+    let mut registry = registry(SimpleNamePolicy::FullNameOnly);
+    registry.register::<foo::Status>()?;
+    assert!(resolves(&registry, "Status"));
+
+    registry.register::<bar::Status>()?;
+    assert!(!resolves(&registry, "Status"));
+    assert!(resolves(&registry, std::any::type_name::<foo::Status>()));
+    assert!(resolves(&registry, std::any::type_name::<bar::Status>()));
+    assert_eq!(registry.simple_name_collisions()[0].resolves_to, None);
+    Ok(())
+}
+
+#[test]
+fn test_namespace_policy_registers_module_qualified_names() -> Result<()> {
+    let mut registry = registry(SimpleNamePolicy::Namespace);
+    registry.register::<foo::Status>()?;
+    registry.register::<bar::Status>()?;
+
+    assert!(resolves(&registry, "Status"));
+    assert!(resolves(&registry, "bar::Status"));
+    assert!(resolves(&registry, "foo::Status"));
+    assert!(!resolves(&registry, "baz::Status"));
+    Ok(())
+}
+
+#[test]
+fn test_generic_types_keep_their_arguments() -> Result<()> {
+    let logger = Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    ));
+    let defaults = SerializerRegistry::with_defaults(logger);
+    assert!(defaults.simple_name_collisions().is_empty());
+
+    let mut registry = registry(SimpleNamePolicy::Error);
+    registry.register::<Vec<foo::Status>>()?;
+    registry.register::<Vec<bar::Status>>()?;
+    assert!(registry.simple_name_collisions().is_empty());
+    Ok(())
+}
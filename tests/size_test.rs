@@ -0,0 +1,53 @@
+use runar_common::types::FieldSchema;
+use runar_common::utils::size::{format_size, parse_size, size_serde};
+use serde::Deserialize;
+use std::time::Duration;
+
+#[test]
+fn test_parse_size() {
+    assert_eq!(parse_size("100").unwrap(), 100);
+    assert_eq!(parse_size("100B").unwrap(), 100);
+    assert_eq!(parse_size("512KiB").unwrap(), 512 * 1024);
+    assert_eq!(parse_size("10MB").unwrap(), 10_000_000);
+    assert_eq!(parse_size("1.5GiB").unwrap(), 1_610_612_736);
+    assert_eq!(parse_size("2 gb").unwrap(), 2_000_000_000);
+
+    for invalid in ["", "MB", "1.5", "10 parsecs", "-1KB"] {
+        assert!(parse_size(invalid).is_err(), "{} should not parse", invalid);
+    }
+}
+
+#[test]
+fn test_format_size() {
+    assert_eq!(format_size(512), "512 B");
+    assert_eq!(format_size(1536), "1.5 KiB");
+    assert_eq!(format_size(1 << 30), "1 GiB");
+}
+
+#[derive(Deserialize)]
+struct Limits {
+    #[serde(with = "size_serde")]
+    max_message: u64,
+}
+
+#[test]
+fn test_size_serde_and_schema_defaults() {
+    let limits: Limits = serde_json::from_str(r#"{"max_message": "4MiB"}"#).unwrap();
+    assert_eq!(limits.max_message, 4 * 1024 * 1024);
+    let limits: Limits = serde_json::from_str(r#"{"max_message": 1024}"#).unwrap();
+    assert_eq!(limits.max_message, 1024);
+
+    let mut field = FieldSchema::string("buffer");
+    assert_eq!(field.default_size().unwrap(), None);
+    field.default_value = Some("64KiB".to_string());
+    assert_eq!(field.default_size().unwrap(), Some(65_536));
+
+    let mut timeout = FieldSchema::string("timeout");
+    timeout.default_value = Some("30s".to_string());
+    assert_eq!(
+        timeout.default_duration().unwrap(),
+        Some(Duration::from_secs(30))
+    );
+    timeout.default_value = Some("soon".to_string());
+    assert!(timeout.default_duration().is_err());
+}
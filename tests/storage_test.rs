@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use runar_common::errors::{ErrorCode, RunarError};
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{ArcValueType, NodeId, SerializerRegistry};
+use runar_common::utils::storage::{
+    decode_blob, encode_blob, FnBackend, KvBackend, MemoryKv, ValueStore,
+};
+
+fn create_test_registry() -> Arc<SerializerRegistry> {
+    Arc::new(SerializerRegistry::with_defaults(Arc::new(
+        Logger::new_root(Component::Custom("Test"), NodeId::new("test-node").unwrap()),
+    )))
+}
+
+#[test]
+fn test_store_round_trip() -> Result<()> {
+    let store = ValueStore::new(create_test_registry(), MemoryKv::new());
+    store.put("answer", &ArcValueType::new_primitive(42i64))?;
+    store.put("names", &ArcValueType::new_list(vec!["a".to_string()]))?;
+
+    assert_eq!(store.get("answer")?.unwrap().as_type::<i64>()?, 42);
+    assert_eq!(
+        *store.get("names")?.unwrap().as_list_ref::<String>()?,
+        vec!["a".to_string()]
+    );
+    assert!(store.get("missing")?.is_none());
+
+    store.remove("answer")?;
+    assert!(store.get("answer")?.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_corruption_is_detected() -> Result<()> {
+    let registry = create_test_registry();
+    let mut blob = encode_blob(&registry, &ArcValueType::new_primitive("hello".to_string()))?;
+    assert_eq!(&blob[..3], b"RV\x01");
+    assert_eq!(decode_blob(&registry, &blob)?.as_type::<String>()?, "hello");
+
+    let last = blob.len() - 1;
+    blob[last] ^= 0xff;
+    let error = decode_blob(&registry, &blob).unwrap_err();
+    let runar = error.downcast_ref::<RunarError>().unwrap();
+    assert_eq!(runar.code, ErrorCode::Serialization);
+    assert!(runar.message.contains("checksum mismatch"));
+
+    assert!(decode_blob(&registry, b"RV").is_err());
+    assert!(decode_blob(&registry, b"XX\x01\x00\x00\x00\x00").is_err());
+    blob[2] = 9;
+    assert!(decode_blob(&registry, &blob).is_err());
+
+    let store = ValueStore::new(registry, MemoryKv::new());
+    store.backend().put(b"broken", b"not a blob")?;
+    assert!(store.get("broken").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_closure_backend() -> Result<()> {
+    let data: Arc<Mutex<HashMap<Vec<u8>, Vec<u8>>>> = Arc::default();
+    let (reader, writer) = (data.clone(), data.clone());
+    let backend = FnBackend::new(
+        move |key: &[u8]| Ok(reader.lock().unwrap().get(key).cloned()),
+        move |key: &[u8], value: &[u8]| {
+            writer.lock().unwrap().insert(key.to_vec(), value.to_vec());
+            Ok(())
+        },
+    );
+    let store = ValueStore::new(create_test_registry(), backend);
+    store.put("flag", &ArcValueType::new_primitive(true))?;
+    assert!(store.get("flag")?.unwrap().as_type::<bool>()?);
+    assert!(data.lock().unwrap().contains_key(b"flag".as_slice()));
+    assert!(store.remove("flag").is_err());
+    Ok(())
+}
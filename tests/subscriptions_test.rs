@@ -0,0 +1,91 @@
+use anyhow::Result;
+use runar_common::utils::paths::TopicPath;
+use runar_common::utils::subscriptions::SubscriptionTable;
+
+fn topic(path: &str) -> TopicPath {
+    TopicPath::new(path).unwrap()
+}
+
+#[test]
+fn test_matches_exact_and_wildcard_patterns() {
+    let mut table = SubscriptionTable::new();
+    table.insert(&topic("sensors/temp/updated"), "exact");
+    table.insert(&topic("sensors/*/updated"), "single");
+    table.insert(&topic("sensors/>"), "multi");
+    table.insert(&topic("*/temp/*"), "double-single");
+    table.insert(&topic("alarms/>"), "other");
+
+    assert_eq!(
+        table.matches(&topic("sensors/temp/updated")),
+        vec![&"exact", &"single", &"multi", &"double-single"]
+    );
+    assert_eq!(
+        table.matches(&topic("sensors/humidity/updated")),
+        vec![&"single", &"multi"]
+    );
+    // `>` needs at least one trailing segment
+    assert!(table.matches(&topic("sensors")).is_empty());
+    assert_eq!(table.matches(&topic("sensors/a/b/c/d")), vec![&"multi"]);
+    assert!(table.matches(&topic("metrics/cpu")).is_empty());
+}
+
+#[test]
+fn test_glob_segment_patterns() {
+    let mut table = SubscriptionTable::new();
+    let temps = table.insert(&topic("sensors/temp-*/updated"), "temps");
+    table.insert(&topic("sensors/~temp-?/updated"), "single-digit");
+    table.insert(&topic("sensors/~[th]*/>"), "class");
+
+    assert_eq!(
+        table.matches(&topic("sensors/temp-1/updated")),
+        vec![&"temps", &"single-digit", &"class"]
+    );
+    assert_eq!(
+        table.matches(&topic("sensors/temp-12/updated")),
+        vec![&"temps", &"class"]
+    );
+    assert_eq!(
+        table.matches(&topic("sensors/humidity/updated")),
+        vec![&"class"]
+    );
+
+    assert_eq!(table.remove(temps), Some("temps"));
+    assert_eq!(
+        table.matches(&topic("sensors/temp-12/updated")),
+        vec![&"class"]
+    );
+
+    // Unmarked segments only treat `*` as a wildcard
+    let literal = table.insert(&topic("sensors/t[1]?/updated"), "literal");
+    assert_eq!(table.matches(&topic("sensors/t1x/updated")), vec![&"class"]);
+    assert_eq!(
+        table.matches(&topic("sensors/t[1]?/updated")),
+        vec![&"class", &"literal"]
+    );
+    assert_eq!(table.remove(literal), Some("literal"));
+}
+
+#[test]
+fn test_remove_subscriptions() -> Result<()> {
+    let mut table = SubscriptionTable::new();
+    let first = table.insert(&topic("a/*"), 1);
+    let second = table.insert(&topic("a/*"), 2);
+    let third = table.insert(&topic("a/>"), 3);
+    assert_eq!(table.len(), 3);
+    assert_eq!(table.pattern(first), Some(&topic("a/*")));
+
+    assert_eq!(table.remove(first), Some(1));
+    assert_eq!(table.remove(first), None);
+    let ids: Vec<_> = table
+        .matches_with_ids(&topic("a/b"))
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    assert_eq!(ids, vec![second, third]);
+
+    assert_eq!(table.remove(second), Some(2));
+    assert_eq!(table.remove(third), Some(3));
+    assert!(table.is_empty());
+    assert!(table.matches(&topic("a/b")).is_empty());
+    Ok(())
+}
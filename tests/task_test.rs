@@ -0,0 +1,51 @@
+use anyhow::Result;
+use runar_common::logging::{Component, Logger};
+use runar_common::metrics::{self, MetricLabels};
+use runar_common::types::NodeId;
+use runar_common::utils::task::{
+    current_logger, spawn_logged, TASK_DURATION_METRIC, TASK_PANICS_METRIC,
+};
+
+fn logger() -> Logger {
+    Logger::new_root(
+        Component::Custom("TaskTest"),
+        NodeId::new("test-node").unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_spawn_logged_returns_output_with_context() -> Result<()> {
+    let logger = logger().with_correlation_id("cid-1");
+    let handle = spawn_logged(&logger, "lookup", async {
+        current_logger().and_then(|logger| logger.correlation_id().map(str::to_string))
+    });
+    assert_eq!(handle.await?, Some(Some("cid-1".to_string())));
+    assert!(current_logger().is_none());
+
+    let labels = MetricLabels::component(Component::Custom("TaskTest")).with_action("lookup");
+    assert_eq!(
+        metrics::global()
+            .histogram(TASK_DURATION_METRIC, &labels)
+            .count(),
+        1
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_spawn_logged_reports_panics() -> Result<()> {
+    let handle = spawn_logged(&logger(), "explode", async {
+        if true {
+            panic!("boom");
+        }
+        1
+    });
+    assert_eq!(handle.await?, None);
+
+    let labels = MetricLabels::component(Component::Custom("TaskTest")).with_action("explode");
+    assert_eq!(
+        metrics::global().counter(TASK_PANICS_METRIC, &labels).get(),
+        1
+    );
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use runar_common::types::{ServiceMetadata, Version};
+use runar_common::utils::time::{
+    duration_serde, format_duration, format_duration_exact, from_epoch_millis, from_epoch_secs,
+    monotonic_nanos, now_millis, now_secs, parse_duration, to_epoch_millis, to_epoch_secs, Clock,
+    MockClock, SystemClock,
+};
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(Duration::from_millis(0)), "0ms");
+    assert_eq!(format_duration(Duration::from_millis(250)), "250ms");
+    assert_eq!(format_duration(Duration::from_secs(45)), "45s");
+    assert_eq!(format_duration(Duration::from_secs(192)), "3m 12s");
+    assert_eq!(format_duration(Duration::from_secs(3_600)), "1h");
+    assert_eq!(format_duration(Duration::from_secs(3_605)), "1h");
+    assert_eq!(format_duration(Duration::from_secs(90_061)), "1d 1h");
+}
+
+#[test]
+fn test_epoch_conversions() {
+    let secs = now_secs();
+    let millis = now_millis();
+    assert!(millis / 1000 >= secs);
+    assert_eq!(to_epoch_secs(from_epoch_secs(secs)), secs);
+    assert_eq!(to_epoch_millis(from_epoch_millis(millis)), millis);
+
+    let first = monotonic_nanos();
+    assert!(monotonic_nanos() >= first);
+}
+
+#[test]
+fn test_service_metadata_time_helpers() {
+    let mut metadata = ServiceMetadata {
+        network_id: "default".to_string(),
+        service_path: "math".to_string(),
+        name: "Math".to_string(),
+        version: Version::new(1, 0, 0),
+        description: "Math service".to_string(),
+        actions: vec![],
+        events: vec![],
+        registration_time: 0,
+        last_start_time: None,
+    };
+    assert!(metadata.last_started_at().is_none());
+    assert!(metadata.uptime().is_none());
+
+    let registered = from_epoch_secs(1_700_000_000);
+    metadata.set_registered_at(registered);
+    assert_eq!(metadata.registration_time, 1_700_000_000);
+    assert_eq!(metadata.registered_at(), registered);
+
+    metadata.mark_started();
+    let started = metadata.last_started_at().unwrap();
+    assert!(started <= SystemTime::now());
+    assert!(metadata.uptime().unwrap() < Duration::from_secs(5));
+}
+
+#[test]
+fn test_parse_duration() {
+    assert_eq!(parse_duration("250ms").unwrap(), Duration::from_millis(250));
+    assert_eq!(parse_duration("5m").unwrap(), Duration::from_secs(300));
+    assert_eq!(parse_duration("1.5h").unwrap(), Duration::from_secs(5_400));
+    assert_eq!(parse_duration("3m 12s").unwrap(), Duration::from_secs(192));
+    assert_eq!(parse_duration("1d2h").unwrap(), Duration::from_secs(93_600));
+    assert_eq!(parse_duration("10us").unwrap(), Duration::from_micros(10));
+
+    for invalid in ["", "30", "5 minutes", "1.2.3s", "-5s", "ms"] {
+        assert!(
+            parse_duration(invalid).is_err(),
+            "{} should not parse",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn test_exact_format_round_trips() {
+    for duration in [
+        Duration::ZERO,
+        Duration::from_millis(250),
+        Duration::from_secs(3_605),
+        Duration::from_millis(90_061_500),
+        Duration::from_micros(1_500),
+        Duration::from_micros(500),
+        Duration::new(86_400 * 400, 123_456_789),
+    ] {
+        assert_eq!(
+            parse_duration(&format_duration_exact(duration)).unwrap(),
+            duration
+        );
+    }
+    assert_eq!(
+        format_duration_exact(Duration::from_millis(120_250)),
+        "2m 250ms"
+    );
+    assert_eq!(
+        format_duration_exact(Duration::from_micros(1_500)),
+        "1ms 500us"
+    );
+    assert_eq!(format_duration_exact(Duration::from_nanos(7)), "7ns");
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Timeouts {
+    #[serde(with = "duration_serde")]
+    connect: Duration,
+    #[serde(with = "duration_serde")]
+    idle: Duration,
+}
+
+#[test]
+fn test_duration_serde() {
+    let timeouts: Timeouts = serde_json::from_str(r#"{"connect": "750ms", "idle": 60}"#).unwrap();
+    assert_eq!(
+        timeouts,
+        Timeouts {
+            connect: Duration::from_millis(750),
+            idle: Duration::from_secs(60),
+        }
+    );
+    assert_eq!(
+        serde_json::to_string(&timeouts).unwrap(),
+        r#"{"connect":"750ms","idle":"1m"}"#
+    );
+    assert!(serde_json::from_str::<Timeouts>(r#"{"connect": "soon", "idle": 1}"#).is_err());
+}
+
+#[test]
+fn test_mock_clock() {
+    let clock = MockClock::at_epoch_millis(1_700_000_000_000);
+    let shared = clock.clone();
+    assert_eq!(clock.now_millis(), 1_700_000_000_000);
+
+    shared.advance(Duration::from_millis(1_500));
+    assert_eq!(clock.now_millis(), 1_700_000_001_500);
+
+    clock.set(from_epoch_secs(5));
+    assert_eq!(shared.now(), from_epoch_secs(5));
+
+    let before = now_millis();
+    assert!(SystemClock.now_millis() >= before);
+}
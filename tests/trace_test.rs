@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{
+    ArcValueType, CorrelationId, NodeId, RequestEnvelope, SerializerRegistry, TraceContext,
+};
+use runar_common::utils::paths::ActionPath;
+
+const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+#[test]
+fn test_parse_and_format_traceparent() -> Result<()> {
+    let trace: TraceContext = TRACEPARENT.parse()?;
+    assert_eq!(trace.trace_id_hex(), "4bf92f3577b34da6a3ce929d0e0e4736");
+    assert_eq!(trace.span_id_hex(), "00f067aa0ba902b7");
+    assert!(trace.is_sampled());
+    assert_eq!(trace.to_string(), TRACEPARENT);
+    assert!(!trace.clone().with_sampled(false).is_sampled());
+
+    // Later versions may append fields
+    let future = TraceContext::parse(&format!("cc{}-extra", &TRACEPARENT[2..]))?;
+    assert_eq!(future.trace_id(), trace.trace_id());
+
+    for invalid in [
+        "",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+        "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+        "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+    ] {
+        assert!(TraceContext::parse(invalid).is_err(), "{}", invalid);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_tracestate_and_children() -> Result<()> {
+    let trace = TraceContext::from_headers(
+        TRACEPARENT,
+        Some("rojo=00f067aa0ba902b7, congo=t61rcWkgMzE"),
+    )?;
+    assert_eq!(
+        trace.tracestate(),
+        Some("rojo=00f067aa0ba902b7,congo=t61rcWkgMzE")
+    );
+    assert!(trace.clone().with_tracestate("novalue").is_err());
+    let too_many = (0..33)
+        .map(|i| format!("k{}=v", i))
+        .collect::<Vec<_>>()
+        .join(",");
+    assert!(trace.clone().with_tracestate(&too_many).is_err());
+
+    let child = trace.child();
+    assert_eq!(child.trace_id(), trace.trace_id());
+    assert_ne!(child.span_id(), trace.span_id());
+    assert_eq!(child.tracestate(), trace.tracestate());
+
+    let root = TraceContext::new_root();
+    assert!(root.is_sampled());
+    assert_eq!(TraceContext::parse(&root.traceparent())?, root);
+    Ok(())
+}
+
+#[test]
+fn test_trace_propagation() -> Result<()> {
+    let logger = Logger::new_root(Component::Custom("Test"), NodeId::new("node-1")?);
+    let registry = SerializerRegistry::with_defaults(Arc::new(logger.clone()));
+    let trace = TraceContext::from_headers(TRACEPARENT, Some("rojo=1"))?;
+
+    let request = RequestEnvelope::new(
+        CorrelationId::generate(),
+        ActionPath::new("math/add")?,
+        ArcValueType::new_primitive(1i32),
+    )
+    .with_trace(trace.child());
+    let decoded = RequestEnvelope::from_bytes(&registry, &request.to_bytes(&registry)?)?;
+    assert_eq!(decoded.trace, request.trace);
+
+    let handler_logger = logger.with_trace(decoded.trace.as_ref().unwrap());
+    assert_eq!(
+        handler_logger.trace().map(TraceContext::trace_id_hex),
+        Some(trace.trace_id_hex())
+    );
+    assert_eq!(
+        handler_logger
+            .with_component(Component::Service)
+            .trace()
+            .map(TraceContext::span_id),
+        decoded.trace.as_ref().map(TraceContext::span_id)
+    );
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::Result;
+use runar_common::types::{ArcValueType, ValueCategory};
+use runar_common::utils::time::UNIX_EPOCH;
+use runar_common::utils::{
+    bytes_value, int_value, list_value, map_value, string_value, struct_value, timestamp_value,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn test_constructor_helpers() -> Result<()> {
+    let mut n = int_value(42);
+    assert_eq!(n.category, ValueCategory::Primitive);
+    assert_eq!(*n.as_type_ref::<i64>()?, 42);
+
+    let mut list = list_value(vec![int_value(1), string_value("two")]);
+    assert_eq!(list.category, ValueCategory::List);
+    assert_eq!(list.as_list_ref::<ArcValueType>()?.len(), 2);
+
+    let mut entries = HashMap::new();
+    entries.insert("n".to_string(), int_value(1));
+    let map = map_value(entries);
+    assert_eq!(map.category, ValueCategory::Map);
+    assert_eq!(map.to_json()?, serde_json::json!({"n": 1}));
+
+    let bytes = bytes_value(vec![1, 2, 3]);
+    assert_eq!(*bytes.as_bytes_ref()?, vec![1, 2, 3]);
+
+    let at = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+    let mut timestamp = timestamp_value(at);
+    assert_eq!(*timestamp.as_type_ref::<i64>()?, 1_700_000_000_123);
+
+    let mut point = struct_value(Point { x: 1, y: 2 });
+    assert_eq!(point.category, ValueCategory::Struct);
+    assert_eq!(*point.as_struct_ref::<Point>()?, Point { x: 1, y: 2 });
+    Ok(())
+}
@@ -1,18 +1,18 @@
-use std::any::Any;
 use std::collections::HashMap;
 use std::sync::Arc;
 
 use anyhow::Result;
-use bincode;
 use runar_common::logging::{Component, Logger};
-use runar_common::types::{ArcValueType, ErasedArc, SerializerRegistry, ValueCategory};
+use runar_common::types::schemas::{FieldSchema, SchemaDataType};
+use runar_common::types::{ArcValueType, NodeId, SerializerRegistry, ValueCategory};
+use runar_common::utils::integrity::ChecksumAlgorithm;
 use serde::{Deserialize, Serialize};
 
 // Create a test registry for use in tests
 fn create_test_registry() -> SerializerRegistry {
     let mut registry = SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
         Component::Custom("Test"),
-        "test-node",
+        NodeId::new("test-node").unwrap(),
     )));
 
     // Register the test struct for serialization
@@ -141,6 +141,279 @@ fn test_struct_arc_preservation() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_struct_arc_identity_from_existing_arc() -> Result<()> {
+    let original = Arc::new(TestStruct {
+        field1: "Shared".to_string(),
+        field2: 7,
+    });
+
+    let value = ArcValueType::from_struct_arc(original.clone());
+    let mut cloned = value.clone();
+    let mut value = value;
+
+    // Both the value and its clone hand out the caller's Arc, not a copy
+    assert!(Arc::ptr_eq(
+        &value.as_struct_ref::<TestStruct>()?,
+        &original
+    ));
+    assert!(Arc::ptr_eq(
+        &cloned.as_struct_ref::<TestStruct>()?,
+        &original
+    ));
+    assert_eq!(Arc::strong_count(&original), 3);
+    Ok(())
+}
+
+#[test]
+fn test_update_map_copy_on_write() -> Result<()> {
+    let mut fields = HashMap::new();
+    fields.insert("a".to_string(), ArcValueType::new_primitive(1i64));
+    let mut value = ArcValueType::from_map(fields);
+
+    // Unshared: updated in place
+    let before = value.as_map_ref::<String, ArcValueType>()?;
+    let before_ptr = Arc::as_ptr(&before);
+    drop(before);
+    value.update_map(|map| {
+        map.insert("b".to_string(), ArcValueType::new_primitive(2i64));
+    })?;
+    let after = value.as_map_ref::<String, ArcValueType>()?;
+    assert_eq!(Arc::as_ptr(&after), before_ptr);
+    drop(after);
+
+    // Shared: the clone keeps the old map
+    let mut shared = value.clone();
+    let removed = value.update_map(|map| map.remove("a"))?;
+    assert!(removed.is_some());
+    assert_eq!(value.as_map_ref::<String, ArcValueType>()?.len(), 1);
+    assert_eq!(shared.as_map_ref::<String, ArcValueType>()?.len(), 2);
+
+    // Only maps of ArcValueType can be updated
+    let mut typed = ArcValueType::from_map(HashMap::from([("x".to_string(), 1i32)]));
+    assert!(typed.update_map(|_| ()).is_err());
+    assert!(ArcValueType::new_primitive(1i32)
+        .update_map(|_| ())
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_list_mutation_helpers() -> Result<()> {
+    let mut list = ArcValueType::new_list(vec![1i64, 2]);
+    list.push(3i64)?;
+    list.extend(vec![4i64, 5])?;
+    assert_eq!(list.get_index::<i64>(2)?, Some(3));
+    assert_eq!(list.get_index::<i64>(9)?, None);
+
+    // Writes to a shared list leave the other holder untouched
+    let mut shared = list.clone();
+    assert_eq!(list.set_index(0, 10i64)?, 1);
+    assert!(list.set_index(5, 0i64).is_err());
+    assert_eq!(*shared.as_list_ref::<i64>()?, vec![1, 2, 3, 4, 5]);
+
+    let mut middle = list.slice::<i64>(1..=2)?;
+    assert_eq!(*middle.as_list_ref::<i64>()?, vec![2, 3]);
+    assert_eq!(list.slice::<i64>(3..)?.as_list_ref::<i64>()?.len(), 2);
+    assert!(list.slice::<i64>(4..9).is_err());
+
+    // Element types must match exactly
+    assert!(list.push(1i32).is_err());
+    assert!(ArcValueType::new_primitive(1i64).push(1i64).is_err());
+
+    // Lazily deserialized lists are materialized on first mutation
+    let registry = create_test_registry();
+    let mut decoded = registry.deserialize_value(registry.serialize_value(&list)?)?;
+    decoded.push(6i64)?;
+    assert_eq!(*decoded.as_list_ref::<i64>()?, vec![10, 2, 3, 4, 5, 6]);
+    Ok(())
+}
+
+#[test]
+fn test_consuming_accessors() -> Result<()> {
+    let original = Arc::new(TestStruct {
+        field1: "owned".to_string(),
+        field2: 7,
+    });
+    let value = ArcValueType::from_struct_arc(original.clone());
+    // Still shared with `original`, so the struct is cloned out
+    assert_eq!(value.into_type::<TestStruct>()?, *original);
+
+    let list = ArcValueType::new_list(vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(list.into_list::<String>()?, vec!["a", "b"]);
+
+    let map = ArcValueType::new_map(HashMap::from([("k".to_string(), "v".to_string())]));
+    let registry = create_test_registry();
+    let lazy = registry.deserialize_value(registry.serialize_value(&map)?)?;
+    assert_eq!(lazy.into_map::<String, String>()?["k"], "v");
+
+    assert_eq!(ArcValueType::new_primitive(5u64).into_type::<u64>()?, 5);
+    assert!(ArcValueType::new_primitive(5u64)
+        .into_list::<u64>()
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_lazy_state_inspection() -> Result<()> {
+    let registry = create_test_registry();
+    let original = ArcValueType::from_struct(TestStruct {
+        field1: "lazy".to_string(),
+        field2: 1,
+    });
+    assert!(original.is_materialized());
+    assert_eq!(original.lazy_type_name(), None);
+    assert_eq!(original.lazy_len(), None);
+    assert!(original.value.try_get_lazy_data().is_err());
+
+    let mut decoded = registry.deserialize_value(registry.serialize_value(&original)?)?;
+    assert!(!decoded.is_materialized());
+    assert!(decoded
+        .lazy_type_name()
+        .is_some_and(|name| name.ends_with("TestStruct")));
+    assert!(decoded.lazy_len().is_some_and(|len| len > 0));
+    assert!(decoded.value.try_get_lazy_data().is_ok());
+
+    decoded.as_struct_ref::<TestStruct>()?;
+    assert!(decoded.is_materialized());
+    assert_eq!(decoded.lazy_len(), None);
+
+    // A value flagged lazy without lazy data is rejected instead of misread
+    let mut forged = ArcValueType::new_primitive(42u64);
+    forged.value.is_lazy = true;
+    assert!(forged.value.try_get_lazy_data().is_err());
+    assert!(forged.is_materialized());
+    Ok(())
+}
+
+#[test]
+fn test_json_passthrough() -> Result<()> {
+    let registry = create_test_registry();
+    let text = r#"{ "field1": "from-http",  "field2": 3 }"#;
+    let value = ArcValueType::new_json(text);
+    assert_eq!(value.category, ValueCategory::Json);
+
+    // Bytes survive the wire untouched and are not parsed on the way through
+    let bytes = registry.serialize_value(&value)?;
+    let decoded = registry.deserialize_value(bytes)?;
+    let json = decoded.as_raw_json()?;
+    assert_eq!(json.as_str()?, text);
+    assert!(!json.is_parsed());
+
+    assert_eq!(decoded.to_json()?["field1"], "from-http");
+    assert!(json.is_parsed());
+    let typed: TestStruct = json.deserialize()?;
+    assert_eq!(typed.field2, 3);
+
+    let built = ArcValueType::from_json_value(serde_json::json!({"ok": true}));
+    assert_eq!(built.as_raw_json()?.as_str()?, r#"{"ok":true}"#);
+    assert_eq!(format!("{}", built), "Json(size: 11 bytes)");
+
+    // Malformed JSON is only reported when someone looks inside
+    let broken = ArcValueType::new_json("{not json");
+    assert!(registry.serialize_value(&broken).is_ok());
+    assert!(broken.to_json().is_err());
+    assert!(ArcValueType::new_bytes(vec![1]).as_raw_json().is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_facade() -> Result<()> {
+    let mut registry = create_test_registry();
+    registry.set_blocking_threshold(1024);
+    let registry = Arc::new(registry);
+
+    // Small values stay inline, large ones go through spawn_blocking; results match
+    for value in [
+        ArcValueType::new_primitive(7i64),
+        ArcValueType::new_bytes(vec![9u8; 4096]),
+        ArcValueType::from_struct(TestStruct {
+            field1: "x".repeat(2048),
+            field2: 1,
+        }),
+    ] {
+        let bytes = registry.serialize_async(&value).await?;
+        assert_eq!(bytes, registry.serialize_value(&value)?);
+        let decoded = registry.deserialize_async(bytes.clone()).await?;
+        assert_eq!(registry.serialize_value(&decoded)?, bytes);
+    }
+
+    assert!(registry
+        .deserialize_async(Arc::from(vec![0xffu8; 2048]))
+        .await
+        .is_err());
+    Ok(())
+}
+
+// Records the threads it is serialized on
+#[derive(Debug, Clone, Deserialize)]
+#[serde(transparent)]
+struct ThreadTracked(String);
+
+static SERIALIZED_ON: std::sync::Mutex<Vec<std::thread::ThreadId>> =
+    std::sync::Mutex::new(Vec::new());
+
+impl Serialize for ThreadTracked {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SERIALIZED_ON
+            .lock()
+            .unwrap()
+            .push(std::thread::current().id());
+        self.0.serialize(serializer)
+    }
+}
+
+#[tokio::test]
+async fn test_small_structs_serialize_inline() -> Result<()> {
+    let mut registry = create_test_registry();
+    registry.register::<ThreadTracked>()?;
+    registry.set_blocking_threshold(1024);
+    let registry = Arc::new(registry);
+    let here = std::thread::current().id();
+
+    let small = ArcValueType::from_struct(ThreadTracked("x".to_string()));
+    registry.serialize_async(&small).await?;
+    let threads = std::mem::take(&mut *SERIALIZED_ON.lock().unwrap());
+    assert!(!threads.is_empty());
+    assert!(threads.iter().all(|thread| *thread == here));
+
+    let large = ArcValueType::from_struct(ThreadTracked("x".repeat(4096)));
+    registry.serialize_async(&large).await?;
+    let threads = std::mem::take(&mut *SERIALIZED_ON.lock().unwrap());
+    assert!(threads.iter().any(|thread| *thread != here));
+    Ok(())
+}
+
+#[test]
+fn test_serialize_batch() -> Result<()> {
+    let mut registry = create_test_registry();
+    registry.set_checksum(Some(ChecksumAlgorithm::Crc32c));
+
+    let values: Vec<ArcValueType> = (0..200)
+        .map(|i| {
+            ArcValueType::from_struct(TestStruct {
+                field1: format!("service-{}", i),
+                field2: i,
+            })
+        })
+        .collect();
+    let encoded = registry.serialize_batch(&values)?;
+    assert_eq!(encoded.len(), values.len());
+    for (value, bytes) in values.iter().zip(&encoded) {
+        assert_eq!(*bytes, registry.serialize_value(value)?);
+    }
+    let mut last = registry.deserialize_value(encoded[199].clone())?;
+    assert_eq!(last.as_struct_ref::<TestStruct>()?.field2, 199);
+
+    // The failing index is reported
+    let mut mixed = values[..3].to_vec();
+    mixed.push(ArcValueType::new_primitive(1u16));
+    let err = registry.serialize_batch(&mixed).unwrap_err();
+    assert!(err.to_string().contains("batch item 3"), "{}", err);
+    assert!(registry.serialize_batch(&[])?.is_empty());
+    Ok(())
+}
+
 #[test]
 fn test_struct_serialization() -> Result<()> {
     // Create test struct
@@ -153,7 +426,7 @@ fn test_struct_serialization() -> Result<()> {
     let registry = create_test_registry();
 
     // First, directly create an ArcValueType from the struct
-    let mut value = ArcValueType::from_struct(test_struct.clone());
+    let value = ArcValueType::from_struct(test_struct.clone());
 
     // Manually serialize it
     let serialized_bytes = registry.serialize_value(&value)?;
@@ -213,7 +486,7 @@ fn test_nested() -> Result<()> {
 
     // Let's check serialization
     let mut registry = create_test_registry();
-    registry.register::<HashMap<String, ArcValueType>>();
+    registry.register::<HashMap<String, ArcValueType>>()?;
 
     // let bytes = registry.serialize_value(&value)?;
     // let mut value_from_bytes = registry.deserialize_value(bytes)?;
@@ -273,7 +546,7 @@ fn test_map_of_struts_serialization() -> Result<()> {
     println!("Content verified for ref2");
 
     // Let's check serialization
-    let mut registry = create_test_registry();
+    let registry = create_test_registry();
     println!("Created registry");
 
     // Print registered deserializers
@@ -324,7 +597,7 @@ fn test_type_mismatch_errors() -> Result<()> {
 
 #[test]
 fn test_null_value() -> Result<()> {
-    let mut value = ArcValueType::null();
+    let value = ArcValueType::null();
     assert!(value.is_null());
 
     Ok(())
@@ -366,7 +639,7 @@ fn test_registry_with_defaults() -> Result<()> {
     // Create a registry with defaults
     let registry = SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
         Component::Custom("Test"),
-        "test-node",
+        NodeId::new("test-node").unwrap(),
     )));
 
     // Test serialization and deserialization of a primitive
@@ -378,3 +651,377 @@ fn test_registry_with_defaults() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_to_debug_tree_is_sorted_and_stable() -> Result<()> {
+    let mut inner = HashMap::new();
+    inner.insert("zeta".to_string(), ArcValueType::new_primitive(1i64));
+    inner.insert("alpha".to_string(), ArcValueType::new_list(vec![1.5f64]));
+    let mut outer = HashMap::new();
+    outer.insert("nested".to_string(), ArcValueType::new_map(inner));
+    outer.insert(
+        "bytes".to_string(),
+        ArcValueType::new_bytes(vec![0xde, 0xad]),
+    );
+    outer.insert(
+        "items".to_string(),
+        ArcValueType::new_list(vec![
+            ArcValueType::null(),
+            ArcValueType::new_primitive("x".to_string()),
+            ArcValueType::from_struct(TestStruct {
+                field1: "a".to_string(),
+                field2: 1,
+            }),
+        ]),
+    );
+    outer.insert(
+        "empty".to_string(),
+        ArcValueType::new_map(HashMap::<String, String>::new()),
+    );
+    let value = ArcValueType::new_map(outer);
+
+    let expected = r#"{
+  "bytes": Bytes[2] dead,
+  "empty": {},
+  "items": [
+    null,
+    "x",
+    Struct<value_type_test::TestStruct>,
+  ],
+  "nested": {
+    "alpha": [
+      1.5,
+    ],
+    "zeta": 1,
+  },
+}"#;
+    assert_eq!(value.to_debug_tree(), expected);
+    assert_eq!(value.clone().to_debug_tree(), expected);
+
+    // Lazily deserialized values render the same as the originals
+    let registry = create_test_registry();
+    let map: HashMap<String, i64> = (0..20).map(|i| (format!("k{:02}", i), i)).collect();
+    let original = ArcValueType::new_map(map);
+    let decoded = registry.deserialize_value(registry.serialize_value(&original)?)?;
+    assert_eq!(decoded.to_debug_tree(), original.to_debug_tree());
+    assert!(original
+        .to_debug_tree()
+        .starts_with("{\n  \"k00\": 0,\n  \"k01\": 1,"));
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Message {
+    topic: String,
+    payload: ArcValueType,
+}
+
+#[test]
+fn test_serde_within_registry_scope() -> Result<()> {
+    let registry = Arc::new(create_test_registry());
+    let message = Message {
+        topic: "users/created".to_string(),
+        payload: ArcValueType::from_struct(TestStruct {
+            field1: "ada".to_string(),
+            field2: 36,
+        }),
+    };
+
+    let json = registry.scope(|| serde_json::to_string(&message))?;
+    let mut decoded: Message = registry.scope(|| serde_json::from_str(&json))?;
+    assert_eq!(decoded.topic, "users/created");
+    assert!(!decoded.payload.is_materialized());
+    assert_eq!(
+        *decoded.payload.as_struct_ref::<TestStruct>()?,
+        TestStruct {
+            field1: "ada".to_string(),
+            field2: 36,
+        }
+    );
+
+    let bytes = registry.scope(|| bincode::serialize(&message))?;
+    let mut decoded: Message = registry.scope(|| bincode::deserialize(&bytes))?;
+    assert_eq!(decoded.payload.as_struct_ref::<TestStruct>()?.field2, 36);
+    Ok(())
+}
+
+#[test]
+fn test_serde_requires_registry_scope() {
+    let message = Message {
+        topic: "t".to_string(),
+        payload: ArcValueType::new_primitive(1i64),
+    };
+    let err = serde_json::to_string(&message).unwrap_err();
+    assert!(err.to_string().contains("SerializerRegistry::scope"));
+
+    // Nested scopes restore the outer registry, and leaving clears it
+    let outer = Arc::new(create_test_registry());
+    let inner = Arc::new(create_test_registry());
+    outer.scope(|| {
+        inner.scope(|| assert!(Arc::ptr_eq(&SerializerRegistry::scoped().unwrap(), &inner)));
+        assert!(Arc::ptr_eq(&SerializerRegistry::scoped().unwrap(), &outer));
+    });
+    assert!(SerializerRegistry::scoped().is_none());
+}
+
+#[test]
+fn test_map_if_present() -> Result<()> {
+    let limit = ArcValueType::new_primitive(10i64);
+    assert_eq!(limit.map_if_present(|n: i64| n * 2)?, Some(20));
+    assert_eq!(ArcValueType::null().map_if_present(|n: i64| n * 2)?, None);
+    assert!(limit.map_if_present(|s: String| s.len()).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_or_null_default_uses_schema_default() -> Result<()> {
+    let mut page_size = FieldSchema::integer("page_size");
+    page_size.default_value = Some("25".to_string());
+    let value = ArcValueType::null().or_null_default(&page_size)?;
+    assert_eq!(value.map_if_present(|n: i32| n)?, Some(25));
+
+    // Present values are kept as they are
+    let value = ArcValueType::new_primitive(50i32).or_null_default(&page_size)?;
+    assert_eq!(value.map_if_present(|n: i32| n)?, Some(50));
+
+    let mut tags = FieldSchema::new("tags", SchemaDataType::Array);
+    tags.default_value = Some(r#"["a", "b"]"#.to_string());
+    let value = ArcValueType::null().or_null_default(&tags)?;
+    assert_eq!(value.to_json()?, serde_json::json!(["a", "b"]));
+
+    let mut bad = FieldSchema::boolean("enabled");
+    bad.default_value = Some("yes".to_string());
+    assert!(ArcValueType::null().or_null_default(&bad).is_err());
+
+    // Without a default, nulls stay null unless the field is not nullable
+    let mut name = FieldSchema::string("name");
+    assert!(ArcValueType::null().or_null_default(&name)?.is_null());
+    name.nullable = Some(false);
+    let err = ArcValueType::null().or_null_default(&name).unwrap_err();
+    assert!(err.to_string().contains("'name'"));
+    Ok(())
+}
+
+#[test]
+fn test_deep_clone_owned_releases_receive_buffer() -> Result<()> {
+    let registry = create_test_registry();
+    let original = ArcValueType::from_struct(TestStruct {
+        field1: "payload".to_string(),
+        field2: 7,
+    });
+    let bytes = registry.serialize_value(&original)?;
+    let decoded = registry.deserialize_value(bytes.clone())?;
+    assert_eq!(Arc::strong_count(&bytes), 2);
+
+    let mut owned = decoded.deep_clone_owned()?;
+    drop(decoded);
+    assert_eq!(Arc::strong_count(&bytes), 1);
+    assert_eq!(
+        owned.lazy_len(),
+        Some(bytes.len() - 2 - owned.lazy_type_name().unwrap().len())
+    );
+    assert_eq!(owned.as_struct_ref::<TestStruct>()?.field1, "payload");
+
+    // Nested values are copied rather than shared
+    let mut tree = ArcValueType::new_list(vec![
+        ArcValueType::new_primitive("shared".to_string()),
+        ArcValueType::new_bytes(vec![1, 2, 3]),
+    ]);
+    let mut copy = tree.deep_clone_owned()?;
+    let original_items = tree.as_type_ref::<Vec<ArcValueType>>()?;
+    let copied_items = copy.as_type_ref::<Vec<ArcValueType>>()?;
+    assert!(!Arc::ptr_eq(&original_items, &copied_items));
+    assert_ne!(
+        original_items[0].value.as_ptr(),
+        copied_items[0].value.as_ptr()
+    );
+    assert_eq!(copy.to_debug_tree(), tree.to_debug_tree());
+    Ok(())
+}
+
+#[test]
+fn test_detach_small_lazy_values_from_buffer() -> Result<()> {
+    let mut registry = create_test_registry();
+    let value = ArcValueType::new_primitive(16i64);
+    let bytes = registry.serialize_value(&value)?;
+
+    let mut decoded = registry.deserialize_value(bytes.clone())?;
+    assert!(!decoded.detach_from_buffer(4));
+    assert_eq!(Arc::strong_count(&bytes), 2);
+    assert!(decoded.detach_from_buffer(64));
+    assert_eq!(Arc::strong_count(&bytes), 1);
+    assert!(!decoded.detach_from_buffer(64));
+    assert_eq!(decoded.as_type::<i64>()?, 16);
+
+    // With a registry policy, small payloads are copied during deserialization
+    registry.set_detach_threshold(Some(64));
+    let decoded = registry.deserialize_value(bytes.clone())?;
+    assert_eq!(Arc::strong_count(&bytes), 1);
+    assert_eq!(decoded.lazy_len(), Some(8));
+
+    let large = ArcValueType::new_list(vec![0i64; 100]);
+    let large_bytes = registry.serialize_value(&large)?;
+    let _decoded = registry.deserialize_value(large_bytes.clone())?;
+    assert_eq!(Arc::strong_count(&large_bytes), 2);
+    Ok(())
+}
+
+#[test]
+fn test_materialization_metrics() -> Result<()> {
+    let mut registry = create_test_registry();
+    assert!(registry.metrics().is_none());
+    registry.enable_metrics();
+    let metrics = registry.metrics().unwrap();
+
+    let list = ArcValueType::new_list(vec![1i64, 2, 3]);
+    let bytes = registry.serialize_value(&list)?;
+    let payload_len = registry
+        .deserialize_value(bytes.clone())?
+        .lazy_len()
+        .unwrap() as u64;
+    for _ in 0..2 {
+        let mut decoded = registry.deserialize_value(bytes.clone())?;
+        decoded.as_list_ref::<i64>()?;
+        // Already materialized: not counted again
+        decoded.as_list_ref::<i64>()?;
+    }
+    let mut never_read = registry.deserialize_value(bytes.clone())?;
+    assert!(never_read.as_type_ref::<String>().is_err());
+
+    let type_name = std::any::type_name::<Vec<i64>>();
+    let stats = metrics.get(type_name).unwrap();
+    assert_eq!(stats.count, 2);
+    assert_eq!(stats.bytes, 2 * payload_len);
+
+    let mut strukt = registry.deserialize_value(registry.serialize_value(
+        &ArcValueType::from_struct(TestStruct {
+            field1: "a".to_string(),
+            field2: 1,
+        }),
+    )?)?;
+    strukt.as_struct_ref::<TestStruct>()?;
+    let snapshot = metrics.snapshot();
+    assert_eq!(snapshot.len(), 2);
+    assert!(snapshot[0].0 < snapshot[1].0);
+
+    metrics.reset();
+    assert!(metrics.snapshot().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_registry_snapshot_is_frozen_and_shareable() -> Result<()> {
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Later {
+        id: u32,
+    }
+
+    let mut registry = create_test_registry();
+    let snapshot = registry.snapshot();
+    assert!(snapshot.is_sealed());
+    assert!(!registry.is_sealed());
+
+    // Registrations after the snapshot stay on the mutable registry
+    registry.register::<Later>()?;
+    let later = ArcValueType::from_struct(Later { id: 1 });
+    assert!(registry.serialize_value(&later).is_ok());
+    assert!(snapshot.serialize_value(&later).is_err());
+
+    let workers: Vec<_> = (0..4)
+        .map(|i| {
+            let snapshot = Arc::clone(&snapshot);
+            std::thread::spawn(move || -> Result<String> {
+                let value = ArcValueType::from_struct(TestStruct {
+                    field1: format!("worker {}", i),
+                    field2: i,
+                });
+                let bytes = snapshot.serialize_value(&value)?;
+                let mut decoded = snapshot.deserialize_value(bytes)?;
+                Ok(decoded.as_struct_ref::<TestStruct>()?.field1.clone())
+            })
+        })
+        .collect();
+    for (i, worker) in workers.into_iter().enumerate() {
+        assert_eq!(worker.join().unwrap()?, format!("worker {}", i));
+    }
+
+    // The frozen registry works with Arc-based APIs such as scope
+    let scoped = snapshot
+        .registry()
+        .scope(|| SerializerRegistry::scoped().map(|r| Arc::ptr_eq(&r, snapshot.registry())));
+    assert_eq!(scoped, Some(true));
+    Ok(())
+}
+
+#[test]
+fn test_materialize_with_registry() -> Result<()> {
+    let mut registry = create_test_registry();
+    registry.enable_metrics();
+    let original = TestStruct {
+        field1: "shared".to_string(),
+        field2: 7,
+    };
+    let bytes = registry.serialize_value(&ArcValueType::from_struct(original.clone()))?;
+
+    let mut value = registry.deserialize_value(bytes)?;
+    assert!(!value.is_materialized());
+    value.materialize(&registry)?;
+    assert!(value.is_materialized());
+    let type_name = std::any::type_name::<TestStruct>();
+    assert_eq!(registry.metrics().unwrap().get(type_name).unwrap().count, 1);
+
+    // Every clone sees the same decoded struct, without decoding again
+    let first = value.clone().as_struct_ref::<TestStruct>()?;
+    assert_eq!(*first, original);
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let mut copy = value.clone();
+            std::thread::spawn(move || copy.as_struct_ref::<TestStruct>().unwrap())
+        })
+        .collect();
+    for handle in handles {
+        assert!(Arc::ptr_eq(&handle.join().unwrap(), &first));
+    }
+    assert_eq!(registry.metrics().unwrap().get(type_name).unwrap().count, 1);
+
+    // Materializing again is a no-op
+    value.materialize(&registry)?;
+    Ok(())
+}
+
+#[test]
+fn test_materialize_nested_and_unknown_types() -> Result<()> {
+    let mut registry = create_test_registry();
+    registry.register::<Vec<ArcValueType>>()?;
+    let registry = Arc::new(registry);
+
+    let list = ArcValueType::new_list(vec![
+        ArcValueType::from_struct(TestStruct {
+            field1: "a".to_string(),
+            field2: 1,
+        }),
+        ArcValueType::new_primitive(2i32),
+    ]);
+    let bytes = registry.scope(|| registry.serialize_value(&list))?;
+    let mut value = registry.scope(|| registry.deserialize_value(bytes))?;
+    assert!(!value.is_fully_materialized());
+    registry.scope(|| value.materialize(&registry))?;
+    assert!(value.is_fully_materialized());
+    let items = value.as_list_ref::<ArcValueType>()?;
+    assert!(items.iter().all(ArcValueType::is_materialized));
+
+    // A registry that does not know the type cannot decode it
+    let bytes = registry.serialize_value(&ArcValueType::from_struct(TestStruct {
+        field1: "b".to_string(),
+        field2: 2,
+    }))?;
+    let mut value = registry.deserialize_value(bytes)?;
+    let other = SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )));
+    let error = value.materialize(&other).unwrap_err();
+    assert!(error.to_string().contains("no typed decoder"));
+    assert!(!value.is_materialized());
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anyhow::Result;
+use runar_common::types::{ServiceMetadata, Version};
+
+fn v(s: &str) -> Version {
+    Version::parse(s).unwrap()
+}
+
+#[test]
+fn test_parse_and_display() -> Result<()> {
+    let version = Version::parse("v1.2.3-alpha.1+build.5")?;
+    assert_eq!((version.major, version.minor, version.patch), (1, 2, 3));
+    assert_eq!(version.pre, vec!["alpha", "1"]);
+    assert_eq!(version.build.as_deref(), Some("build.5"));
+    assert_eq!(version.to_string(), "1.2.3-alpha.1+build.5");
+    assert_eq!("0.1.0".parse::<Version>()?, Version::new(0, 1, 0));
+
+    for invalid in [
+        "",
+        "1",
+        "1.2",
+        "1.2.3.4",
+        "01.2.3",
+        "1.x.3",
+        "1.2.3-",
+        "1.2.3+a..b",
+    ] {
+        assert!(
+            Version::parse(invalid).is_err(),
+            "{} should not parse",
+            invalid
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_ordering() {
+    assert!(v("0.10.0") > v("0.9.0"));
+    assert!(v("1.0.0-alpha") < v("1.0.0"));
+    assert!(v("1.0.0-alpha") < v("1.0.0-alpha.1"));
+    assert!(v("1.0.0-alpha.2") < v("1.0.0-alpha.10"));
+    assert!(v("1.0.0-1") < v("1.0.0-alpha"));
+    assert!(v("1.0.0-beta") < v("1.0.0-rc.1"));
+    assert_eq!(v("1.0.0+a"), v("1.0.0+b"));
+}
+
+#[test]
+fn test_compatibility() {
+    assert!(v("1.4.0").is_compatible_with(&v("1.2.0")));
+    assert!(!v("1.1.0").is_compatible_with(&v("1.2.0")));
+    assert!(!v("2.0.0").is_compatible_with(&v("1.2.0")));
+    assert!(v("0.2.5").is_compatible_with(&v("0.2.1")));
+    assert!(!v("0.3.0").is_compatible_with(&v("0.2.0")));
+    assert!(!v("0.0.4").is_compatible_with(&v("0.0.3")));
+
+    assert!(v("1.2.9").is_tilde_compatible_with(&v("1.2.3")));
+    assert!(!v("1.3.0").is_tilde_compatible_with(&v("1.2.3")));
+}
+
+#[test]
+fn test_serde_as_string() -> Result<()> {
+    let version = v("2.0.1-rc.1");
+    assert_eq!(serde_json::to_string(&version)?, "\"2.0.1-rc.1\"");
+    assert_eq!(serde_json::from_str::<Version>("\"2.0.1-rc.1\"")?, version);
+    assert!(serde_json::from_str::<Version>("\"2.0\"").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_old_metadata_versions_parse_leniently() -> Result<()> {
+    assert_eq!(Version::parse_lenient("1.0"), v("1.0.0"));
+    assert_eq!(Version::parse_lenient("v2"), v("2.0.0"));
+    assert_eq!(Version::parse_lenient("1.2.3-rc.1"), v("1.2.3-rc.1"));
+    let latest = Version::parse_lenient("latest");
+    assert_eq!(latest.to_string(), "0.0.0+latest");
+    assert!(latest < v("0.0.1"));
+
+    let blob = r#"{
+        "network_id": "default",
+        "service_path": "math",
+        "name": "Math",
+        "version": "1.0",
+        "description": "Arithmetic",
+        "actions": [],
+        "events": [],
+        "registration_time": 0,
+        "last_start_time": null
+    }"#;
+    let metadata: ServiceMetadata = serde_json::from_str(blob)?;
+    assert_eq!(metadata.version, v("1.0.0"));
+    Ok(())
+}
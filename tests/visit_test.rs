@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{
+    format_path, ArcValueType, NodeId, PathSegment, SerializerRegistry, ValueVisitor, Visit,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn last_key(path: &[PathSegment]) -> Option<&str> {
+    match path.last() {
+        Some(PathSegment::Key(key)) => Some(key),
+        _ => None,
+    }
+}
+
+// Replaces every "password" entry and does not look inside it
+struct RedactPasswords;
+
+impl ValueVisitor for RedactPasswords {
+    fn enter(&mut self, path: &[PathSegment], _value: &ArcValueType) -> Result<Visit> {
+        Ok(match last_key(path) {
+            Some("password") => Visit::Replace(ArcValueType::new_primitive("***".to_string())),
+            _ => Visit::Continue,
+        })
+    }
+}
+
+#[test]
+fn test_transform_rewrites_nested_nodes() -> Result<()> {
+    let value = ArcValueType::from_json(json!({
+        "user": {"name": "ada", "password": {"hash": "x"}},
+        "sessions": [{"password": "p1", "timeout_ms": 1500}, {"timeout_ms": 250}],
+    }));
+
+    let redacted = value.transform(&mut RedactPasswords)?;
+    assert_eq!(redacted.to_json()?["user"]["password"], json!("***"));
+    assert_eq!(redacted.to_json()?["sessions"][0]["password"], json!("***"));
+    assert_eq!(redacted.to_json()?["user"]["name"], json!("ada"));
+
+    // Unit conversion with a closure: "*_ms" integers become seconds
+    let converted = redacted.transform(&mut |path: &[PathSegment], mut value: ArcValueType| {
+        if last_key(path).is_some_and(|key| key.ends_with("_ms")) {
+            let millis: i64 = value.as_type()?;
+            return Ok(ArcValueType::new_primitive(millis as f64 / 1000.0));
+        }
+        Ok(value)
+    })?;
+    assert_eq!(
+        converted.to_json()?["sessions"],
+        json!([{"password": "***", "timeout_ms": 1.5}, {"timeout_ms": 0.25}])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_visit_order_paths_and_skip() -> Result<()> {
+    #[derive(Default)]
+    struct Recorder {
+        entered: Vec<String>,
+        left: Vec<String>,
+    }
+
+    impl ValueVisitor for Recorder {
+        fn enter(&mut self, path: &[PathSegment], _value: &ArcValueType) -> Result<Visit> {
+            self.entered.push(format_path(path));
+            Ok(if last_key(path) == Some("skipped") {
+                Visit::Skip
+            } else {
+                Visit::Continue
+            })
+        }
+
+        fn leave(&mut self, path: &[PathSegment], value: ArcValueType) -> Result<ArcValueType> {
+            self.left.push(format_path(path));
+            Ok(value)
+        }
+    }
+
+    let value = ArcValueType::from_json(json!({
+        "b": [1, {"c": true}],
+        "a": null,
+        "skipped": {"inner": 1},
+    }));
+    let mut recorder = Recorder::default();
+    let unchanged = value.transform(&mut recorder)?;
+
+    assert_eq!(
+        recorder.entered,
+        vec![
+            "$",
+            "$.a",
+            "$.b",
+            "$.b[0]",
+            "$.b[1]",
+            "$.b[1].c",
+            "$.skipped"
+        ]
+    );
+    assert_eq!(
+        recorder.left,
+        vec!["$.a", "$.b[0]", "$.b[1].c", "$.b[1]", "$.b", "$"]
+    );
+    assert_eq!(unchanged.to_json()?["skipped"], json!({"inner": 1}));
+
+    // Visitor errors stop the walk
+    let err = ArcValueType::from_json(json!([1, "two"]))
+        .transform(&mut |_: &[PathSegment], mut value: ArcValueType| {
+            value.as_type::<i64>()?;
+            Ok(value)
+        })
+        .unwrap_err();
+    assert!(err.to_string().contains("i64"), "{}", err);
+    Ok(())
+}
+
+#[test]
+fn test_transform_materializes_lazy_values() -> Result<()> {
+    let mut registry = SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )));
+    registry.register::<Point>()?;
+    registry.register::<Vec<ArcValueType>>()?;
+    registry.register::<HashMap<String, ArcValueType>>()?;
+    let registry = Arc::new(registry);
+
+    let mut entries = HashMap::new();
+    entries.insert(
+        "points".to_string(),
+        ArcValueType::new_list(vec![
+            ArcValueType::from_struct(Point { x: 1, y: 2 }),
+            ArcValueType::from_struct(Point { x: 3, y: 4 }),
+        ]),
+    );
+    let bytes = registry.scope(|| registry.serialize_value(&ArcValueType::new_map(entries)))?;
+    let value = registry.scope(|| registry.deserialize_value(bytes))?;
+    assert!(!value.is_materialized());
+
+    // Mirror every point; structs reach the visitor decoded
+    let mirrored = registry.scope(|| {
+        value.transform(&mut |_: &[PathSegment], mut value: ArcValueType| {
+            if !value.is_materialized() {
+                return Err(anyhow::anyhow!("lazy value reached the visitor"));
+            }
+            match value.as_struct_ref::<Point>() {
+                Ok(point) => Ok(ArcValueType::from_struct(Point {
+                    x: point.y,
+                    y: point.x,
+                })),
+                Err(_) => Ok(value),
+            }
+        })
+    })?;
+
+    let mut points = mirrored.into_map::<String, ArcValueType>()?["points"].clone();
+    let points = points.as_list_ref::<ArcValueType>()?;
+    let mut first = points[0].clone();
+    assert_eq!(*first.as_struct_ref::<Point>()?, Point { x: 2, y: 1 });
+    Ok(())
+}
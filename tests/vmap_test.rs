@@ -5,7 +5,8 @@ mod tests {
     use anyhow::Result;
 
     use runar_common::types::ArcValueType;
-    use runar_common::types::VMap;
+    use runar_common::types::{EntryState, VMap};
+    use runar_common::vmap_entry;
 
     // Test implementation
     fn create_test_vmap() -> VMap<ArcValueType> {
@@ -101,4 +102,97 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_entry_state_distinguishes_null_from_missing() -> Result<()> {
+        let mut vmap = create_test_vmap();
+        vmap.insert("cleared", ArcValueType::null());
+
+        assert!(vmap.entry_state("key1").is_present());
+        assert_eq!(vmap.entry_state("cleared"), EntryState::ExplicitNull);
+        assert_eq!(vmap.entry_state("absent"), EntryState::Missing);
+
+        let mut params = vmap.to_arc_value_type();
+        let name = params
+            .entry_state("key1")?
+            .try_map(|mut value| value.as_type::<String>())?;
+        assert_eq!(name, EntryState::Present("value1".to_string()));
+        assert!(params.entry_state("cleared")?.is_null());
+        assert!(params.entry_state("absent")?.present().is_none());
+
+        // No parameters at all: every field is missing
+        assert!(ArcValueType::null().entry_state("key1")?.is_missing());
+        assert!(ArcValueType::new_primitive(1i32)
+            .entry_state("key1")
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_vmap_entry_macro() -> Result<()> {
+        let params = create_test_vmap().to_arc_value_type();
+        assert_eq!(
+            vmap_entry!(params, "key2" => f64)?,
+            EntryState::Present(42.0)
+        );
+        assert!(vmap_entry!(params, "missing" => f64)?.is_missing());
+        assert!(vmap_entry!(params, "key1" => f64).is_err());
+        assert!(vmap_entry!(params, "key3")?.is_present());
+        Ok(())
+    }
+}
+
+mod keyed {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use anyhow::Result;
+    use runar_common::logging::{Component, Logger};
+    use runar_common::types::{ArcValueType, NodeId, SerializerRegistry, VMap, ValueCategory};
+    use runar_common::vmap_keyed;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_numeric_keys() {
+        let mut routes: VMap<String, u64> = VMap::new();
+        routes.insert(7u64, "node-a".to_string());
+        routes.insert(9u64, "node-b".to_string());
+        assert_eq!(routes.get(&7).map(String::as_str), Some("node-a"));
+        assert!(routes.get(&8).is_none());
+
+        let mut value = routes.to_arc_value_type();
+        assert_eq!(value.as_map_ref::<u64, String>().unwrap().len(), 2);
+        assert!(value.as_map_ref::<String, String>().is_err());
+    }
+
+    #[test]
+    fn test_default_registry_round_trips_keyed_maps() -> Result<()> {
+        let registry = SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+            Component::Custom("Test"),
+            NodeId::new("test-node").unwrap(),
+        )));
+
+        let id = Uuid::new_v4();
+        let by_uuid = ArcValueType::new_map(HashMap::from([(id, 1.5f64)]));
+        let mut decoded = registry.deserialize_value(registry.serialize_value(&by_uuid)?)?;
+        assert_eq!(decoded.as_map_ref::<Uuid, f64>()?[&id], 1.5);
+
+        let by_id = ArcValueType::new_map(HashMap::from([(-3i64, true)]));
+        let decoded = registry.deserialize_value(registry.serialize_value(&by_id)?)?;
+        assert_eq!(decoded.to_json()?, serde_json::json!({"-3": true}));
+        Ok(())
+    }
+
+    #[test]
+    fn test_vmap_keyed_macro() -> Result<()> {
+        let mut routes = vmap_keyed! { u64; 1 => "node-a", 2 => "node-b" };
+        assert_eq!(routes.category, ValueCategory::Map);
+        let map = routes.as_map_ref::<u64, ArcValueType>()?;
+        assert_eq!(map.len(), 2);
+        assert!(map.contains_key(&1) && map.contains_key(&2));
+
+        let mut empty = vmap_keyed! { i64; };
+        assert!(empty.as_map_ref::<i64, ArcValueType>()?.is_empty());
+        Ok(())
+    }
 }
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{ArcValueType, NodeId};
+use runar_common::utils::watch::WatchValue;
+
+fn test_logger() -> Arc<Logger> {
+    Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    ))
+}
+
+#[tokio::test]
+async fn test_producers_and_observers() -> Result<()> {
+    let members = WatchValue::new("membership", vec!["a".to_string()], test_logger());
+    let producer = members.clone();
+    let mut observer = members.subscribe();
+    assert_eq!(observer.get(), vec!["a"]);
+    assert!(!observer.has_changed());
+
+    producer.update(|list| list.push("b".to_string()));
+    assert!(observer.has_changed());
+    assert_eq!(
+        observer.changed().await,
+        Some(vec!["a".to_string(), "b".to_string()])
+    );
+
+    assert!(!members.set_if_changed(vec!["a".to_string(), "b".to_string()]));
+    assert_eq!(members.set(vec![]), vec!["a", "b"]);
+    assert_eq!(members.version(), 2);
+    assert_eq!(members.observer_count(), 1);
+
+    let waiter = tokio::spawn(async move { observer.changed().await });
+    drop(members);
+    drop(producer);
+    // The last published value is delivered, then the stream ends
+    assert_eq!(waiter.await?, Some(Vec::<String>::new()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_value_cell() -> Result<()> {
+    let config = WatchValue::new_value("config", ArcValueType::null(), test_logger());
+    let mut observer = config.subscribe();
+
+    let mut settings = HashMap::new();
+    settings.insert("interval".to_string(), 30i64);
+    assert!(config.set_if_content_changed(ArcValueType::new_map(settings.clone())));
+    // Equal content published from a different value is not a change
+    assert!(!config.set_if_content_changed(ArcValueType::new_map(settings)));
+
+    let mut current = observer.changed().await.unwrap();
+    assert_eq!(current.as_map_ref::<String, i64>()?["interval"], 30);
+    assert_eq!(config.version(), 1);
+    assert_eq!(config.name(), "config");
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use runar_common::errors::{ErrorCode, RunarError};
+use runar_common::logging::{Component, Logger};
+use runar_common::types::{
+    hex_snippet, ArcValueType, FailureLogging, NodeId, SerializerRegistry, WireError,
+};
+use serde::{Deserialize, Serialize};
+
+fn registry() -> SerializerRegistry {
+    SerializerRegistry::with_defaults(Arc::new(Logger::new_root(
+        Component::Custom("Test"),
+        NodeId::new("test-node").unwrap(),
+    )))
+}
+
+fn decode_error(bytes: &[u8]) -> WireError {
+    registry()
+        .deserialize_value(Arc::from(bytes))
+        .unwrap_err()
+        .downcast::<WireError>()
+        .expect("header failures are WireErrors")
+}
+
+#[test]
+fn test_header_failures() {
+    assert_eq!(
+        decode_error(&[0x38, 0x01]),
+        WireError::BadCategory {
+            byte: 0x38,
+            snippet: "38 01 (2 bytes)".to_string(),
+        }
+    );
+
+    match decode_error(&[0x04, 0x05, b'a', b'b']) {
+        WireError::TruncatedAt { offset, needed, .. } => {
+            assert_eq!((offset, needed), (4, 3));
+        }
+        other => panic!("unexpected error: {}", other),
+    }
+    assert_eq!(decode_error(&[]).offset(), Some(0));
+    assert_eq!(decode_error(&[0x04, 0x01, 0xff]).offset(), Some(2));
+
+    let err = decode_error(&[0x04, 0x03, b'F', b'o', b'o', 0x00]);
+    assert!(matches!(err, WireError::UnknownType { ref name, .. } if name == "Foo"));
+    assert_eq!(err.to_string(), "unknown type 'Foo'");
+    assert_eq!(err.snippet(), Some("04 03 46 6f 6f 00 (6 bytes)"));
+
+    let runar: RunarError = err.into();
+    assert_eq!(runar.code(), ErrorCode::Serialization);
+}
+
+#[test]
+fn test_long_type_names_round_trip() -> anyhow::Result<()> {
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Named<T>(T);
+    type Deep = Named<Named<Named<Named<Named<Named<Named<Named<u8>>>>>>>>;
+    type Payload = HashMap<String, Vec<Named<Deep>>>;
+    assert!(std::any::type_name::<Payload>().len() > 255);
+
+    let mut registry = registry();
+    registry.register_map::<String, Vec<Named<Deep>>>()?;
+    let deep = Named(Named(Named(Named(Named(Named(Named(Named(Named(7u8)))))))));
+    let mut payload = Payload::new();
+    payload.insert("readings".to_string(), vec![deep]);
+
+    let bytes = registry.serialize_value(&ArcValueType::new_map(payload.clone()))?;
+    let mut value = registry.deserialize_value(bytes)?;
+    assert_eq!(*value.as_map_ref::<String, Vec<Named<Deep>>>()?, payload);
+    Ok(())
+}
+
+#[test]
+fn test_hex_snippet() {
+    assert_eq!(hex_snippet(&[]), "(0 bytes)");
+    let long: Vec<u8> = (0..20).collect();
+    assert_eq!(
+        hex_snippet(&long),
+        "00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f ... (20 bytes)"
+    );
+}
+
+#[test]
+fn test_failure_report_respects_privacy_setting() {
+    let mut registry = registry();
+    let bytes: Vec<u8> = [0x04, 0x03, b'F', b'o', b'o']
+        .into_iter()
+        .chain(0u8..40)
+        .collect();
+    let err = registry
+        .deserialize_value_from(Arc::from(bytes.as_slice()), "peer-7")
+        .unwrap_err();
+    assert!(registry.failure_report(&bytes, None, &err).is_none());
+
+    registry.set_failure_logging(FailureLogging::Metadata);
+    let report = registry
+        .failure_report(&bytes, Some("peer-7"), &err)
+        .unwrap();
+    assert!(report.starts_with("Failed to deserialize value from peer-7: unknown type 'Foo'"));
+    assert!(report.contains("category: Struct, type: Foo, length: 45 bytes"));
+    assert!(!report.contains("prefix") && !report.contains("04 03"));
+
+    registry.set_failure_logging(FailureLogging::WithPrefix(4));
+    let report = registry.failure_report(&bytes, None, &err).unwrap();
+    assert!(report.ends_with("length: 45 bytes, prefix: 0403466f...)"));
+
+    registry.set_failure_logging(FailureLogging::WithPrefix(16));
+    let report = registry.failure_report(&bytes, None, &err).unwrap();
+    assert!(
+        report.contains(", at: [04 03 46 6f 6f 00 01 02 03 04 05 06 07 08 09 0a ... (45 bytes)]")
+    );
+
+    // Logging does not change the error returned to the caller
+    let again = registry
+        .deserialize_value(Arc::from(bytes.as_slice()))
+        .unwrap_err();
+    assert!(matches!(
+        again.downcast_ref::<WireError>(),
+        Some(WireError::UnknownType { .. })
+    ));
+
+    let report = registry.failure_report(&[0xf8], None, &again).unwrap();
+    assert!(report.contains("category: unknown, type: <unreadable>, length: 1 bytes"));
+}
@@ -0,0 +1,79 @@
+use runar_common::wire::{
+    decode_header, encode_header, ValueCategory, WireError, LONG_TYPE_NAME_FLAG, MAX_TYPE_NAME_LEN,
+};
+
+#[test]
+fn test_header_round_trip() {
+    let mut bytes = Vec::new();
+    encode_header(ValueCategory::Struct, "sensor::Reading", &mut bytes).unwrap();
+    bytes.extend_from_slice(&[1, 2, 3]);
+
+    let header = decode_header(&bytes).unwrap();
+    assert_eq!(header.category, ValueCategory::Struct);
+    assert_eq!(header.type_name, "sensor::Reading");
+    assert_eq!(&bytes[header.data_offset..], &[1, 2, 3]);
+
+    let mut null = Vec::new();
+    encode_header(ValueCategory::Null, "ignored", &mut null).unwrap();
+    assert_eq!(null, vec![ValueCategory::Null.marker()]);
+    assert_eq!(decode_header(&null).unwrap().type_name, "");
+}
+
+#[test]
+fn test_category_markers() {
+    for category in [
+        ValueCategory::Primitive,
+        ValueCategory::List,
+        ValueCategory::Map,
+        ValueCategory::Struct,
+        ValueCategory::Null,
+        ValueCategory::Bytes,
+        ValueCategory::Json,
+    ] {
+        assert_eq!(
+            ValueCategory::from_marker(category.marker()),
+            Some(category)
+        );
+    }
+    assert_eq!(ValueCategory::from_marker(0), None);
+
+    let long_name = "x".repeat(MAX_TYPE_NAME_LEN + 1);
+    assert!(matches!(
+        encode_header(ValueCategory::Struct, &long_name, &mut Vec::new()),
+        Err(WireError::TypeNameTooLong { len, .. }) if len == MAX_TYPE_NAME_LEN + 1
+    ));
+}
+
+#[test]
+fn test_type_name_length_prefix() {
+    // Names up to 255 bytes keep the one byte length older peers expect
+    let mut bytes = Vec::new();
+    let name = "y".repeat(255);
+    encode_header(ValueCategory::Map, &name, &mut bytes).unwrap();
+    assert_eq!(&bytes[..2], &[ValueCategory::Map.marker(), 255]);
+    assert_eq!(decode_header(&bytes).unwrap().type_name, name);
+
+    for len in [256, 1000, MAX_TYPE_NAME_LEN] {
+        let name = "z".repeat(len);
+        let mut bytes = Vec::new();
+        encode_header(ValueCategory::Map, &name, &mut bytes).unwrap();
+        bytes.push(42);
+        assert_eq!(bytes[0], ValueCategory::Map.marker() | LONG_TYPE_NAME_FLAG);
+
+        let header = decode_header(&bytes).unwrap();
+        assert_eq!(header.category, ValueCategory::Map);
+        assert_eq!(header.type_name, name);
+        assert_eq!(&bytes[header.data_offset..], &[42]);
+    }
+
+    // A varint that does not end within two bytes is rejected
+    let marker = ValueCategory::Struct.marker() | LONG_TYPE_NAME_FLAG;
+    assert!(matches!(
+        decode_header(&[marker, 0x80, 0x80, 0x01]),
+        Err(WireError::InvalidTypeNameLength { offset: 1, .. })
+    ));
+    assert!(matches!(
+        decode_header(&[marker, 0x80]),
+        Err(WireError::TruncatedAt { offset: 2, .. })
+    ));
+}